@@ -69,6 +69,12 @@ impl ExampleCustomProvider {
                                         "audio_url": audio_url,
                                         "format": format
                                     }),
+                                    ContentPart::Document { source, media_type, title, .. } => json!({
+                                        "type": "document",
+                                        "source": source,
+                                        "media_type": media_type,
+                                        "title": title
+                                    }),
                                 }
                             }).collect();
                             json!(content_parts)
@@ -171,6 +177,7 @@ impl ExampleCustomProvider {
                 .unwrap_or(0),
             reasoning_tokens: None,
             cached_tokens: None,
+            cache_write_tokens: None,
         });
 
         let mut response = CustomChatResponse::new(content);
@@ -268,6 +275,8 @@ impl CustomProvider for ExampleCustomProvider {
                     finish_reason: Some(FinishReason::Stop),
                     tool_calls: None,
                     thinking: None,
+                    citations: Vec::new(),
+                    sources: Vec::new(),
                     metadata: std::collections::HashMap::new(),
                 },
             })))
@@ -25,7 +25,7 @@ use axum::{
 };
 use rmcp::{
     ServerHandler,
-    handler::server::{router::tool::ToolRouter, tool::Parameters},
+    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
     model::*,
     schemars, tool, tool_handler, tool_router,
 };
@@ -114,6 +114,9 @@ impl ServerHandler for McpServer {
             server_info: Implementation {
                 name: "siumai-http-mcp-server".into(),
                 version: "1.0.0".into(),
+                title: None,
+                icons: None,
+                website_url: None,
             },
             instructions: Some("HTTP MCP Server providing simple tools like add and get_time for siumai LLM integration examples.".to_string()),
         }
@@ -23,7 +23,7 @@
 
 use rmcp::{
     ServerHandler, ServiceExt,
-    handler::server::{router::tool::ToolRouter, tool::Parameters},
+    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
     model::*,
     schemars, tool, tool_handler, tool_router,
     transport::stdio,
@@ -129,6 +129,9 @@ impl ServerHandler for StdioMcpServer {
             server_info: Implementation {
                 name: "siumai-stdio-mcp-server".into(),
                 version: "1.0.0".into(),
+                title: None,
+                icons: None,
+                website_url: None,
             },
             instructions: Some("Stdio MCP Server providing simple tools like add and get_time for siumai LLM integration examples.".to_string()),
         }
@@ -250,6 +250,8 @@ pub struct LlmBuilder {
     pub(crate) proxy: Option<String>,
     /// Enable cookies
     pub(crate) cookie_store: Option<bool>,
+    /// Record/replay cassette shared by the built clients
+    pub(crate) cassette: Option<std::sync::Arc<crate::cassette::Cassette>>,
     // Note: redirect policy removed due to Clone constraint issues
 }
 
@@ -267,6 +269,7 @@ impl LlmBuilder {
             brotli: None,
             proxy: None,
             cookie_store: None,
+            cassette: None,
             // redirect_policy removed
         }
     }
@@ -419,6 +422,15 @@ impl LlmBuilder {
         self
     }
 
+    /// Record or replay every request of the built clients with a cassette.
+    ///
+    /// # Arguments
+    /// * `cassette` - The cassette, see [`crate::cassette`]
+    pub fn with_cassette(mut self, cassette: std::sync::Arc<crate::cassette::Cassette>) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
     // Note: redirect policy configuration removed due to Clone constraints
 
     // Provider-specific builders
@@ -535,6 +547,7 @@ impl LlmBuilder {
     pub fn openrouter(self) -> crate::providers::openai::OpenAiBuilder {
         // Create OpenAI builder with OpenRouter-specific defaults
         crate::providers::openai::OpenAiBuilder::new(self)
+            .provider_id("openrouter")
             .base_url("https://openrouter.ai/api/v1")
             .model("openai/gpt-4o")
     }
@@ -752,6 +765,21 @@ impl OpenAiBuilder {
         self
     }
 
+    /// Sets the client-side rate limiter shared by clients built with it
+    pub fn rate_limiter(
+        mut self,
+        limiter: std::sync::Arc<crate::rate_limiter::RateLimiter>,
+    ) -> Self {
+        self.http_config.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Sets the cassette requests are recorded into or replayed from
+    pub fn cassette(mut self, cassette: std::sync::Arc<crate::cassette::Cassette>) -> Self {
+        self.http_config.cassette = Some(cassette);
+        self
+    }
+
     // === Tracing Configuration ===
 
     /// Set custom tracing configuration
@@ -857,7 +885,11 @@ impl OpenAiBuilder {
     }
 
     /// Builds the `OpenAI` client
-    pub async fn build(self) -> Result<OpenAiClient, LlmError> {
+    pub async fn build(mut self) -> Result<OpenAiClient, LlmError> {
+        if self.http_config.cassette.is_none() {
+            self.http_config.cassette = self.base.cassette.clone();
+        }
+
         let api_key = self
             .api_key
             .or_else(|| std::env::var("OPENAI_API_KEY").ok())
@@ -1001,6 +1033,21 @@ impl AnthropicBuilder {
         self
     }
 
+    /// Sets the client-side rate limiter shared by clients built with it
+    pub fn rate_limiter(
+        mut self,
+        limiter: std::sync::Arc<crate::rate_limiter::RateLimiter>,
+    ) -> Self {
+        self.http_config.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Sets the cassette requests are recorded into or replayed from
+    pub fn cassette(mut self, cassette: std::sync::Arc<crate::cassette::Cassette>) -> Self {
+        self.http_config.cassette = Some(cassette);
+        self
+    }
+
     // === Tracing Configuration ===
 
     /// Set custom tracing configuration
@@ -1064,7 +1111,11 @@ impl AnthropicBuilder {
     }
 
     /// Builds the Anthropic client
-    pub async fn build(self) -> Result<AnthropicClient, LlmError> {
+    pub async fn build(mut self) -> Result<AnthropicClient, LlmError> {
+        if self.http_config.cassette.is_none() {
+            self.http_config.cassette = self.base.cassette.clone();
+        }
+
         let api_key = self
             .api_key
             .or_else(|| std::env::var("ANTHROPIC_API_KEY").ok())
@@ -1184,6 +1235,8 @@ pub struct GeminiBuilder {
     json_schema: Option<serde_json::Value>,
     /// Thinking configuration
     thinking_config: Option<crate::providers::gemini::ThinkingConfig>,
+    /// Client-side rate limiter
+    rate_limiter: Option<std::sync::Arc<crate::rate_limiter::RateLimiter>>,
     /// Tracing configuration
     tracing_config: Option<crate::tracing::TracingConfig>,
 }
@@ -1206,6 +1259,7 @@ impl GeminiBuilder {
             safety_settings: None,
             json_schema: None,
             thinking_config: None,
+            rate_limiter: None,
             tracing_config: None,
         }
     }
@@ -1333,6 +1387,21 @@ impl GeminiBuilder {
         self
     }
 
+    /// Sets the client-side rate limiter shared by clients built with it
+    pub fn rate_limiter(
+        mut self,
+        limiter: std::sync::Arc<crate::rate_limiter::RateLimiter>,
+    ) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Sets the cassette requests are recorded into or replayed from
+    pub fn cassette(mut self, cassette: std::sync::Arc<crate::cassette::Cassette>) -> Self {
+        self.base.cassette = Some(cassette);
+        self
+    }
+
     // === Tracing Configuration ===
 
     /// Set custom tracing configuration
@@ -1459,6 +1528,14 @@ impl GeminiBuilder {
             config = config.with_timeout(timeout.as_secs());
         }
 
+        if let Some(rate_limiter) = self.rate_limiter {
+            config = config.with_rate_limiter(rate_limiter);
+        }
+
+        if let Some(cassette) = self.base.cassette {
+            config = config.with_cassette(cassette);
+        }
+
         let mut client = crate::providers::gemini::GeminiClient::new(config)?;
         client.set_tracing_guard(_tracing_guard);
         client.set_tracing_config(self.tracing_config);
@@ -1674,6 +1751,21 @@ impl OllamaBuilder {
         self.reasoning(think)
     }
 
+    /// Sets the client-side rate limiter shared by clients built with it
+    pub fn rate_limiter(
+        mut self,
+        limiter: std::sync::Arc<crate::rate_limiter::RateLimiter>,
+    ) -> Self {
+        self.http_config.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Sets the cassette requests are recorded into or replayed from
+    pub fn cassette(mut self, cassette: std::sync::Arc<crate::cassette::Cassette>) -> Self {
+        self.http_config.cassette = Some(cassette);
+        self
+    }
+
     // === Tracing Configuration ===
 
     /// Set custom tracing configuration
@@ -1720,7 +1812,11 @@ impl OllamaBuilder {
     }
 
     /// Build the Ollama client
-    pub async fn build(self) -> Result<crate::providers::ollama::OllamaClient, LlmError> {
+    pub async fn build(mut self) -> Result<crate::providers::ollama::OllamaClient, LlmError> {
+        if self.http_config.cassette.is_none() {
+            self.http_config.cassette = self.base.cassette.clone();
+        }
+
         let base_url = self
             .base_url
             .unwrap_or_else(|| "http://localhost:11434".to_string());
@@ -1838,6 +1934,21 @@ impl XaiBuilderWrapper {
         self
     }
 
+    /// Set the client-side rate limiter
+    pub fn rate_limiter(
+        mut self,
+        limiter: std::sync::Arc<crate::rate_limiter::RateLimiter>,
+    ) -> Self {
+        self.xai_builder = self.xai_builder.rate_limiter(limiter);
+        self
+    }
+
+    /// Sets the cassette requests are recorded into or replayed from
+    pub fn cassette(mut self, cassette: std::sync::Arc<crate::cassette::Cassette>) -> Self {
+        self.base.cassette = Some(cassette);
+        self
+    }
+
     /// Enable tracing
     pub fn tracing(mut self, config: crate::tracing::TracingConfig) -> Self {
         self.xai_builder = self.xai_builder.tracing(config);
@@ -1867,8 +1978,13 @@ impl XaiBuilderWrapper {
         // Build HTTP client from base configuration
         let http_client = self.base.build_http_client()?;
 
+        let mut xai_builder = self.xai_builder;
+        if let Some(cassette) = self.base.cassette {
+            xai_builder = xai_builder.cassette(cassette);
+        }
+
         // Use the build_with_client method to pass the custom HTTP client
-        self.xai_builder.build_with_client(http_client).await
+        xai_builder.build_with_client(http_client).await
     }
 }
 
@@ -1948,6 +2064,21 @@ impl GroqBuilderWrapper {
         self
     }
 
+    /// Set the client-side rate limiter
+    pub fn rate_limiter(
+        mut self,
+        limiter: std::sync::Arc<crate::rate_limiter::RateLimiter>,
+    ) -> Self {
+        self.groq_builder = self.groq_builder.rate_limiter(limiter);
+        self
+    }
+
+    /// Sets the cassette requests are recorded into or replayed from
+    pub fn cassette(mut self, cassette: std::sync::Arc<crate::cassette::Cassette>) -> Self {
+        self.base.cassette = Some(cassette);
+        self
+    }
+
     /// Enable tracing
     pub fn tracing(mut self, config: crate::tracing::TracingConfig) -> Self {
         self.groq_builder = self.groq_builder.tracing(config);
@@ -2000,6 +2131,11 @@ impl GroqBuilderWrapper {
             groq_builder = groq_builder.header(key, value);
         }
 
+        // Apply cassette
+        if let Some(cassette) = self.base.cassette {
+            groq_builder = groq_builder.cassette(cassette);
+        }
+
         groq_builder.build().await
     }
 }
@@ -0,0 +1,1223 @@
+//! Response Cache Layer
+//!
+//! This module provides [`CacheLayer`], a wrapper around any chat client that
+//! serves repeated requests from a pluggable [`CacheBackend`] instead of calling
+//! the provider again.
+//!
+//! Cache keys are a SHA-256 digest of the full normalized request: the messages,
+//! the common parameters, the tools and the provider-specific parameters.
+//! Changing any of them produces a different key, so a cached answer is never
+//! returned for a different model or configuration. The digest is stable across
+//! processes and toolchains, so persistent backends keep their entries.
+//!
+//! Two backends are included:
+//! - [`InMemoryCacheBackend`] - a bounded LRU map, suitable for a single process
+//! - [`FileCacheBackend`] - one JSON file per entry, shared across restarts
+//!
+//! # Example
+//! ```rust,no_run
+//! use siumai::cache::{CacheLayer, FileCacheBackend};
+//! use siumai::prelude::*;
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<(), LlmError> {
+//! let client = LlmBuilder::new()
+//!     .openai()
+//!     .api_key("your-api-key")
+//!     .model("gpt-4o-mini")
+//!     .build()
+//!     .await?;
+//!
+//! let cached = CacheLayer::new(client)
+//!     .with_common_params(CommonParams {
+//!         model: "gpt-4o-mini".to_string(),
+//!         ..Default::default()
+//!     })
+//!     .with_backend(FileCacheBackend::new(".siumai-cache").with_max_entries(500))
+//!     .with_ttl(Duration::from_secs(3600));
+//!
+//! // The second call is served from the cache
+//! let first = cached.chat(vec![user!("What is Rust?")]).await?;
+//! let second = cached.chat(vec![user!("What is Rust?")]).await?;
+//! assert_eq!(first.content_text(), second.content_text());
+//! println!("hits: {}", cached.stats().hits);
+//! # Ok(())
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::client::LlmClient;
+use crate::error::LlmError;
+use crate::stream::{ChatStream, ChatStreamEvent, StreamProcessor};
+use crate::traits::*;
+use crate::types::*;
+
+/// Default maximum number of entries kept by [`CacheLayer::new`]
+pub const DEFAULT_MAX_ENTRIES: usize = 1000;
+
+/// Metadata key set on responses served from the cache
+pub const CACHE_HIT_METADATA_KEY: &str = "cache_hit";
+
+/// Current time in seconds since the Unix epoch
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// A cached chat response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// The cached response
+    pub response: ChatResponse,
+    /// Creation time (seconds since the Unix epoch)
+    pub created_at: u64,
+    /// Expiry time (seconds since the Unix epoch), `None` if the entry never expires
+    pub expires_at: Option<u64>,
+}
+
+impl CacheEntry {
+    /// Create a new entry, optionally expiring after `ttl`
+    pub fn new(response: ChatResponse, ttl: Option<Duration>) -> Self {
+        let created_at = unix_now();
+        Self {
+            response,
+            created_at,
+            expires_at: ttl.map(|ttl| created_at.saturating_add(ttl.as_secs())),
+        }
+    }
+
+    /// Check whether the entry has expired
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|at| unix_now() >= at)
+    }
+}
+
+/// Storage backend for cached responses.
+///
+/// Backends are responsible for their own size limits and eviction policy;
+/// expiry is checked by [`CacheLayer`] on read.
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    /// Get an entry by key
+    async fn get(&self, key: &str) -> Result<Option<CacheEntry>, LlmError>;
+
+    /// Insert or replace an entry
+    async fn put(&self, key: &str, entry: CacheEntry) -> Result<(), LlmError>;
+
+    /// Remove an entry
+    async fn remove(&self, key: &str) -> Result<(), LlmError>;
+
+    /// Remove all entries
+    async fn clear(&self) -> Result<(), LlmError>;
+
+    /// Number of stored entries
+    async fn len(&self) -> Result<usize, LlmError>;
+
+    /// Whether the backend holds no entries
+    async fn is_empty(&self) -> Result<bool, LlmError> {
+        Ok(self.len().await? == 0)
+    }
+}
+
+/// In-memory LRU state
+#[derive(Debug, Default)]
+struct LruState {
+    entries: HashMap<String, CacheEntry>,
+    /// Keys ordered from least to most recently used
+    order: VecDeque<String>,
+}
+
+impl LruState {
+    fn touch(&mut self, key: &str) {
+        if let Some(pos) = self.order.iter().position(|k| k == key) {
+            self.order.remove(pos);
+        }
+        self.order.push_back(key.to_string());
+    }
+}
+
+/// In-memory cache backend with LRU eviction
+#[derive(Debug)]
+pub struct InMemoryCacheBackend {
+    state: Mutex<LruState>,
+    max_entries: usize,
+}
+
+impl InMemoryCacheBackend {
+    /// Create a backend holding at most `max_entries` responses
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            state: Mutex::new(LruState::default()),
+            max_entries: max_entries.max(1),
+        }
+    }
+
+    /// Maximum number of entries
+    pub const fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for InMemoryCacheBackend {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ENTRIES)
+    }
+}
+
+#[async_trait]
+impl CacheBackend for InMemoryCacheBackend {
+    async fn get(&self, key: &str) -> Result<Option<CacheEntry>, LlmError> {
+        let mut state = self.lock();
+        let entry = state.entries.get(key).cloned();
+        if entry.is_some() {
+            state.touch(key);
+        }
+        Ok(entry)
+    }
+
+    async fn put(&self, key: &str, entry: CacheEntry) -> Result<(), LlmError> {
+        let mut state = self.lock();
+        state.entries.insert(key.to_string(), entry);
+        state.touch(key);
+
+        while state.entries.len() > self.max_entries {
+            match state.order.pop_front() {
+                Some(oldest) => {
+                    state.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), LlmError> {
+        let mut state = self.lock();
+        state.entries.remove(key);
+        if let Some(pos) = state.order.iter().position(|k| k == key) {
+            state.order.remove(pos);
+        }
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<(), LlmError> {
+        let mut state = self.lock();
+        state.entries.clear();
+        state.order.clear();
+        Ok(())
+    }
+
+    async fn len(&self) -> Result<usize, LlmError> {
+        Ok(self.lock().entries.len())
+    }
+}
+
+/// On-disk cache backend storing one JSON file per entry.
+///
+/// Recency is tracked through file modification times, so the LRU order
+/// survives restarts and the directory can be shared between processes.
+#[derive(Debug, Clone)]
+pub struct FileCacheBackend {
+    dir: PathBuf,
+    max_entries: Option<usize>,
+}
+
+impl FileCacheBackend {
+    /// Create a backend storing entries under `dir` (created on first write)
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: dir.into(),
+            max_entries: None,
+        }
+    }
+
+    /// Limit the number of stored entries, evicting the least recently used
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries.max(1));
+        self
+    }
+
+    /// Cache directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    /// List cache files with their modification times
+    async fn list_entries(&self) -> Result<Vec<(PathBuf, SystemTime)>, LlmError> {
+        let mut dir = match tokio::fs::read_dir(&self.dir).await {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(io_error("read cache directory", e)),
+        };
+
+        let mut entries = Vec::new();
+        while let Some(item) = dir
+            .next_entry()
+            .await
+            .map_err(|e| io_error("read cache directory", e))?
+        {
+            let path = item.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let modified = item
+                    .metadata()
+                    .await
+                    .and_then(|m| m.modified())
+                    .unwrap_or(UNIX_EPOCH);
+                entries.push((path, modified));
+            }
+        }
+        Ok(entries)
+    }
+
+    async fn evict(&self, max_entries: usize) -> Result<(), LlmError> {
+        let mut entries = self.list_entries().await?;
+        if entries.len() <= max_entries {
+            return Ok(());
+        }
+
+        entries.sort_by_key(|(_, modified)| *modified);
+        let excess = entries.len() - max_entries;
+        for (path, _) in entries.into_iter().take(excess) {
+            remove_file(&path).await?;
+        }
+        Ok(())
+    }
+}
+
+fn io_error(action: &str, error: std::io::Error) -> LlmError {
+    LlmError::IoError(format!("Failed to {action}: {error}"))
+}
+
+async fn remove_file(path: &Path) -> Result<(), LlmError> {
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(io_error("remove cache entry", e)),
+    }
+}
+
+/// Mark a cache file as recently used
+fn touch_file(path: &Path) {
+    if let Ok(file) = std::fs::File::options().append(true).open(path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+}
+
+#[async_trait]
+impl CacheBackend for FileCacheBackend {
+    async fn get(&self, key: &str) -> Result<Option<CacheEntry>, LlmError> {
+        let path = self.entry_path(key);
+        let bytes = match tokio::fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error("read cache entry", e)),
+        };
+
+        match serde_json::from_slice::<CacheEntry>(&bytes) {
+            Ok(entry) => {
+                if self.max_entries.is_some() {
+                    touch_file(&path);
+                }
+                Ok(Some(entry))
+            }
+            Err(e) => {
+                // A corrupt entry is dropped rather than failing every lookup
+                tracing::warn!("Discarding unreadable cache entry {}: {e}", path.display());
+                remove_file(&path).await?;
+                Ok(None)
+            }
+        }
+    }
+
+    async fn put(&self, key: &str, entry: CacheEntry) -> Result<(), LlmError> {
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| io_error("create cache directory", e))?;
+
+        let data = serde_json::to_vec(&entry)
+            .map_err(|e| LlmError::ParseError(format!("Failed to serialize cache entry: {e}")))?;
+
+        // Write to a temporary file first so readers never see a partial entry;
+        // the name is unique so concurrent writers of a key don't collide
+        let path = self.entry_path(key);
+        let tmp_path = self
+            .dir
+            .join(format!("{key}.{}.tmp", uuid::Uuid::new_v4().simple()));
+        let written = match tokio::fs::write(&tmp_path, data).await {
+            Ok(()) => tokio::fs::rename(&tmp_path, &path).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = written {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(io_error("write cache entry", e));
+        }
+
+        if let Some(max_entries) = self.max_entries {
+            self.evict(max_entries).await?;
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), LlmError> {
+        remove_file(&self.entry_path(key)).await
+    }
+
+    async fn clear(&self) -> Result<(), LlmError> {
+        for (path, _) in self.list_entries().await? {
+            remove_file(&path).await?;
+        }
+        Ok(())
+    }
+
+    async fn len(&self) -> Result<usize, LlmError> {
+        Ok(self.list_entries().await?.len())
+    }
+}
+
+/// Cache hit/miss statistics for a [`CacheLayer`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheLayerStats {
+    /// Requests served from the cache
+    pub hits: u64,
+    /// Requests forwarded to the wrapped client
+    pub misses: u64,
+}
+
+impl CacheLayerStats {
+    /// Fraction of requests served from the cache
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// 64-bit FNV-1a hash, stable across processes and platforms
+fn fnv1a(data: &[u8]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    data.iter().fold(OFFSET, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Response caching wrapper for chat clients.
+///
+/// Wraps any [`ChatCapability`] (and, when the inner type is an [`LlmClient`],
+/// forwards all other capabilities untouched). Non-streaming and streaming calls
+/// share the same cache: a response produced by a stream is stored once the
+/// stream ends, and a cached response is replayed as a synthetic [`ChatStream`].
+///
+/// The key always includes the wrapped client's model (see
+/// [`ChatCapability::default_model`]). Its other parameters are not visible
+/// through `ChatCapability`, so pass them with
+/// [`with_common_params`](Self::with_common_params) and
+/// [`with_provider_params`](Self::with_provider_params) to make them part of the
+/// key. Use [`with_namespace`](Self::with_namespace) when several clients share
+/// one backend.
+pub struct CacheLayer<C: ?Sized> {
+    inner: Arc<C>,
+    backend: Arc<dyn CacheBackend>,
+    ttl: Option<Duration>,
+    namespace: Option<String>,
+    common_params: CommonParams,
+    provider_params: Option<ProviderParams>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+}
+
+impl<C> CacheLayer<C> {
+    /// Wrap a client with an in-memory cache of [`DEFAULT_MAX_ENTRIES`] entries
+    pub fn new(inner: C) -> Self {
+        Self::from_arc(Arc::new(inner))
+    }
+}
+
+impl<C: ?Sized> CacheLayer<C> {
+    /// Wrap a shared client with an in-memory cache of [`DEFAULT_MAX_ENTRIES`] entries
+    pub fn from_arc(inner: Arc<C>) -> Self {
+        Self {
+            inner,
+            backend: Arc::new(InMemoryCacheBackend::default()),
+            ttl: None,
+            namespace: None,
+            common_params: CommonParams::default(),
+            provider_params: None,
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Use a different storage backend
+    pub fn with_backend<B: CacheBackend + 'static>(mut self, backend: B) -> Self {
+        self.backend = Arc::new(backend);
+        self
+    }
+
+    /// Use a storage backend shared with other cache layers
+    pub fn with_shared_backend(mut self, backend: Arc<dyn CacheBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Expire entries after `ttl`
+    pub const fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Prefix all keys with a namespace
+    pub fn with_namespace<S: Into<String>>(mut self, namespace: S) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Set the common parameters included in cache keys
+    pub fn with_common_params(mut self, params: CommonParams) -> Self {
+        self.common_params = params;
+        self
+    }
+
+    /// Set the provider parameters included in cache keys
+    pub fn with_provider_params(mut self, params: ProviderParams) -> Self {
+        self.provider_params = Some(params);
+        self
+    }
+
+    /// Get the wrapped client
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Get the storage backend
+    pub fn backend(&self) -> &Arc<dyn CacheBackend> {
+        &self.backend
+    }
+
+    /// Get the configured time-to-live
+    pub const fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+
+    /// Get hit/miss statistics
+    pub fn stats(&self) -> CacheLayerStats {
+        CacheLayerStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Remove all cached entries
+    pub async fn clear(&self) -> Result<(), LlmError> {
+        self.backend.clear().await
+    }
+
+    /// Compute the cache key of a normalized request.
+    ///
+    /// Message metadata (timestamps, IDs, cache control) does not affect the
+    /// key, and provider parameters are hashed in sorted order.
+    pub fn cache_key(request: &ChatRequest) -> String {
+        let messages: Vec<_> = request
+            .messages
+            .iter()
+            .map(|m| {
+                serde_json::json!({
+                    "role": m.role,
+                    "content": m.content,
+                    "tool_calls": m.tool_calls,
+                    "tool_call_id": m.tool_call_id,
+                })
+            })
+            .collect();
+        let provider_params: Option<BTreeMap<_, _>> = request
+            .provider_params
+            .as_ref()
+            .map(|p| p.params.iter().collect());
+
+        let normalized = serde_json::json!({
+            "messages": messages,
+            "tools": request.tools,
+            "common_params": request.common_params,
+            "provider_params": provider_params,
+            "web_search": request.web_search,
+            "output_schema": request.output_schema,
+        });
+        // serde_json maps are ordered, so this rendering is deterministic
+        Sha256::digest(normalized.to_string().as_bytes())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    /// Look up a live entry, dropping it if expired
+    async fn lookup(&self, key: &str) -> Option<ChatResponse> {
+        let entry = match self.backend.get(key).await {
+            Ok(entry) => entry,
+            Err(e) => {
+                tracing::warn!("Cache lookup failed: {e}");
+                None
+            }
+        };
+
+        match entry {
+            Some(entry) if entry.is_expired() => {
+                if let Err(e) = self.backend.remove(key).await {
+                    tracing::warn!("Failed to remove expired cache entry: {e}");
+                }
+                None
+            }
+            Some(entry) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                let mut response = entry.response;
+                response
+                    .metadata
+                    .insert(CACHE_HIT_METADATA_KEY.to_string(), true.into());
+                Some(response)
+            }
+            None => None,
+        }
+    }
+
+    /// Store a response; backend failures never fail the request
+    async fn store(&self, key: &str, response: &ChatResponse) {
+        if let Err(e) = self
+            .backend
+            .put(key, CacheEntry::new(response.clone(), self.ttl))
+            .await
+        {
+            tracing::warn!("Failed to store cache entry: {e}");
+        }
+    }
+}
+
+impl<C: ChatCapability + ?Sized> CacheLayer<C> {
+    /// Remove the cached response for a conversation, if any
+    pub async fn invalidate(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+    ) -> Result<(), LlmError> {
+        let key = self.key_for(messages, tools);
+        self.backend.remove(&key).await
+    }
+
+    /// Build the key for a call on this layer
+    fn key_for(&self, messages: &[ChatMessage], tools: Option<&[Tool]>) -> String {
+        self.key_for_request(&ChatRequest {
+            messages: messages.to_vec(),
+            tools: tools.map(<[Tool]>::to_vec),
+            ..Default::default()
+        })
+    }
+
+    /// Cache key of a request whose parameters override the configured ones
+    fn key_for_request(&self, request: &ChatRequest) -> String {
+        // Always key on the model, even when no common parameters were configured
+        let mut common_params = self.common_params.merged_with(&request.common_params);
+        if common_params.model.is_empty() {
+            common_params.model = self.inner.default_model().unwrap_or_default();
+        }
+        let provider_params = ProviderParams::overlay(
+            self.provider_params.as_ref(),
+            request.provider_params.as_ref(),
+        );
+
+        let request = ChatRequest {
+            messages: request.messages.clone(),
+            tools: request.tools.clone(),
+            common_params,
+            provider_params,
+            web_search: request.web_search.clone(),
+            output_schema: request.output_schema.clone(),
+            ..Default::default()
+        };
+
+        let key = Self::cache_key(&request);
+        match &self.namespace {
+            Some(namespace) => format!("{}-{key}", fnv1a(namespace.as_bytes())),
+            None => key,
+        }
+    }
+}
+
+impl<C: ?Sized> Clone for CacheLayer<C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            backend: self.backend.clone(),
+            ttl: self.ttl,
+            namespace: self.namespace.clone(),
+            common_params: self.common_params.clone(),
+            provider_params: self.provider_params.clone(),
+            hits: self.hits.clone(),
+            misses: self.misses.clone(),
+        }
+    }
+}
+
+impl<C: ?Sized> std::fmt::Debug for CacheLayer<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CacheLayer")
+            .field("ttl", &self.ttl)
+            .field("namespace", &self.namespace)
+            .field("common_params", &self.common_params)
+            .field("stats", &self.stats())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Stream wrapper that stores the accumulated response once the stream is done.
+///
+/// Providers end streams with an empty response (OpenAI even sends two), so the
+/// cached response is built from the deltas. It is stored when the stream is
+/// dropped, so consumers that stop reading at `StreamEnd` are covered too.
+/// Streams that failed or never reached `StreamEnd` are not cached.
+struct StoringStream {
+    inner: ChatStream,
+    layer: CacheLayer<()>,
+    key: String,
+    processor: StreamProcessor,
+    end: Option<ChatResponse>,
+    failed: bool,
+}
+
+impl futures::Stream for StoringStream {
+    type Item = Result<ChatStreamEvent, LlmError>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let poll = self.inner.poll_next_unpin(cx);
+        if let std::task::Poll::Ready(Some(item)) = &poll {
+            match item {
+                Ok(ChatStreamEvent::StreamEnd { response }) => {
+                    if self.end.is_none() {
+                        self.end = Some(response.clone());
+                    }
+                }
+                Ok(ChatStreamEvent::StreamStart { .. }) => {}
+                Ok(ChatStreamEvent::Error { .. }) | Err(_) => self.failed = true,
+                Ok(event) => {
+                    self.processor.process_event(event.clone());
+                }
+            }
+        }
+        poll
+    }
+}
+
+impl Drop for StoringStream {
+    fn drop(&mut self) {
+        if self.failed {
+            return;
+        }
+        let Some(end) = self.end.take() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        let response = self.processor.build_final_response_with_end(Some(&end));
+        let layer = self.layer.clone();
+        let key = std::mem::take(&mut self.key);
+        runtime.spawn(async move { layer.store(&key, &response).await });
+    }
+}
+
+/// Replay a complete response as a chat stream.
+///
+/// Emits `StreamStart`, the thinking, content and tool call deltas, the usage
+/// and finally `StreamEnd` carrying the response itself.
+pub fn replay_stream(response: ChatResponse) -> ChatStream {
+    let mut events = vec![ChatStreamEvent::StreamStart {
+        metadata: ResponseMetadata {
+            id: response.id.clone(),
+            model: response.model.clone(),
+            created: None,
+            provider: "cache".to_string(),
+            request_id: None,
+        },
+    }];
+
+    if let Some(thinking) = response.thinking.as_ref().filter(|t| !t.is_empty()) {
+        events.push(ChatStreamEvent::ThinkingDelta {
+            delta: thinking.clone(),
+        });
+    }
+
+    let text = response.content.all_text();
+    if !text.is_empty() {
+        events.push(ChatStreamEvent::ContentDelta {
+            delta: text,
+            index: None,
+        });
+    }
+
+    for (index, call) in response.tool_calls.iter().flatten().enumerate() {
+        events.push(ChatStreamEvent::ToolCallDelta {
+            id: call.id.clone(),
+            function_name: call.function.as_ref().map(|f| f.name.clone()),
+            arguments_delta: call.function.as_ref().map(|f| f.arguments.clone()),
+            index: Some(index),
+        });
+    }
+
+    if let Some(usage) = response.usage.clone() {
+        events.push(ChatStreamEvent::UsageUpdate { usage });
+    }
+
+    events.push(ChatStreamEvent::StreamEnd { response });
+    Box::pin(futures::stream::iter(events.into_iter().map(Ok)))
+}
+
+#[async_trait]
+impl<C: ChatCapability + ?Sized> ChatCapability for CacheLayer<C> {
+    fn default_model(&self) -> Option<String> {
+        Some(self.common_params.model.clone())
+            .filter(|model| !model.is_empty())
+            .or_else(|| self.inner.default_model())
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+    ) -> Result<ChatResponse, LlmError> {
+        let key = self.key_for(&messages, tools.as_deref());
+        if let Some(response) = self.lookup(&key).await {
+            return Ok(response);
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let response = self.inner.chat_with_tools(messages, tools).await?;
+        self.store(&key, &response).await;
+        Ok(response)
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+    ) -> Result<ChatStream, LlmError> {
+        let key = self.key_for(&messages, tools.as_deref());
+        if let Some(response) = self.lookup(&key).await {
+            return Ok(replay_stream(response));
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let stream = self.inner.chat_stream(messages, tools).await?;
+        Ok(self.storing_stream(stream, key))
+    }
+
+    async fn chat_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatResponse, LlmError> {
+        self.chat_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    async fn chat_stream_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatStream, LlmError> {
+        self.chat_stream_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    async fn chat_with_request(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        let key = self.key_for_request(&request);
+        if let Some(response) = self.lookup(&key).await {
+            return Ok(response);
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let response = self.inner.chat_with_request(request).await?;
+        self.store(&key, &response).await;
+        Ok(response)
+    }
+
+    async fn chat_stream_with_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
+        let key = self.key_for_request(&request);
+        if let Some(response) = self.lookup(&key).await {
+            return Ok(replay_stream(response));
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let stream = self.inner.chat_stream_with_request(request).await?;
+        Ok(self.storing_stream(stream, key))
+    }
+}
+
+impl<C: ?Sized> CacheLayer<C> {
+    /// Pass a stream through, caching its response once it completes
+    fn storing_stream(&self, stream: ChatStream, key: String) -> ChatStream {
+        Box::pin(StoringStream {
+            inner: stream,
+            layer: self.detached(),
+            key,
+            processor: StreamProcessor::new(),
+            end: None,
+            failed: false,
+        })
+    }
+
+    /// Copy of the cache state without the wrapped client
+    fn detached(&self) -> CacheLayer<()> {
+        CacheLayer {
+            inner: Arc::new(()),
+            backend: self.backend.clone(),
+            ttl: self.ttl,
+            namespace: self.namespace.clone(),
+            common_params: self.common_params.clone(),
+            provider_params: self.provider_params.clone(),
+            hits: self.hits.clone(),
+            misses: self.misses.clone(),
+        }
+    }
+}
+
+impl<C: LlmClient + ?Sized + 'static> LlmClient for CacheLayer<C> {
+    fn provider_name(&self) -> &'static str {
+        self.inner.provider_name()
+    }
+
+    fn supported_models(&self) -> Vec<String> {
+        self.inner.supported_models()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn LlmClient> {
+        Box::new(self.clone())
+    }
+
+    fn as_embedding_capability(&self) -> Option<&dyn EmbeddingCapability> {
+        self.inner.as_embedding_capability()
+    }
+
+    fn as_audio_capability(&self) -> Option<&dyn AudioCapability> {
+        self.inner.as_audio_capability()
+    }
+
+    fn as_vision_capability(&self) -> Option<&dyn VisionCapability> {
+        self.inner.as_vision_capability()
+    }
+
+    fn as_image_generation_capability(&self) -> Option<&dyn ImageGenerationCapability> {
+        self.inner.as_image_generation_capability()
+    }
+
+    fn as_completion_capability(&self) -> Option<&dyn CompletionCapability> {
+        self.inner.as_completion_capability()
+    }
+
+    fn as_batch_capability(&self) -> Option<&dyn BatchCapability> {
+        self.inner.as_batch_capability()
+    }
+
+    fn as_conversation_state_capability(&self) -> Option<&dyn ConversationStateCapability> {
+        self.inner.as_conversation_state_capability()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    /// Chat client answering with a numbered response per call
+    #[derive(Default)]
+    struct CountingClient {
+        calls: AtomicUsize,
+        model: Option<String>,
+    }
+
+    impl CountingClient {
+        fn response(&self) -> ChatResponse {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            let mut response = ChatResponse::new(MessageContent::Text(format!("answer {n}")));
+            response.usage = Some(Usage::new(3, 2));
+            response
+        }
+    }
+
+    #[async_trait]
+    impl ChatCapability for CountingClient {
+        async fn chat_with_tools(
+            &self,
+            _messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+        ) -> Result<ChatResponse, LlmError> {
+            Ok(self.response())
+        }
+
+        async fn chat_stream(
+            &self,
+            _messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+        ) -> Result<ChatStream, LlmError> {
+            // Shaped like an OpenAI stream: empty end responses, sent twice
+            let response = self.response();
+            let mut end = ChatResponse::new(MessageContent::Text(String::new()));
+            end.finish_reason = Some(FinishReason::Stop);
+            let events = vec![
+                ChatStreamEvent::ContentDelta {
+                    delta: response.content_text().unwrap_or_default().to_string(),
+                    index: None,
+                },
+                ChatStreamEvent::UsageUpdate {
+                    usage: response.usage.clone().unwrap(),
+                },
+                ChatStreamEvent::StreamEnd {
+                    response: end.clone(),
+                },
+                ChatStreamEvent::StreamEnd { response: end },
+            ];
+            Ok(Box::pin(futures::stream::iter(events.into_iter().map(Ok))))
+        }
+
+        fn default_model(&self) -> Option<String> {
+            self.model.clone()
+        }
+    }
+
+    fn request(model: &str) -> ChatRequest {
+        let mut request = ChatRequest::new(vec![ChatMessage::user("hello").build()]);
+        request.common_params.model = model.to_string();
+        request
+    }
+
+    #[test]
+    fn test_cache_key_covers_model_and_tools() {
+        let base = CacheLayer::<()>::cache_key(&request("gpt-4o"));
+        assert_eq!(base, CacheLayer::<()>::cache_key(&request("gpt-4o")));
+        assert_ne!(base, CacheLayer::<()>::cache_key(&request("gpt-4o-mini")));
+
+        let mut with_tools = request("gpt-4o");
+        with_tools.tools = Some(vec![Tool::function(
+            "lookup".to_string(),
+            "Look something up".to_string(),
+            serde_json::json!({"type": "object"}),
+        )]);
+        assert_ne!(base, CacheLayer::<()>::cache_key(&with_tools));
+
+        let mut with_params = request("gpt-4o");
+        with_params.provider_params = Some(ProviderParams {
+            params: HashMap::from([("reasoning_effort".to_string(), "high".into())]),
+        });
+        assert_ne!(base, CacheLayer::<()>::cache_key(&with_params));
+    }
+
+    #[tokio::test]
+    async fn test_chat_served_from_cache() {
+        let layer = CacheLayer::new(CountingClient::default());
+        let messages = vec![ChatMessage::user("hello").build()];
+
+        let first = layer.chat(messages.clone()).await.unwrap();
+        let second = layer.chat(messages).await.unwrap();
+
+        assert_eq!(first.content_text(), Some("answer 1"));
+        assert_eq!(second.content_text(), Some("answer 1"));
+        assert_eq!(
+            second.metadata.get(CACHE_HIT_METADATA_KEY),
+            Some(&true.into())
+        );
+        assert_eq!(layer.stats(), CacheLayerStats { hits: 1, misses: 1 });
+    }
+
+    #[tokio::test]
+    async fn test_expired_entries_are_refetched() {
+        let layer = CacheLayer::new(CountingClient::default()).with_ttl(Duration::ZERO);
+        let messages = vec![ChatMessage::user("hello").build()];
+
+        layer.chat(messages.clone()).await.unwrap();
+        let second = layer.chat(messages).await.unwrap();
+
+        assert_eq!(second.content_text(), Some("answer 2"));
+        assert_eq!(layer.stats().hits, 0);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_lru_eviction() {
+        let backend = InMemoryCacheBackend::new(2);
+        let entry = |text: &str| {
+            CacheEntry::new(
+                ChatResponse::new(MessageContent::Text(text.to_string())),
+                None,
+            )
+        };
+
+        backend.put("a", entry("a")).await.unwrap();
+        backend.put("b", entry("b")).await.unwrap();
+        // Touch "a" so that "b" becomes the least recently used entry
+        backend.get("a").await.unwrap();
+        backend.put("c", entry("c")).await.unwrap();
+
+        assert_eq!(backend.len().await.unwrap(), 2);
+        assert!(backend.get("a").await.unwrap().is_some());
+        assert!(backend.get("b").await.unwrap().is_none());
+        assert!(backend.get("c").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_file_backend_round_trip() {
+        let dir = std::env::temp_dir().join(format!("siumai-cache-test-{}", std::process::id()));
+        let backend = FileCacheBackend::new(&dir).with_max_entries(1);
+
+        backend
+            .put(
+                "first",
+                CacheEntry::new(
+                    ChatResponse::new(MessageContent::Text("one".to_string())),
+                    None,
+                ),
+            )
+            .await
+            .unwrap();
+        let entry = backend.get("first").await.unwrap().unwrap();
+        assert_eq!(entry.response.content_text(), Some("one"));
+
+        backend
+            .put(
+                "second",
+                CacheEntry::new(
+                    ChatResponse::new(MessageContent::Text("two".to_string())),
+                    None,
+                ),
+            )
+            .await
+            .unwrap();
+        assert_eq!(backend.len().await.unwrap(), 1);
+
+        backend.clear().await.unwrap();
+        assert!(backend.is_empty().await.unwrap());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_file_backend_concurrent_writes() {
+        let dir = std::env::temp_dir().join(format!(
+            "siumai-cache-concurrent-test-{}",
+            std::process::id()
+        ));
+        let backend = Arc::new(FileCacheBackend::new(&dir));
+
+        let writes = (0..8).map(|i| {
+            let backend = backend.clone();
+            tokio::spawn(async move {
+                let response = ChatResponse::new(MessageContent::Text(i.to_string()));
+                backend.put("shared", CacheEntry::new(response, None)).await
+            })
+        });
+        for write in futures::future::join_all(writes).await {
+            write.unwrap().unwrap();
+        }
+
+        assert!(backend.get("shared").await.unwrap().is_some());
+        let files = std::fs::read_dir(&dir).unwrap().count();
+        assert_eq!(files, 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_stream_replayed_from_cache() {
+        let layer = CacheLayer::new(CountingClient::default());
+        let messages = vec![ChatMessage::user("hello").build()];
+
+        layer.chat(messages.clone()).await.unwrap();
+        let events: Vec<_> = layer
+            .chat_stream(messages, None)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert!(matches!(
+            events.first(),
+            Some(Ok(ChatStreamEvent::StreamStart { .. }))
+        ));
+        assert!(events.iter().any(|e| matches!(
+            e,
+            Ok(ChatStreamEvent::ContentDelta { delta, .. }) if delta == "answer 1"
+        )));
+        match events.last() {
+            Some(Ok(ChatStreamEvent::StreamEnd { response })) => {
+                assert_eq!(response.content_text(), Some("answer 1"))
+            }
+            other => panic!("Expected StreamEnd, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streamed_response_cached_from_deltas() {
+        let layer = CacheLayer::new(CountingClient::default());
+        let messages = vec![ChatMessage::user("hello").build()];
+
+        let streamed = crate::stream::collect_stream_response(
+            layer.chat_stream(messages.clone(), None).await.unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(streamed.content_text(), Some("answer 1"));
+
+        // The entry is written in the background once the stream is dropped
+        tokio::task::yield_now().await;
+        let cached = layer.chat(messages).await.unwrap();
+        assert_eq!(cached.content_text(), Some("answer 1"));
+        assert_eq!(cached.usage.unwrap().total_tokens, 5);
+        assert_eq!(cached.finish_reason, Some(FinishReason::Stop));
+        assert_eq!(layer.stats(), CacheLayerStats { hits: 1, misses: 1 });
+        assert_eq!(layer.backend().len().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_key_includes_client_model() {
+        let backend: Arc<dyn CacheBackend> = Arc::new(InMemoryCacheBackend::default());
+        let client = |model: &str| CountingClient {
+            model: Some(model.to_string()),
+            ..Default::default()
+        };
+        let mini = CacheLayer::new(client("gpt-4o-mini")).with_shared_backend(backend.clone());
+        let full = CacheLayer::new(client("gpt-4o")).with_shared_backend(backend.clone());
+        let messages = vec![ChatMessage::user("hello").build()];
+
+        mini.chat(messages.clone()).await.unwrap();
+        full.chat(messages).await.unwrap();
+
+        assert_eq!(full.stats().hits, 0);
+        assert_eq!(backend.len().await.unwrap(), 2);
+    }
+}
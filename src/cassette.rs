@@ -0,0 +1,673 @@
+//! Record/Replay HTTP Cassettes
+//!
+//! A [`Cassette`] captures real request/response pairs into a JSON file and
+//! replays them later without network access, which makes tests against real
+//! provider responses deterministic and runnable without API keys.
+//!
+//! - **Record**: requests are sent to the provider; each response is stored with
+//!   its status, headers and body chunks. The body is passed through to the
+//!   caller as it arrives, so SSE streams are not delayed and keep their
+//!   original chunk boundaries; the interaction is written once the body ends.
+//! - **Replay**: requests are matched against the recorded ones on method, URL
+//!   and normalized body (JSON key order does not matter), and the recorded
+//!   response is returned byte-for-byte. Each recorded interaction is used once,
+//!   in recording order. A request with no recorded match fails with
+//!   `LlmError::HttpError` instead of reaching the network.
+//!
+//! API keys found in `Authorization`, `x-api-key`, `x-goog-api-key`, `api-key`
+//! headers and in `key` query parameters are never written to the cassette;
+//! they are replaced by `[REDACTED]` wherever they appear. Request headers are
+//! not recorded at all.
+//!
+//! A cassette is attached through [`HttpConfig::cassette`], or for every client
+//! built from a builder with [`LlmBuilder::with_cassette`] and
+//! [`SiumaiBuilder::cassette`].
+//!
+//! [`HttpConfig::cassette`]: crate::types::HttpConfig::cassette
+//! [`LlmBuilder::with_cassette`]: crate::builder::LlmBuilder::with_cassette
+//! [`SiumaiBuilder::cassette`]: crate::provider::SiumaiBuilder::cassette
+//!
+//! # Example
+//! ```rust,no_run
+//! use siumai::cassette::Cassette;
+//! use siumai::prelude::*;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> Result<(), LlmError> {
+//! // Records on the first run, replays offline afterwards
+//! let cassette = Arc::new(Cassette::auto("tests/cassettes/openai_chat.json")?);
+//!
+//! let client = LlmBuilder::new()
+//!     .with_cassette(cassette)
+//!     .openai()
+//!     .api_key(std::env::var("OPENAI_API_KEY").unwrap_or_default())
+//!     .model("gpt-4o-mini")
+//!     .build()
+//!     .await?;
+//!
+//! let response = client.chat(vec![user!("Say hello")]).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+
+use base64::Engine;
+use bytes::Bytes;
+use futures_util::StreamExt;
+use reqwest::ResponseBuilderExt;
+use serde::{Deserialize, Serialize};
+
+use crate::error::LlmError;
+
+/// Placeholder written in place of secrets
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Headers whose values are API keys
+const SECRET_HEADERS: &[&str] = &["authorization", "x-api-key", "x-goog-api-key", "api-key"];
+
+/// Query parameters whose values are API keys
+const SECRET_QUERY_PARAMS: &[&str] = &["key", "api_key"];
+
+/// Response headers that are not recorded
+const SKIPPED_RESPONSE_HEADERS: &[&str] = &["set-cookie", "transfer-encoding", "connection"];
+
+/// Whether a cassette sends requests or answers them from the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Send requests and record the interactions
+    Record,
+    /// Answer requests from recorded interactions only
+    Replay,
+}
+
+/// A recorded request, as used for matching
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// HTTP method
+    pub method: String,
+    /// Request URL with secrets redacted
+    pub url: String,
+    /// JSON body, or the raw text for non-JSON bodies; `None` for empty and streamed bodies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+}
+
+/// Encoding of the recorded body chunks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyEncoding {
+    /// Chunks are stored as UTF-8 text
+    #[default]
+    Text,
+    /// Chunks are stored base64-encoded
+    Base64,
+}
+
+/// A recorded response
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers in the order received
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// Encoding of `chunks`
+    #[serde(default)]
+    pub body_encoding: BodyEncoding,
+    /// Body chunks as they arrived from the network
+    #[serde(default)]
+    pub chunks: Vec<String>,
+}
+
+/// A recorded request/response pair
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// The request
+    pub request: RecordedRequest,
+    /// The response
+    pub response: RecordedResponse,
+}
+
+/// On-disk cassette format
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+/// Recorded interactions and which of them have been replayed
+#[derive(Debug, Default)]
+struct CassetteState {
+    interactions: Vec<Interaction>,
+    used: Vec<bool>,
+}
+
+/// HTTP record/replay transport.
+///
+/// Shared through an `Arc`, so every client built with the same cassette
+/// records into (or replays from) the same file.
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    secrets: Vec<String>,
+    state: Arc<Mutex<CassetteState>>,
+}
+
+impl Cassette {
+    /// Record interactions into `path`, replacing any existing cassette
+    pub fn record<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            mode: CassetteMode::Record,
+            secrets: Vec::new(),
+            state: Arc::default(),
+        }
+    }
+
+    /// Replay interactions recorded in `path`
+    pub fn replay<P: AsRef<Path>>(path: P) -> Result<Self, LlmError> {
+        let path = path.as_ref().to_path_buf();
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            LlmError::IoError(format!("Failed to read cassette {}: {e}", path.display()))
+        })?;
+        let file: CassetteFile = serde_json::from_str(&content).map_err(|e| {
+            LlmError::ParseError(format!("Invalid cassette {}: {e}", path.display()))
+        })?;
+
+        Ok(Self {
+            path,
+            mode: CassetteMode::Replay,
+            secrets: Vec::new(),
+            state: Arc::new(Mutex::new(CassetteState {
+                used: vec![false; file.interactions.len()],
+                interactions: file.interactions,
+            })),
+        })
+    }
+
+    /// Replay `path` if it exists, record into it otherwise
+    pub fn auto<P: AsRef<Path>>(path: P) -> Result<Self, LlmError> {
+        if path.as_ref().exists() {
+            Self::replay(path)
+        } else {
+            Ok(Self::record(path))
+        }
+    }
+
+    /// Redact an additional secret (for example a key sent in the request body)
+    pub fn with_secret<S: Into<String>>(mut self, secret: S) -> Self {
+        let secret = secret.into();
+        if !secret.is_empty() {
+            self.secrets.push(secret);
+        }
+        self
+    }
+
+    /// Cassette file path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record or replay mode
+    pub const fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Recorded interactions
+    pub fn interactions(&self) -> Vec<Interaction> {
+        lock(&self.state).interactions.clone()
+    }
+
+    /// Number of recorded interactions not replayed yet
+    pub fn remaining(&self) -> usize {
+        lock(&self.state).used.iter().filter(|used| !**used).count()
+    }
+
+    /// Send a request through the cassette
+    pub async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, LlmError> {
+        let (client, request) = request.build_split();
+        let request = request?;
+        let secrets = self.request_secrets(&request);
+        let recorded_request = RecordedRequest::from_request(&request, &secrets);
+        let url = request.url().clone();
+
+        match self.mode {
+            CassetteMode::Replay => {
+                let response = self.take_match(&recorded_request)?;
+                response.to_response(url)
+            }
+            CassetteMode::Record => {
+                let response = client.execute(request).await?;
+                let status = response.status().as_u16();
+                let headers = recorded_headers(response.headers());
+                let mut recorder = Some(Recorder {
+                    path: self.path.clone(),
+                    state: self.state.clone(),
+                    request: Some(recorded_request),
+                    secrets,
+                    status,
+                    headers: headers.clone(),
+                    chunks: Vec::new(),
+                });
+
+                // Tee the body: chunks reach the caller as they arrive and are
+                // recorded alongside; the recorder saves when the body ends
+                let mut body = Box::pin(response.bytes_stream());
+                let body = futures_util::stream::poll_fn(move |cx| {
+                    let item = futures_util::ready!(body.poll_next_unpin(cx));
+                    match &item {
+                        Some(Ok(chunk)) => {
+                            if let Some(recorder) = &mut recorder {
+                                recorder.chunks.push(chunk.clone());
+                            }
+                        }
+                        Some(Err(_)) => {
+                            if let Some(recorder) = &mut recorder {
+                                recorder.request = None;
+                            }
+                            recorder = None;
+                        }
+                        None => recorder = None,
+                    }
+                    Poll::Ready(item)
+                });
+
+                http_response(status, &headers, url, reqwest::Body::wrap_stream(body))
+            }
+        }
+    }
+
+    /// Secrets to redact from this request: the configured ones plus the API keys it carries
+    fn request_secrets(&self, request: &reqwest::Request) -> Vec<String> {
+        let mut secrets = self.secrets.clone();
+
+        for name in SECRET_HEADERS {
+            if let Some(value) = request.headers().get(*name).and_then(|v| v.to_str().ok()) {
+                let value = value
+                    .strip_prefix("Bearer ")
+                    .or_else(|| value.strip_prefix("bearer "))
+                    .unwrap_or(value);
+                secrets.push(value.to_string());
+            }
+        }
+
+        for (name, value) in request.url().query_pairs() {
+            if SECRET_QUERY_PARAMS.contains(&name.as_ref()) {
+                secrets.push(value.into_owned());
+            }
+        }
+
+        secrets.retain(|secret| !secret.is_empty());
+        // Replace longer secrets first so a key containing another is fully redacted
+        secrets.sort_by_key(|secret| std::cmp::Reverse(secret.len()));
+        secrets.dedup();
+        secrets
+    }
+
+    /// Take the first unused interaction matching the request
+    fn take_match(&self, request: &RecordedRequest) -> Result<RecordedResponse, LlmError> {
+        let mut state = lock(&self.state);
+        let state = &mut *state;
+        let index = state
+            .interactions
+            .iter()
+            .zip(&state.used)
+            .position(|(interaction, used)| !used && interaction.request == *request)
+            .ok_or_else(|| {
+                LlmError::HttpError(format!(
+                    "No recorded interaction in cassette {} matches {} {}",
+                    self.path.display(),
+                    request.method,
+                    request.url
+                ))
+            })?;
+
+        state.used[index] = true;
+        Ok(state.interactions[index].response.clone())
+    }
+}
+
+/// Records one interaction while its response body streams to the caller.
+///
+/// The interaction is saved when the recorder is dropped, which happens when
+/// the body ends or the caller drops the response. Bodies that fail mid-stream
+/// are not recorded.
+struct Recorder {
+    path: PathBuf,
+    state: Arc<Mutex<CassetteState>>,
+    request: Option<RecordedRequest>,
+    secrets: Vec<String>,
+    status: u16,
+    headers: Vec<(String, String)>,
+    chunks: Vec<Bytes>,
+}
+
+impl Recorder {
+    /// Append the interaction and rewrite the cassette file
+    fn save(&mut self, request: RecordedRequest) -> Result<(), LlmError> {
+        let response = RecordedResponse::from_chunks(
+            self.status,
+            std::mem::take(&mut self.headers),
+            &self.chunks,
+        );
+        let content = {
+            let mut state = lock(&self.state);
+            state.interactions.push(Interaction {
+                request,
+                response: response.redacted(&self.secrets),
+            });
+            state.used.push(true);
+            serde_json::to_string_pretty(&CassetteFile {
+                interactions: state.interactions.clone(),
+            })?
+        };
+
+        if let Some(parent) = self.path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                LlmError::IoError(format!("Failed to create {}: {e}", parent.display()))
+            })?;
+        }
+        std::fs::write(&self.path, content).map_err(|e| {
+            LlmError::IoError(format!(
+                "Failed to write cassette {}: {e}",
+                self.path.display()
+            ))
+        })
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        if let Some(request) = self.request.take()
+            && let Err(e) = self.save(request)
+        {
+            tracing::warn!("Failed to record interaction: {e}");
+        }
+    }
+}
+
+fn lock(state: &Mutex<CassetteState>) -> std::sync::MutexGuard<'_, CassetteState> {
+    state
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+impl RecordedRequest {
+    /// Build the matching key of a request, redacting `secrets`
+    fn from_request(request: &reqwest::Request, secrets: &[String]) -> Self {
+        let body = request
+            .body()
+            .and_then(reqwest::Body::as_bytes)
+            .filter(|bytes| !bytes.is_empty())
+            .map(|bytes| {
+                let text = redact(&String::from_utf8_lossy(bytes), secrets);
+                serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text))
+            });
+
+        Self {
+            method: request.method().to_string(),
+            url: redact(request.url().as_str(), secrets),
+            body,
+        }
+    }
+}
+
+impl RecordedResponse {
+    /// Build a recorded response from the chunks as they arrived from the network
+    fn from_chunks(status: u16, headers: Vec<(String, String)>, raw_chunks: &[Bytes]) -> Self {
+        let text_chunks: Option<Vec<String>> = raw_chunks
+            .iter()
+            .map(|chunk| String::from_utf8(chunk.to_vec()).ok())
+            .collect();
+        let (body_encoding, chunks) = match text_chunks {
+            Some(chunks) => (BodyEncoding::Text, chunks),
+            None => (
+                BodyEncoding::Base64,
+                raw_chunks
+                    .iter()
+                    .map(|chunk| base64::engine::general_purpose::STANDARD.encode(chunk))
+                    .collect(),
+            ),
+        };
+
+        Self {
+            status,
+            headers,
+            body_encoding,
+            chunks,
+        }
+    }
+
+    /// Copy of this response with `secrets` redacted from headers and text chunks
+    fn redacted(&self, secrets: &[String]) -> Self {
+        let mut response = self.clone();
+        for (_, value) in &mut response.headers {
+            *value = redact(value, secrets);
+        }
+        if response.body_encoding == BodyEncoding::Text {
+            for chunk in &mut response.chunks {
+                *chunk = redact(chunk, secrets);
+            }
+        }
+        response
+    }
+
+    /// Decoded body chunks
+    fn chunk_bytes(&self) -> Result<Vec<Bytes>, LlmError> {
+        self.chunks
+            .iter()
+            .map(|chunk| match self.body_encoding {
+                BodyEncoding::Text => Ok(Bytes::from(chunk.clone())),
+                BodyEncoding::Base64 => base64::engine::general_purpose::STANDARD
+                    .decode(chunk)
+                    .map(Bytes::from)
+                    .map_err(|e| LlmError::ParseError(format!("Invalid cassette chunk: {e}"))),
+            })
+            .collect()
+    }
+
+    /// Rebuild a `reqwest::Response` that streams the recorded chunks
+    fn to_response(&self, url: reqwest::Url) -> Result<reqwest::Response, LlmError> {
+        let chunks = self.chunk_bytes()?.into_iter().map(Ok::<_, std::io::Error>);
+        let body = reqwest::Body::wrap_stream(futures_util::stream::iter(chunks));
+        http_response(self.status, &self.headers, url, body)
+    }
+}
+
+/// Response headers worth recording
+fn recorded_headers(headers: &reqwest::header::HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter(|(name, _)| !SKIPPED_RESPONSE_HEADERS.contains(&name.as_str()))
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
+
+/// Assemble a `reqwest::Response` from its parts
+fn http_response(
+    status: u16,
+    headers: &[(String, String)],
+    url: reqwest::Url,
+    body: reqwest::Body,
+) -> Result<reqwest::Response, LlmError> {
+    let mut builder = http::Response::builder().status(status).url(url);
+    for (name, value) in headers {
+        builder = builder.header(name, value);
+    }
+
+    let response = builder
+        .body(body)
+        .map_err(|e| LlmError::HttpError(format!("Invalid recorded response: {e}")))?;
+    Ok(reqwest::Response::from(response))
+}
+
+/// Replace every occurrence of the secrets in `text`
+fn redact(text: &str, secrets: &[String]) -> String {
+    secrets.iter().fold(text.to_string(), |text, secret| {
+        text.replace(secret, REDACTED)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cassette_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "siumai-cassette-{}-{name}.json",
+            std::process::id()
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_replay_matches_normalized_body_and_keeps_chunks() {
+        let path = cassette_path("replay");
+        let file = serde_json::json!({
+            "interactions": [{
+                "request": {
+                    "method": "POST",
+                    "url": "https://api.example.com/v1/chat?key=[REDACTED]",
+                    "body": {"model": "m", "stream": true}
+                },
+                "response": {
+                    "status": 200,
+                    "headers": [["content-type", "text/event-stream"]],
+                    "chunks": ["data: {\"a\":", "1}\n\n", "data: [DONE]\n\n"]
+                }
+            }]
+        });
+        std::fs::write(&path, file.to_string()).unwrap();
+
+        let cassette = Cassette::replay(&path).unwrap();
+        let client = reqwest::Client::new();
+        let request = client
+            .post("https://api.example.com/v1/chat?key=another-key")
+            .header("content-type", "application/json")
+            .body(r#"{"stream": true, "model": "m"}"#);
+
+        let response = cassette.send(request).await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+        let chunks: Vec<Bytes> = response
+            .bytes_stream()
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[1], Bytes::from("1}\n\n"));
+        assert_eq!(cassette.remaining(), 0);
+
+        // Each interaction is replayed once
+        let request = client
+            .post("https://api.example.com/v1/chat")
+            .body(r#"{"model": "m", "stream": true}"#);
+        assert!(cassette.send(request).await.is_err());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_record_streams_body_before_it_ends() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        fn chunk(data: &str) -> String {
+            format!("{:x}\r\n{data}\r\n", data.len())
+        }
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (release, released) = tokio::sync::oneshot::channel::<()>();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 4096];
+            let _ = socket.read(&mut request).await.unwrap();
+            let head = "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ntransfer-encoding: chunked\r\n\r\n";
+            socket.write_all(head.as_bytes()).await.unwrap();
+            socket
+                .write_all(chunk("data: 1\n\n").as_bytes())
+                .await
+                .unwrap();
+            released.await.ok();
+            socket
+                .write_all(chunk("data: [DONE]\n\n").as_bytes())
+                .await
+                .unwrap();
+            socket.write_all(b"0\r\n\r\n").await.unwrap();
+        });
+
+        let path = cassette_path("record");
+        let cassette = Cassette::record(&path);
+        let request = reqwest::Client::new().get(format!("http://{addr}/v1/events"));
+        let response =
+            tokio::time::timeout(std::time::Duration::from_secs(5), cassette.send(request))
+                .await
+                .expect("response returned before the body ended")
+                .unwrap();
+
+        // The first event arrives while the server is still holding the rest
+        let mut stream = response.bytes_stream();
+        assert_eq!(stream.next().await.unwrap().unwrap(), "data: 1\n\n");
+        assert!(cassette.interactions().is_empty());
+
+        release.send(()).unwrap();
+        let rest: Vec<Bytes> = stream.map(|chunk| chunk.unwrap()).collect().await;
+        assert_eq!(rest.concat(), b"data: [DONE]\n\n");
+
+        let interactions = cassette.interactions();
+        assert_eq!(interactions.len(), 1);
+        assert_eq!(
+            interactions[0].request.url,
+            format!("http://{addr}/v1/events")
+        );
+        assert_eq!(interactions[0].response.chunks[0], "data: 1\n\n");
+        assert_eq!(
+            interactions[0].response.chunks.concat(),
+            "data: 1\n\ndata: [DONE]\n\n"
+        );
+
+        let replay = Cassette::replay(&path).unwrap();
+        let request = reqwest::Client::new().get(format!("http://{addr}/v1/events"));
+        let text = replay.send(request).await.unwrap().text().await.unwrap();
+        assert_eq!(text, "data: 1\n\ndata: [DONE]\n\n");
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_request_secrets_are_redacted() {
+        let cassette = Cassette::record(cassette_path("secrets")).with_secret("org-secret");
+        let request = reqwest::Client::new()
+            .post("https://api.example.com/v1/chat?key=query-key")
+            .header("Authorization", "Bearer sk-live-123")
+            .header("x-api-key", "anthropic-key")
+            .body(r#"{"user":"org-secret","model":"m"}"#)
+            .build()
+            .unwrap();
+
+        let secrets = cassette.request_secrets(&request);
+        let recorded = RecordedRequest::from_request(&request, &secrets);
+        assert_eq!(
+            recorded.url,
+            "https://api.example.com/v1/chat?key=[REDACTED]"
+        );
+        assert_eq!(recorded.body.unwrap()["user"], REDACTED);
+
+        let response = RecordedResponse {
+            status: 200,
+            headers: vec![("x-echo".to_string(), "sk-live-123".to_string())],
+            body_encoding: BodyEncoding::Text,
+            chunks: vec!["key anthropic-key".to_string()],
+        }
+        .redacted(&secrets);
+        assert_eq!(response.headers[0].1, REDACTED);
+        assert_eq!(response.chunks[0], "key [REDACTED]");
+    }
+}
@@ -55,6 +55,30 @@ pub trait LlmClient: ChatCapability + Send + Sync {
     fn as_image_generation_capability(&self) -> Option<&dyn ImageGenerationCapability> {
         None
     }
+
+    /// Get as text completion capability if supported
+    ///
+    /// Returns None by default. Providers that support legacy text completion
+    /// should override this method to return Some(self).
+    fn as_completion_capability(&self) -> Option<&dyn CompletionCapability> {
+        None
+    }
+
+    /// Get as batch capability if supported
+    ///
+    /// Returns None by default. Providers that support batch processing
+    /// should override this method to return Some(self).
+    fn as_batch_capability(&self) -> Option<&dyn BatchCapability> {
+        None
+    }
+
+    /// Get as server-side conversation state capability if supported
+    ///
+    /// Returns None by default. Providers that can continue a conversation
+    /// stored on their side should override this method to return Some(self).
+    fn as_conversation_state_capability(&self) -> Option<&dyn ConversationStateCapability> {
+        None
+    }
 }
 
 /// Client Wrapper - provides dynamic dispatch for different provider clients
@@ -198,6 +222,10 @@ impl ClientWrapper {
 
 #[async_trait::async_trait]
 impl ChatCapability for ClientWrapper {
+    fn default_model(&self) -> Option<String> {
+        self.client().default_model()
+    }
+
     async fn chat_with_tools(
         &self,
         messages: Vec<ChatMessage>,
@@ -213,6 +241,32 @@ impl ChatCapability for ClientWrapper {
     ) -> Result<ChatStream, LlmError> {
         self.client().chat_stream(messages, tools).await
     }
+
+    async fn chat_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatResponse, LlmError> {
+        self.client().chat_with_schema(messages, schema).await
+    }
+
+    async fn chat_stream_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatStream, LlmError> {
+        self.client()
+            .chat_stream_with_schema(messages, schema)
+            .await
+    }
+
+    async fn chat_with_request(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        self.client().chat_with_request(request).await
+    }
+
+    async fn chat_stream_with_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
+        self.client().chat_stream_with_request(request).await
+    }
 }
 
 // UnifiedLlmClient has been removed as it was redundant with ClientWrapper.
@@ -246,6 +300,26 @@ impl LlmClient for ClientWrapper {
     fn clone_box(&self) -> Box<dyn LlmClient> {
         Box::new(self.clone())
     }
+
+    fn as_completion_capability(&self) -> Option<&dyn CompletionCapability> {
+        self.client().as_completion_capability()
+    }
+
+    fn as_audio_capability(&self) -> Option<&dyn AudioCapability> {
+        self.client().as_audio_capability()
+    }
+
+    fn as_vision_capability(&self) -> Option<&dyn VisionCapability> {
+        self.client().as_vision_capability()
+    }
+
+    fn as_batch_capability(&self) -> Option<&dyn BatchCapability> {
+        self.client().as_batch_capability()
+    }
+
+    fn as_conversation_state_capability(&self) -> Option<&dyn ConversationStateCapability> {
+        self.client().as_conversation_state_capability()
+    }
 }
 
 /// Client Configuration for advanced client setup
@@ -0,0 +1,433 @@
+//! Context Window Management
+//!
+//! This module provides [`ContextWindowManager`], which makes a conversation fit
+//! a model's context window before it is sent. Leading system messages and
+//! everything from the latest user message on are always kept; older turns are
+//! dropped oldest-first, or replaced by a summary when a summarizer client is
+//! configured.
+//!
+//! Turns are kept intact: tool results are never separated from the assistant
+//! message that requested them, and the trimmed conversation never starts
+//! with an assistant reply.
+//!
+//! # Example
+//! ```rust,no_run
+//! use siumai::context_window::ContextWindowManager;
+//! use siumai::prelude::*;
+//!
+//! # async fn example(client: Siumai, history: Vec<ChatMessage>) -> Result<(), LlmError> {
+//! let manager = ContextWindowManager::for_model("gpt-4o")?.with_max_tokens(4096);
+//! let response = manager.chat(&client, history).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::Arc;
+
+use crate::error::LlmError;
+use crate::token_counter::{TokenCounter, context_window_for_model, token_counter_for_model};
+use crate::traits::ChatCapability;
+use crate::types::*;
+
+/// Instruction sent to the summarizer with the dropped turns
+const SUMMARY_INSTRUCTION: &str = "Summarize the following conversation concisely. Preserve facts, decisions, names and open questions needed to continue it.";
+
+/// Trims or summarizes conversations to fit a context window.
+#[derive(Clone)]
+pub struct ContextWindowManager {
+    counter: Arc<dyn TokenCounter>,
+    context_window: u32,
+    max_tokens: u32,
+    summarizer: Option<Arc<dyn ChatCapability>>,
+}
+
+impl ContextWindowManager {
+    /// Create a manager for a context window of `context_window` tokens
+    pub fn new(counter: Arc<dyn TokenCounter>, context_window: u32) -> Self {
+        Self {
+            counter,
+            context_window,
+            max_tokens: 0,
+            summarizer: None,
+        }
+    }
+
+    /// Create a manager using the best local counter and known context window of a model
+    pub fn for_model(model: &str) -> Result<Self, LlmError> {
+        let context_window = context_window_for_model(model).ok_or_else(|| {
+            LlmError::ConfigurationError(format!("Unknown context window for model '{model}'"))
+        })?;
+        Ok(Self::new(token_counter_for_model(model), context_window))
+    }
+
+    /// Reserve `max_tokens` of the window for the response
+    pub const fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    /// Summarize dropped turns with this client instead of discarding them
+    pub fn with_summarizer(mut self, summarizer: Arc<dyn ChatCapability>) -> Self {
+        self.summarizer = Some(summarizer);
+        self
+    }
+
+    /// Get the context window size
+    pub const fn context_window(&self) -> u32 {
+        self.context_window
+    }
+
+    /// Get the number of tokens available for the prompt
+    pub const fn budget(&self) -> u32 {
+        self.context_window.saturating_sub(self.max_tokens)
+    }
+
+    /// Make a conversation fit the prompt budget.
+    ///
+    /// Returns the messages unchanged when they already fit, and an
+    /// `InvalidInput` error when the system messages and the messages from the
+    /// latest user turn on exceed the budget.
+    pub async fn fit(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Option<&[Tool]>,
+    ) -> Result<Vec<ChatMessage>, LlmError> {
+        let budget = self.budget();
+        if self.counter.count_messages(&messages, tools).await? <= budget {
+            return Ok(messages);
+        }
+
+        let (pinned, turns) = split_turns(messages);
+        let starts = turn_starts(&turns);
+
+        let Some(dropped) = self
+            .min_dropped(&pinned, None, &turns, &starts, tools)
+            .await?
+        else {
+            return Err(LlmError::InvalidInput(format!(
+                "Conversation does not fit the context window: system messages and the latest user turn exceed {budget} tokens"
+            )));
+        };
+
+        if let Some(summarizer) = &self.summarizer
+            && dropped > 0
+            && let Some(fitted) = self
+                .fit_with_summary(
+                    summarizer.as_ref(),
+                    &pinned,
+                    &turns,
+                    &starts,
+                    dropped,
+                    tools,
+                )
+                .await?
+        {
+            return Ok(fitted);
+        }
+
+        Ok(assemble(&pinned, None, &turns[dropped..]))
+    }
+
+    /// Fit a conversation and send it
+    pub async fn chat<C: ChatCapability + ?Sized>(
+        &self,
+        client: &C,
+        messages: Vec<ChatMessage>,
+    ) -> Result<ChatResponse, LlmError> {
+        let messages = self.fit(messages, None).await?;
+        client.chat(messages).await
+    }
+
+    /// Fit a conversation with tools and send it
+    pub async fn chat_with_tools<C: ChatCapability + ?Sized>(
+        &self,
+        client: &C,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+    ) -> Result<ChatResponse, LlmError> {
+        let messages = self.fit(messages, tools.as_deref()).await?;
+        client.chat_with_tools(messages, tools).await
+    }
+
+    /// Smallest number of leading turns to drop so that the rest fits.
+    ///
+    /// Only cut points in `starts` are considered. Dropping more turns never
+    /// increases the count, so the search is a binary search over cut points.
+    async fn min_dropped(
+        &self,
+        pinned: &[ChatMessage],
+        summary: Option<&ChatMessage>,
+        turns: &[Vec<ChatMessage>],
+        starts: &[usize],
+        tools: Option<&[Tool]>,
+    ) -> Result<Option<usize>, LlmError> {
+        let budget = self.budget();
+        let (mut lo, mut hi) = (0, starts.len());
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            let candidate = assemble(pinned, summary, &turns[starts[mid]..]);
+            if self.counter.count_messages(&candidate, tools).await? <= budget {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        Ok(starts.get(lo).copied())
+    }
+
+    /// Try to replace the dropped turns with a summary
+    async fn fit_with_summary(
+        &self,
+        summarizer: &dyn ChatCapability,
+        pinned: &[ChatMessage],
+        turns: &[Vec<ChatMessage>],
+        starts: &[usize],
+        mut dropped: usize,
+        tools: Option<&[Tool]>,
+    ) -> Result<Option<Vec<ChatMessage>>, LlmError> {
+        // The summary takes room itself, so more turns may have to go. The
+        // summary is regenerated once to cover them.
+        for _ in 0..2 {
+            let summary = summarize(summarizer, &turns[..dropped]).await?;
+            let valid: Vec<usize> = starts.iter().copied().filter(|&s| s >= dropped).collect();
+            match self
+                .min_dropped(pinned, Some(&summary), turns, &valid, tools)
+                .await?
+            {
+                Some(needed) if needed == dropped => {
+                    return Ok(Some(assemble(pinned, Some(&summary), &turns[dropped..])));
+                }
+                Some(needed) => dropped = needed,
+                None => return Ok(None),
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl std::fmt::Debug for ContextWindowManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContextWindowManager")
+            .field("context_window", &self.context_window)
+            .field("max_tokens", &self.max_tokens)
+            .field("summarize", &self.summarizer.is_some())
+            .finish()
+    }
+}
+
+/// Split leading system messages from the rest of the conversation, grouped into turns.
+///
+/// A turn starts at every non-tool message, so tool results stay with the
+/// assistant message that requested them.
+fn split_turns(messages: Vec<ChatMessage>) -> (Vec<ChatMessage>, Vec<Vec<ChatMessage>>) {
+    let mut pinned = Vec::new();
+    let mut turns: Vec<Vec<ChatMessage>> = Vec::new();
+
+    for message in messages {
+        let is_system = matches!(message.role, MessageRole::System | MessageRole::Developer);
+        if is_system && turns.is_empty() {
+            pinned.push(message);
+        } else if message.role == MessageRole::Tool
+            && let Some(turn) = turns.last_mut()
+        {
+            turn.push(message);
+        } else {
+            turns.push(vec![message]);
+        }
+    }
+
+    (pinned, turns)
+}
+
+/// Indices of turns the trimmed conversation may start at.
+///
+/// Only user turns qualify, so a trimmed conversation never opens with an
+/// assistant reply or tool call. The first turn is always included, since
+/// starting there keeps the conversation as given.
+fn turn_starts(turns: &[Vec<ChatMessage>]) -> Vec<usize> {
+    (0..turns.len())
+        .filter(|&i| i == 0 || turns[i][0].role == MessageRole::User)
+        .collect()
+}
+
+fn assemble(
+    pinned: &[ChatMessage],
+    summary: Option<&ChatMessage>,
+    turns: &[Vec<ChatMessage>],
+) -> Vec<ChatMessage> {
+    pinned
+        .iter()
+        .chain(summary)
+        .chain(turns.iter().flatten())
+        .cloned()
+        .collect()
+}
+
+/// Summarize turns into a system message
+pub(crate) async fn summarize(
+    summarizer: &dyn ChatCapability,
+    turns: &[Vec<ChatMessage>],
+) -> Result<ChatMessage, LlmError> {
+    let transcript = turns
+        .iter()
+        .flatten()
+        .map(|message| {
+            let role = match message.role {
+                MessageRole::System | MessageRole::Developer => "system",
+                MessageRole::User => "user",
+                MessageRole::Assistant => "assistant",
+                MessageRole::Tool => "tool",
+            };
+            format!("{role}: {}", message.content.all_text())
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let response = summarizer
+        .chat(vec![
+            ChatMessage::system(SUMMARY_INSTRUCTION).build(),
+            ChatMessage::user(transcript).build(),
+        ])
+        .await?;
+
+    Ok(ChatMessage::system(format!(
+        "Summary of the earlier conversation:\n{}",
+        response.content.all_text()
+    ))
+    .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::ChatStream;
+    use crate::token_counter::HeuristicTokenCounter;
+    use async_trait::async_trait;
+
+    fn manager(context_window: u32) -> ContextWindowManager {
+        ContextWindowManager::new(Arc::new(HeuristicTokenCounter::new(4.0)), context_window)
+    }
+
+    /// System prompt followed by `turns` user/assistant exchanges of 40 characters each
+    fn conversation(turns: usize) -> Vec<ChatMessage> {
+        let mut messages = vec![ChatMessage::system("Be brief.").build()];
+        for i in 0..turns {
+            messages.push(ChatMessage::user(format!("{i:02} {}", "u".repeat(37))).build());
+            messages.push(ChatMessage::assistant(format!("{i:02} {}", "a".repeat(37))).build());
+        }
+        messages.push(ChatMessage::user("latest question").build());
+        messages
+    }
+
+    #[tokio::test]
+    async fn test_messages_that_fit_are_unchanged() {
+        let messages = conversation(2);
+        let fitted = manager(10_000).fit(messages.clone(), None).await.unwrap();
+        assert_eq!(fitted.len(), messages.len());
+    }
+
+    #[tokio::test]
+    async fn test_oldest_turns_are_dropped() {
+        // Each exchange is 2 * (4 + 10) = 28 tokens
+        let fitted = manager(120).fit(conversation(5), None).await.unwrap();
+
+        assert_eq!(fitted[0].role, MessageRole::System);
+        assert_eq!(fitted[1].role, MessageRole::User);
+        assert_eq!(
+            fitted.last().unwrap().content_text(),
+            Some("latest question")
+        );
+        assert!(fitted.len() < conversation(5).len());
+        assert!(fitted[1].content_text().unwrap().starts_with("0"));
+        assert!(!fitted[1].content_text().unwrap().starts_with("00"));
+    }
+
+    #[tokio::test]
+    async fn test_latest_turn_too_large_is_an_error() {
+        let result = manager(10).fit(conversation(1), None).await;
+        assert!(matches!(result, Err(LlmError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_tool_results_stay_with_their_turn() {
+        let messages = vec![
+            ChatMessage::user("weather?").build(),
+            ChatMessage::assistant("").build(),
+            ChatMessage::tool("sunny", "call_1").build(),
+            ChatMessage::user("thanks").build(),
+        ];
+        let (pinned, turns) = split_turns(messages);
+
+        assert!(pinned.is_empty());
+        assert_eq!(turns.len(), 3);
+        assert_eq!(turns[1].len(), 2);
+        assert_eq!(turn_starts(&turns), vec![0, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_trimmed_conversation_never_starts_with_assistant_turn() {
+        let mut messages = conversation(1);
+        messages.pop();
+        messages.push(ChatMessage::user("q".repeat(400)).build());
+        messages.push(ChatMessage::assistant("").build());
+        messages.push(ChatMessage::tool("ok", "call_1").build());
+
+        let (_, turns) = split_turns(messages.clone());
+        assert_eq!(turn_starts(&turns), vec![0, 2]);
+
+        // The tool-calling turn alone fits, but it must not be sent without its user turn
+        let result = manager(60).fit(messages.clone(), None).await;
+        assert!(matches!(result, Err(LlmError::InvalidInput(_))));
+
+        let fitted = manager(140).fit(messages, None).await.unwrap();
+        assert_eq!(fitted.len(), 4);
+        assert_eq!(fitted[0].role, MessageRole::System);
+        assert_eq!(fitted[1].role, MessageRole::User);
+        assert_eq!(fitted[1].content_text().map(str::len), Some(400));
+        assert_eq!(fitted.last().unwrap().role, MessageRole::Tool);
+    }
+
+    struct FixedSummarizer;
+
+    #[async_trait]
+    impl ChatCapability for FixedSummarizer {
+        async fn chat_with_tools(
+            &self,
+            _messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+        ) -> Result<ChatResponse, LlmError> {
+            Ok(ChatResponse::new(MessageContent::Text(
+                "talked about numbers".to_string(),
+            )))
+        }
+
+        async fn chat_stream(
+            &self,
+            _messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+        ) -> Result<ChatStream, LlmError> {
+            Err(LlmError::UnsupportedOperation("no streaming".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dropped_turns_are_summarized() {
+        let fitted = manager(120)
+            .with_summarizer(Arc::new(FixedSummarizer))
+            .fit(conversation(5), None)
+            .await
+            .unwrap();
+
+        assert_eq!(fitted[1].role, MessageRole::System);
+        assert!(
+            fitted[1]
+                .content_text()
+                .unwrap()
+                .contains("talked about numbers")
+        );
+        assert_eq!(
+            fitted.last().unwrap().content_text(),
+            Some("latest question")
+        );
+    }
+}
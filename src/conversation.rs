@@ -0,0 +1,758 @@
+//! Conversations
+//!
+//! A [`Conversation`] owns a message history, system prompt and tool set, and
+//! sends each new message with the history that precedes it. The history can
+//! be bounded with a [`MemoryStrategy`] and persisted through a
+//! [`MemoryStore`], so a conversation survives restarts.
+//!
+//! When the client keeps conversation state on the server (OpenAI Responses
+//! API), only the new messages are sent and the conversation continues from
+//! the previous response ID; the local history is still kept and persisted.
+//!
+//! # Example
+//! ```rust,no_run
+//! use siumai::conversation::{Conversation, JsonFileStore, MemoryStrategy};
+//! use siumai::prelude::*;
+//! use std::sync::Arc;
+//!
+//! # async fn example(client: Siumai) -> Result<(), LlmError> {
+//! let mut conversation = Conversation::new(Arc::new(client))
+//!     .with_id("support-42")
+//!     .with_system_prompt("You are a helpful support agent.")
+//!     .with_strategy(MemoryStrategy::SlidingWindow { max_messages: 20 })
+//!     .with_store(Arc::new(JsonFileStore::new("conversations")))
+//!     .load()
+//!     .await?;
+//!
+//! let response = conversation.send_text("My order has not arrived").await?;
+//! println!("{}", response.content_text().unwrap_or_default());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::client::LlmClient;
+use crate::context_window::{ContextWindowManager, summarize};
+use crate::error::LlmError;
+use crate::traits::ChatCapability;
+use crate::types::*;
+
+/// Persisted state of a conversation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConversationState {
+    /// System prompt
+    pub system_prompt: Option<String>,
+    /// Message history, oldest first
+    pub messages: Vec<ChatMessage>,
+    /// Summary of messages removed from the history
+    pub summary: Option<String>,
+    /// Response ID continuing the server-side conversation
+    pub previous_response_id: Option<String>,
+    /// Number of leading messages already held by the server-side conversation
+    #[serde(default)]
+    pub synced_messages: usize,
+}
+
+/// How a conversation bounds its history.
+#[derive(Debug, Clone, Default)]
+pub enum MemoryStrategy {
+    /// Keep every message
+    #[default]
+    KeepAll,
+    /// Drop the oldest turns once the history exceeds `max_messages`
+    SlidingWindow {
+        /// Maximum number of messages kept
+        max_messages: usize,
+    },
+    /// Once the history exceeds `max_messages`, replace all but the latest
+    /// `keep_recent` messages with a summary
+    ///
+    /// Clients keeping conversation state on the server already hold the full
+    /// conversation, so no summary is generated for them; the local history is
+    /// trimmed as with `SlidingWindow`.
+    Summarize {
+        /// Number of messages that triggers summarization
+        max_messages: usize,
+        /// Number of recent messages kept verbatim
+        keep_recent: usize,
+    },
+    /// Keep every message, but fit each request to a context window
+    ContextWindow(ContextWindowManager),
+}
+
+/// Storage backend for conversation state.
+#[async_trait]
+pub trait MemoryStore: Send + Sync {
+    /// Load a conversation by ID
+    async fn load(&self, id: &str) -> Result<Option<ConversationState>, LlmError>;
+
+    /// Insert or replace a conversation
+    async fn save(&self, id: &str, state: &ConversationState) -> Result<(), LlmError>;
+
+    /// Remove a conversation
+    async fn delete(&self, id: &str) -> Result<(), LlmError>;
+
+    /// IDs of the stored conversations
+    async fn list(&self) -> Result<Vec<String>, LlmError>;
+}
+
+/// Memory store keeping conversations in process memory
+#[derive(Debug, Default)]
+pub struct InMemoryStore {
+    conversations: Mutex<HashMap<String, ConversationState>>,
+}
+
+impl InMemoryStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, ConversationState>> {
+        self.conversations
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[async_trait]
+impl MemoryStore for InMemoryStore {
+    async fn load(&self, id: &str) -> Result<Option<ConversationState>, LlmError> {
+        Ok(self.lock().get(id).cloned())
+    }
+
+    async fn save(&self, id: &str, state: &ConversationState) -> Result<(), LlmError> {
+        self.lock().insert(id.to_string(), state.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), LlmError> {
+        self.lock().remove(id);
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<String>, LlmError> {
+        Ok(self.lock().keys().cloned().collect())
+    }
+}
+
+/// Memory store writing one JSON file per conversation
+#[derive(Debug, Clone)]
+pub struct JsonFileStore {
+    dir: PathBuf,
+}
+
+impl JsonFileStore {
+    /// Create a store under `dir` (created on first write)
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Storage directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, id: &str) -> Result<PathBuf, LlmError> {
+        let valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            && !id.starts_with('.');
+        if !valid {
+            return Err(LlmError::InvalidInput(format!(
+                "Invalid conversation ID '{id}': use letters, digits, '-', '_' and '.'"
+            )));
+        }
+        Ok(self.dir.join(format!("{id}.json")))
+    }
+}
+
+fn io_error(action: &str, error: std::io::Error) -> LlmError {
+    LlmError::IoError(format!("Failed to {action}: {error}"))
+}
+
+#[async_trait]
+impl MemoryStore for JsonFileStore {
+    async fn load(&self, id: &str) -> Result<Option<ConversationState>, LlmError> {
+        let path = self.path(id)?;
+        let bytes = match tokio::fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error("read conversation", e)),
+        };
+
+        serde_json::from_slice(&bytes).map(Some).map_err(|e| {
+            LlmError::ParseError(format!("Invalid conversation file {}: {e}", path.display()))
+        })
+    }
+
+    async fn save(&self, id: &str, state: &ConversationState) -> Result<(), LlmError> {
+        let path = self.path(id)?;
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| io_error("create conversation directory", e))?;
+
+        let data = serde_json::to_vec_pretty(state)
+            .map_err(|e| LlmError::ParseError(format!("Failed to serialize conversation: {e}")))?;
+
+        // Write to a temporary file first so readers never see a partial conversation
+        let tmp_path = self.dir.join(format!("{id}.tmp"));
+        tokio::fs::write(&tmp_path, data)
+            .await
+            .map_err(|e| io_error("write conversation", e))?;
+        tokio::fs::rename(&tmp_path, &path)
+            .await
+            .map_err(|e| io_error("write conversation", e))
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), LlmError> {
+        match tokio::fs::remove_file(self.path(id)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(io_error("remove conversation", e)),
+        }
+    }
+
+    async fn list(&self) -> Result<Vec<String>, LlmError> {
+        let mut dir = match tokio::fs::read_dir(&self.dir).await {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(io_error("read conversation directory", e)),
+        };
+
+        let mut ids = Vec::new();
+        while let Some(item) = dir
+            .next_entry()
+            .await
+            .map_err(|e| io_error("read conversation directory", e))?
+        {
+            let path = item.path();
+            if path.extension().is_some_and(|ext| ext == "json")
+                && let Some(id) = path.file_stem().and_then(|stem| stem.to_str())
+            {
+                ids.push(id.to_string());
+            }
+        }
+        Ok(ids)
+    }
+}
+
+/// A stateful conversation with a client.
+pub struct Conversation {
+    id: String,
+    client: Arc<dyn LlmClient>,
+    tools: Option<Vec<Tool>>,
+    strategy: MemoryStrategy,
+    store: Option<Arc<dyn MemoryStore>>,
+    summarizer: Option<Arc<dyn ChatCapability>>,
+    state: ConversationState,
+}
+
+impl Conversation {
+    /// Start a conversation with a random ID
+    pub fn new(client: Arc<dyn LlmClient>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            client,
+            tools: None,
+            strategy: MemoryStrategy::default(),
+            store: None,
+            summarizer: None,
+            state: ConversationState::default(),
+        }
+    }
+
+    /// Set the conversation ID used by the memory store
+    pub fn with_id<S: Into<String>>(mut self, id: S) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// Set the system prompt
+    pub fn with_system_prompt<S: Into<String>>(mut self, prompt: S) -> Self {
+        self.state.system_prompt = Some(prompt.into());
+        self
+    }
+
+    /// Set the tools offered with every message
+    pub fn with_tools(mut self, tools: Vec<Tool>) -> Self {
+        self.tools = Some(tools);
+        self
+    }
+
+    /// Set how the history is bounded
+    pub fn with_strategy(mut self, strategy: MemoryStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Persist the conversation after every exchange
+    pub fn with_store(mut self, store: Arc<dyn MemoryStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Summarize with this client instead of the conversation's client
+    pub fn with_summarizer(mut self, summarizer: Arc<dyn ChatCapability>) -> Self {
+        self.summarizer = Some(summarizer);
+        self
+    }
+
+    /// Restore the stored state of this conversation, if any.
+    ///
+    /// The configured system prompt is kept when the stored state has none.
+    pub async fn load(mut self) -> Result<Self, LlmError> {
+        if let Some(store) = &self.store
+            && let Some(mut state) = store.load(&self.id).await?
+        {
+            if state.system_prompt.is_none() {
+                state.system_prompt = self.state.system_prompt.take();
+            }
+            self.state = state;
+        }
+        Ok(self)
+    }
+
+    /// Conversation ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// System prompt
+    pub fn system_prompt(&self) -> Option<&str> {
+        self.state.system_prompt.as_deref()
+    }
+
+    /// Tools offered with every message
+    pub fn tools(&self) -> Option<&[Tool]> {
+        self.tools.as_deref()
+    }
+
+    /// Message history, oldest first
+    pub fn messages(&self) -> &[ChatMessage] {
+        &self.state.messages
+    }
+
+    /// Summary of messages removed from the history
+    pub fn summary(&self) -> Option<&str> {
+        self.state.summary.as_deref()
+    }
+
+    /// Response ID continuing the server-side conversation
+    pub fn previous_response_id(&self) -> Option<&str> {
+        self.state.previous_response_id.as_deref()
+    }
+
+    /// Full state, as persisted
+    pub const fn state(&self) -> &ConversationState {
+        &self.state
+    }
+
+    /// Send a user message
+    pub async fn send_text<S: Into<String>>(&mut self, text: S) -> Result<ChatResponse, LlmError> {
+        self.send(ChatMessage::user(text).build()).await
+    }
+
+    /// Send a message
+    pub async fn send(&mut self, message: ChatMessage) -> Result<ChatResponse, LlmError> {
+        self.send_messages(vec![message]).await
+    }
+
+    /// Send several messages at once, such as the results of the tool calls of
+    /// the previous response.
+    ///
+    /// The messages and the response are added to the history; nothing is
+    /// added when the request fails.
+    pub async fn send_messages(
+        &mut self,
+        messages: Vec<ChatMessage>,
+    ) -> Result<ChatResponse, LlmError> {
+        let len = self.state.messages.len();
+        self.state.messages.extend(messages);
+
+        let response = match self.request().await {
+            Ok(response) => response,
+            Err(e) => {
+                self.state.messages.truncate(len);
+                return Err(e);
+            }
+        };
+
+        self.state.messages.push(assistant_message(&response));
+        self.compact().await?;
+        self.state.synced_messages = self.state.messages.len();
+        self.save().await?;
+        Ok(response)
+    }
+
+    /// Forget the history and summary, keeping the system prompt, and remove the stored state
+    pub async fn clear(&mut self) -> Result<(), LlmError> {
+        self.state = ConversationState {
+            system_prompt: self.state.system_prompt.take(),
+            ..Default::default()
+        };
+        if let Some(store) = &self.store {
+            store.delete(&self.id).await?;
+        }
+        Ok(())
+    }
+
+    /// Persist the state to the memory store, if any
+    pub async fn save(&self) -> Result<(), LlmError> {
+        match &self.store {
+            Some(store) => store.save(&self.id, &self.state).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Messages sent to a stateless client: system prompt, summary and history
+    fn request_messages(&self) -> Vec<ChatMessage> {
+        let system = self.state.system_prompt.iter().chain(&self.state.summary);
+        system
+            .map(|text| ChatMessage::system(text.clone()).build())
+            .chain(self.state.messages.iter().cloned())
+            .collect()
+    }
+
+    /// Send the pending messages and return the response
+    async fn request(&mut self) -> Result<ChatResponse, LlmError> {
+        let tools = self.tools.clone();
+
+        if let Some(stateful) = self.client.as_conversation_state_capability() {
+            let previous = self.state.previous_response_id.clone();
+            let messages = match &previous {
+                Some(_) => self.state.messages[self.state.synced_messages..].to_vec(),
+                None => self.request_messages(),
+            };
+            let response = stateful
+                .chat_with_previous_response(messages, tools, previous)
+                .await?;
+            self.state.previous_response_id = response.id.clone();
+            return Ok(response);
+        }
+
+        let mut messages = self.request_messages();
+        if let MemoryStrategy::ContextWindow(manager) = &self.strategy {
+            messages = manager.fit(messages, tools.as_deref()).await?;
+        }
+        self.client.chat_with_tools(messages, tools).await
+    }
+
+    /// Apply the memory strategy to the history
+    async fn compact(&mut self) -> Result<(), LlmError> {
+        let stateful = self.client.as_conversation_state_capability().is_some();
+        let messages = &mut self.state.messages;
+        match self.strategy {
+            MemoryStrategy::KeepAll | MemoryStrategy::ContextWindow(_) => {}
+            MemoryStrategy::SlidingWindow { max_messages } => {
+                if messages.len() > max_messages {
+                    let cut = cut_index(messages, max_messages);
+                    messages.drain(..cut);
+                }
+            }
+            MemoryStrategy::Summarize {
+                max_messages,
+                keep_recent,
+            } => {
+                if messages.len() <= max_messages {
+                    return Ok(());
+                }
+                let cut = cut_index(messages, keep_recent);
+                if cut == 0 {
+                    return Ok(());
+                }
+                if stateful {
+                    messages.drain(..cut);
+                    return Ok(());
+                }
+
+                let mut dropped: Vec<ChatMessage> = self
+                    .state
+                    .summary
+                    .iter()
+                    .map(|summary| ChatMessage::system(summary.clone()).build())
+                    .collect();
+                dropped.extend(self.state.messages.drain(..cut));
+
+                let summarizer: &dyn ChatCapability = match &self.summarizer {
+                    Some(summarizer) => summarizer.as_ref(),
+                    None => self.client.as_ref(),
+                };
+                let summary = summarize(summarizer, &[dropped]).await?;
+                self.state.summary = Some(summary.content.all_text());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Conversation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Conversation")
+            .field("id", &self.id)
+            .field("provider", &self.client.provider_name())
+            .field("strategy", &self.strategy)
+            .field("messages", &self.state.messages.len())
+            .field("persistent", &self.store.is_some())
+            .finish()
+    }
+}
+
+/// Index of the first kept message when keeping about `keep` messages.
+///
+/// The kept history starts at a user message so tool results stay with the
+/// assistant message that requested them; the latest turn is always kept.
+fn cut_index(messages: &[ChatMessage], keep: usize) -> usize {
+    let is_user = |i: &usize| messages[*i].role == MessageRole::User;
+    (messages.len().saturating_sub(keep)..messages.len())
+        .find(is_user)
+        .or_else(|| (0..messages.len()).rev().find(is_user))
+        .unwrap_or(0)
+}
+
+fn assistant_message(response: &ChatResponse) -> ChatMessage {
+    ChatMessage {
+        role: MessageRole::Assistant,
+        content: response.content.clone(),
+        metadata: MessageMetadata::default(),
+        tool_calls: response.tool_calls.clone(),
+        tool_call_id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::ChatStream;
+    use crate::testing::{MockClient, MockReply};
+    use crate::traits::{ConversationStateCapability, ProviderCapabilities};
+
+    #[tokio::test]
+    async fn test_sliding_window_keeps_system_prompt_and_recent_turns() {
+        let mock = MockClient::new().with_replies((0..3).map(|i| MockReply::text(format!("a{i}"))));
+        let mut conversation = Conversation::new(Arc::new(mock.clone()))
+            .with_system_prompt("Be brief.")
+            .with_strategy(MemoryStrategy::SlidingWindow { max_messages: 3 });
+
+        for i in 0..3 {
+            conversation.send_text(format!("u{i}")).await.unwrap();
+        }
+
+        let texts: Vec<_> = conversation
+            .messages()
+            .iter()
+            .map(|m| m.content.all_text())
+            .collect();
+        assert_eq!(texts, ["u2", "a2"]);
+
+        // The last request carried the system prompt and the previous turn
+        let request = mock.last_request().unwrap();
+        assert_eq!(request.messages[0].role, MessageRole::System);
+        assert_eq!(request.messages.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_summarize_on_overflow() {
+        let mock = MockClient::new().with_replies([
+            MockReply::text("a0"),
+            MockReply::text("a1"),
+            MockReply::text("a2"),
+            MockReply::text("the user asked twice"),
+            MockReply::text("a3"),
+        ]);
+        let mut conversation =
+            Conversation::new(Arc::new(mock.clone())).with_strategy(MemoryStrategy::Summarize {
+                max_messages: 4,
+                keep_recent: 2,
+            });
+
+        for i in 0..4 {
+            conversation.send_text(format!("u{i}")).await.unwrap();
+        }
+
+        assert!(
+            conversation
+                .summary()
+                .unwrap()
+                .contains("the user asked twice")
+        );
+        assert_eq!(conversation.messages().len(), 4);
+        let request = mock.last_request().unwrap();
+        assert!(
+            request.messages[0]
+                .content
+                .all_text()
+                .contains("the user asked twice")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_request_leaves_history_unchanged() {
+        let mock = MockClient::new().with_error(LlmError::TimeoutError("slow".to_string()));
+        let mut conversation = Conversation::new(Arc::new(mock));
+
+        assert!(conversation.send_text("hello").await.is_err());
+        assert!(conversation.messages().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_json_file_store_round_trip() {
+        let dir = std::env::temp_dir().join(format!("siumai-conversations-{}", std::process::id()));
+        let store: Arc<dyn MemoryStore> = Arc::new(JsonFileStore::new(&dir));
+        let mock = MockClient::new().with_text("hi there");
+
+        let mut conversation = Conversation::new(Arc::new(mock.clone()))
+            .with_id("chat-1")
+            .with_system_prompt("Be kind.")
+            .with_store(store.clone());
+        conversation.send_text("hello").await.unwrap();
+
+        let restored = Conversation::new(Arc::new(mock))
+            .with_id("chat-1")
+            .with_store(store.clone())
+            .load()
+            .await
+            .unwrap();
+        assert_eq!(restored.system_prompt(), Some("Be kind."));
+        assert_eq!(restored.messages().len(), 2);
+        assert_eq!(store.list().await.unwrap(), ["chat-1"]);
+        assert!(store.load("../escape").await.is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// Number of messages sent and previous response ID, per request
+    type StatefulCalls = Arc<Mutex<Vec<(usize, Option<String>)>>>;
+
+    /// Client keeping the conversation on the "server"
+    #[derive(Clone, Default)]
+    struct StatefulClient {
+        calls: StatefulCalls,
+    }
+
+    #[async_trait]
+    impl ChatCapability for StatefulClient {
+        async fn chat_with_tools(
+            &self,
+            _messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+        ) -> Result<ChatResponse, LlmError> {
+            Err(LlmError::UnsupportedOperation("stateless".to_string()))
+        }
+
+        async fn chat_stream(
+            &self,
+            _messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+        ) -> Result<ChatStream, LlmError> {
+            Err(LlmError::UnsupportedOperation("stateless".to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl ConversationStateCapability for StatefulClient {
+        async fn chat_with_previous_response(
+            &self,
+            messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+            previous_response_id: Option<String>,
+        ) -> Result<ChatResponse, LlmError> {
+            let mut calls = self.calls.lock().unwrap();
+            calls.push((messages.len(), previous_response_id));
+            let mut response = ChatResponse::new(MessageContent::Text("ok".to_string()));
+            response.id = Some(format!("resp_{}", calls.len()));
+            Ok(response)
+        }
+    }
+
+    impl LlmClient for StatefulClient {
+        fn provider_name(&self) -> &'static str {
+            "stateful"
+        }
+
+        fn supported_models(&self) -> Vec<String> {
+            Vec::new()
+        }
+
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities::new().with_chat()
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn clone_box(&self) -> Box<dyn LlmClient> {
+            Box::new(self.clone())
+        }
+
+        fn as_conversation_state_capability(&self) -> Option<&dyn ConversationStateCapability> {
+            Some(self)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_server_side_state_sends_only_new_messages() {
+        let client = StatefulClient::default();
+        let mut conversation =
+            Conversation::new(Arc::new(client.clone())).with_system_prompt("Be brief.");
+
+        conversation.send_text("first").await.unwrap();
+        conversation.send_text("second").await.unwrap();
+
+        let calls = client.calls.lock().unwrap().clone();
+        assert_eq!(calls, [(2, None), (1, Some("resp_1".to_string()))]);
+        assert_eq!(conversation.previous_response_id(), Some("resp_2"));
+        assert_eq!(conversation.messages().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_server_side_state_survives_wrappers() {
+        let client = StatefulClient::default();
+        let wrapped: Vec<Arc<dyn LlmClient>> = vec![
+            Arc::new(crate::middleware::MiddlewareClient::new(client.clone())),
+            Arc::new(crate::cache::CacheLayer::new(client.clone())),
+            Arc::new(crate::fallback::FallbackClient::new().with_target(
+                crate::fallback::FallbackTarget::new(crate::client::ClientWrapper::custom(
+                    Box::new(client.clone()),
+                )),
+            )),
+        ];
+
+        for wrapper in wrapped {
+            client.calls.lock().unwrap().clear();
+            let mut conversation = Conversation::new(wrapper);
+            conversation.send_text("first").await.unwrap();
+            conversation.send_text("second").await.unwrap();
+
+            let calls = client.calls.lock().unwrap().clone();
+            assert_eq!(calls, [(1, None), (1, Some("resp_1".to_string()))]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_server_side_state_skips_summarizer() {
+        // The client cannot answer stateless chats, so summarizing would fail
+        let client = StatefulClient::default();
+        let mut conversation =
+            Conversation::new(Arc::new(client.clone())).with_strategy(MemoryStrategy::Summarize {
+                max_messages: 2,
+                keep_recent: 2,
+            });
+
+        conversation.send_text("first").await.unwrap();
+        conversation.send_text("second").await.unwrap();
+
+        assert_eq!(conversation.summary(), None);
+        assert_eq!(conversation.messages().len(), 2);
+        assert_eq!(conversation.messages()[0].content_text(), Some("second"));
+        assert_eq!(client.calls.lock().unwrap().len(), 2);
+    }
+}
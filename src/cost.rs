@@ -0,0 +1,894 @@
+//! Cost Tracking and Budgets
+//!
+//! This module turns the [`Usage`] reported with each response into money and
+//! enforces spending limits.
+//!
+//! - [`PricingTable`] maps provider and model to per-token prices, with
+//!   separate rates for cache reads, cache writes and reasoning tokens.
+//!   Built-in estimates cover OpenAI, Anthropic and Gemini models; overrides
+//!   can be loaded from a JSON file.
+//! - [`CostTracker`] keeps running totals per client, per tag and per tenant,
+//!   and checks [`Budget`]s. Hard budgets reject requests with
+//!   [`LlmError::BudgetExceeded`] before they are sent; soft budgets only log
+//!   a warning.
+//! - [`CostLayer`] is the [`LlmMiddleware`] that connects a client to a tracker.
+//!
+//! # Example
+//! ```rust,no_run
+//! use siumai::cost::{Budget, BudgetScope, CostTracker, PricingTable};
+//! use siumai::prelude::*;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> Result<(), LlmError> {
+//! let tracker = Arc::new(
+//!     CostTracker::new()
+//!         .with_pricing(PricingTable::new().with_overrides_file("pricing.json")?)
+//!         .with_budget(Budget::hard(BudgetScope::Tenant("acme".to_string()), 25.0)),
+//! );
+//!
+//! let client = Siumai::builder()
+//!     .openai()
+//!     .api_key("your-api-key")
+//!     .model("gpt-4o-mini")
+//!     .layer(tracker.layer("nightly").with_tag("summaries").with_tenant("acme"))
+//!     .build()
+//!     .await?;
+//!
+//! client.chat(vec![user!("Summarize this report")]).await?;
+//! println!("Spent ${:.4}", tracker.totals().total);
+//! # Ok(())
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::error::LlmError;
+use crate::middleware::LlmMiddleware;
+use crate::types::*;
+
+/// Response metadata key holding the cost of a response in USD
+pub const COST_METADATA_KEY: &str = "cost_usd";
+
+/// Per-token prices of a model, in USD
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    /// Price per prompt token
+    pub input_cost_per_token: f64,
+    /// Price per completion token
+    pub output_cost_per_token: f64,
+    /// Price per cached prompt token (defaults to the prompt price)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_input_cost_per_token: Option<f64>,
+    /// Price per token written to the prompt cache (defaults to the prompt price)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write_cost_per_token: Option<f64>,
+    /// Price per reasoning token (defaults to the completion price)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_cost_per_token: Option<f64>,
+    /// Whether cache reads and writes are reported next to the prompt tokens
+    /// instead of as part of them, as Anthropic does
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cache_tokens_separate: bool,
+}
+
+impl ModelPricing {
+    /// Create pricing from per-token prompt and completion prices
+    pub const fn new(input_cost_per_token: f64, output_cost_per_token: f64) -> Self {
+        Self {
+            input_cost_per_token,
+            output_cost_per_token,
+            cached_input_cost_per_token: None,
+            cache_write_cost_per_token: None,
+            reasoning_cost_per_token: None,
+            cache_tokens_separate: false,
+        }
+    }
+
+    /// Create pricing from prices per million tokens, as providers publish them
+    pub fn per_million(input: f64, output: f64) -> Self {
+        Self::new(input / 1_000_000.0, output / 1_000_000.0)
+    }
+
+    /// Set the price per cached prompt token
+    pub const fn with_cached_input_cost(mut self, cost_per_token: f64) -> Self {
+        self.cached_input_cost_per_token = Some(cost_per_token);
+        self
+    }
+
+    /// Set the price per token written to the prompt cache
+    pub const fn with_cache_write_cost(mut self, cost_per_token: f64) -> Self {
+        self.cache_write_cost_per_token = Some(cost_per_token);
+        self
+    }
+
+    /// Set the price per reasoning token
+    pub const fn with_reasoning_cost(mut self, cost_per_token: f64) -> Self {
+        self.reasoning_cost_per_token = Some(cost_per_token);
+        self
+    }
+
+    /// Count cache reads and writes as reported next to the prompt tokens
+    pub const fn with_separate_cache_tokens(mut self) -> Self {
+        self.cache_tokens_separate = true;
+        self
+    }
+
+    /// Calculate the cost of a response.
+    ///
+    /// Reasoning tokens are counted as part of the completion tokens. Cache
+    /// reads and writes are counted as part of the prompt tokens (OpenAI,
+    /// Gemini) unless `cache_tokens_separate` is set (Anthropic).
+    pub fn cost(&self, usage: &Usage) -> f64 {
+        let mut cached = usage.cached_tokens.unwrap_or(0);
+        let mut written = usage.cache_write_tokens.unwrap_or(0);
+        let mut uncached = usage.prompt_tokens;
+        if !self.cache_tokens_separate {
+            cached = cached.min(uncached);
+            written = written.min(uncached - cached);
+            uncached -= cached + written;
+        }
+        let reasoning = usage
+            .reasoning_tokens
+            .unwrap_or(0)
+            .min(usage.completion_tokens);
+
+        let prompt = f64::from(uncached) * self.input_cost_per_token
+            + f64::from(cached)
+                * self
+                    .cached_input_cost_per_token
+                    .unwrap_or(self.input_cost_per_token)
+            + f64::from(written)
+                * self
+                    .cache_write_cost_per_token
+                    .unwrap_or(self.input_cost_per_token);
+        let completion = f64::from(usage.completion_tokens - reasoning)
+            * self.output_cost_per_token
+            + f64::from(reasoning)
+                * self
+                    .reasoning_cost_per_token
+                    .unwrap_or(self.output_cost_per_token);
+
+        prompt + completion
+    }
+}
+
+/// Prices per provider and model.
+///
+/// Overrides take precedence over the built-in estimates. The JSON override
+/// format maps provider names to models to [`ModelPricing`]:
+///
+/// ```json
+/// {
+///   "openai": {
+///     "gpt-4o-mini": { "input_cost_per_token": 1.5e-7, "output_cost_per_token": 6e-7 }
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PricingTable {
+    overrides: HashMap<String, HashMap<String, ModelPricing>>,
+}
+
+impl PricingTable {
+    /// Create a table with the built-in estimates only
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the price of a model
+    pub fn with_price(
+        mut self,
+        provider: impl Into<String>,
+        model: impl Into<String>,
+        pricing: ModelPricing,
+    ) -> Self {
+        self.set_price(provider, model, pricing);
+        self
+    }
+
+    /// Set the price of a model
+    pub fn set_price(
+        &mut self,
+        provider: impl Into<String>,
+        model: impl Into<String>,
+        pricing: ModelPricing,
+    ) {
+        self.overrides
+            .entry(provider.into())
+            .or_default()
+            .insert(model.into(), pricing);
+    }
+
+    /// Add overrides from a JSON string
+    pub fn with_overrides_json(mut self, json: &str) -> Result<Self, LlmError> {
+        let overrides: HashMap<String, HashMap<String, ModelPricing>> = serde_json::from_str(json)?;
+        for (provider, models) in overrides {
+            self.overrides.entry(provider).or_default().extend(models);
+        }
+        Ok(self)
+    }
+
+    /// Add overrides from a JSON file
+    pub fn with_overrides_file(self, path: impl AsRef<Path>) -> Result<Self, LlmError> {
+        let json = std::fs::read_to_string(path.as_ref()).map_err(|e| {
+            LlmError::IoError(format!(
+                "Failed to read pricing file {}: {e}",
+                path.as_ref().display()
+            ))
+        })?;
+        self.with_overrides_json(&json)
+    }
+
+    /// Look up the price of a model.
+    ///
+    /// Without a provider, the provider is inferred from the model name.
+    /// Dated snapshots (e.g. `gpt-4o-2024-08-06`) fall back to the base model.
+    /// Anthropic prices always count cache tokens separately, matching how
+    /// its usage is reported.
+    pub fn get(&self, provider: Option<&str>, model: &str) -> Option<ModelPricing> {
+        let provider = provider.or_else(|| infer_provider(model))?;
+        let base_model = strip_date_suffix(model);
+
+        let overrides = self.overrides.get(provider);
+        let pricing = overrides
+            .and_then(|models| models.get(model).or_else(|| models.get(base_model)))
+            .copied()
+            .or_else(|| builtin_pricing(provider, base_model))?;
+        Some(if provider == "anthropic" {
+            pricing.with_separate_cache_tokens()
+        } else {
+            pricing
+        })
+    }
+}
+
+/// Infer the provider of a model from its name
+fn infer_provider(model: &str) -> Option<&'static str> {
+    if model.contains("claude") {
+        Some("anthropic")
+    } else if model.contains("gemini") {
+        Some("gemini")
+    } else if ["gpt-", "o1", "o3", "o4", "chatgpt-"]
+        .iter()
+        .any(|prefix| model.starts_with(prefix))
+    {
+        Some("openai")
+    } else {
+        None
+    }
+}
+
+/// Strip a `-YYYY-MM-DD` snapshot suffix from a model name
+fn strip_date_suffix(model: &str) -> &str {
+    let Some(split) = model.len().checked_sub(11) else {
+        return model;
+    };
+    let (Some(base), Some(suffix)) = (model.get(..split), model.get(split..)) else {
+        return model;
+    };
+    let is_date = suffix.bytes().enumerate().all(|(i, b)| match i {
+        0 | 5 | 8 => b == b'-',
+        _ => b.is_ascii_digit(),
+    });
+    if is_date { base } else { model }
+}
+
+/// Built-in price estimates from the provider model catalogs
+#[allow(unused_variables)]
+fn builtin_pricing(provider: &str, model: &str) -> Option<ModelPricing> {
+    match provider {
+        // Cached prompt tokens are billed at half price
+        #[cfg(feature = "openai")]
+        "openai" => {
+            let (_, _, input, output) =
+                crate::providers::openai::models::estimate_model_specs(model);
+            let input = input?;
+            Some(ModelPricing::new(input, output?).with_cached_input_cost(input * 0.5))
+        }
+        // Cache reads are billed at a tenth of the prompt price, writes at 1.25x
+        #[cfg(feature = "anthropic")]
+        "anthropic" => {
+            let (_, _, input, output) =
+                crate::providers::anthropic::models::estimate_model_specs(model);
+            let input = input?;
+            Some(
+                ModelPricing::new(input, output?)
+                    .with_cached_input_cost(input * 0.1)
+                    .with_cache_write_cost(input * 1.25),
+            )
+        }
+        // Cached prompt tokens are billed at a quarter of the prompt price
+        "gemini" => {
+            let (input, output) = gemini_prices_per_million(model)?;
+            let pricing = ModelPricing::per_million(input, output);
+            Some(pricing.with_cached_input_cost(pricing.input_cost_per_token * 0.25))
+        }
+        _ => None,
+    }
+}
+
+/// Published Gemini prices per million tokens, for prompts up to 200k tokens
+fn gemini_prices_per_million(model: &str) -> Option<(f64, f64)> {
+    let model = model.strip_prefix("models/").unwrap_or(model);
+    let prices = [
+        ("gemini-2.5-pro", (1.25, 10.0)),
+        ("gemini-2.5-flash-lite", (0.10, 0.40)),
+        ("gemini-2.5-flash", (0.30, 2.50)),
+        ("gemini-2.0-flash-lite", (0.075, 0.30)),
+        ("gemini-2.0-flash", (0.10, 0.40)),
+        ("gemini-1.5-pro", (1.25, 5.0)),
+        ("gemini-1.5-flash-8b", (0.0375, 0.15)),
+        ("gemini-1.5-flash", (0.075, 0.30)),
+    ];
+    prices
+        .iter()
+        .find(|(prefix, _)| model.starts_with(prefix))
+        .map(|(_, prices)| *prices)
+}
+
+/// What a budget applies to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BudgetScope {
+    /// All spending recorded by the tracker
+    Global,
+    /// Spending of one client
+    Client(String),
+    /// Spending of requests with a tag
+    Tag(String),
+    /// Spending of one tenant
+    Tenant(String),
+}
+
+impl std::fmt::Display for BudgetScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Global => write!(f, "all requests"),
+            Self::Client(client) => write!(f, "client '{client}'"),
+            Self::Tag(tag) => write!(f, "tag '{tag}'"),
+            Self::Tenant(tenant) => write!(f, "tenant '{tenant}'"),
+        }
+    }
+}
+
+/// How a budget is enforced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetEnforcement {
+    /// Reject requests once the budget is spent
+    Hard,
+    /// Log a warning once the budget is spent
+    Soft,
+}
+
+/// A spending limit in USD
+#[derive(Debug, Clone, PartialEq)]
+pub struct Budget {
+    /// What the budget applies to
+    pub scope: BudgetScope,
+    /// Limit in USD
+    pub limit: f64,
+    /// How the budget is enforced
+    pub enforcement: BudgetEnforcement,
+}
+
+impl Budget {
+    /// Create a budget that rejects requests once spent
+    pub const fn hard(scope: BudgetScope, limit: f64) -> Self {
+        Self {
+            scope,
+            limit,
+            enforcement: BudgetEnforcement::Hard,
+        }
+    }
+
+    /// Create a budget that only warns once spent
+    pub const fn soft(scope: BudgetScope, limit: f64) -> Self {
+        Self {
+            scope,
+            limit,
+            enforcement: BudgetEnforcement::Soft,
+        }
+    }
+}
+
+/// Who a request is attributed to
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CostContext {
+    /// Client name
+    pub client: String,
+    /// Provider used for price lookup (inferred from the model when unset)
+    pub provider: Option<String>,
+    /// Tags
+    pub tags: Vec<String>,
+    /// Tenant
+    pub tenant: Option<String>,
+}
+
+impl CostContext {
+    /// Create a context for a client
+    pub fn new(client: impl Into<String>) -> Self {
+        Self {
+            client: client.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the provider used for price lookup
+    pub fn with_provider(mut self, provider: impl Into<String>) -> Self {
+        self.provider = Some(provider.into());
+        self
+    }
+
+    /// Add a tag
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Set the tenant
+    pub fn with_tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.tenant = Some(tenant.into());
+        self
+    }
+
+    /// Budget scopes this context is charged to
+    fn scopes(&self) -> impl Iterator<Item = BudgetScope> + '_ {
+        [
+            BudgetScope::Global,
+            BudgetScope::Client(self.client.clone()),
+        ]
+        .into_iter()
+        .chain(self.tags.iter().cloned().map(BudgetScope::Tag))
+        .chain(self.tenant.iter().cloned().map(BudgetScope::Tenant))
+    }
+}
+
+/// Spending totals in USD
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CostTotals {
+    /// Total spending
+    pub total: f64,
+    /// Spending per client
+    pub by_client: HashMap<String, f64>,
+    /// Spending per tag
+    pub by_tag: HashMap<String, f64>,
+    /// Spending per tenant
+    pub by_tenant: HashMap<String, f64>,
+    /// Responses whose model had no known price
+    pub unpriced_responses: u64,
+}
+
+impl CostTotals {
+    /// Spending charged to a budget scope
+    pub fn spent(&self, scope: &BudgetScope) -> f64 {
+        match scope {
+            BudgetScope::Global => self.total,
+            BudgetScope::Client(client) => self.by_client.get(client).copied().unwrap_or(0.0),
+            BudgetScope::Tag(tag) => self.by_tag.get(tag).copied().unwrap_or(0.0),
+            BudgetScope::Tenant(tenant) => self.by_tenant.get(tenant).copied().unwrap_or(0.0),
+        }
+    }
+}
+
+/// Tracks spending and enforces budgets.
+///
+/// A tracker is shared between clients through an `Arc`; attach it to a
+/// client with [`CostTracker::layer`].
+#[derive(Debug, Default)]
+pub struct CostTracker {
+    pricing: RwLock<PricingTable>,
+    budgets: RwLock<Vec<Budget>>,
+    totals: Mutex<CostTotals>,
+}
+
+impl CostTracker {
+    /// Create a tracker with the built-in prices and no budgets
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the pricing table
+    pub fn with_pricing(self, pricing: PricingTable) -> Self {
+        *self.pricing.write().unwrap() = pricing;
+        self
+    }
+
+    /// Add a budget
+    pub fn with_budget(self, budget: Budget) -> Self {
+        self.set_budget(budget);
+        self
+    }
+
+    /// Add a budget, replacing any existing budget with the same scope
+    pub fn set_budget(&self, budget: Budget) {
+        let mut budgets = self.budgets.write().unwrap();
+        budgets.retain(|b| b.scope != budget.scope);
+        budgets.push(budget);
+    }
+
+    /// Remove the budget of a scope
+    pub fn remove_budget(&self, scope: &BudgetScope) {
+        self.budgets.write().unwrap().retain(|b| &b.scope != scope);
+    }
+
+    /// Replace the pricing table
+    pub fn set_pricing(&self, pricing: PricingTable) {
+        *self.pricing.write().unwrap() = pricing;
+    }
+
+    /// Create a middleware layer that charges a client to this tracker
+    pub fn layer(self: &Arc<Self>, client: impl Into<String>) -> CostLayer {
+        CostLayer::new(self.clone(), CostContext::new(client))
+    }
+
+    /// Calculate the cost of a response, or `None` if the model has no known price
+    pub fn cost_of(&self, provider: Option<&str>, model: &str, usage: &Usage) -> Option<f64> {
+        self.pricing
+            .read()
+            .unwrap()
+            .get(provider, model)
+            .map(|pricing| pricing.cost(usage))
+    }
+
+    /// Check the budgets a request would be charged to.
+    ///
+    /// Returns [`LlmError::BudgetExceeded`] if a hard budget is spent, and logs
+    /// a warning for every spent soft budget.
+    pub fn check_budget(&self, context: &CostContext) -> Result<(), LlmError> {
+        let budgets = self.budgets.read().unwrap();
+        let totals = self.totals.lock().unwrap();
+
+        for scope in context.scopes() {
+            for budget in budgets.iter().filter(|b| b.scope == scope) {
+                let spent = totals.spent(&scope);
+                if spent < budget.limit {
+                    continue;
+                }
+                match budget.enforcement {
+                    BudgetEnforcement::Hard => {
+                        return Err(LlmError::BudgetExceeded {
+                            scope: scope.to_string(),
+                            spent,
+                            limit: budget.limit,
+                        });
+                    }
+                    BudgetEnforcement::Soft => {
+                        tracing::warn!(
+                            "Soft budget for {scope} exceeded: spent ${spent:.4} of ${:.4}",
+                            budget.limit
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record the usage of a response and return its cost
+    pub fn record(&self, context: &CostContext, model: &str, usage: &Usage) -> Option<f64> {
+        let cost = self.cost_of(context.provider.as_deref(), model, usage);
+        let mut totals = self.totals.lock().unwrap();
+
+        let Some(cost) = cost else {
+            tracing::debug!("No price known for model '{model}', usage not charged");
+            totals.unpriced_responses += 1;
+            return None;
+        };
+
+        totals.total += cost;
+        *totals.by_client.entry(context.client.clone()).or_default() += cost;
+        for tag in &context.tags {
+            *totals.by_tag.entry(tag.clone()).or_default() += cost;
+        }
+        if let Some(tenant) = &context.tenant {
+            *totals.by_tenant.entry(tenant.clone()).or_default() += cost;
+        }
+
+        Some(cost)
+    }
+
+    /// Get a snapshot of the spending totals
+    pub fn totals(&self) -> CostTotals {
+        self.totals.lock().unwrap().clone()
+    }
+
+    /// Reset all spending totals
+    pub fn reset(&self) {
+        *self.totals.lock().unwrap() = CostTotals::default();
+    }
+}
+
+/// Middleware that checks budgets before each request and records the cost
+/// of each response.
+///
+/// The cost of a response is also stored in its metadata under
+/// [`COST_METADATA_KEY`].
+#[derive(Debug, Clone)]
+pub struct CostLayer {
+    tracker: Arc<CostTracker>,
+    context: CostContext,
+}
+
+impl CostLayer {
+    /// Create a layer charging requests to `context`
+    pub const fn new(tracker: Arc<CostTracker>, context: CostContext) -> Self {
+        Self { tracker, context }
+    }
+
+    /// Set the provider used for price lookup
+    pub fn with_provider(mut self, provider: impl Into<String>) -> Self {
+        self.context = self.context.with_provider(provider);
+        self
+    }
+
+    /// Add a tag
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.context = self.context.with_tag(tag);
+        self
+    }
+
+    /// Set the tenant
+    pub fn with_tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.context = self.context.with_tenant(tenant);
+        self
+    }
+
+    /// Get the tracker
+    pub fn tracker(&self) -> &Arc<CostTracker> {
+        &self.tracker
+    }
+
+    /// Get the attribution context
+    pub const fn context(&self) -> &CostContext {
+        &self.context
+    }
+}
+
+#[async_trait]
+impl LlmMiddleware for CostLayer {
+    async fn on_request(&self, _request: &mut ChatRequest) -> Result<(), LlmError> {
+        self.tracker.check_budget(&self.context)
+    }
+
+    async fn on_response(&self, response: &mut ChatResponse) -> Result<(), LlmError> {
+        if let (Some(usage), Some(model)) = (&response.usage, &response.model)
+            && let Some(cost) = self.tracker.record(&self.context, model, usage)
+        {
+            response
+                .metadata
+                .insert(COST_METADATA_KEY.to_string(), serde_json::json!(cost));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::MiddlewareClient;
+    use crate::stream::ChatStream;
+    use crate::traits::ChatCapability;
+
+    fn usage(prompt: u32, completion: u32) -> Usage {
+        Usage::new(prompt, completion)
+    }
+
+    #[test]
+    fn test_pricing_with_cached_and_reasoning_tokens() {
+        let pricing = ModelPricing::per_million(2.0, 8.0)
+            .with_cached_input_cost(0.5 / 1_000_000.0)
+            .with_reasoning_cost(10.0 / 1_000_000.0);
+        let mut usage = usage(1_000_000, 1_000_000);
+        usage.cached_tokens = Some(500_000);
+        usage.reasoning_tokens = Some(500_000);
+
+        // 0.5M * 2 + 0.5M * 0.5 + 0.5M * 8 + 0.5M * 10
+        assert!((pricing.cost(&usage) - 10.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pricing_follows_provider_cache_convention() {
+        let pricing = ModelPricing::per_million(3.0, 15.0)
+            .with_cached_input_cost(0.3 / 1_000_000.0)
+            .with_cache_write_cost(3.75 / 1_000_000.0);
+        let mut usage = usage(1_000_000, 0);
+        usage.cached_tokens = Some(1_000_000);
+        usage.cache_write_tokens = Some(1_000_000);
+
+        // Anthropic: 1M uncached + 1M read + 1M written, all on top of each other
+        let anthropic = pricing.with_separate_cache_tokens();
+        assert!((anthropic.cost(&usage) - 7.05).abs() < 1e-9);
+
+        // OpenAI: cached tokens are part of the prompt tokens
+        usage.prompt_tokens = 2_000_000;
+        usage.cache_write_tokens = None;
+        assert!((pricing.cost(&usage) - 3.3).abs() < 1e-9);
+
+        let table = PricingTable::new().with_price("anthropic", "claude-custom", pricing);
+        assert!(
+            table
+                .get(None, "claude-custom")
+                .unwrap()
+                .cache_tokens_separate
+        );
+        #[cfg(feature = "anthropic")]
+        {
+            let builtin = table.get(None, "claude-sonnet-4-20250514").unwrap();
+            assert!((builtin.cache_write_cost_per_token.unwrap() - 0.000_003_75).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_gemini_builtin_pricing() {
+        let table = PricingTable::new();
+        let pricing = table.get(None, "gemini-2.5-flash").unwrap();
+        assert!((pricing.cost(&usage(1_000_000, 1_000_000)) - 2.8).abs() < 1e-9);
+        assert!(
+            table
+                .get(None, "gemini-2.5-flash-lite")
+                .unwrap()
+                .input_cost_per_token
+                < pricing.input_cost_per_token
+        );
+        assert!(table.get(None, "gemini-unknown").is_none());
+    }
+
+    #[test]
+    fn test_pricing_table_lookup() {
+        let table = PricingTable::new()
+            .with_overrides_json(
+                r#"{"openai": {"gpt-4o-mini": {"input_cost_per_token": 1.0, "output_cost_per_token": 2.0}}}"#,
+            )
+            .unwrap();
+
+        let pricing = table.get(None, "gpt-4o-mini-2024-07-18").unwrap();
+        assert_eq!(pricing.input_cost_per_token, 1.0);
+
+        let builtin = table
+            .get(Some("anthropic"), "claude-sonnet-4-20250514")
+            .unwrap();
+        assert_eq!(builtin.input_cost_per_token, 0.000_003);
+        assert!(table.get(None, "llama3.2").is_none());
+    }
+
+    #[test]
+    fn test_strip_date_suffix() {
+        assert_eq!(strip_date_suffix("gpt-4o-2024-08-06"), "gpt-4o");
+        assert_eq!(strip_date_suffix("gpt-4o"), "gpt-4o");
+        // Byte 11 from the end falls inside a multibyte character
+        assert_eq!(
+            strip_date_suffix("模型-部署-名称-测试"),
+            "模型-部署-名称-测试"
+        );
+        assert!(
+            PricingTable::new()
+                .get(None, "模型-部署-名称-测试")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_hard_and_soft_budgets() {
+        let tracker = CostTracker::new()
+            .with_pricing(PricingTable::new().with_price(
+                "openai",
+                "gpt-4o",
+                ModelPricing::new(0.01, 0.01),
+            ))
+            .with_budget(Budget::hard(BudgetScope::Tenant("acme".to_string()), 1.0))
+            .with_budget(Budget::soft(BudgetScope::Global, 0.5));
+        let acme = CostContext::new("nightly").with_tenant("acme");
+        let other = CostContext::new("nightly").with_tenant("other");
+
+        assert!(tracker.check_budget(&acme).is_ok());
+        assert_eq!(tracker.record(&acme, "gpt-4o", &usage(50, 50)), Some(1.0));
+
+        assert!(matches!(
+            tracker.check_budget(&acme),
+            Err(LlmError::BudgetExceeded { .. })
+        ));
+        // The global budget is soft
+        assert!(tracker.check_budget(&other).is_ok());
+
+        let totals = tracker.totals();
+        assert_eq!(totals.by_client["nightly"], 1.0);
+        assert_eq!(totals.by_tenant["acme"], 1.0);
+    }
+
+    struct FixedClient;
+
+    #[async_trait]
+    impl ChatCapability for FixedClient {
+        async fn chat_with_tools(
+            &self,
+            _messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+        ) -> Result<ChatResponse, LlmError> {
+            let mut response = ChatResponse::new(MessageContent::Text("ok".to_string()));
+            response.model = Some("gpt-4o".to_string());
+            response.usage = Some(Usage::new(100, 100));
+            Ok(response)
+        }
+
+        async fn chat_stream(
+            &self,
+            _messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+        ) -> Result<ChatStream, LlmError> {
+            Err(LlmError::UnsupportedOperation("no streaming".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cost_layer() {
+        let tracker = Arc::new(
+            CostTracker::new()
+                .with_pricing(PricingTable::new().with_price(
+                    "openai",
+                    "gpt-4o",
+                    ModelPricing::new(0.01, 0.01),
+                ))
+                .with_budget(Budget::hard(BudgetScope::Tag("batch".to_string()), 2.0)),
+        );
+        let client =
+            MiddlewareClient::new(FixedClient).layer(tracker.layer("test").with_tag("batch"));
+        let messages = vec![ChatMessage::user("hi").build()];
+
+        let response = client.chat(messages.clone()).await.unwrap();
+        assert_eq!(response.metadata[COST_METADATA_KEY], serde_json::json!(2.0));
+
+        let result = client.chat(messages).await;
+        assert!(matches!(result, Err(LlmError::BudgetExceeded { .. })));
+        assert_eq!(tracker.totals().by_tag["batch"], 2.0);
+    }
+
+    #[tokio::test]
+    async fn test_cost_layer_charges_streams() {
+        use crate::stream::ChatStreamEvent;
+        use crate::testing::{MockClient, MockReply};
+        use futures::StreamExt;
+
+        let tracker = Arc::new(
+            CostTracker::new().with_pricing(PricingTable::new().with_price(
+                "openai",
+                "gpt-4o",
+                ModelPricing::new(0.01, 0.01),
+            )),
+        );
+        // Provider-shaped stream: usage arrives separately and the end names no model
+        let mock = MockClient::new()
+            .with_model("gpt-4o")
+            .with_reply(MockReply::stream(vec![
+                ChatStreamEvent::ContentDelta {
+                    delta: "ok".to_string(),
+                    index: None,
+                },
+                ChatStreamEvent::UsageUpdate {
+                    usage: Usage::new(100, 100),
+                },
+                ChatStreamEvent::StreamEnd {
+                    response: ChatResponse::new(MessageContent::Text(String::new())),
+                },
+            ]));
+        let client = MiddlewareClient::new(mock).layer(tracker.layer("test"));
+
+        let events: Vec<_> = client
+            .chat_stream(vec![ChatMessage::user("hi").build()], None)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        match events.last() {
+            Some(Ok(ChatStreamEvent::StreamEnd { response })) => {
+                assert_eq!(response.metadata[COST_METADATA_KEY], serde_json::json!(2.0));
+            }
+            other => panic!("Expected StreamEnd, got {other:?}"),
+        }
+        assert_eq!(tracker.totals().total, 2.0);
+    }
+}
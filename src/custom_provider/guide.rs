@@ -188,6 +188,7 @@ impl HuggingFaceProvider {
                 .unwrap_or(0),
             reasoning_tokens: None,
             cached_tokens: None,
+            cache_write_tokens: None,
         });
 
         let mut response = CustomChatResponse::new(content);
@@ -397,6 +398,12 @@ pub mod utils {
                                 "audio_url": audio_url,
                                 "format": format
                             }),
+                            ContentPart::Document { source, media_type, title, .. } => serde_json::json!({
+                                "type": "document",
+                                "source": source,
+                                "media_type": media_type,
+                                "title": title
+                            }),
                         }
                     }).collect();
                     serde_json::Value::Array(content_parts)
@@ -245,6 +245,8 @@ impl CustomChatResponse {
             }),
             tool_calls: self.tool_calls.clone(),
             thinking: None,
+            citations: Vec::new(),
+            sources: Vec::new(),
             metadata: self.metadata.clone(),
         }
     }
@@ -305,6 +307,10 @@ impl CustomProviderClient {
 
 #[async_trait]
 impl ChatCapability for CustomProviderClient {
+    fn default_model(&self) -> Option<String> {
+        self.config.model.clone()
+    }
+
     async fn chat_with_tools(
         &self,
         messages: Vec<ChatMessage>,
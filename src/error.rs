@@ -92,6 +92,14 @@ pub enum LlmError {
     #[error("Quota exceeded: {0}")]
     QuotaExceededError(String),
 
+    /// Spending budget exceeded (from `CostTracker`)
+    #[error("Budget exceeded for {scope}: spent ${spent:.4} of ${limit:.4}")]
+    BudgetExceeded {
+        scope: String,
+        spent: f64,
+        limit: f64,
+    },
+
     /// Model not supported error
     #[error("Model not supported: {0}")]
     ModelNotSupported(String),
@@ -277,6 +285,7 @@ impl LlmError {
             Self::TimeoutError(_) => true,
             Self::ConnectionError(_) => true,
             Self::QuotaExceededError(_) => false, // Don't retry quota errors
+            Self::BudgetExceeded { .. } => false, // Spending does not go down by retrying
             Self::AuthenticationError(_) => false, // Don't retry auth errors
             Self::ModelNotSupported(_) => false,  // Don't retry unsupported models
             Self::InvalidInput(_) | Self::InvalidParameter(_) => false, // Don't retry validation errors
@@ -334,7 +343,10 @@ impl LlmError {
             Self::InvalidInput(_) | Self::InvalidParameter(_) | Self::ToolValidationError(_) => {
                 ErrorCategory::Validation
             }
-            Self::ConfigurationError(_) => ErrorCategory::Configuration,
+            // A local spending limit, not a provider limit that clears over time
+            Self::ConfigurationError(_) | Self::BudgetExceeded { .. } => {
+                ErrorCategory::Configuration
+            }
             Self::ModelNotSupported(_)
             | Self::UnsupportedOperation(_)
             | Self::UnsupportedToolType(_) => ErrorCategory::Unsupported,
@@ -361,6 +373,9 @@ impl LlmError {
             Self::QuotaExceededError(_) => {
                 "API quota exceeded. Please check your usage limits.".to_string()
             }
+            Self::BudgetExceeded { scope, .. } => {
+                format!("The spending budget for {scope} has been exhausted.")
+            }
             Self::ModelNotSupported(model) => {
                 format!("The model '{model}' is not supported by this provider.")
             }
@@ -403,6 +418,13 @@ impl LlmError {
                     "Optimize your requests to use fewer tokens".to_string(),
                 ]
             }
+            Self::BudgetExceeded { .. } => {
+                vec![
+                    "Raise the budget limit or reset the cost tracker".to_string(),
+                    "Switch to a cheaper model for this workload".to_string(),
+                    "Use batch processing for non-urgent requests".to_string(),
+                ]
+            }
             Self::ConnectionError(_) | Self::TimeoutError(_) => {
                 vec![
                     "Check your internet connection stability".to_string(),
@@ -547,6 +569,14 @@ mod tests {
 
         let parse_error = LlmError::JsonError("Invalid JSON".to_string());
         assert_eq!(parse_error.category(), ErrorCategory::Parsing);
+
+        let budget = LlmError::BudgetExceeded {
+            scope: "all requests".to_string(),
+            spent: 2.0,
+            limit: 1.0,
+        };
+        assert_eq!(budget.category(), ErrorCategory::Configuration);
+        assert!(!budget.is_retryable());
     }
 
     #[test]
@@ -0,0 +1,656 @@
+//! Provider Fallback
+//!
+//! This module provides [`FallbackClient`], which routes chat calls across an
+//! ordered list of provider clients. When a provider fails with a retryable
+//! error (rate limit, 5xx, timeout, connection failure) the next provider is
+//! tried. Provider health is tracked with a [`FailoverManager`], so a provider
+//! that keeps failing is skipped until its cooldown period has elapsed.
+//!
+//! Every response records which provider served it in `ChatResponse.metadata`
+//! under [`FALLBACK_PROVIDER_METADATA_KEY`].
+//!
+//! # Example
+//! ```rust,no_run
+//! use siumai::client::ClientWrapper;
+//! use siumai::fallback::{FallbackClient, FallbackTarget};
+//! use siumai::prelude::*;
+//!
+//! # async fn example() -> Result<(), LlmError> {
+//! let openai = LlmBuilder::new()
+//!     .openai()
+//!     .api_key("openai-key")
+//!     .model("gpt-4o")
+//!     .build()
+//!     .await?;
+//! let anthropic = LlmBuilder::new()
+//!     .anthropic()
+//!     .api_key("anthropic-key")
+//!     .model("claude-3-5-sonnet-20241022")
+//!     .build()
+//!     .await?;
+//!
+//! let client = FallbackClient::new()
+//!     .with_target(FallbackTarget::new(ClientWrapper::openai(Box::new(openai))).with_model("gpt-4o"))
+//!     .with_target(
+//!         FallbackTarget::new(ClientWrapper::anthropic(Box::new(anthropic)))
+//!             .with_model("claude-3-5-sonnet-20241022"),
+//!     );
+//!
+//! let response = client.chat(vec![user!("Hello!")]).await?;
+//! println!("served by {:?}", response.metadata.get("fallback_provider"));
+//! # Ok(())
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use crate::client::{ClientWrapper, LlmClient};
+use crate::error::LlmError;
+use crate::retry_strategy::{FailoverConfig, FailoverManager, ProviderHealth};
+use crate::stream::{ChatStream, ChatStreamEvent};
+use crate::traits::*;
+use crate::types::*;
+
+/// Metadata key holding the name of the provider that served a response
+pub const FALLBACK_PROVIDER_METADATA_KEY: &str = "fallback_provider";
+
+/// Metadata key holding the model configured for the serving provider
+pub const FALLBACK_MODEL_METADATA_KEY: &str = "fallback_model";
+
+/// Metadata key holding the number of providers tried for a response
+pub const FALLBACK_ATTEMPTS_METADATA_KEY: &str = "fallback_attempts";
+
+/// A provider client taking part in fallback routing
+#[derive(Clone)]
+pub struct FallbackTarget {
+    name: String,
+    client: ClientWrapper,
+    model: Option<String>,
+}
+
+impl FallbackTarget {
+    /// Create a target named after the client's provider
+    pub fn new(client: ClientWrapper) -> Self {
+        Self {
+            name: client.provider_name().to_string(),
+            client,
+            model: None,
+        }
+    }
+
+    /// Set the target name (required when several targets share a provider)
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Set the model name this provider serves requests with.
+    ///
+    /// Requests to this target are sent with the model through
+    /// [`ChatCapability::chat_with_request`] unless the client is already
+    /// configured with it. Clients that cannot change their model, and
+    /// structured output calls that would need to, fail with
+    /// `LlmError::UnsupportedOperation`. The model is also reported in
+    /// response metadata and fills `ChatResponse.model` when the provider does
+    /// not return one.
+    pub fn with_model<S: Into<String>>(mut self, model: S) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Get the target name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the wrapped client
+    pub const fn client(&self) -> &ClientWrapper {
+        &self.client
+    }
+
+    /// Get the model name configured for this provider
+    pub fn model(&self) -> Option<&str> {
+        self.model.as_deref()
+    }
+
+    /// The model to request, if it differs from the one the client is configured with
+    fn model_override(&self) -> Option<&str> {
+        let model = self.model.as_deref()?;
+        (self.client.default_model().as_deref() != Some(model)).then_some(model)
+    }
+
+    /// Apply the target's model to a request
+    fn prepare(&self, mut request: ChatRequest) -> ChatRequest {
+        if let Some(model) = self.model_override() {
+            request.common_params.model = model.to_string();
+        }
+        request
+    }
+
+    /// Send a chat request to this target
+    async fn chat(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        let request = self.prepare(request);
+        if request.has_params() {
+            return self.client.chat_with_request(request).await;
+        }
+        match request.output_schema {
+            Some(schema) => self.client.chat_with_schema(request.messages, schema).await,
+            None => {
+                self.client
+                    .chat_with_tools(request.messages, request.tools)
+                    .await
+            }
+        }
+    }
+
+    /// Stream a chat request from this target
+    async fn chat_stream(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
+        let request = self.prepare(request);
+        if request.has_params() {
+            self.client.chat_stream_with_request(request).await
+        } else if let Some(schema) = request.output_schema {
+            self.client
+                .chat_stream_with_schema(request.messages, schema)
+                .await
+        } else {
+            self.client
+                .chat_stream(request.messages, request.tools)
+                .await
+        }
+    }
+
+    /// Record the serving provider in a response
+    fn annotate(&self, response: &mut ChatResponse, attempts: usize) {
+        if response.model.is_none() {
+            response.model = self.model.clone();
+        }
+        response.metadata.insert(
+            FALLBACK_PROVIDER_METADATA_KEY.to_string(),
+            self.name.clone().into(),
+        );
+        if let Some(model) = &self.model {
+            response.metadata.insert(
+                FALLBACK_MODEL_METADATA_KEY.to_string(),
+                model.clone().into(),
+            );
+        }
+        response
+            .metadata
+            .insert(FALLBACK_ATTEMPTS_METADATA_KEY.to_string(), attempts.into());
+    }
+}
+
+impl std::fmt::Debug for FallbackTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FallbackTarget")
+            .field("name", &self.name)
+            .field("provider", &self.client.provider_name())
+            .field("model", &self.model)
+            .finish()
+    }
+}
+
+/// Chat client that falls back to the next provider on retryable errors.
+///
+/// Providers are tried in the order they were added (or by the priorities in
+/// [`FailoverConfig::provider_priorities`]). Non-retryable errors such as
+/// invalid parameters or authentication failures are returned immediately.
+/// Clones share the same health state.
+#[derive(Clone)]
+pub struct FallbackClient {
+    targets: Vec<FallbackTarget>,
+    manager: Arc<Mutex<FailoverManager>>,
+}
+
+impl FallbackClient {
+    /// Create a fallback client without any targets
+    pub fn new() -> Self {
+        Self {
+            targets: Vec::new(),
+            manager: Arc::new(Mutex::new(FailoverManager::new(FailoverConfig::default()))),
+        }
+    }
+
+    /// Add a provider target
+    pub fn with_target(mut self, target: FallbackTarget) -> Self {
+        self.targets.push(target);
+        self
+    }
+
+    /// Add a provider client, named after its provider
+    pub fn with_client(self, client: ClientWrapper) -> Self {
+        self.with_target(FallbackTarget::new(client))
+    }
+
+    /// Set the failover configuration (resets health tracking)
+    pub fn with_failover_config(mut self, config: FailoverConfig) -> Self {
+        self.manager = Arc::new(Mutex::new(FailoverManager::new(config)));
+        self
+    }
+
+    /// Get the configured targets
+    pub fn targets(&self) -> &[FallbackTarget] {
+        &self.targets
+    }
+
+    /// Get the health of every provider tried so far
+    pub fn health(&self) -> HashMap<String, ProviderHealth> {
+        self.lock_manager().get_all_health().clone()
+    }
+
+    fn lock_manager(&self) -> std::sync::MutexGuard<'_, FailoverManager> {
+        self.manager.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Run `op` against each available target until one succeeds
+    async fn try_targets<'a, T, F, Fut>(
+        &'a self,
+        mut op: F,
+    ) -> Result<(T, &'a FallbackTarget, usize), LlmError>
+    where
+        F: FnMut(&'a FallbackTarget) -> Fut,
+        Fut: Future<Output = Result<T, LlmError>> + 'a,
+    {
+        if self.targets.is_empty() {
+            return Err(LlmError::ConfigurationError(
+                "No fallback providers configured".to_string(),
+            ));
+        }
+
+        let names: Vec<String> = self.targets.iter().map(|t| t.name.clone()).collect();
+        let order = self.lock_manager().available_providers(&names);
+        if order.is_empty() {
+            return Err(LlmError::ProviderError {
+                provider: "fallback".to_string(),
+                message: "All providers are unavailable while cooling down after failures"
+                    .to_string(),
+                error_code: Some("circuit_open".to_string()),
+            });
+        }
+
+        let mut last_error = None;
+        for (attempt, name) in order.iter().enumerate() {
+            let Some(target) = self.targets.iter().find(|t| &t.name == name) else {
+                continue;
+            };
+
+            match op(target).await {
+                Ok(value) => {
+                    self.lock_manager().record_success(name);
+                    return Ok((value, target, attempt + 1));
+                }
+                Err(e) if e.is_retryable() => {
+                    tracing::warn!("Provider {name} failed, trying next provider: {e}");
+                    self.lock_manager().record_failure(name);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            LlmError::InternalError("No fallback provider was tried".to_string())
+        }))
+    }
+
+    /// First target exposing a capability
+    fn find_capability<'a, T: ?Sized>(
+        &'a self,
+        get: impl Fn(&'a dyn LlmClient) -> Option<&'a T>,
+    ) -> Option<&'a T> {
+        self.targets.iter().find_map(|t| get(t.client.client()))
+    }
+}
+
+impl Default for FallbackClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for FallbackClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FallbackClient")
+            .field("targets", &self.targets)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl ChatCapability for FallbackClient {
+    async fn chat_with_tools(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+    ) -> Result<ChatResponse, LlmError> {
+        self.chat_with_request(ChatRequest {
+            messages,
+            tools,
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Streams fall back only while the stream is being established; errors
+    /// after the first event are passed through.
+    async fn chat_stream(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+    ) -> Result<ChatStream, LlmError> {
+        self.chat_stream_with_request(ChatRequest {
+            messages,
+            tools,
+            ..Default::default()
+        })
+        .await
+    }
+
+    async fn chat_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatResponse, LlmError> {
+        self.chat_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    async fn chat_stream_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatStream, LlmError> {
+        self.chat_stream_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    /// The target's model replaces the request's, if one is configured
+    async fn chat_with_request(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        let (mut response, target, attempts) = self
+            .try_targets(|target| target.chat(request.clone()))
+            .await?;
+        target.annotate(&mut response, attempts);
+        Ok(response)
+    }
+
+    async fn chat_stream_with_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
+        let (stream, target, attempts) = self
+            .try_targets(|target| target.chat_stream(request.clone()))
+            .await?;
+
+        let target = target.clone();
+        let stream = stream.map(move |event| match event {
+            Ok(ChatStreamEvent::StreamEnd { mut response }) => {
+                target.annotate(&mut response, attempts);
+                Ok(ChatStreamEvent::StreamEnd { response })
+            }
+            other => other,
+        });
+        Ok(Box::pin(stream))
+    }
+}
+
+impl LlmClient for FallbackClient {
+    fn provider_name(&self) -> &'static str {
+        "fallback"
+    }
+
+    fn supported_models(&self) -> Vec<String> {
+        let mut models: Vec<String> = Vec::new();
+        for target in &self.targets {
+            let target_models = match &target.model {
+                Some(model) => vec![model.clone()],
+                None => target.client.supported_models(),
+            };
+            for model in target_models {
+                if !models.contains(&model) {
+                    models.push(model);
+                }
+            }
+        }
+        models
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.targets
+            .first()
+            .map(|t| t.client.capabilities())
+            .unwrap_or_default()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn LlmClient> {
+        Box::new(self.clone())
+    }
+
+    fn as_embedding_capability(&self) -> Option<&dyn EmbeddingCapability> {
+        self.find_capability(|c| c.as_embedding_capability())
+    }
+
+    fn as_audio_capability(&self) -> Option<&dyn AudioCapability> {
+        self.find_capability(|c| c.as_audio_capability())
+    }
+
+    fn as_vision_capability(&self) -> Option<&dyn VisionCapability> {
+        self.find_capability(|c| c.as_vision_capability())
+    }
+
+    fn as_image_generation_capability(&self) -> Option<&dyn ImageGenerationCapability> {
+        self.find_capability(|c| c.as_image_generation_capability())
+    }
+
+    fn as_completion_capability(&self) -> Option<&dyn CompletionCapability> {
+        self.find_capability(|c| c.as_completion_capability())
+    }
+
+    fn as_conversation_state_capability(&self) -> Option<&dyn ConversationStateCapability> {
+        self.find_capability(|c| c.as_conversation_state_capability())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// Provider that fails with a fixed error, or answers with its name
+    #[derive(Clone)]
+    struct MockProvider {
+        name: &'static str,
+        error: Option<LlmError>,
+        calls: Arc<AtomicUsize>,
+        models: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl MockProvider {
+        fn new(name: &'static str, error: Option<LlmError>) -> Self {
+            Self {
+                name,
+                error,
+                calls: Arc::new(AtomicUsize::new(0)),
+                models: Arc::new(Mutex::new(Vec::new())),
+            }
+        }
+
+        fn target(&self) -> FallbackTarget {
+            FallbackTarget::new(ClientWrapper::custom(Box::new(self.clone()))).with_name(self.name)
+        }
+    }
+
+    #[async_trait]
+    impl ChatCapability for MockProvider {
+        async fn chat_with_tools(
+            &self,
+            _messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+        ) -> Result<ChatResponse, LlmError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match &self.error {
+                Some(error) => Err(error.clone()),
+                None => Ok(ChatResponse::new(MessageContent::Text(
+                    self.name.to_string(),
+                ))),
+            }
+        }
+
+        async fn chat_stream(
+            &self,
+            _messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+        ) -> Result<ChatStream, LlmError> {
+            Err(LlmError::UnsupportedOperation("no streaming".to_string()))
+        }
+
+        async fn chat_with_request(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+            let model = request.common_params.model.clone();
+            self.models.lock().unwrap().push(model);
+            self.chat_with_tools(request.messages, request.tools).await
+        }
+    }
+
+    impl LlmClient for MockProvider {
+        fn provider_name(&self) -> &'static str {
+            self.name
+        }
+
+        fn supported_models(&self) -> Vec<String> {
+            vec![format!("{}-model", self.name)]
+        }
+
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities::new().with_chat()
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn clone_box(&self) -> Box<dyn LlmClient> {
+            Box::new(self.clone())
+        }
+    }
+
+    fn rate_limited() -> LlmError {
+        LlmError::RateLimitError("slow down".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_on_retryable_error() {
+        let primary = MockProvider::new("primary", Some(rate_limited()));
+        let secondary = MockProvider::new("secondary", None);
+        let client = FallbackClient::new()
+            .with_target(primary.target())
+            .with_target(secondary.target().with_model("secondary-large"));
+
+        let response = client
+            .chat(vec![ChatMessage::user("hi").build()])
+            .await
+            .unwrap();
+
+        assert_eq!(response.content_text(), Some("secondary"));
+        assert_eq!(response.model.as_deref(), Some("secondary-large"));
+        assert_eq!(*secondary.models.lock().unwrap(), vec!["secondary-large"]);
+        assert_eq!(
+            response.metadata.get(FALLBACK_PROVIDER_METADATA_KEY),
+            Some(&"secondary".into())
+        );
+        assert_eq!(
+            response.metadata.get(FALLBACK_ATTEMPTS_METADATA_KEY),
+            Some(&2.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_error_is_returned() {
+        let primary = MockProvider::new(
+            "primary",
+            Some(LlmError::InvalidParameter("bad".to_string())),
+        );
+        let secondary = MockProvider::new("secondary", None);
+        let client = FallbackClient::new()
+            .with_target(primary.target())
+            .with_target(secondary.target());
+
+        let result = client.chat(vec![ChatMessage::user("hi").build()]).await;
+
+        assert!(matches!(result, Err(LlmError::InvalidParameter(_))));
+        assert_eq!(secondary.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_unhealthy_provider_is_skipped() {
+        let primary = MockProvider::new(
+            "primary",
+            Some(LlmError::ApiError {
+                code: 503,
+                message: "unavailable".to_string(),
+                details: None,
+            }),
+        );
+        let secondary = MockProvider::new("secondary", None);
+        let client = FallbackClient::new()
+            .with_failover_config(FailoverConfig {
+                max_failures: 1,
+                cooldown_period: Duration::from_secs(60),
+                ..Default::default()
+            })
+            .with_target(primary.target())
+            .with_target(secondary.target());
+
+        client
+            .chat(vec![ChatMessage::user("hi").build()])
+            .await
+            .unwrap();
+        let response = client
+            .chat(vec![ChatMessage::user("hi").build()])
+            .await
+            .unwrap();
+
+        assert_eq!(primary.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            response.metadata.get(FALLBACK_ATTEMPTS_METADATA_KEY),
+            Some(&1.into())
+        );
+        assert!(!client.health()["primary"].is_healthy);
+    }
+
+    #[tokio::test]
+    async fn test_target_model_sent_with_requests() {
+        let mock = crate::testing::MockClient::new()
+            .with_model("base-model")
+            .with_text("ok");
+        let target = |model: &str| {
+            FallbackTarget::new(ClientWrapper::custom(Box::new(mock.clone()))).with_model(model)
+        };
+
+        // The mock applies per-request models
+        let client = FallbackClient::new().with_target(target("other-model"));
+        client
+            .chat(vec![ChatMessage::user("hi").build()])
+            .await
+            .unwrap();
+        assert_eq!(
+            mock.last_request().unwrap().common_params.model,
+            "other-model"
+        );
+
+        // Structured output is sent with the target's model
+        let schema = OutputSchema::new("answer", serde_json::json!({"type": "object"}));
+        mock.push_reply(crate::testing::MockReply::text("{}"));
+        client
+            .chat_with_schema(vec![ChatMessage::user("hi").build()], schema.clone())
+            .await
+            .unwrap();
+        let request = mock.last_request().unwrap();
+        assert_eq!(request.common_params.model, "other-model");
+        assert_eq!(request.output_schema, Some(schema));
+    }
+}
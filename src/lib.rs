@@ -69,7 +69,10 @@
 //!
 //!     // The actual operation will succeed or fail based on the model's real capabilities
 //!     // No pre-emptive blocking, no automatic noise
-//!     // vision.analyze_image(...).await?;
+//!     let request = VisionRequest::new("Describe this image")
+//!         .with_image_url("https://example.com/image.jpg");
+//!     let response = vision.analyze_image(request).await?;
+//!     println!("{}", response.text);
 //!
 //!     Ok(())
 //! }
@@ -83,10 +86,20 @@ pub const PROVIDER_COUNT: &str = env!("SIUMAI_PROVIDER_COUNT");
 
 pub mod benchmarks;
 pub mod builder;
+pub mod cache;
+pub mod cassette;
 pub mod client;
+pub mod context_window;
+pub mod conversation;
+pub mod cost;
 pub mod custom_provider;
 pub mod error;
+pub mod fallback;
+#[cfg(feature = "mcp")]
+pub mod mcp;
+pub mod middleware;
 pub mod multimodal;
+pub mod object_stream;
 pub mod params;
 pub mod performance;
 pub mod provider;
@@ -100,11 +113,17 @@ pub mod provider_features;
     feature = "groq"
 ))]
 pub mod providers;
+pub mod rate_limiter;
 pub mod request_factory;
 pub mod retry;
 pub mod retry_backoff;
 pub mod retry_strategy;
 pub mod stream;
+pub mod stream_handle;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod token_counter;
+pub mod tool_runner;
 pub mod tracing;
 pub mod traits;
 pub mod types;
@@ -152,9 +171,46 @@ pub use types::{WebSearchConfig, WebSearchResult};
 // Performance monitoring
 pub use performance::{PerformanceMetrics, PerformanceMonitor};
 
+// Response caching
+pub use cache::CacheLayer;
+
+// Provider fallback
+pub use fallback::{FallbackClient, FallbackTarget};
+
+// Middleware chain
+pub use middleware::{LlmMiddleware, MiddlewareClient};
+
+// Token counting and context window management
+pub use context_window::ContextWindowManager;
+pub use token_counter::TokenCounter;
+
+// Streaming structured output
+pub use object_stream::{ObjectStream, ObjectStreamEvent, PartialJsonParser};
+
+// Cancellable and resumable streams
+pub use stream_handle::{ChatStreamHandle, StreamCanceller};
+
+// Stateful conversations
+pub use conversation::{Conversation, InMemoryStore, JsonFileStore, MemoryStore, MemoryStrategy};
+
+// Cost tracking and budgets
+pub use cost::{Budget, BudgetScope, CostLayer, CostTracker, PricingTable};
+
 // Retry strategy
 pub use retry_strategy::RetryStrategy;
 
+// Client-side rate limiting
+pub use rate_limiter::{RateLimit, RateLimiter};
+
+// Record/replay HTTP cassettes
+pub use cassette::{Cassette, CassetteMode};
+
+// JSON Schema derivation for structured output
+pub use schemars;
+
+// Tool execution loop
+pub use tool_runner::{ToolRegistry, ToolRunner, TypedTool};
+
 // Benchmarks
 pub use benchmarks::{BenchmarkConfig, BenchmarkResults, BenchmarkRunner};
 
@@ -177,20 +233,37 @@ pub use tracing::{OutputFormat, TracingConfig, init_debug_tracing, init_tracing}
 pub mod prelude {
     pub use crate::benchmarks::*;
     pub use crate::builder::*;
+    pub use crate::cache::{CacheBackend, CacheLayer, FileCacheBackend, InMemoryCacheBackend};
+    pub use crate::cassette::{Cassette, CassetteMode};
     pub use crate::client::*;
+    pub use crate::context_window::ContextWindowManager;
+    pub use crate::conversation::{
+        Conversation, InMemoryStore, JsonFileStore, MemoryStore, MemoryStrategy,
+    };
+    pub use crate::cost::{
+        Budget, BudgetScope, CostLayer, CostTracker, ModelPricing, PricingTable,
+    };
     pub use crate::custom_provider::*;
     pub use crate::error::LlmError;
+    pub use crate::fallback::{FallbackClient, FallbackTarget};
+    pub use crate::middleware::{LlmMiddleware, MiddlewareClient};
     pub use crate::multimodal::*;
+    pub use crate::object_stream::{ObjectStream, ObjectStreamEvent, PartialJsonParser};
     pub use crate::performance::*;
     pub use crate::provider::Siumai;
     pub use crate::provider::*;
     pub use crate::provider_features::*;
+    pub use crate::rate_limiter::{RateLimit, RateLimiter};
     pub use crate::retry_strategy::*;
     pub use crate::stream::*;
+    pub use crate::stream_handle::{ChatStreamHandle, StreamCanceller};
+    pub use crate::token_counter::{HeuristicTokenCounter, TokenCounter};
+    pub use crate::tool_runner::*;
     pub use crate::tracing::*;
     pub use crate::traits::*;
     pub use crate::types::*;
     pub use crate::web_search::*;
+    pub use schemars::JsonSchema;
     // Model constants for easy access
     pub use crate::constants;
     pub use crate::models;
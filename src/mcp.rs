@@ -0,0 +1,351 @@
+//! Model Context Protocol Tools
+//!
+//! This module connects to MCP servers and exposes their tools to any
+//! `ChatCapability` client. `McpToolProvider` lists the server's tools as
+//! siumai `Tool` definitions and forwards `ToolCall`s back to the server, so
+//! MCP tools work with every provider, including those without native MCP
+//! support. OpenAI Responses can still use its built-in remote MCP tool,
+//! which runs on OpenAI's side instead.
+//!
+//! Requires the `mcp` feature.
+//!
+//! # Example
+//! ```rust,no_run
+//! use siumai::mcp::McpToolProvider;
+//! use siumai::prelude::*;
+//! use siumai::tool_runner::{ToolRegistry, ToolRunner};
+//!
+//! # async fn example(client: Siumai) -> Result<(), LlmError> {
+//! let mcp = McpToolProvider::stdio("npx", ["-y", "@modelcontextprotocol/server-everything"]).await?;
+//!
+//! let mut registry = ToolRegistry::new();
+//! mcp.register_into(&mut registry).await?;
+//!
+//! let result = ToolRunner::new(registry)
+//!     .run(&client, vec![user!("Add 2 and 3 using the available tools")])
+//!     .await?;
+//! println!("{}", result.response.text().unwrap_or_default());
+//! # Ok(())
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use rmcp::model::{CallToolRequestParam, CallToolResult, RawContent, ServerInfo};
+use rmcp::service::{RoleClient, RunningService, ServiceExt};
+use rmcp::transport::{IntoTransport, StreamableHttpClientTransport, TokioChildProcess};
+use std::ffi::OsStr;
+use std::sync::Arc;
+
+use crate::error::LlmError;
+use crate::tool_runner::{ToolHandler, ToolRegistry};
+use crate::types::{Tool, ToolCall};
+
+/// Tools served by a connected MCP server.
+///
+/// Cloning is cheap and shares the same connection. The connection is closed
+/// when the last clone is dropped or `close` is called.
+#[derive(Clone)]
+pub struct McpToolProvider {
+    service: Arc<RunningService<RoleClient, ()>>,
+}
+
+impl McpToolProvider {
+    /// Spawn an MCP server as a child process and connect over stdio
+    pub async fn stdio<I, S>(command: impl AsRef<OsStr>, args: I) -> Result<Self, LlmError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let mut command = tokio::process::Command::new(command);
+        command.args(args);
+        let transport = TokioChildProcess::new(command)
+            .map_err(|e| LlmError::ConnectionError(format!("Failed to spawn MCP server: {e}")))?;
+        Self::connect(transport).await
+    }
+
+    /// Connect to an MCP server over streamable HTTP
+    pub async fn http(url: impl Into<String>) -> Result<Self, LlmError> {
+        let transport = StreamableHttpClientTransport::from_uri(url.into());
+        Self::connect(transport).await
+    }
+
+    /// Connect over any rmcp client transport
+    pub async fn connect<T, E, A>(transport: T) -> Result<Self, LlmError>
+    where
+        T: IntoTransport<RoleClient, E, A>,
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let service = ().serve(transport).await.map_err(|e| {
+            LlmError::ConnectionError(format!("Failed to initialize MCP session: {e}"))
+        })?;
+        Ok(Self {
+            service: Arc::new(service),
+        })
+    }
+
+    /// Information the server reported during initialization
+    pub fn server_info(&self) -> Option<&ServerInfo> {
+        self.service.peer_info()
+    }
+
+    /// List the server's tools as function tool definitions
+    pub async fn tools(&self) -> Result<Vec<Tool>, LlmError> {
+        let tools =
+            self.service.list_all_tools().await.map_err(|e| {
+                LlmError::provider_error("MCP", format!("Failed to list tools: {e}"))
+            })?;
+
+        Ok(tools
+            .into_iter()
+            .map(|tool| {
+                Tool::function(
+                    tool.name.to_string(),
+                    tool.description.map(|d| d.to_string()).unwrap_or_default(),
+                    serde_json::Value::Object((*tool.input_schema).clone()),
+                )
+            })
+            .collect())
+    }
+
+    /// Execute a model-issued tool call on the server, returning its text output
+    pub async fn call_tool(&self, tool_call: &ToolCall) -> Result<String, LlmError> {
+        let function = tool_call.function.as_ref().ok_or_else(|| {
+            LlmError::ToolCallError(format!("Tool call '{}' has no function", tool_call.id))
+        })?;
+        let arguments = if function.arguments.trim().is_empty() {
+            serde_json::Value::Object(Default::default())
+        } else {
+            serde_json::from_str(&function.arguments).map_err(|e| {
+                LlmError::ToolValidationError(format!(
+                    "Invalid arguments for tool '{}': {e}",
+                    function.name
+                ))
+            })?
+        };
+
+        let result = self.call(&function.name, arguments).await?;
+        Ok(result_text(&result))
+    }
+
+    /// Call a tool by name with JSON arguments
+    ///
+    /// Results flagged as errors by the server are returned as
+    /// `LlmError::ToolCallError`.
+    pub async fn call(
+        &self,
+        name: &str,
+        arguments: serde_json::Value,
+    ) -> Result<CallToolResult, LlmError> {
+        let arguments = match arguments {
+            serde_json::Value::Object(map) => Some(map),
+            serde_json::Value::Null => None,
+            other => {
+                return Err(LlmError::ToolValidationError(format!(
+                    "Arguments for tool '{name}' must be a JSON object, got {other}"
+                )));
+            }
+        };
+
+        let result = self
+            .service
+            .call_tool(CallToolRequestParam {
+                name: name.to_string().into(),
+                arguments,
+            })
+            .await
+            .map_err(|e| LlmError::ToolCallError(format!("MCP tool '{name}' failed: {e}")))?;
+
+        if result.is_error == Some(true) {
+            return Err(LlmError::ToolCallError(format!(
+                "MCP tool '{name}' returned an error: {}",
+                result_text(&result)
+            )));
+        }
+        Ok(result)
+    }
+
+    /// Register every server tool in a `ToolRegistry`
+    ///
+    /// Returns the number of tools registered.
+    pub async fn register_into(&self, registry: &mut ToolRegistry) -> Result<usize, LlmError> {
+        let tools = self.tools().await?;
+        let count = tools.len();
+        for tool in tools {
+            let handler = McpToolHandler {
+                provider: self.clone(),
+                name: tool.function.name.clone(),
+            };
+            registry.register(tool, handler);
+        }
+        Ok(count)
+    }
+
+    /// Close the connection, stopping stdio servers spawned by this provider
+    pub fn close(&self) {
+        self.service.cancellation_token().cancel();
+    }
+}
+
+impl std::fmt::Debug for McpToolProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("McpToolProvider")
+            .field(
+                "server",
+                &self.server_info().map(|info| &info.server_info.name),
+            )
+            .finish()
+    }
+}
+
+/// Forwards calls for one registered tool to the MCP server.
+struct McpToolHandler {
+    provider: McpToolProvider,
+    name: String,
+}
+
+#[async_trait]
+impl ToolHandler for McpToolHandler {
+    async fn call(&self, arguments: serde_json::Value) -> Result<serde_json::Value, LlmError> {
+        let result = self.provider.call(&self.name, arguments).await?;
+        Ok(match result.structured_content {
+            Some(structured) => structured,
+            None => serde_json::Value::String(result_text(&result)),
+        })
+    }
+}
+
+/// Flatten tool result content into text, serializing non-text parts as JSON.
+fn result_text(result: &CallToolResult) -> String {
+    if result.content.is_empty()
+        && let Some(structured) = &result.structured_content
+    {
+        return structured.to_string();
+    }
+
+    result
+        .content
+        .iter()
+        .map(|content| match &content.raw {
+            RawContent::Text(text) => text.text.clone(),
+            other => serde_json::to_string(other).unwrap_or_default(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FunctionCall;
+    use rmcp::handler::server::ServerHandler;
+    use rmcp::model::{Content, ListToolsResult, PaginatedRequestParam, ServerCapabilities};
+    use rmcp::service::{RequestContext, RoleServer};
+    use serde_json::json;
+
+    struct AddServer;
+
+    impl ServerHandler for AddServer {
+        fn get_info(&self) -> ServerInfo {
+            ServerInfo {
+                capabilities: ServerCapabilities::builder().enable_tools().build(),
+                ..Default::default()
+            }
+        }
+
+        async fn list_tools(
+            &self,
+            _request: Option<PaginatedRequestParam>,
+            _context: RequestContext<RoleServer>,
+        ) -> Result<ListToolsResult, rmcp::ErrorData> {
+            let schema = json!({
+                "type": "object",
+                "properties": { "a": { "type": "number" }, "b": { "type": "number" } },
+                "required": ["a", "b"]
+            });
+            Ok(ListToolsResult {
+                tools: vec![rmcp::model::Tool::new(
+                    "add",
+                    "Add two numbers",
+                    Arc::new(schema.as_object().unwrap().clone()),
+                )],
+                next_cursor: None,
+            })
+        }
+
+        async fn call_tool(
+            &self,
+            request: CallToolRequestParam,
+            _context: RequestContext<RoleServer>,
+        ) -> Result<CallToolResult, rmcp::ErrorData> {
+            let args = request.arguments.unwrap_or_default();
+            match (
+                args.get("a").and_then(|v| v.as_f64()),
+                args.get("b").and_then(|v| v.as_f64()),
+            ) {
+                (Some(a), Some(b)) => Ok(CallToolResult::success(vec![Content::text(
+                    (a + b).to_string(),
+                )])),
+                _ => Ok(CallToolResult::error(vec![Content::text(
+                    "a and b are required",
+                )])),
+            }
+        }
+    }
+
+    async fn connect() -> McpToolProvider {
+        let (client, server) = tokio::io::duplex(4096);
+        tokio::spawn(async move {
+            let service = AddServer.serve(server).await.unwrap();
+            let _ = service.waiting().await;
+        });
+        McpToolProvider::connect(client).await.unwrap()
+    }
+
+    fn tool_call(arguments: &str) -> ToolCall {
+        ToolCall {
+            id: "call_1".to_string(),
+            r#type: "function".to_string(),
+            function: Some(FunctionCall {
+                name: "add".to_string(),
+                arguments: arguments.to_string(),
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lists_and_calls_server_tools() {
+        let provider = connect().await;
+
+        let tools = provider.tools().await.unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].function.name, "add");
+        assert_eq!(tools[0].function.description, "Add two numbers");
+        assert_eq!(tools[0].function.parameters["required"], json!(["a", "b"]));
+
+        let output = provider
+            .call_tool(&tool_call(r#"{"a":2,"b":3}"#))
+            .await
+            .unwrap();
+        assert_eq!(output, "5");
+
+        let error = provider.call_tool(&tool_call("{}")).await.unwrap_err();
+        assert!(
+            matches!(error, LlmError::ToolCallError(ref m) if m.contains("a and b are required"))
+        );
+
+        provider.close();
+    }
+
+    #[tokio::test]
+    async fn test_register_into_forwards_calls() {
+        let provider = connect().await;
+
+        let mut registry = ToolRegistry::new();
+        assert_eq!(provider.register_into(&mut registry).await.unwrap(), 1);
+        assert!(registry.contains("add"));
+
+        let handler = registry.handler("add").unwrap();
+        let output = handler.call(json!({"a": 1, "b": 4})).await.unwrap();
+        assert_eq!(output, json!("5"));
+    }
+}
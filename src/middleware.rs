@@ -0,0 +1,822 @@
+//! Middleware Chain
+//!
+//! This module provides a tower-like interception layer for chat calls. An
+//! [`LlmMiddleware`] can inspect or rewrite the outgoing [`ChatRequest`], the
+//! returned [`ChatResponse`] and every streamed [`ChatStreamEvent`], and is
+//! notified of errors. Cross-cutting concerns such as redaction, default prompt
+//! injection, auditing and cost accounting can therefore be written once and
+//! applied to every provider.
+//!
+//! Middleware is composed with [`MiddlewareClient`], or directly on the unified
+//! builder with [`SiumaiBuilder::layer`](crate::provider::SiumaiBuilder::layer).
+//! Layers run in the order they were added for requests, and in reverse order
+//! for responses, stream events and errors, so the first layer is the outermost.
+//!
+//! # Example
+//! ```rust,no_run
+//! use siumai::middleware::LlmMiddleware;
+//! use siumai::prelude::*;
+//!
+//! /// Prepends a default system prompt to every conversation
+//! struct DefaultSystemPrompt(String);
+//!
+//! #[async_trait::async_trait]
+//! impl LlmMiddleware for DefaultSystemPrompt {
+//!     async fn on_request(&self, request: &mut ChatRequest) -> Result<(), LlmError> {
+//!         if !request.messages.iter().any(|m| m.role == MessageRole::System) {
+//!             request
+//!                 .messages
+//!                 .insert(0, ChatMessage::system(self.0.clone()).build());
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! # async fn example() -> Result<(), LlmError> {
+//! let client = Siumai::builder()
+//!     .openai()
+//!     .api_key("your-api-key")
+//!     .layer(DefaultSystemPrompt("Answer concisely.".to_string()))
+//!     .build()
+//!     .await?;
+//!
+//! let response = client.chat(vec![user!("What is Rust?")]).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::Arc;
+
+use crate::client::LlmClient;
+use crate::error::LlmError;
+use crate::stream::{ChatStream, ChatStreamEvent, StreamProcessor};
+use crate::traits::*;
+use crate::types::*;
+
+/// Interceptor for chat requests, responses and stream events.
+///
+/// All hooks have no-op default implementations, so a middleware only needs
+/// to implement the ones it cares about. Returning an error from a hook aborts
+/// the call with that error.
+#[async_trait]
+pub trait LlmMiddleware: Send + Sync {
+    /// Called before the request is sent.
+    ///
+    /// The request carries the client's parameters. Changes to `messages`,
+    /// `tools`, `common_params`, `provider_params`, `web_search`,
+    /// `prompt_cache` and `output_schema` are forwarded to the wrapped client;
+    /// parameters are sent with [`ChatCapability::chat_with_request`], so
+    /// clients that cannot apply them fail with
+    /// `LlmError::UnsupportedOperation`. Clearing a parameter keeps the
+    /// client's value.
+    async fn on_request(&self, _request: &mut ChatRequest) -> Result<(), LlmError> {
+        Ok(())
+    }
+
+    /// Called with the response of a completed call.
+    ///
+    /// For streaming calls this receives the response accumulated from the
+    /// stream's events, after `on_stream_event` has seen its `StreamEnd`.
+    /// Responses that do not name their model report the requested one.
+    async fn on_response(&self, _response: &mut ChatResponse) -> Result<(), LlmError> {
+        Ok(())
+    }
+
+    /// Called for every event of a streaming call
+    async fn on_stream_event(&self, _event: &mut ChatStreamEvent) -> Result<(), LlmError> {
+        Ok(())
+    }
+
+    /// Called when the call fails, including failures raised by other middleware
+    async fn on_error(&self, _error: &LlmError) {}
+}
+
+/// Ordered list of middleware layers
+#[derive(Clone, Default)]
+struct Layers(Arc<Vec<Arc<dyn LlmMiddleware>>>);
+
+impl Layers {
+    async fn on_request(&self, request: &mut ChatRequest) -> Result<(), LlmError> {
+        for layer in self.0.iter() {
+            layer.on_request(request).await?;
+        }
+        Ok(())
+    }
+
+    async fn on_response(&self, response: &mut ChatResponse) -> Result<(), LlmError> {
+        for layer in self.0.iter().rev() {
+            layer.on_response(response).await?;
+        }
+        Ok(())
+    }
+
+    async fn on_stream_event(&self, event: &mut ChatStreamEvent) -> Result<(), LlmError> {
+        for layer in self.0.iter().rev() {
+            layer.on_stream_event(event).await?;
+        }
+        Ok(())
+    }
+
+    /// Run `on_stream_event` and `on_response` on the final `StreamEnd`
+    async fn on_stream_end(&self, response: ChatResponse) -> Result<ChatStreamEvent, LlmError> {
+        let mut event = ChatStreamEvent::StreamEnd { response };
+        self.on_stream_event(&mut event).await?;
+        if let ChatStreamEvent::StreamEnd { response } = &mut event {
+            self.on_response(response).await?;
+        }
+        Ok(event)
+    }
+
+    /// Notify all layers of an error and hand it back
+    async fn on_error(&self, error: LlmError) -> LlmError {
+        for layer in self.0.iter().rev() {
+            layer.on_error(&error).await;
+        }
+        error
+    }
+}
+
+/// Chat client wrapper running a chain of [`LlmMiddleware`].
+///
+/// When the wrapped type is an [`LlmClient`], all other capabilities are
+/// forwarded untouched and `as_any` resolves to the wrapped client, so
+/// provider-specific downcasts keep working.
+///
+/// Streams are passed through with a single `StreamEnd`, emitted once the
+/// wrapped stream finishes and carrying the accumulated response.
+pub struct MiddlewareClient<C: ?Sized> {
+    inner: Arc<C>,
+    layers: Layers,
+    common_params: CommonParams,
+    provider_params: Option<ProviderParams>,
+}
+
+impl<C> MiddlewareClient<C> {
+    /// Wrap a client without any middleware
+    pub fn new(inner: C) -> Self {
+        Self::from_arc(Arc::new(inner))
+    }
+}
+
+impl<C: ?Sized> MiddlewareClient<C> {
+    /// Wrap a shared client without any middleware
+    pub fn from_arc(inner: Arc<C>) -> Self {
+        Self {
+            inner,
+            layers: Layers::default(),
+            common_params: CommonParams::default(),
+            provider_params: None,
+        }
+    }
+
+    /// Add a middleware layer
+    pub fn layer<M: LlmMiddleware + 'static>(self, middleware: M) -> Self {
+        self.layer_arc(Arc::new(middleware))
+    }
+
+    /// Add a shared middleware layer
+    pub fn layer_arc(mut self, middleware: Arc<dyn LlmMiddleware>) -> Self {
+        Arc::make_mut(&mut self.layers.0).push(middleware);
+        self
+    }
+
+    /// Set the wrapped client's common parameters, as seen by middleware
+    pub fn with_common_params(mut self, params: CommonParams) -> Self {
+        self.common_params = params;
+        self
+    }
+
+    /// Set the wrapped client's provider parameters, as seen by middleware
+    pub fn with_provider_params(mut self, params: ProviderParams) -> Self {
+        self.provider_params = Some(params);
+        self
+    }
+
+    /// Get the wrapped client
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Number of middleware layers
+    pub fn len(&self) -> usize {
+        self.layers.0.len()
+    }
+
+    /// Whether no middleware layers are configured
+    pub fn is_empty(&self) -> bool {
+        self.layers.0.is_empty()
+    }
+
+    /// Run `on_response` (or `on_error`) on a call result for `model`
+    async fn finish(
+        &self,
+        result: Result<ChatResponse, LlmError>,
+        model: &str,
+    ) -> Result<ChatResponse, LlmError> {
+        let result = match result {
+            Ok(response) => {
+                let mut response = with_model(response, model);
+                self.layers
+                    .on_response(&mut response)
+                    .await
+                    .map(|()| response)
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(response) => Ok(response),
+            Err(e) => Err(self.layers.on_error(e).await),
+        }
+    }
+}
+
+/// Fill in the requested model on a response that names none
+fn with_model(mut response: ChatResponse, model: &str) -> ChatResponse {
+    if response.model.is_none() && !model.is_empty() {
+        response.model = Some(model.to_string());
+    }
+    response
+}
+
+impl<C: ChatCapability + ?Sized> MiddlewareClient<C> {
+    /// Build the request seen by middleware and run `on_request`
+    ///
+    /// `request` carries the caller's messages, tools and parameter
+    /// overrides. The returned request keeps only the parameters to forward:
+    /// those the caller set, or all of them once middleware changed them. The
+    /// model the request is sent to is returned alongside.
+    async fn prepare(
+        &self,
+        request: ChatRequest,
+        stream: bool,
+    ) -> Result<(ChatRequest, String), LlmError> {
+        let ChatRequest {
+            messages,
+            tools,
+            common_params: common_overrides,
+            provider_params: provider_overrides,
+            http_config,
+            web_search,
+            prompt_cache,
+            output_schema,
+            ..
+        } = request;
+
+        let mut common_params = self.common_params.merged_with(&common_overrides);
+        if common_params.model.is_empty() {
+            common_params.model = self.inner.default_model().unwrap_or_default();
+        }
+        let provider_params =
+            ProviderParams::overlay(self.provider_params.as_ref(), provider_overrides.as_ref());
+
+        let mut request = ChatRequest {
+            messages,
+            tools,
+            common_params: common_params.clone(),
+            provider_params: provider_params.clone(),
+            http_config,
+            web_search,
+            stream,
+            prompt_cache,
+            output_schema,
+        };
+        if let Err(e) = self.layers.on_request(&mut request).await {
+            return Err(self.layers.on_error(e).await);
+        }
+
+        let model = request.common_params.model.clone();
+        if request.common_params == common_params {
+            request.common_params = common_overrides;
+        }
+        if request.provider_params == provider_params {
+            request.provider_params = provider_overrides;
+        }
+        Ok((request, model))
+    }
+
+    /// Send a prepared request, using the request path only when it carries parameters
+    async fn send(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        if request.has_params() {
+            return self.inner.chat_with_request(request).await;
+        }
+        match request.output_schema {
+            Some(schema) => self.inner.chat_with_schema(request.messages, schema).await,
+            None => {
+                self.inner
+                    .chat_with_tools(request.messages, request.tools)
+                    .await
+            }
+        }
+    }
+
+    /// Stream a prepared request for `model` through the middleware chain
+    async fn send_stream(
+        &self,
+        request: ChatRequest,
+        model: String,
+    ) -> Result<ChatStream, LlmError> {
+        let result = if request.has_params() {
+            self.inner.chat_stream_with_request(request).await
+        } else if let Some(schema) = request.output_schema {
+            self.inner
+                .chat_stream_with_schema(request.messages, schema)
+                .await
+        } else {
+            self.inner
+                .chat_stream(request.messages, request.tools)
+                .await
+        };
+        let mut stream = match result {
+            Ok(stream) => stream,
+            Err(e) => return Err(self.layers.on_error(e).await),
+        };
+
+        if self.is_empty() {
+            return Ok(stream);
+        }
+
+        let layers = self.layers.clone();
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<Result<ChatStreamEvent, LlmError>>(64);
+
+        tokio::spawn(async move {
+            let mut processor = StreamProcessor::new();
+            let mut end: Option<ChatResponse> = None;
+
+            while let Some(event) = stream.next().await {
+                let result = match event {
+                    // Providers may end more than once (OpenAI sends usage after the
+                    // first end), so the end is held back until the stream finishes.
+                    Ok(ChatStreamEvent::StreamEnd { response }) => {
+                        end = Some(merge_end(end, response));
+                        continue;
+                    }
+                    Ok(mut event) => layers.on_stream_event(&mut event).await.map(|()| {
+                        processor.process_event(event.clone());
+                        event
+                    }),
+                    Err(e) => Err(e),
+                };
+                let result = match result {
+                    Ok(event) => Ok(event),
+                    Err(e) => Err(layers.on_error(e).await),
+                };
+
+                let failed = result.is_err();
+                if tx.send(result).await.is_err() || failed {
+                    return;
+                }
+            }
+
+            if let Some(end) = end {
+                let response = with_model(processor.complete_response(end), &model);
+                let result = match layers.on_stream_end(response).await {
+                    Ok(event) => Ok(event),
+                    Err(e) => Err(layers.on_error(e).await),
+                };
+                let _ = tx.send(result).await;
+            }
+        });
+
+        Ok(Box::pin(futures::stream::poll_fn(move |cx| {
+            rx.poll_recv(cx)
+        })))
+    }
+}
+
+/// Combine repeated `StreamEnd` responses, keeping the first id, model and finish reason
+fn merge_end(previous: Option<ChatResponse>, end: ChatResponse) -> ChatResponse {
+    let Some(mut previous) = previous else {
+        return end;
+    };
+    previous.id = previous.id.or(end.id);
+    previous.model = previous.model.or(end.model);
+    previous.finish_reason = previous.finish_reason.or(end.finish_reason);
+    previous.usage = end.usage.or(previous.usage);
+    previous
+}
+
+impl<C: ?Sized> Clone for MiddlewareClient<C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            layers: self.layers.clone(),
+            common_params: self.common_params.clone(),
+            provider_params: self.provider_params.clone(),
+        }
+    }
+}
+
+impl<C: ?Sized> std::fmt::Debug for MiddlewareClient<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MiddlewareClient")
+            .field("layers", &self.len())
+            .field("common_params", &self.common_params)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl<C: ChatCapability + ?Sized + 'static> ChatCapability for MiddlewareClient<C> {
+    fn default_model(&self) -> Option<String> {
+        Some(self.common_params.model.clone())
+            .filter(|model| !model.is_empty())
+            .or_else(|| self.inner.default_model())
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+    ) -> Result<ChatResponse, LlmError> {
+        self.chat_with_request(ChatRequest {
+            messages,
+            tools,
+            ..Default::default()
+        })
+        .await
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+    ) -> Result<ChatStream, LlmError> {
+        self.chat_stream_with_request(ChatRequest {
+            messages,
+            tools,
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Chat with structured output
+    ///
+    /// Parameters changed by middleware are sent with the schema on the
+    /// request path.
+    async fn chat_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatResponse, LlmError> {
+        self.chat_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    /// Stream structured output, keeping the provider's native schema support
+    async fn chat_stream_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatStream, LlmError> {
+        self.chat_stream_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    async fn chat_with_request(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        let (request, model) = self.prepare(request, false).await?;
+        let result = self.send(request).await;
+        self.finish(result, &model).await
+    }
+
+    async fn chat_stream_with_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
+        let (request, model) = self.prepare(request, true).await?;
+        self.send_stream(request, model).await
+    }
+}
+
+impl<C: LlmClient + ?Sized + 'static> LlmClient for MiddlewareClient<C> {
+    fn provider_name(&self) -> &'static str {
+        self.inner.provider_name()
+    }
+
+    fn supported_models(&self) -> Vec<String> {
+        self.inner.supported_models()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self.inner.as_any()
+    }
+
+    fn clone_box(&self) -> Box<dyn LlmClient> {
+        Box::new(self.clone())
+    }
+
+    fn as_embedding_capability(&self) -> Option<&dyn EmbeddingCapability> {
+        self.inner.as_embedding_capability()
+    }
+
+    fn as_audio_capability(&self) -> Option<&dyn AudioCapability> {
+        self.inner.as_audio_capability()
+    }
+
+    fn as_vision_capability(&self) -> Option<&dyn VisionCapability> {
+        self.inner.as_vision_capability()
+    }
+
+    fn as_image_generation_capability(&self) -> Option<&dyn ImageGenerationCapability> {
+        self.inner.as_image_generation_capability()
+    }
+
+    fn as_completion_capability(&self) -> Option<&dyn CompletionCapability> {
+        self.inner.as_completion_capability()
+    }
+
+    fn as_batch_capability(&self) -> Option<&dyn BatchCapability> {
+        self.inner.as_batch_capability()
+    }
+
+    fn as_conversation_state_capability(&self) -> Option<&dyn ConversationStateCapability> {
+        self.inner.as_conversation_state_capability()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{MockClient, MockReply};
+    use std::sync::Mutex;
+
+    /// Chat client echoing the number of received messages
+    struct EchoClient;
+
+    #[async_trait]
+    impl ChatCapability for EchoClient {
+        async fn chat_with_tools(
+            &self,
+            messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+        ) -> Result<ChatResponse, LlmError> {
+            if messages.iter().any(|m| m.content_text() == Some("fail")) {
+                return Err(LlmError::ApiError {
+                    code: 500,
+                    message: "boom".to_string(),
+                    details: None,
+                });
+            }
+            Ok(ChatResponse::new(MessageContent::Text(format!(
+                "{} messages",
+                messages.len()
+            ))))
+        }
+
+        async fn chat_stream(
+            &self,
+            messages: Vec<ChatMessage>,
+            tools: Option<Vec<Tool>>,
+        ) -> Result<ChatStream, LlmError> {
+            let response = self.chat_with_tools(messages, tools).await?;
+            let events = vec![
+                Ok(ChatStreamEvent::ContentDelta {
+                    delta: response.content.all_text(),
+                    index: None,
+                }),
+                Ok(ChatStreamEvent::StreamEnd { response }),
+            ];
+            Ok(Box::pin(futures::stream::iter(events)))
+        }
+    }
+
+    /// Records hook calls and tags requests and responses
+    struct Recorder {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl LlmMiddleware for Recorder {
+        async fn on_request(&self, request: &mut ChatRequest) -> Result<(), LlmError> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{}:request", self.name));
+            request
+                .messages
+                .insert(0, ChatMessage::system(self.name).build());
+            Ok(())
+        }
+
+        async fn on_response(&self, response: &mut ChatResponse) -> Result<(), LlmError> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{}:response", self.name));
+            response
+                .metadata
+                .insert(self.name.to_string(), serde_json::Value::Bool(true));
+            Ok(())
+        }
+
+        async fn on_stream_event(&self, event: &mut ChatStreamEvent) -> Result<(), LlmError> {
+            if let ChatStreamEvent::ContentDelta { delta, .. } = event {
+                delta.push_str(&format!(" [{}]", self.name));
+            }
+            Ok(())
+        }
+
+        async fn on_error(&self, error: &LlmError) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{}:error:{error}", self.name));
+        }
+    }
+
+    fn client(log: &Arc<Mutex<Vec<String>>>) -> MiddlewareClient<EchoClient> {
+        MiddlewareClient::new(EchoClient)
+            .layer(Recorder {
+                name: "outer",
+                log: log.clone(),
+            })
+            .layer(Recorder {
+                name: "inner",
+                log: log.clone(),
+            })
+    }
+
+    #[tokio::test]
+    async fn test_hooks_run_in_layer_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let response = client(&log)
+            .chat(vec![ChatMessage::user("hi").build()])
+            .await
+            .unwrap();
+
+        // Both layers injected a system message
+        assert_eq!(response.content_text(), Some("3 messages"));
+        assert!(response.metadata.contains_key("outer"));
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "outer:request",
+                "inner:request",
+                "inner:response",
+                "outer:response"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_errors_reach_every_layer() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let result = client(&log)
+            .chat(vec![ChatMessage::user("fail").build()])
+            .await;
+
+        assert!(result.is_err());
+        let log = log.lock().unwrap();
+        assert_eq!(log.len(), 4);
+        assert!(log[2].starts_with("inner:error"));
+        assert!(log[3].starts_with("outer:error"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_events_pass_through_middleware() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let events: Vec<_> = client(&log)
+            .chat_stream(vec![ChatMessage::user("hi").build()], None)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        match &events[0] {
+            Ok(ChatStreamEvent::ContentDelta { delta, .. }) => {
+                assert_eq!(delta, "3 messages [inner] [outer]")
+            }
+            other => panic!("Expected ContentDelta, got {other:?}"),
+        }
+        match &events[1] {
+            Ok(ChatStreamEvent::StreamEnd { response }) => {
+                assert!(response.metadata.contains_key("inner"));
+                assert!(response.metadata.contains_key("outer"));
+            }
+            other => panic!("Expected StreamEnd, got {other:?}"),
+        }
+    }
+
+    /// Switches every request to another model and temperature
+    struct Reroute;
+
+    #[async_trait]
+    impl LlmMiddleware for Reroute {
+        async fn on_request(&self, request: &mut ChatRequest) -> Result<(), LlmError> {
+            request.common_params.model = "rerouted-model".to_string();
+            request.common_params.temperature = Some(0.0);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parameter_changes_reach_the_client() {
+        let mock = MockClient::new().with_text("ok");
+        let client = MiddlewareClient::new(mock.clone()).layer(Reroute);
+
+        let response = client
+            .chat(vec![ChatMessage::user("hi").build()])
+            .await
+            .unwrap();
+
+        let request = mock.last_request().unwrap();
+        assert_eq!(request.common_params.model, "rerouted-model");
+        assert_eq!(request.common_params.temperature, Some(0.0));
+        assert_eq!(response.model.as_deref(), Some("rerouted-model"));
+    }
+
+    #[tokio::test]
+    async fn test_parameter_changes_reach_structured_output_calls() {
+        let mock = MockClient::new().with_text("{}");
+        let client = MiddlewareClient::new(mock.clone()).layer(Reroute);
+        let schema = OutputSchema::new("answer", serde_json::json!({"type": "object"}));
+
+        client
+            .chat_with_schema(vec![ChatMessage::user("hi").build()], schema.clone())
+            .await
+            .unwrap();
+        let request = mock.last_request().unwrap();
+        assert_eq!(request.common_params.model, "rerouted-model");
+        assert_eq!(request.common_params.temperature, Some(0.0));
+        assert_eq!(request.output_schema.as_ref(), Some(&schema));
+
+        mock.push_reply(MockReply::text("{}"));
+        let stream = client
+            .chat_stream_with_schema(vec![ChatMessage::user("hi").build()], schema.clone())
+            .await
+            .unwrap();
+        crate::stream::collect_stream_response(stream)
+            .await
+            .unwrap();
+        let request = mock.last_request().unwrap();
+        assert!(request.stream);
+        assert_eq!(request.common_params.model, "rerouted-model");
+        assert_eq!(request.output_schema, Some(schema));
+    }
+
+    /// Records the responses passed to `on_response`
+    struct ResponseLog(Arc<Mutex<Vec<ChatResponse>>>);
+
+    #[async_trait]
+    impl LlmMiddleware for ResponseLog {
+        async fn on_response(&self, response: &mut ChatResponse) -> Result<(), LlmError> {
+            self.0.lock().unwrap().push(response.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_response_accumulated_for_on_response() {
+        // Provider-shaped: empty ends, usage between them
+        let end = ChatResponse {
+            finish_reason: Some(FinishReason::Stop),
+            ..ChatResponse::new(MessageContent::Text(String::new()))
+        };
+        let mock = MockClient::new().with_reply(MockReply::stream(vec![
+            ChatStreamEvent::ContentDelta {
+                delta: "Hello".to_string(),
+                index: None,
+            },
+            ChatStreamEvent::ContentDelta {
+                delta: " world".to_string(),
+                index: None,
+            },
+            ChatStreamEvent::StreamEnd {
+                response: end.clone(),
+            },
+            ChatStreamEvent::UsageUpdate {
+                usage: Usage::new(3, 2),
+            },
+            ChatStreamEvent::StreamEnd { response: end },
+        ]));
+        let responses = Arc::new(Mutex::new(Vec::new()));
+        let client = MiddlewareClient::new(mock).layer(ResponseLog(responses.clone()));
+
+        let events: Vec<_> = client
+            .chat_stream(vec![ChatMessage::user("hi").build()], None)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        let ends: Vec<_> = events
+            .iter()
+            .filter(|event| matches!(event, Ok(ChatStreamEvent::StreamEnd { .. })))
+            .collect();
+        assert_eq!(ends.len(), 1);
+        assert!(matches!(
+            events.last(),
+            Some(Ok(ChatStreamEvent::StreamEnd { .. }))
+        ));
+
+        let responses = responses.lock().unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].content_text(), Some("Hello world"));
+        assert_eq!(responses[0].usage.as_ref().map(|u| u.total_tokens), Some(5));
+        assert_eq!(responses[0].finish_reason, Some(FinishReason::Stop));
+    }
+}
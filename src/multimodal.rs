@@ -3,11 +3,15 @@
 //! This module provides comprehensive multimodal capabilities including
 //! image processing, audio handling, document processing, and format conversion.
 
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::error::LlmError;
-use crate::types::{ContentPart, MessageContent};
+use crate::types::{
+    ChatMessage, ContentPart, DocumentSource, ImageInput, MessageContent, VisionRequest,
+};
 
 /// Multimodal content processor
 #[allow(dead_code)]
@@ -82,6 +86,30 @@ impl MultimodalProcessor {
                     metadata: audio_info.metadata,
                 })
             }
+            ContentPart::Document {
+                source,
+                media_type,
+                title,
+                ..
+            } => {
+                let mut metadata = ContentMetadata::default();
+                if let Some(title) = title {
+                    metadata.insert(
+                        "title".to_string(),
+                        serde_json::Value::String(title.clone()),
+                    );
+                }
+                let data = match source {
+                    DocumentSource::Base64(data)
+                    | DocumentSource::Url(data)
+                    | DocumentSource::FileId(data) => data.clone(),
+                };
+                Ok(ProcessedContentPart::Document {
+                    data,
+                    format: DocumentFormat::from_mime_type(media_type),
+                    metadata,
+                })
+            }
         }
     }
 
@@ -309,7 +337,7 @@ impl ImageFormat {
     /// Detect format from base64 data
     pub fn detect_from_base64(data: &str) -> Self {
         // Simple magic number detection
-        if data.starts_with("/9j/") || data.starts_with("iVBOR") {
+        if data.starts_with("/9j/") {
             Self::Jpeg
         } else if data.starts_with("iVBOR") {
             Self::Png
@@ -322,6 +350,47 @@ impl ImageFormat {
         }
     }
 
+    /// Get format from a file extension
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_lowercase().as_str() {
+            "jpg" | "jpeg" => Some(Self::Jpeg),
+            "png" => Some(Self::Png),
+            "gif" => Some(Self::Gif),
+            "webp" => Some(Self::WebP),
+            "bmp" => Some(Self::Bmp),
+            "tif" | "tiff" => Some(Self::Tiff),
+            "svg" => Some(Self::Svg),
+            _ => None,
+        }
+    }
+
+    /// Detect format from raw image bytes
+    pub fn detect_from_bytes(data: &[u8]) -> Option<Self> {
+        match data {
+            [0xFF, 0xD8, 0xFF, ..] => Some(Self::Jpeg),
+            [0x89, b'P', b'N', b'G', ..] => Some(Self::Png),
+            [b'G', b'I', b'F', b'8', ..] => Some(Self::Gif),
+            [
+                b'R',
+                b'I',
+                b'F',
+                b'F',
+                _,
+                _,
+                _,
+                _,
+                b'W',
+                b'E',
+                b'B',
+                b'P',
+                ..,
+            ] => Some(Self::WebP),
+            [b'B', b'M', ..] => Some(Self::Bmp),
+            [b'I', b'I', 0x2A, 0x00, ..] | [b'M', b'M', 0x00, 0x2A, ..] => Some(Self::Tiff),
+            _ => None,
+        }
+    }
+
     /// Get MIME type
     pub const fn mime_type(&self) -> &'static str {
         match self {
@@ -475,6 +544,177 @@ impl std::fmt::Display for DocumentFormat {
     }
 }
 
+/// Image normalized for provider requests.
+///
+/// Providers accept images either by reference (URL) or inline as base64 data
+/// with a media type; every [`ImageInput`] variant maps onto one of the two.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NormalizedImage {
+    /// Remote image referenced by URL
+    Url(String),
+    /// Inline base64 encoded image
+    Inline { media_type: String, data: String },
+}
+
+impl NormalizedImage {
+    /// Parse the `image_url` of a [`ContentPart::Image`].
+    ///
+    /// Accepts `data:` URLs, remote URLs and bare base64 data.
+    pub fn parse(image_url: &str) -> Self {
+        if let Some(rest) = image_url.strip_prefix("data:")
+            && let Some((header, data)) = rest.split_once(',')
+        {
+            let media_type = header
+                .split(';')
+                .next()
+                .filter(|m| !m.is_empty())
+                .unwrap_or("image/jpeg");
+            return Self::Inline {
+                media_type: media_type.to_string(),
+                data: data.to_string(),
+            };
+        }
+
+        if image_url.contains("://") {
+            return Self::Url(image_url.to_string());
+        }
+
+        Self::Inline {
+            media_type: ImageFormat::detect_from_base64(image_url)
+                .mime_type()
+                .to_string(),
+            data: image_url.to_string(),
+        }
+    }
+
+    /// Create an inline image from raw bytes, detecting the media type when not given
+    pub fn from_bytes(data: &[u8], media_type: Option<&str>) -> Self {
+        let media_type = media_type
+            .map(str::to_string)
+            .or_else(|| ImageFormat::detect_from_bytes(data).map(|f| f.mime_type().to_string()))
+            .unwrap_or_else(|| "image/jpeg".to_string());
+        Self::Inline {
+            media_type,
+            data: BASE64.encode(data),
+        }
+    }
+
+    /// Get the media type if known
+    pub fn media_type(&self) -> Option<&str> {
+        match self {
+            Self::Url(_) => None,
+            Self::Inline { media_type, .. } => Some(media_type),
+        }
+    }
+
+    /// Convert to a value suitable for [`ContentPart::Image`] (URL or `data:` URL)
+    pub fn to_image_url(&self) -> String {
+        match self {
+            Self::Url(url) => url.clone(),
+            Self::Inline { media_type, data } => format!("data:{media_type};base64,{data}"),
+        }
+    }
+
+    /// Download remote `http(s)` images so that the data is inline.
+    ///
+    /// Needed for providers that only accept base64 image data. Other URL
+    /// schemes (such as `gs://`) are returned unchanged.
+    pub async fn into_inline(self, http_client: &reqwest::Client) -> Result<Self, LlmError> {
+        let url = match self {
+            Self::Url(url) if url.starts_with("http://") || url.starts_with("https://") => url,
+            other => return Ok(other),
+        };
+
+        let response = http_client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| LlmError::HttpError(format!("Failed to fetch image {url}: {e}")))?;
+        if !response.status().is_success() {
+            return Err(LlmError::HttpError(format!(
+                "Failed to fetch image {url}: HTTP {}",
+                response.status()
+            )));
+        }
+
+        let media_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.split(';').next().unwrap_or(v).trim().to_string())
+            .filter(|v| v.starts_with("image/"));
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| LlmError::HttpError(format!("Failed to read image {url}: {e}")))?;
+
+        Ok(Self::from_bytes(&bytes, media_type.as_deref()))
+    }
+}
+
+/// Normalize an [`ImageInput`] into a URL or inline base64 image.
+///
+/// Local files are read and encoded; the media type is taken from the file
+/// extension, falling back to magic-number detection.
+pub async fn normalize_image_input(input: &ImageInput) -> Result<NormalizedImage, LlmError> {
+    match input {
+        ImageInput::Url(url) => Ok(NormalizedImage::parse(url)),
+        ImageInput::Base64 { data, media_type } => Ok(match media_type {
+            Some(media_type) => NormalizedImage::Inline {
+                media_type: media_type.clone(),
+                data: data.clone(),
+            },
+            None => NormalizedImage::parse(data),
+        }),
+        ImageInput::Bytes { data, media_type } => {
+            Ok(NormalizedImage::from_bytes(data, media_type.as_deref()))
+        }
+        ImageInput::File(path) => {
+            let data = tokio::fs::read(path).await.map_err(|e| {
+                LlmError::IoError(format!("Failed to read image {}: {e}", path.display()))
+            })?;
+            let media_type = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .and_then(ImageFormat::from_extension)
+                .map(|f| f.mime_type());
+            Ok(NormalizedImage::from_bytes(&data, media_type))
+        }
+    }
+}
+
+/// Build the multimodal chat messages for a [`VisionRequest`].
+///
+/// When `inline_client` is given, remote `http(s)` images are downloaded and
+/// sent inline, for providers that cannot fetch arbitrary URLs themselves.
+pub async fn build_vision_messages(
+    request: &VisionRequest,
+    inline_client: Option<&reqwest::Client>,
+) -> Result<Vec<ChatMessage>, LlmError> {
+    if request.images.is_empty() {
+        return Err(LlmError::InvalidInput(
+            "Vision request requires at least one image".to_string(),
+        ));
+    }
+
+    let mut messages = Vec::new();
+    if let Some(system_prompt) = &request.system_prompt {
+        messages.push(ChatMessage::system(system_prompt.clone()).build());
+    }
+
+    let mut user = ChatMessage::user(request.prompt.clone());
+    for image in &request.images {
+        let mut image = normalize_image_input(image).await?;
+        if let Some(http_client) = inline_client {
+            image = image.into_inline(http_client).await?;
+        }
+        user = user.with_image(image.to_image_url(), request.detail.clone());
+    }
+    messages.push(user.build());
+
+    Ok(messages)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -493,6 +733,55 @@ mod tests {
         assert_eq!(AudioFormat::from_extension("flac"), AudioFormat::Flac);
     }
 
+    #[test]
+    fn test_normalized_image_parse() {
+        assert_eq!(
+            NormalizedImage::parse("https://example.com/cat.png"),
+            NormalizedImage::Url("https://example.com/cat.png".to_string())
+        );
+        assert_eq!(
+            NormalizedImage::parse("data:image/png;base64,iVBORw0KGgo="),
+            NormalizedImage::Inline {
+                media_type: "image/png".to_string(),
+                data: "iVBORw0KGgo=".to_string(),
+            }
+        );
+        assert_eq!(
+            NormalizedImage::parse("iVBORw0KGgo=").media_type(),
+            Some("image/png")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_vision_messages_from_bytes() {
+        let png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        let request = VisionRequest::new("What is this?")
+            .with_image_bytes(png)
+            .with_detail("low");
+
+        let messages = build_vision_messages(&request, None).await.unwrap();
+        assert_eq!(messages.len(), 1);
+        match &messages[0].content {
+            MessageContent::MultiModal(parts) => {
+                assert!(matches!(&parts[0], ContentPart::Text { text } if text == "What is this?"));
+                match &parts[1] {
+                    ContentPart::Image { image_url, detail } => {
+                        assert!(image_url.starts_with("data:image/png;base64,"));
+                        assert_eq!(detail.as_deref(), Some("low"));
+                    }
+                    other => panic!("Expected image part, got {other:?}"),
+                }
+            }
+            other => panic!("Expected multimodal content, got {other:?}"),
+        }
+
+        assert!(
+            build_vision_messages(&VisionRequest::new("empty"), None)
+                .await
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_multimodal_processor() {
         let processor = MultimodalProcessor::new();
@@ -0,0 +1,558 @@
+//! Streaming Structured Output
+//!
+//! Parses JSON while it is still being generated. [`PartialJsonParser`] turns
+//! the text received so far into a best-effort JSON snapshot by closing open
+//! strings, arrays and objects and dropping incomplete keys and literals.
+//!
+//! [`ObjectStream`] applies it to a chat stream, reading the JSON from content
+//! deltas or, for providers that enforce schemas through tool use, from tool
+//! argument deltas. It yields snapshots as they grow, partial typed objects
+//! once a snapshot deserializes, and finally the validated object.
+//!
+//! # Example
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use siumai::object_stream::ObjectStreamEvent;
+//! use siumai::prelude::*;
+//!
+//! #[derive(Debug, serde::Deserialize, JsonSchema)]
+//! struct Recipe {
+//!     name: String,
+//!     #[serde(default)]
+//!     ingredients: Vec<String>,
+//! }
+//!
+//! # async fn example(client: Siumai) -> Result<(), LlmError> {
+//! let mut stream = client
+//!     .stream_object::<Recipe>(vec![user!("Give me a pancake recipe")])
+//!     .await?;
+//!
+//! while let Some(event) = stream.next().await {
+//!     match event? {
+//!         ObjectStreamEvent::Partial(recipe) => println!("{} ingredients so far", recipe.ingredients.len()),
+//!         ObjectStreamEvent::Complete(result) => println!("{:?}", result.object),
+//!         ObjectStreamEvent::PartialObject(_) => {}
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::error::LlmError;
+use crate::stream::{ChatStream, StreamProcessor};
+use crate::types::*;
+
+/// Open JSON container
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Container {
+    Object { expect_key: bool },
+    Array,
+}
+
+/// Incremental scanner tracking the JSON structure of a growing buffer
+#[derive(Debug, Clone, Default)]
+struct Scanner {
+    /// Bytes scanned so far
+    pos: usize,
+    /// Start of the top-level object or array
+    start: Option<usize>,
+    stack: Vec<Container>,
+    in_string: bool,
+    string_is_key: bool,
+    escape: bool,
+    /// Remaining hex digits of a `\u` escape
+    unicode_digits: u8,
+    /// Start of the number or literal being scanned
+    literal_start: Option<usize>,
+    /// End of the longest complete prefix and the containers open there
+    safe: Option<(usize, Vec<Container>)>,
+    complete: bool,
+}
+
+impl Scanner {
+    fn scan(&mut self, text: &str) {
+        // Structural characters are ASCII, so scanning bytes is UTF-8 safe
+        let bytes = text.as_bytes();
+        while self.pos < bytes.len() && !self.complete {
+            let i = self.pos;
+            let b = bytes[i];
+            self.pos += 1;
+
+            // Skip prose or code fences before the JSON
+            if self.start.is_none() {
+                if b != b'{' && b != b'[' {
+                    continue;
+                }
+                self.start = Some(i);
+            }
+
+            if self.in_string {
+                if self.unicode_digits > 0 {
+                    self.unicode_digits -= 1;
+                } else if self.escape {
+                    self.escape = false;
+                    if b == b'u' {
+                        self.unicode_digits = 4;
+                    }
+                } else if b == b'\\' {
+                    self.escape = true;
+                } else if b == b'"' {
+                    self.in_string = false;
+                    if !self.string_is_key {
+                        self.value_end(i + 1);
+                    }
+                }
+                continue;
+            }
+
+            if let Some(start) = self.literal_start {
+                if is_literal_byte(b) {
+                    continue;
+                }
+                self.literal_start = None;
+                if is_valid_literal(&text[start..i]) {
+                    self.value_end(i);
+                }
+            }
+
+            match b {
+                b'{' => {
+                    self.stack.push(Container::Object { expect_key: true });
+                    self.mark_safe(i + 1);
+                }
+                b'[' => {
+                    self.stack.push(Container::Array);
+                    self.mark_safe(i + 1);
+                }
+                b'}' | b']' => {
+                    self.stack.pop();
+                    self.value_end(i + 1);
+                }
+                b'"' => {
+                    self.in_string = true;
+                    self.string_is_key = matches!(
+                        self.stack.last(),
+                        Some(Container::Object { expect_key: true })
+                    );
+                }
+                b':' | b',' => {
+                    if let Some(Container::Object { expect_key }) = self.stack.last_mut() {
+                        *expect_key = b == b',';
+                    }
+                }
+                b if is_literal_byte(b) => self.literal_start = Some(i),
+                _ => {}
+            }
+        }
+    }
+
+    fn value_end(&mut self, end: usize) {
+        self.complete = self.stack.is_empty();
+        self.mark_safe(end);
+    }
+
+    fn mark_safe(&mut self, end: usize) {
+        self.safe = Some((end, self.stack.clone()));
+    }
+
+    /// Best-effort value of the scanned text
+    fn snapshot(&self, text: &str) -> Option<Value> {
+        let start = self.start?;
+        let scanned = &text[..self.pos];
+
+        let candidate = if self.complete {
+            let (end, _) = self.safe.as_ref()?;
+            text[start..*end].to_string()
+        } else if self.in_string && !self.string_is_key && !self.escape && self.unicode_digits == 0
+        {
+            // Keep the partial string value
+            format!("{}\"{}", &scanned[start..], closers(&self.stack))
+        } else if let Some(literal) = self.literal_start
+            && is_valid_literal(&scanned[literal..])
+        {
+            format!("{}{}", &scanned[start..], closers(&self.stack))
+        } else {
+            let (end, stack) = self.safe.as_ref()?;
+            format!("{}{}", &text[start..*end], closers(stack))
+        };
+
+        serde_json::from_str(&candidate).ok()
+    }
+}
+
+fn closers(stack: &[Container]) -> String {
+    stack
+        .iter()
+        .rev()
+        .map(|container| match container {
+            Container::Object { .. } => '}',
+            Container::Array => ']',
+        })
+        .collect()
+}
+
+const fn is_literal_byte(b: u8) -> bool {
+    matches!(b, b'-' | b'+' | b'.') || b.is_ascii_alphanumeric()
+}
+
+fn is_valid_literal(literal: &str) -> bool {
+    serde_json::from_str::<Value>(literal).is_ok()
+}
+
+/// Best-effort parse of a possibly truncated JSON object or array.
+///
+/// Text before the first `{` or `[` is ignored. Returns `None` until the
+/// opening bracket has been received.
+pub fn parse_partial_json(text: &str) -> Option<Value> {
+    let mut scanner = Scanner::default();
+    scanner.scan(text);
+    scanner.snapshot(text)
+}
+
+/// Incremental parser for JSON received in chunks
+#[derive(Debug, Clone, Default)]
+pub struct PartialJsonParser {
+    buffer: String,
+    scanner: Scanner,
+    last: Option<Value>,
+}
+
+impl PartialJsonParser {
+    /// Create an empty parser
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a chunk, returning the new snapshot if it changed
+    pub fn push(&mut self, chunk: &str) -> Option<Value> {
+        self.buffer.push_str(chunk);
+        self.scanner.scan(&self.buffer);
+
+        let value = self.scanner.snapshot(&self.buffer)?;
+        if self.last.as_ref() == Some(&value) {
+            return None;
+        }
+        self.last = Some(value.clone());
+        Some(value)
+    }
+
+    /// Latest snapshot
+    pub const fn value(&self) -> Option<&Value> {
+        self.last.as_ref()
+    }
+
+    /// Text received so far
+    pub fn buffer(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Whether the top-level object or array has been closed
+    pub const fn is_complete(&self) -> bool {
+        self.scanner.complete
+    }
+}
+
+/// Event of an [`ObjectStream`]
+#[derive(Debug, Clone)]
+pub enum ObjectStreamEvent<T> {
+    /// Snapshot of the JSON received so far, emitted whenever it grows
+    PartialObject(Value),
+    /// Snapshot deserialized into `T`; follows `PartialObject` once the
+    /// snapshot has every required field
+    Partial(T),
+    /// Final object, validated against the schema
+    Complete(Box<ObjectResponse<T>>),
+}
+
+/// Stream of partial and final structured output.
+///
+/// Created by `ChatExtensions::stream_object`, or from any chat stream with
+/// [`ObjectStream::new`]. Partial objects are held unpinned, so the stream
+/// requires `T: Unpin`.
+pub struct ObjectStream<T> {
+    stream: ChatStream,
+    schema: OutputSchema,
+    content: PartialJsonParser,
+    arguments: PartialJsonParser,
+    processor: StreamProcessor,
+    end: Option<ChatResponse>,
+    pending: VecDeque<ObjectStreamEvent<T>>,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> ObjectStream<T> {
+    /// Parse the output of a chat stream against a schema
+    pub fn new(stream: ChatStream, schema: OutputSchema) -> Self {
+        Self {
+            stream,
+            schema,
+            content: PartialJsonParser::new(),
+            arguments: PartialJsonParser::new(),
+            processor: StreamProcessor::new(),
+            end: None,
+            pending: VecDeque::new(),
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Latest JSON snapshot
+    pub fn partial_object(&self) -> Option<&Value> {
+        self.content.value().or_else(|| self.arguments.value())
+    }
+
+    fn observe(&mut self, event: ChatStreamEvent) -> Result<(), LlmError> {
+        let snapshot = match &event {
+            ChatStreamEvent::ContentDelta { delta, .. } => self.content.push(delta),
+            ChatStreamEvent::ToolCallDelta {
+                arguments_delta: Some(delta),
+                ..
+            } => self.arguments.push(delta),
+            ChatStreamEvent::StreamEnd { response } => {
+                self.end = Some(response.clone());
+                return Ok(());
+            }
+            ChatStreamEvent::Error { error } => return Err(LlmError::StreamError(error.clone())),
+            _ => None,
+        };
+        self.processor.process_event(event);
+
+        if let Some(value) = snapshot {
+            let object = serde_json::from_value(value.clone()).ok();
+            self.pending
+                .push_back(ObjectStreamEvent::PartialObject(value));
+            self.pending.extend(object.map(ObjectStreamEvent::Partial));
+        }
+        Ok(())
+    }
+
+    /// Validate the complete output
+    fn finish(&mut self) -> Result<ObjectResponse<T>, LlmError> {
+        let mut response = match self.end.take() {
+            Some(end) => self.processor.complete_response(end),
+            None => self.processor.build_final_response(),
+        };
+        // Providers report usage in `UsageUpdate` events rather than the end
+        if let Some(usage) = self.processor.usage() {
+            response.usage = Some(usage.clone());
+        }
+
+        // Some streams end with an empty response; tool-based schemas carry
+        // the output in the tool arguments
+        if extract_json(&response.content.all_text()).is_none() {
+            let json = if self.content.buffer().trim().is_empty() {
+                self.arguments.buffer()
+            } else {
+                self.content.buffer()
+            };
+            response.content = MessageContent::Text(json.to_string());
+        }
+
+        let object = self.schema.parse_response(&response)?;
+        Ok(ObjectResponse {
+            object,
+            response,
+            attempts: 1,
+        })
+    }
+}
+
+impl<T: DeserializeOwned + Unpin> Stream for ObjectStream<T> {
+    type Item = Result<ObjectStreamEvent<T>, LlmError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(event) = this.pending.pop_front() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(Ok(event)) => {
+                    if let Err(e) = this.observe(event) {
+                        this.done = true;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                }
+                Some(Err(e)) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+                None => {
+                    this.done = true;
+                    let result = this
+                        .finish()
+                        .map(|result| ObjectStreamEvent::Complete(Box::new(result)));
+                    return Poll::Ready(Some(result));
+                }
+            }
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for ObjectStream<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObjectStream")
+            .field("schema", &self.schema.name)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{MockClient, MockReply};
+    use crate::traits::ChatExtensions;
+    use futures::StreamExt;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_partial_json() {
+        let cases = [
+            ("", None),
+            ("Sure! ```json\n{", Some(json!({}))),
+            (r#"{"name": "Pan"#, Some(json!({"name": "Pan"}))),
+            (
+                r#"{"name": "Pancakes", "ing"#,
+                Some(json!({"name": "Pancakes"})),
+            ),
+            (
+                r#"{"name": "Pancakes", "servings":"#,
+                Some(json!({"name": "Pancakes"})),
+            ),
+            (r#"{"servings": 4"#, Some(json!({"servings": 4}))),
+            (r#"{"vegan": tr"#, Some(json!({}))),
+            (
+                r#"{"items": ["egg", "mi"#,
+                Some(json!({"items": ["egg", "mi"]})),
+            ),
+            (
+                r#"{"items": [{"a": 1}, {"b""#,
+                Some(json!({"items": [{"a": 1}, {}]})),
+            ),
+            (r#"{"quote": "say \"hi\"#, Some(json!({}))),
+            (r#"{"n": -1.5e3} trailing"#, Some(json!({"n": -1500.0}))),
+        ];
+
+        for (text, expected) in cases {
+            assert_eq!(parse_partial_json(text), expected, "input: {text}");
+        }
+    }
+
+    #[test]
+    fn test_parser_emits_only_changed_snapshots() {
+        let mut parser = PartialJsonParser::new();
+        assert_eq!(parser.push(r#"{"a": "#), Some(json!({})));
+        assert_eq!(parser.push("1"), Some(json!({"a": 1})));
+        assert_eq!(parser.push(", "), None);
+        assert_eq!(parser.push(r#""b": "€"#), Some(json!({"a": 1, "b": "€"})));
+        assert_eq!(parser.push(r#""}"#), None);
+        assert!(parser.is_complete());
+    }
+
+    #[derive(Debug, serde::Deserialize, schemars::JsonSchema, PartialEq)]
+    struct Recipe {
+        name: String,
+        #[serde(default)]
+        ingredients: Vec<String>,
+    }
+
+    #[tokio::test]
+    async fn test_stream_object_yields_partials_then_final() {
+        let mock = MockClient::new().with_reply(MockReply::text_stream([
+            r#"{"name": "Pan"#,
+            r#"cakes", "ingredients": ["eggs"#,
+            r#"", "milk"]}"#,
+        ]));
+
+        let events: Vec<_> = mock
+            .stream_object::<Recipe>(vec![ChatMessage::user("Pancakes?").build()])
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        let partials: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                Ok(ObjectStreamEvent::Partial(recipe)) => Some(recipe.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(partials, ["Pan", "Pancakes", "Pancakes"]);
+
+        match events.last() {
+            Some(Ok(ObjectStreamEvent::Complete(result))) => {
+                assert_eq!(result.object.ingredients, ["eggs", "milk"]);
+            }
+            other => panic!("Expected Complete, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_object_stream_reports_streamed_usage() {
+        let mut response = ChatResponse::new(MessageContent::Text(r#"{"name": "Crepes"}"#.into()));
+        response.usage = Some(Usage::new(10, 5));
+        let mock = MockClient::new().with_reply(MockReply::response(response));
+
+        let mut objects = mock
+            .stream_object::<Recipe>(vec![ChatMessage::user("Crepes?").build()])
+            .await
+            .unwrap();
+        let mut last = None;
+        while let Some(event) = objects.next().await {
+            last = Some(event.unwrap());
+        }
+
+        match last {
+            Some(ObjectStreamEvent::Complete(result)) => {
+                assert_eq!(result.object.name, "Crepes");
+                assert_eq!(result.response.usage.unwrap().total_tokens, 15);
+            }
+            other => panic!("Expected Complete, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_object_stream_reads_tool_arguments() {
+        let tool_delta = |arguments: &str| ChatStreamEvent::ToolCallDelta {
+            id: "call_1".to_string(),
+            function_name: None,
+            arguments_delta: Some(arguments.to_string()),
+            index: Some(0),
+        };
+        let events = vec![
+            tool_delta(r#"{"name": "Wa"#),
+            tool_delta(r#"ffles"}"#),
+            ChatStreamEvent::StreamEnd {
+                response: ChatResponse::new(MessageContent::Text(String::new())),
+            },
+        ];
+        let stream: ChatStream = Box::pin(futures::stream::iter(events.into_iter().map(Ok)));
+
+        let mut objects = ObjectStream::<Recipe>::new(stream, OutputSchema::from_type::<Recipe>());
+        let mut last = None;
+        while let Some(event) = objects.next().await {
+            last = Some(event.unwrap());
+        }
+
+        match last {
+            Some(ObjectStreamEvent::Complete(result)) => assert_eq!(result.object.name, "Waffles"),
+            other => panic!("Expected Complete, got {other:?}"),
+        }
+    }
+}
@@ -479,6 +479,9 @@ pub mod optimization {
     }
 
     /// High-performance LRU cache for chat responses
+    ///
+    /// Keys only cover the messages. To cache a client's responses keyed on the
+    /// full request, wrap it in [`crate::cache::CacheLayer`].
     pub struct ResponseCache {
         cache: std::collections::HashMap<String, CachedResponse>,
         access_order: std::collections::VecDeque<String>,
@@ -6,11 +6,13 @@
 
 use crate::client::LlmClient;
 use crate::error::LlmError;
+use crate::middleware::{LlmMiddleware, MiddlewareClient};
 use crate::stream::ChatStream;
 use crate::traits::*;
 use crate::types::*;
 use std::any::Any;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// The main siumai LLM provider that can dynamically dispatch to different capabilities
 ///
@@ -147,6 +149,10 @@ impl Siumai {
 
 #[async_trait::async_trait]
 impl ChatCapability for Siumai {
+    fn default_model(&self) -> Option<String> {
+        self.client.default_model()
+    }
+
     async fn chat_with_tools(
         &self,
         messages: Vec<ChatMessage>,
@@ -162,6 +168,58 @@ impl ChatCapability for Siumai {
     ) -> Result<ChatStream, LlmError> {
         self.client.chat_stream(messages, tools).await
     }
+
+    async fn chat_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatResponse, LlmError> {
+        self.client.chat_with_schema(messages, schema).await
+    }
+
+    async fn chat_stream_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatStream, LlmError> {
+        self.client.chat_stream_with_schema(messages, schema).await
+    }
+
+    async fn chat_with_request(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        self.client.chat_with_request(request).await
+    }
+
+    async fn chat_stream_with_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
+        self.client.chat_stream_with_request(request).await
+    }
+}
+
+#[async_trait::async_trait]
+impl CompletionCapability for Siumai {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        if let Some(completion_client) = self.client.as_completion_capability() {
+            completion_client.complete(request).await
+        } else {
+            Err(LlmError::UnsupportedOperation(format!(
+                "Provider {} does not support text completion. Consider using OpenAI or Ollama for completions.",
+                self.client.provider_name()
+            )))
+        }
+    }
+
+    async fn complete_stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionStream, LlmError> {
+        if let Some(completion_client) = self.client.as_completion_capability() {
+            completion_client.complete_stream(request).await
+        } else {
+            Err(LlmError::UnsupportedOperation(format!(
+                "Provider {} does not support text completion. Consider using OpenAI or Ollama for completions.",
+                self.client.provider_name()
+            )))
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -234,6 +292,139 @@ impl EmbeddingCapability for Siumai {
     }
 }
 
+#[async_trait::async_trait]
+impl AudioCapability for Siumai {
+    fn supported_features(&self) -> &[AudioFeature] {
+        self.client
+            .as_audio_capability()
+            .map(|audio_client| audio_client.supported_features())
+            .unwrap_or(&[])
+    }
+
+    async fn text_to_speech(&self, request: TtsRequest) -> Result<TtsResponse, LlmError> {
+        self.audio_client()?.text_to_speech(request).await
+    }
+
+    async fn text_to_speech_stream(&self, request: TtsRequest) -> Result<AudioStream, LlmError> {
+        self.audio_client()?.text_to_speech_stream(request).await
+    }
+
+    async fn speech_to_text(&self, request: SttRequest) -> Result<SttResponse, LlmError> {
+        self.audio_client()?.speech_to_text(request).await
+    }
+
+    async fn translate_audio(
+        &self,
+        request: AudioTranslationRequest,
+    ) -> Result<SttResponse, LlmError> {
+        self.audio_client()?.translate_audio(request).await
+    }
+
+    async fn get_voices(&self) -> Result<Vec<VoiceInfo>, LlmError> {
+        self.audio_client()?.get_voices().await
+    }
+
+    async fn get_supported_languages(&self) -> Result<Vec<LanguageInfo>, LlmError> {
+        self.audio_client()?.get_supported_languages().await
+    }
+
+    fn get_supported_audio_formats(&self) -> Vec<String> {
+        self.client
+            .as_audio_capability()
+            .map(|audio_client| audio_client.get_supported_audio_formats())
+            .unwrap_or_default()
+    }
+}
+
+impl Siumai {
+    /// Get the underlying audio capability or an `UnsupportedOperation` error
+    fn audio_client(&self) -> Result<&dyn AudioCapability, LlmError> {
+        self.client.as_audio_capability().ok_or_else(|| {
+            LlmError::UnsupportedOperation(format!(
+                "Provider {} does not support audio. Consider using OpenAI or Groq for audio.",
+                self.client.provider_name()
+            ))
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl VisionCapability for Siumai {
+    async fn analyze_image(&self, request: VisionRequest) -> Result<VisionResponse, LlmError> {
+        if let Some(vision_client) = self.client.as_vision_capability() {
+            vision_client.analyze_image(request).await
+        } else {
+            Err(LlmError::UnsupportedOperation(format!(
+                "Provider {} does not support image analysis. Consider using OpenAI, Anthropic, Gemini, or Ollama for vision.",
+                self.client.provider_name()
+            )))
+        }
+    }
+
+    async fn generate_image(&self, request: ImageGenRequest) -> Result<ImageResponse, LlmError> {
+        if let Some(vision_client) = self.client.as_vision_capability() {
+            vision_client.generate_image(request).await
+        } else {
+            Err(LlmError::UnsupportedOperation(format!(
+                "Provider {} does not support image generation through the vision capability.",
+                self.client.provider_name()
+            )))
+        }
+    }
+
+    fn get_supported_input_formats(&self) -> Vec<String> {
+        self.client
+            .as_vision_capability()
+            .map(|vision_client| vision_client.get_supported_input_formats())
+            .unwrap_or_default()
+    }
+
+    fn get_supported_output_formats(&self) -> Vec<String> {
+        self.client
+            .as_vision_capability()
+            .map(|vision_client| vision_client.get_supported_output_formats())
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait::async_trait]
+impl BatchCapability for Siumai {
+    async fn create_batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResponse, LlmError> {
+        self.batch_client()?.create_batch(requests).await
+    }
+
+    async fn retrieve_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError> {
+        self.batch_client()?.retrieve_batch(batch_id).await
+    }
+
+    async fn list_batches(
+        &self,
+        query: Option<BatchListQuery>,
+    ) -> Result<BatchListResponse, LlmError> {
+        self.batch_client()?.list_batches(query).await
+    }
+
+    async fn cancel_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError> {
+        self.batch_client()?.cancel_batch(batch_id).await
+    }
+
+    async fn get_batch_results(&self, batch_id: String) -> Result<Vec<BatchResult>, LlmError> {
+        self.batch_client()?.get_batch_results(batch_id).await
+    }
+}
+
+impl Siumai {
+    /// Get the underlying batch capability or an `UnsupportedOperation` error
+    fn batch_client(&self) -> Result<&dyn BatchCapability, LlmError> {
+        self.client.as_batch_capability().ok_or_else(|| {
+            LlmError::UnsupportedOperation(format!(
+                "Provider {} does not support batch processing. Consider using OpenAI, Anthropic, or Groq for batches.",
+                self.client.provider_name()
+            ))
+        })
+    }
+}
+
 impl LlmClient for Siumai {
     fn provider_name(&self) -> &'static str {
         // We need to return a static str, so we'll use a match
@@ -263,6 +454,34 @@ impl LlmClient for Siumai {
     fn clone_box(&self) -> Box<dyn LlmClient> {
         Box::new(self.clone())
     }
+
+    fn as_completion_capability(&self) -> Option<&dyn CompletionCapability> {
+        self.client
+            .as_completion_capability()
+            .map(|_| self as &dyn CompletionCapability)
+    }
+
+    fn as_audio_capability(&self) -> Option<&dyn AudioCapability> {
+        self.client
+            .as_audio_capability()
+            .map(|_| self as &dyn AudioCapability)
+    }
+
+    fn as_vision_capability(&self) -> Option<&dyn VisionCapability> {
+        self.client
+            .as_vision_capability()
+            .map(|_| self as &dyn VisionCapability)
+    }
+
+    fn as_batch_capability(&self) -> Option<&dyn BatchCapability> {
+        self.client
+            .as_batch_capability()
+            .map(|_| self as &dyn BatchCapability)
+    }
+
+    fn as_conversation_state_capability(&self) -> Option<&dyn ConversationStateCapability> {
+        self.client.as_conversation_state_capability()
+    }
 }
 
 /// Unified Interface Builder - Provider Abstraction Layer
@@ -340,6 +559,8 @@ pub struct SiumaiBuilder {
     // Unified reasoning configuration
     reasoning_enabled: Option<bool>,
     reasoning_budget: Option<i32>,
+    // Middleware layers applied to every chat call
+    middleware: Vec<Arc<dyn LlmMiddleware>>,
 }
 
 impl SiumaiBuilder {
@@ -358,6 +579,7 @@ impl SiumaiBuilder {
             tracing_config: None,
             reasoning_enabled: None,
             reasoning_budget: None,
+            middleware: Vec::new(),
         }
     }
 
@@ -628,6 +850,58 @@ impl SiumaiBuilder {
         self.tracing(crate::tracing::TracingConfig::disabled())
     }
 
+    /// Add a middleware layer applied to every chat call.
+    ///
+    /// Layers run in the order they are added for requests and in reverse
+    /// order for responses, stream events and errors.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use siumai::middleware::LlmMiddleware;
+    /// use siumai::prelude::*;
+    ///
+    /// struct Audit;
+    ///
+    /// #[async_trait::async_trait]
+    /// impl LlmMiddleware for Audit {
+    ///     async fn on_response(&self, response: &mut ChatResponse) -> Result<(), LlmError> {
+    ///         println!("usage: {:?}", response.usage);
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// # async fn example() -> Result<(), LlmError> {
+    /// let client = Siumai::builder()
+    ///     .openai()
+    ///     .api_key("your-key")
+    ///     .layer(Audit)
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn layer<M: LlmMiddleware + 'static>(mut self, middleware: M) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Throttle requests through a client-side rate limiter.
+    ///
+    /// The limiter is shared through the `Arc`, so clients built with the same
+    /// limiter draw from the same request and token budgets.
+    pub fn rate_limiter(mut self, limiter: Arc<crate::rate_limiter::RateLimiter>) -> Self {
+        self.http_config.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Record or replay every request through a cassette.
+    ///
+    /// See [`crate::cassette`] for the file format and request matching.
+    pub fn cassette(mut self, cassette: Arc<crate::cassette::Cassette>) -> Self {
+        self.http_config.cassette = Some(cassette);
+        self
+    }
+
     /// Build the siumai provider
     pub async fn build(self) -> Result<Siumai, LlmError> {
         // Extract all needed values first to avoid borrow checker issues
@@ -657,6 +931,7 @@ impl SiumaiBuilder {
         let reasoning_enabled = self.reasoning_enabled;
         let reasoning_budget = self.reasoning_budget;
         let http_config = self.http_config.clone();
+        let middleware = self.middleware.clone();
 
         // Prepare common parameters with the correct model
         let mut common_params = self.common_params.clone();
@@ -801,6 +1076,9 @@ impl SiumaiBuilder {
                     config = config.with_project(proj);
                 }
 
+                config.http_config.rate_limiter = http_config.rate_limiter.clone();
+                config.http_config.cassette = http_config.cassette.clone();
+
                 let http_client = reqwest::Client::new();
                 Box::new(crate::providers::openai::OpenAiClient::new(
                     config,
@@ -857,6 +1135,13 @@ impl SiumaiBuilder {
                     builder = builder.thinking_budget(budget as i32);
                 }
 
+                if let Some(limiter) = &http_config.rate_limiter {
+                    builder = builder.rate_limiter(limiter.clone());
+                }
+                if let Some(cassette) = &http_config.cassette {
+                    builder = builder.cassette(cassette.clone());
+                }
+
                 Box::new(builder.build().await.map_err(|e| {
                     LlmError::ConfigurationError(format!("Failed to build Gemini client: {e}"))
                 })?)
@@ -881,6 +1166,13 @@ impl SiumaiBuilder {
                     builder = builder.top_p(top_p);
                 }
 
+                if let Some(limiter) = &http_config.rate_limiter {
+                    builder = builder.rate_limiter(limiter.clone());
+                }
+                if let Some(cassette) = &http_config.cassette {
+                    builder = builder.cassette(cassette.clone());
+                }
+
                 Box::new(builder.build().await.map_err(|e| {
                     LlmError::ConfigurationError(format!("Failed to build xAI client: {e}"))
                 })?)
@@ -929,6 +1221,9 @@ impl SiumaiBuilder {
                     config = config.with_max_tokens(max_tokens);
                 }
 
+                config.http_config.rate_limiter = http_config.rate_limiter.clone();
+                config.http_config.cassette = http_config.cassette.clone();
+
                 let http_client = reqwest::Client::new();
                 Box::new(crate::providers::groq::GroqClient::new(config, http_client))
             }
@@ -938,6 +1233,7 @@ impl SiumaiBuilder {
                     "deepseek" => {
                         // Use OpenAI-compatible client for DeepSeek
                         let mut config = crate::providers::openai::OpenAiConfig::new(api_key)
+                            .with_provider_id("deepseek")
                             .with_base_url(
                                 base_url.unwrap_or_else(|| "https://api.deepseek.com".to_string()),
                             )
@@ -951,6 +1247,9 @@ impl SiumaiBuilder {
                             config = config.with_max_tokens(max_tokens);
                         }
 
+                        config.http_config.rate_limiter = http_config.rate_limiter.clone();
+                        config.http_config.cassette = http_config.cassette.clone();
+
                         let http_client = reqwest::Client::new();
                         Box::new(crate::providers::openai::OpenAiClient::new(
                             config,
@@ -961,6 +1260,7 @@ impl SiumaiBuilder {
                     "openrouter" => {
                         // Use OpenAI-compatible client for OpenRouter
                         let mut config = crate::providers::openai::OpenAiConfig::new(api_key)
+                            .with_provider_id("openrouter")
                             .with_base_url(
                                 base_url
                                     .unwrap_or_else(|| "https://openrouter.ai/api/v1".to_string()),
@@ -975,6 +1275,9 @@ impl SiumaiBuilder {
                             config = config.with_max_tokens(max_tokens);
                         }
 
+                        config.http_config.rate_limiter = http_config.rate_limiter.clone();
+                        config.http_config.cassette = http_config.cassette.clone();
+
                         let http_client = reqwest::Client::new();
                         Box::new(crate::providers::openai::OpenAiClient::new(
                             config,
@@ -1029,7 +1332,20 @@ impl SiumaiBuilder {
             }
         };
 
-        Ok(Siumai::new(client))
+        if middleware.is_empty() {
+            return Ok(Siumai::new(client));
+        }
+
+        let mut wrapped = MiddlewareClient::from_arc(Arc::<dyn LlmClient>::from(client))
+            .with_common_params(common_params);
+        if let Some(params) = provider_params {
+            wrapped = wrapped.with_provider_params(params);
+        }
+        let wrapped = middleware
+            .into_iter()
+            .fold(wrapped, MiddlewareClient::layer_arc);
+
+        Ok(Siumai::new(Box::new(wrapped)))
     }
 }
 
@@ -1076,14 +1392,52 @@ impl<'a> AudioCapabilityProxy<'a> {
         }
     }
 
-    /// Placeholder for future audio operations
+    /// Convert text to speech
     ///
     /// This will attempt the operation regardless of reported support.
     /// Actual errors will come from the API if the model doesn't support it.
+    pub async fn text_to_speech(&self, request: TtsRequest) -> Result<TtsResponse, LlmError> {
+        AudioCapability::text_to_speech(self.provider, request).await
+    }
+
+    /// Convert text to speech, streaming audio chunks as they are generated
+    pub async fn text_to_speech_stream(
+        &self,
+        request: TtsRequest,
+    ) -> Result<AudioStream, LlmError> {
+        AudioCapability::text_to_speech_stream(self.provider, request).await
+    }
+
+    /// Transcribe speech to text
+    pub async fn speech_to_text(&self, request: SttRequest) -> Result<SttResponse, LlmError> {
+        AudioCapability::speech_to_text(self.provider, request).await
+    }
+
+    /// Translate speech to English text
+    pub async fn translate_audio(
+        &self,
+        request: AudioTranslationRequest,
+    ) -> Result<SttResponse, LlmError> {
+        AudioCapability::translate_audio(self.provider, request).await
+    }
+
+    /// Get available voices
+    pub async fn get_voices(&self) -> Result<Vec<VoiceInfo>, LlmError> {
+        AudioCapability::get_voices(self.provider).await
+    }
+
+    /// Get the audio features supported by the underlying provider
+    pub fn supported_features(&self) -> &[AudioFeature] {
+        AudioCapability::supported_features(self.provider)
+    }
+
+    /// Placeholder for future audio operations (deprecated, use text_to_speech() and friends instead)
+    #[deprecated(note = "Use text_to_speech(), speech_to_text() or translate_audio() instead")]
     pub async fn placeholder_operation(&self) -> Result<String, LlmError> {
         // No automatic warnings - let the user decide if they want to check support
         Err(LlmError::UnsupportedOperation(
-            "Audio operations not yet implemented. Use provider-specific client.".to_string(),
+            "Use text_to_speech(), speech_to_text() or translate_audio() instead of placeholder_operation()"
+                .to_string(),
         ))
     }
 }
@@ -1193,11 +1547,30 @@ impl<'a> VisionCapabilityProxy<'a> {
         }
     }
 
-    /// Placeholder for future vision operations
+    /// Analyze one or more images with a text prompt
+    pub async fn analyze_image(&self, request: VisionRequest) -> Result<VisionResponse, LlmError> {
+        VisionCapability::analyze_image(self.provider, request).await
+    }
+
+    /// Generate images from a text prompt
+    pub async fn generate_image(
+        &self,
+        request: ImageGenRequest,
+    ) -> Result<ImageResponse, LlmError> {
+        VisionCapability::generate_image(self.provider, request).await
+    }
+
+    /// Get supported image formats for input
+    pub fn supported_input_formats(&self) -> Vec<String> {
+        VisionCapability::get_supported_input_formats(self.provider)
+    }
+
+    /// Placeholder for future vision operations (deprecated, use analyze_image() instead)
+    #[deprecated(note = "Use analyze_image() method instead")]
     pub async fn placeholder_operation(&self) -> Result<String, LlmError> {
         // No automatic warnings - let the user decide if they want to check support
         Err(LlmError::UnsupportedOperation(
-            "Vision operations not yet implemented. Use provider-specific client.".to_string(),
+            "Use analyze_image() method instead of placeholder_operation()".to_string(),
         ))
     }
 }
@@ -1288,6 +1661,8 @@ mod tests {
                 finish_reason: Some(FinishReason::Stop),
                 tool_calls: None,
                 thinking: None,
+                citations: Vec::new(),
+                sources: Vec::new(),
                 metadata: std::collections::HashMap::new(),
             })
         }
@@ -1369,6 +1744,62 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_audio_capability_proxy_unsupported() {
+        let siumai = Siumai::new(Box::new(MockProvider));
+
+        assert!(siumai.as_audio_capability().is_none());
+        let proxy = siumai.audio_capability();
+        assert!(proxy.supported_features().is_empty());
+
+        let result = proxy
+            .text_to_speech(TtsRequest::new("Hello".to_string()))
+            .await;
+        assert!(matches!(result, Err(LlmError::UnsupportedOperation(_))));
+    }
+
+    #[cfg(feature = "groq")]
+    #[tokio::test]
+    async fn test_groq_audio_through_siumai() {
+        let siumai = SiumaiBuilder::new()
+            .groq()
+            .api_key("test-key")
+            .model("llama-3.3-70b-versatile")
+            .build()
+            .await
+            .unwrap();
+
+        assert!(siumai.as_audio_capability().is_some());
+        let features = siumai.audio_capability().supported_features().to_vec();
+        assert!(features.contains(&AudioFeature::SpeechToText));
+        assert!(features.contains(&AudioFeature::TextToSpeech));
+    }
+
+    #[cfg(feature = "groq")]
+    #[tokio::test]
+    async fn test_builder_layer_keeps_provider_client() {
+        struct Noop;
+        impl LlmMiddleware for Noop {}
+
+        let siumai = SiumaiBuilder::new()
+            .groq()
+            .api_key("test-key")
+            .layer(Noop)
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(siumai.metadata().provider_name, "groq");
+        assert!(siumai.as_audio_capability().is_some());
+        assert!(
+            siumai
+                .client()
+                .as_any()
+                .downcast_ref::<crate::providers::groq::GroqClient>()
+                .is_some()
+        );
+    }
+
     #[tokio::test]
     async fn test_ollama_build_without_api_key() {
         // Test that Ollama can be built without API key
@@ -0,0 +1,481 @@
+//! Anthropic Message Batches Implementation
+//!
+//! Implements the `BatchCapability` trait for the Anthropic Message Batches API.
+//! Requests are submitted inline; results are downloaded as JSONL from the
+//! batch's `results_url`.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::error::LlmError;
+use crate::rate_limiter::send_request;
+use crate::traits::BatchCapability;
+use crate::types::*;
+
+use super::chat::AnthropicChatCapability;
+use super::types::*;
+use super::utils::*;
+
+/// Anthropic message batch object
+#[derive(Debug, Clone, Deserialize)]
+struct AnthropicMessageBatch {
+    id: String,
+    processing_status: String,
+    request_counts: AnthropicBatchRequestCounts,
+    created_at: Option<String>,
+    ended_at: Option<String>,
+    expires_at: Option<String>,
+    cancel_initiated_at: Option<String>,
+    archived_at: Option<String>,
+    results_url: Option<String>,
+}
+
+/// Anthropic batch request counts
+#[derive(Debug, Clone, Default, Deserialize)]
+struct AnthropicBatchRequestCounts {
+    processing: u32,
+    succeeded: u32,
+    errored: u32,
+    canceled: u32,
+    expired: u32,
+}
+
+/// Anthropic batch list response
+#[derive(Debug, Clone, Deserialize)]
+struct AnthropicMessageBatchList {
+    data: Vec<AnthropicMessageBatch>,
+    has_more: bool,
+    last_id: Option<String>,
+}
+
+/// A request in the batch creation body
+#[derive(Debug, Serialize)]
+struct AnthropicBatchRequestItem<'a> {
+    custom_id: &'a str,
+    params: serde_json::Value,
+}
+
+/// A line of the batch results file
+#[derive(Debug, Deserialize)]
+struct AnthropicBatchResultLine {
+    custom_id: String,
+    result: AnthropicBatchResultBody,
+}
+
+/// The outcome of a single batch request
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicBatchResultBody {
+    Succeeded { message: AnthropicChatResponse },
+    Errored { error: serde_json::Value },
+    Canceled,
+    Expired,
+}
+
+/// Parse an RFC 3339 timestamp into Unix seconds
+fn parse_timestamp(timestamp: Option<&str>) -> Option<u64> {
+    timestamp
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.timestamp() as u64)
+}
+
+/// Anthropic Message Batches Implementation
+///
+/// API Reference: <https://docs.anthropic.com/en/api/creating-message-batches>
+#[derive(Clone)]
+pub struct AnthropicBatches {
+    chat_capability: AnthropicChatCapability,
+    specific_params: AnthropicSpecificParams,
+}
+
+impl AnthropicBatches {
+    /// Create a new Anthropic batches instance
+    pub const fn new(
+        chat_capability: AnthropicChatCapability,
+        specific_params: AnthropicSpecificParams,
+    ) -> Self {
+        Self {
+            chat_capability,
+            specific_params,
+        }
+    }
+
+    /// Build the batch creation body
+    fn build_create_body(&self, requests: &[BatchRequest]) -> Result<serde_json::Value, LlmError> {
+        if requests.is_empty() {
+            return Err(LlmError::InvalidInput(
+                "A batch must contain at least one request".to_string(),
+            ));
+        }
+
+        let items = requests
+            .iter()
+            .map(|request| {
+                let params = self.chat_capability.build_chat_request_body(
+                    &request.resolve(&self.chat_capability.common_params),
+                    Some(&self.specific_params),
+                )?;
+                Ok(AnthropicBatchRequestItem {
+                    custom_id: &request.custom_id,
+                    params,
+                })
+            })
+            .collect::<Result<Vec<_>, LlmError>>()?;
+
+        Ok(serde_json::json!({ "requests": items }))
+    }
+
+    /// Parse the results file
+    fn parse_results(&self, content: &str) -> Result<Vec<BatchResult>, LlmError> {
+        let mut results = Vec::new();
+
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            let line: AnthropicBatchResultLine = serde_json::from_str(line)?;
+            let result = match line.result {
+                AnthropicBatchResultBody::Succeeded { message } => {
+                    self.chat_capability.parse_chat_response(message)
+                }
+                AnthropicBatchResultBody::Errored { error } => {
+                    let error_obj = error.get("error").unwrap_or(&error);
+                    Err(LlmError::ProviderError {
+                        provider: "anthropic".to_string(),
+                        message: error_obj
+                            .get("message")
+                            .and_then(|m| m.as_str())
+                            .unwrap_or("Batch request failed")
+                            .to_string(),
+                        error_code: error_obj
+                            .get("type")
+                            .and_then(|t| t.as_str())
+                            .map(str::to_string),
+                    })
+                }
+                AnthropicBatchResultBody::Canceled => Err(LlmError::ProviderError {
+                    provider: "anthropic".to_string(),
+                    message: "Batch request was canceled".to_string(),
+                    error_code: Some("canceled".to_string()),
+                }),
+                AnthropicBatchResultBody::Expired => Err(LlmError::ProviderError {
+                    provider: "anthropic".to_string(),
+                    message: "Batch request expired".to_string(),
+                    error_code: Some("expired".to_string()),
+                }),
+            };
+            results.push(BatchResult {
+                custom_id: line.custom_id,
+                result,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Convert an Anthropic message batch to our BatchResponse
+    fn convert_batch(&self, batch: AnthropicMessageBatch) -> Result<BatchResponse, LlmError> {
+        let status = match batch.processing_status.as_str() {
+            "ended" if batch.cancel_initiated_at.is_some() => BatchStatus::Cancelled,
+            other => other.parse()?,
+        };
+
+        let counts = batch.request_counts;
+        let request_counts = BatchRequestCounts {
+            total: counts.processing
+                + counts.succeeded
+                + counts.errored
+                + counts.canceled
+                + counts.expired,
+            completed: counts.succeeded,
+            failed: counts.errored + counts.canceled + counts.expired,
+        };
+
+        let mut metadata = HashMap::new();
+        if let Some(archived_at) = batch.archived_at {
+            metadata.insert(
+                "archived_at".to_string(),
+                serde_json::Value::String(archived_at),
+            );
+        }
+        if let Some(cancel_initiated_at) = batch.cancel_initiated_at {
+            metadata.insert(
+                "cancel_initiated_at".to_string(),
+                serde_json::Value::String(cancel_initiated_at),
+            );
+        }
+
+        Ok(BatchResponse {
+            id: batch.id,
+            status,
+            request_counts,
+            created_at: parse_timestamp(batch.created_at.as_deref()),
+            ended_at: parse_timestamp(batch.ended_at.as_deref()),
+            expires_at: parse_timestamp(batch.expires_at.as_deref()),
+            output_file_id: None,
+            error_file_id: None,
+            results_url: batch.results_url,
+            metadata,
+        })
+    }
+
+    /// Send a request with authentication headers and return the successful response
+    async fn send(
+        &self,
+        request_builder: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, LlmError> {
+        let headers = build_headers(
+            &self.chat_capability.api_key,
+            &self.chat_capability.http_config.headers,
+        )?;
+        let response = send_request(
+            request_builder.headers(headers),
+            self.chat_capability.http_config.rate_limiter.as_deref(),
+            self.chat_capability.http_config.cassette.as_deref(),
+            "anthropic",
+            "",
+            0,
+        )
+        .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+
+            if let Ok(error_json) = serde_json::from_str::<serde_json::Value>(&error_text)
+                && let Some(error_obj) = error_json.get("error")
+            {
+                let error_type = error_obj
+                    .get("type")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or("unknown");
+                let error_message = error_obj
+                    .get("message")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or("Unknown error");
+
+                return Err(map_anthropic_error(
+                    status.as_u16(),
+                    error_type,
+                    error_message,
+                    error_json.clone(),
+                ));
+            }
+
+            return Err(LlmError::ApiError {
+                code: status.as_u16(),
+                message: format!("Anthropic API error: {error_text}"),
+                details: serde_json::from_str(&error_text).ok(),
+            });
+        }
+
+        Ok(response)
+    }
+
+    fn url(&self, path: &str) -> String {
+        crate::utils::url::join_url(&self.chat_capability.base_url, path)
+    }
+}
+
+#[async_trait]
+impl BatchCapability for AnthropicBatches {
+    async fn create_batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResponse, LlmError> {
+        let body = self.build_create_body(&requests)?;
+        let request_builder = self
+            .chat_capability
+            .http_client
+            .post(self.url("v1/messages/batches"))
+            .json(&body);
+
+        let batch: AnthropicMessageBatch = self.send(request_builder).await?.json().await?;
+        self.convert_batch(batch)
+    }
+
+    async fn retrieve_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError> {
+        let request_builder = self
+            .chat_capability
+            .http_client
+            .get(self.url(&format!("v1/messages/batches/{batch_id}")));
+
+        let batch: AnthropicMessageBatch = self.send(request_builder).await?.json().await?;
+        self.convert_batch(batch)
+    }
+
+    async fn list_batches(
+        &self,
+        query: Option<BatchListQuery>,
+    ) -> Result<BatchListResponse, LlmError> {
+        let mut request_builder = self
+            .chat_capability
+            .http_client
+            .get(self.url("v1/messages/batches"));
+        if let Some(q) = query {
+            if let Some(limit) = q.limit {
+                request_builder = request_builder.query(&[("limit", limit.to_string())]);
+            }
+            if let Some(after) = q.after {
+                request_builder = request_builder.query(&[("after_id", after)]);
+            }
+        }
+
+        let list: AnthropicMessageBatchList = self.send(request_builder).await?.json().await?;
+
+        Ok(BatchListResponse {
+            batches: list
+                .data
+                .into_iter()
+                .map(|batch| self.convert_batch(batch))
+                .collect::<Result<_, _>>()?,
+            has_more: list.has_more,
+            next_cursor: list.last_id,
+        })
+    }
+
+    async fn cancel_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError> {
+        let request_builder = self
+            .chat_capability
+            .http_client
+            .post(self.url(&format!("v1/messages/batches/{batch_id}/cancel")));
+
+        let batch: AnthropicMessageBatch = self.send(request_builder).await?.json().await?;
+        self.convert_batch(batch)
+    }
+
+    async fn get_batch_results(&self, batch_id: String) -> Result<Vec<BatchResult>, LlmError> {
+        let batch = self.retrieve_batch(batch_id.clone()).await?;
+        let results_url = batch.results_url.ok_or_else(|| {
+            LlmError::InvalidInput(format!(
+                "Batch {batch_id} has no results yet (status: {:?})",
+                batch.status
+            ))
+        })?;
+
+        let request_builder = self.chat_capability.http_client.get(&results_url);
+        let content = self.send(request_builder).await?.text().await?;
+        self.parse_results(&content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_batches() -> AnthropicBatches {
+        let chat_capability = AnthropicChatCapability::new(
+            "test-key".to_string(),
+            "https://api.anthropic.com".to_string(),
+            reqwest::Client::new(),
+            HttpConfig::default(),
+            AnthropicSpecificParams::default(),
+            CommonParams {
+                model: "claude-3-5-haiku-20241022".to_string(),
+                max_tokens: Some(1024),
+                ..Default::default()
+            },
+        );
+        AnthropicBatches::new(chat_capability, AnthropicSpecificParams::default())
+    }
+
+    #[test]
+    fn test_build_create_body() {
+        let batches = create_test_batches();
+        let requests = vec![BatchRequest::new(
+            "req-1",
+            vec![
+                ChatMessage::system("Be brief").build(),
+                ChatMessage::user("Hello").build(),
+            ],
+        )];
+
+        let body = batches.build_create_body(&requests).unwrap();
+        let item = &body["requests"][0];
+
+        assert_eq!(item["custom_id"], "req-1");
+        assert_eq!(item["params"]["model"], "claude-3-5-haiku-20241022");
+        assert_eq!(item["params"]["system"], "Be brief");
+        assert!(item["params"].get("stream").is_none());
+    }
+
+    #[test]
+    fn test_convert_batch() {
+        let batches = create_test_batches();
+        let batch: AnthropicMessageBatch = serde_json::from_value(serde_json::json!({
+            "id": "msgbatch_123",
+            "type": "message_batch",
+            "processing_status": "ended",
+            "request_counts": {
+                "processing": 0,
+                "succeeded": 3,
+                "errored": 1,
+                "canceled": 0,
+                "expired": 0
+            },
+            "created_at": "2024-09-24T18:37:24.100435Z",
+            "ended_at": "2024-09-24T18:40:00Z",
+            "expires_at": "2024-09-25T18:37:24.100435Z",
+            "cancel_initiated_at": null,
+            "archived_at": null,
+            "results_url": "https://api.anthropic.com/v1/messages/batches/msgbatch_123/results"
+        }))
+        .unwrap();
+
+        let batch = batches.convert_batch(batch).unwrap();
+
+        assert_eq!(batch.status, BatchStatus::Completed);
+        assert_eq!(batch.request_counts.total, 4);
+        assert_eq!(batch.request_counts.failed, 1);
+        assert_eq!(batch.created_at, Some(1_727_203_044));
+        assert!(batch.results_url.is_some());
+    }
+
+    #[test]
+    fn test_parse_results() {
+        let batches = create_test_batches();
+        let content = [
+            serde_json::json!({
+                "custom_id": "req-1",
+                "result": {
+                    "type": "succeeded",
+                    "message": {
+                        "id": "msg_1",
+                        "type": "message",
+                        "role": "assistant",
+                        "content": [{"type": "text", "text": "Hello!"}],
+                        "model": "claude-3-5-haiku-20241022",
+                        "stop_reason": "end_turn",
+                        "stop_sequence": null,
+                        "usage": {"input_tokens": 10, "output_tokens": 2}
+                    }
+                }
+            }),
+            serde_json::json!({
+                "custom_id": "req-2",
+                "result": {
+                    "type": "errored",
+                    "error": {
+                        "type": "error",
+                        "error": {"type": "invalid_request_error", "message": "Bad request"}
+                    }
+                }
+            }),
+            serde_json::json!({"custom_id": "req-3", "result": {"type": "expired"}}),
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        let results = batches.parse_results(&content).unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[0].result.as_ref().unwrap().content_text(),
+            Some("Hello!")
+        );
+        match &results[1].result {
+            Err(LlmError::ProviderError { error_code, .. }) => {
+                assert_eq!(error_code.as_deref(), Some("invalid_request_error"));
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+        assert!(!results[2].is_success());
+    }
+}
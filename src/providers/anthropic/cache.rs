@@ -182,11 +182,7 @@ impl CacheAwareMessageBuilder {
                         ContentPart::Image { image_url, detail } => {
                             let mut image_part = serde_json::json!({
                                 "type": "image",
-                                "source": {
-                                    "type": "base64",
-                                    "media_type": "image/jpeg", // Default, should be detected
-                                    "data": image_url
-                                }
+                                "source": super::utils::convert_image_source(image_url)
                             });
                             if let Some(detail) = detail {
                                 image_part["detail"] = serde_json::Value::String(detail.clone());
@@ -203,6 +199,19 @@ impl CacheAwareMessageBuilder {
                                 }
                             }));
                         }
+                        ContentPart::Document {
+                            source,
+                            media_type,
+                            title,
+                            citations,
+                        } => {
+                            content_parts.push(super::utils::convert_document(
+                                source,
+                                media_type,
+                                title.as_deref(),
+                                *citations,
+                            ));
+                        }
                     }
                 }
                 message_json["content"] = serde_json::Value::Array(content_parts);
@@ -8,6 +8,7 @@ use std::time::Instant;
 
 use crate::error::LlmError;
 use crate::params::{AnthropicParameterMapper, ParameterMapper};
+use crate::rate_limiter::{estimate_request_tokens, send_request};
 use crate::stream::{ChatStream, ChatStreamEvent};
 use crate::tracing::ProviderTracer;
 use crate::traits::ChatCapability;
@@ -98,6 +99,12 @@ impl AnthropicChatCapability {
             body["tools"] = serde_json::Value::Array(anthropic_tools);
         }
 
+        // Add the server-side web search tool
+        apply_web_search(&mut body, request);
+
+        // Translate prompt caching into cache_control breakpoints
+        apply_prompt_cache(&mut body, request);
+
         // Add streaming if enabled
         if request.stream {
             body["stream"] = serde_json::Value::Bool(true);
@@ -141,6 +148,10 @@ impl AnthropicChatCapability {
             );
         }
 
+        let citations = parse_citations(&response.content);
+        let mut sources = parse_search_sources(&response.content);
+        SearchSource::merge(&mut sources, SearchSource::from_citations(&citations));
+
         Ok(ChatResponse {
             id: Some(response.id),
             content,
@@ -149,6 +160,8 @@ impl AnthropicChatCapability {
             finish_reason,
             tool_calls,
             thinking: extract_thinking_content(&response.content),
+            citations,
+            sources,
             metadata: provider_data,
         })
     }
@@ -172,26 +185,31 @@ impl ChatCapability for AnthropicChatCapability {
             http_config: None,
             web_search: None,
             stream: false,
+            prompt_cache: None,
+            output_schema: None,
         };
 
         // Extract model name for tracing
         let model = request.common_params.model.clone();
         let tracer = ProviderTracer::new("anthropic").with_model(model);
 
-        let headers = build_headers(&self.api_key, &self.http_config.headers)?;
+        let mut headers = build_headers(&self.api_key, &self.http_config.headers)?;
         let body = self.build_chat_request_body(&request, Some(&self.anthropic_params))?;
+        add_files_api_beta(&mut headers, &body);
         let url = crate::utils::url::join_url(&self.base_url, "v1/messages");
 
         tracer.trace_request_start("POST", &url);
         tracer.trace_request_details(&headers, &body);
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "anthropic",
+            body["model"].as_str().unwrap_or_default(),
+            estimate_request_tokens(&body),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -256,8 +274,17 @@ impl ChatCapability for AnthropicChatCapability {
             http_config: None,
             web_search: None,
             stream: true,
+            prompt_cache: None,
+            output_schema: None,
         };
 
+        self.chat_stream_request(request).await
+    }
+}
+
+impl AnthropicChatCapability {
+    /// Chat stream with a `ChatRequest`
+    pub async fn chat_stream_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
         // Use the new streaming infrastructure
         let anthropic_params = crate::params::AnthropicParams::default();
         let streaming = super::streaming::AnthropicStreaming::new(
@@ -269,9 +296,7 @@ impl ChatCapability for AnthropicChatCapability {
         );
         streaming.create_chat_stream(request).await
     }
-}
 
-impl AnthropicChatCapability {
     /// Parse SSE event from Anthropic streaming response (original method for complete lines)
     pub fn parse_sse_event(chunk: &str) -> Option<Result<ChatStreamEvent, LlmError>> {
         for line in chunk.lines() {
@@ -344,6 +369,11 @@ impl AnthropicChatCapability {
                                 // Handle signature delta (for thinking mode)
                                 Some(Ok(ChatStreamEvent::ThinkingDelta { delta: signature }))
                             }
+                            AnthropicDelta::CitationsDelta { citation } => {
+                                Some(Ok(ChatStreamEvent::CitationDelta {
+                                    citation: convert_citation(&citation, None),
+                                }))
+                            }
                         }
                     }
                     Err(e) => Some(Err(LlmError::ParseError(format!(
@@ -10,8 +10,10 @@ use crate::params::AnthropicParams;
 use crate::stream::ChatStream;
 use crate::traits::*;
 use crate::types::*;
+use crate::web_search::{WebSearchCapability, WebSearchProvider};
 
 use super::chat::AnthropicChatCapability;
+use super::files::AnthropicFiles;
 use super::models::AnthropicModels;
 use super::types::AnthropicSpecificParams;
 use super::utils::get_default_models;
@@ -23,6 +25,8 @@ pub struct AnthropicClient {
     chat_capability: AnthropicChatCapability,
     /// Models capability implementation
     models_capability: AnthropicModels,
+    /// Files capability implementation
+    files_capability: AnthropicFiles,
     /// Common parameters
     common_params: CommonParams,
     /// Anthropic-specific parameters
@@ -40,6 +44,7 @@ impl Clone for AnthropicClient {
         Self {
             chat_capability: self.chat_capability.clone(),
             models_capability: self.models_capability.clone(),
+            files_capability: self.files_capability.clone(),
             common_params: self.common_params.clone(),
             anthropic_params: self.anthropic_params.clone(),
             specific_params: self.specific_params.clone(),
@@ -70,11 +75,19 @@ impl AnthropicClient {
             common_params.clone(),
         );
 
-        let models_capability = AnthropicModels::new(api_key, base_url, http_client, http_config);
+        let models_capability = AnthropicModels::new(
+            api_key.clone(),
+            base_url.clone(),
+            http_client.clone(),
+            http_config.clone(),
+        );
+
+        let files_capability = AnthropicFiles::new(api_key, base_url, http_client, http_config);
 
         Self {
             chat_capability,
             models_capability,
+            files_capability,
             common_params,
             anthropic_params,
             specific_params,
@@ -98,6 +111,14 @@ impl AnthropicClient {
         &self.chat_capability
     }
 
+    /// Create a Message Batches capability using the client's configuration
+    pub fn batches(&self) -> super::batches::AnthropicBatches {
+        super::batches::AnthropicBatches::new(
+            self.chat_capability.clone(),
+            self.specific_params.clone(),
+        )
+    }
+
     /// Set the tracing guard to keep tracing system active
     pub(crate) fn set_tracing_guard(
         &mut self,
@@ -163,6 +184,10 @@ impl AnthropicClient {
 
 #[async_trait]
 impl ChatCapability for AnthropicClient {
+    fn default_model(&self) -> Option<String> {
+        Some(self.common_params.model.clone()).filter(|model| !model.is_empty())
+    }
+
     async fn chat_with_tools(
         &self,
         messages: Vec<ChatMessage>,
@@ -177,25 +202,160 @@ impl ChatCapability for AnthropicClient {
             http_config: None,
             web_search: None,
             stream: false,
+            prompt_cache: None,
+            output_schema: None,
         };
 
-        let headers = super::utils::build_headers(
+        self.send_chat_request(request).await
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+    ) -> Result<ChatStream, LlmError> {
+        // Now that AnthropicChatCapability has the correct common_params, we can use the trait method directly
+        self.chat_capability.chat_stream(messages, tools).await
+    }
+
+    /// Chat with structured output via forced tool use
+    ///
+    /// The schema is exposed as a single tool that the model must call; the
+    /// tool input is returned as the response content.
+    async fn chat_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatResponse, LlmError> {
+        self.chat_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    /// Stream structured output via forced tool use
+    ///
+    /// The output arrives as argument deltas of the schema tool call.
+    async fn chat_stream_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatStream, LlmError> {
+        self.chat_stream_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    async fn chat_with_request(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        let request = ChatRequest {
+            common_params: self.common_params.merged_with(&request.common_params),
+            http_config: None,
+            stream: false,
+            ..request
+        };
+        match request.output_schema.clone() {
+            Some(schema) => {
+                let response = self
+                    .send_chat_request(Self::with_schema_tool(request, &schema))
+                    .await?;
+                Self::schema_output(response, &schema)
+            }
+            None => self.send_chat_request(request).await,
+        }
+    }
+
+    async fn chat_stream_with_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
+        let request = ChatRequest {
+            common_params: self.common_params.merged_with(&request.common_params),
+            http_config: None,
+            stream: true,
+            ..request
+        };
+        let request = match request.output_schema.clone() {
+            Some(schema) => Self::with_schema_tool(request, &schema),
+            None => request,
+        };
+        self.chat_capability.chat_stream_request(request).await
+    }
+}
+
+impl AnthropicClient {
+    /// Force the model to call a tool whose input is the output schema
+    fn with_schema_tool(request: ChatRequest, schema: &OutputSchema) -> ChatRequest {
+        let description = schema
+            .description
+            .clone()
+            .unwrap_or_else(|| "Respond with the structured output".to_string());
+        let tool = Tool::function(schema.name.clone(), description, schema.schema.clone());
+        let tool_choice = ProviderParams::new().with_param(
+            "tool_choice",
+            serde_json::json!({ "type": "tool", "name": schema.name }),
+        );
+
+        ChatRequest {
+            tools: Some(vec![tool]),
+            provider_params: ProviderParams::overlay(
+                request.provider_params.as_ref(),
+                Some(&tool_choice),
+            ),
+            output_schema: None,
+            ..request
+        }
+    }
+
+    /// Move the schema tool input into the response content
+    fn schema_output(
+        mut response: ChatResponse,
+        schema: &OutputSchema,
+    ) -> Result<ChatResponse, LlmError> {
+        let arguments = response
+            .tool_calls
+            .as_ref()
+            .and_then(|calls| {
+                calls
+                    .iter()
+                    .filter_map(|call| call.function.as_ref())
+                    .find(|function| function.name == schema.name)
+            })
+            .map(|function| function.arguments.clone())
+            .ok_or_else(|| {
+                LlmError::ParseError(
+                    "Anthropic response did not include the output tool call".to_string(),
+                )
+            })?;
+
+        response.content = MessageContent::Text(arguments);
+        response.tool_calls = None;
+        response.finish_reason = Some(FinishReason::Stop);
+        Ok(response)
+    }
+
+    /// Send a prepared chat request to the Messages API.
+    ///
+    /// Unlike the `ChatCapability` methods, this honors the request's own
+    /// `web_search` and `prompt_cache` settings.
+    pub async fn send_chat_request(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        let mut headers = super::utils::build_headers(
             &self.chat_capability.api_key,
             &self.chat_capability.http_config.headers,
         )?;
         let body = self
             .chat_capability
             .build_chat_request_body(&request, Some(&self.specific_params))?;
+        super::utils::add_files_api_beta(&mut headers, &body);
         let url = format!("{}/v1/messages", self.chat_capability.base_url);
 
-        let response = self
-            .chat_capability
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let http_config = &self.chat_capability.http_config;
+        let response = crate::rate_limiter::send_request(
+            self.chat_capability
+                .http_client
+                .post(&url)
+                .headers(headers)
+                .json(&body),
+            http_config.rate_limiter.as_deref(),
+            http_config.cassette.as_deref(),
+            "anthropic",
+            body["model"].as_str().unwrap_or_default(),
+            crate::rate_limiter::estimate_request_tokens(&body),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -212,13 +372,150 @@ impl ChatCapability for AnthropicClient {
         self.chat_capability.parse_chat_response(anthropic_response)
     }
 
-    async fn chat_stream(
+    /// Count the input tokens of a conversation with the count-tokens endpoint
+    ///
+    /// API Reference: <https://docs.anthropic.com/en/api/messages-count-tokens>
+    pub async fn count_tokens(
         &self,
-        messages: Vec<ChatMessage>,
-        tools: Option<Vec<Tool>>,
-    ) -> Result<ChatStream, LlmError> {
-        // Now that AnthropicChatCapability has the correct common_params, we can use the trait method directly
-        self.chat_capability.chat_stream(messages, tools).await
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+    ) -> Result<u32, LlmError> {
+        let request = ChatRequest {
+            messages: messages.to_vec(),
+            tools: tools.map(<[Tool]>::to_vec),
+            common_params: self.common_params.clone(),
+            ..Default::default()
+        };
+        let mut body = self
+            .chat_capability
+            .build_chat_request_body(&request, Some(&self.specific_params))?;
+
+        // The endpoint rejects sampling parameters
+        if let Some(object) = body.as_object_mut() {
+            object.retain(|key, _| {
+                matches!(
+                    key.as_str(),
+                    "model" | "messages" | "system" | "tools" | "tool_choice" | "thinking"
+                )
+            });
+        }
+
+        let mut headers = super::utils::build_headers(
+            &self.chat_capability.api_key,
+            &self.chat_capability.http_config.headers,
+        )?;
+        super::utils::add_files_api_beta(&mut headers, &body);
+        let url = format!("{}/v1/messages/count_tokens", self.chat_capability.base_url);
+
+        let http_config = &self.chat_capability.http_config;
+        let response = crate::rate_limiter::send_request(
+            self.chat_capability
+                .http_client
+                .post(&url)
+                .headers(headers)
+                .json(&body),
+            http_config.rate_limiter.as_deref(),
+            http_config.cassette.as_deref(),
+            "anthropic",
+            body["model"].as_str().unwrap_or_default(),
+            crate::rate_limiter::estimate_request_tokens(&body),
+        )
+        .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+
+            return Err(LlmError::ApiError {
+                code: status.as_u16(),
+                message: format!("Anthropic API error: {error_text}"),
+                details: serde_json::from_str(&error_text).ok(),
+            });
+        }
+
+        let count: super::types::AnthropicTokenCount = response.json().await?;
+        Ok(count.input_tokens)
+    }
+}
+
+#[async_trait]
+impl crate::token_counter::TokenCounter for AnthropicClient {
+    async fn count_text(&self, text: &str) -> Result<u32, LlmError> {
+        self.count_tokens(&[ChatMessage::user(text).build()], None)
+            .await
+    }
+
+    async fn count_messages(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+    ) -> Result<u32, LlmError> {
+        self.count_tokens(messages, tools).await
+    }
+}
+
+#[async_trait]
+impl VisionCapability for AnthropicClient {
+    async fn analyze_image(&self, request: VisionRequest) -> Result<VisionResponse, LlmError> {
+        let messages = crate::multimodal::build_vision_messages(&request, None).await?;
+        let chat_request = ChatRequest {
+            messages,
+            tools: None,
+            common_params: request.common_params(&self.common_params),
+            provider_params: None,
+            http_config: None,
+            web_search: None,
+            stream: false,
+            prompt_cache: None,
+            output_schema: None,
+        };
+
+        self.send_chat_request(chat_request).await.map(Into::into)
+    }
+
+    async fn generate_image(&self, _request: ImageGenRequest) -> Result<ImageResponse, LlmError> {
+        Err(LlmError::UnsupportedOperation(
+            "Anthropic does not support image generation".to_string(),
+        ))
+    }
+
+    fn get_supported_input_formats(&self) -> Vec<String> {
+        vec![
+            "jpeg".to_string(),
+            "png".to_string(),
+            "gif".to_string(),
+            "webp".to_string(),
+        ]
+    }
+
+    fn get_supported_output_formats(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+#[async_trait]
+impl BatchCapability for AnthropicClient {
+    async fn create_batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResponse, LlmError> {
+        self.batches().create_batch(requests).await
+    }
+
+    async fn retrieve_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError> {
+        self.batches().retrieve_batch(batch_id).await
+    }
+
+    async fn list_batches(
+        &self,
+        query: Option<BatchListQuery>,
+    ) -> Result<BatchListResponse, LlmError> {
+        self.batches().list_batches(query).await
+    }
+
+    async fn cancel_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError> {
+        self.batches().cancel_batch(batch_id).await
+    }
+
+    async fn get_batch_results(&self, batch_id: String) -> Result<Vec<BatchResult>, LlmError> {
+        self.batches().get_batch_results(batch_id).await
     }
 }
 
@@ -233,6 +530,52 @@ impl ModelListingCapability for AnthropicClient {
     }
 }
 
+#[async_trait]
+impl FileManagementCapability for AnthropicClient {
+    async fn upload_file(&self, request: FileUploadRequest) -> Result<FileObject, LlmError> {
+        self.files_capability.upload_file(request).await
+    }
+
+    async fn list_files(&self, query: Option<FileListQuery>) -> Result<FileListResponse, LlmError> {
+        self.files_capability.list_files(query).await
+    }
+
+    async fn retrieve_file(&self, file_id: String) -> Result<FileObject, LlmError> {
+        self.files_capability.retrieve_file(file_id).await
+    }
+
+    async fn delete_file(&self, file_id: String) -> Result<FileDeleteResponse, LlmError> {
+        self.files_capability.delete_file(file_id).await
+    }
+
+    async fn get_file_content(&self, file_id: String) -> Result<Vec<u8>, LlmError> {
+        self.files_capability.get_file_content(file_id).await
+    }
+}
+
+#[async_trait]
+impl WebSearchCapability for AnthropicClient {
+    /// Answer the query with the server-side `web_search` tool and return the pages it consulted
+    async fn web_search(
+        &self,
+        query: String,
+        config: Option<WebSearchConfig>,
+    ) -> Result<Vec<WebSearchResult>, LlmError> {
+        let provider = WebSearchProvider::for_query("anthropic", config);
+        let request = provider.search_request(query, self.common_params.clone());
+        let response = self.send_chat_request(request).await?;
+        Ok(provider.results_from_response(&response))
+    }
+
+    fn supports_web_search(&self) -> bool {
+        true
+    }
+
+    fn web_search_strategy(&self) -> WebSearchStrategy {
+        WebSearchStrategy::Tool
+    }
+}
+
 impl LlmClient for AnthropicClient {
     fn provider_name(&self) -> &'static str {
         "anthropic"
@@ -248,8 +591,11 @@ impl LlmClient for AnthropicClient {
             .with_streaming()
             .with_tools()
             .with_vision()
+            .with_file_management()
             .with_custom_feature("prompt_caching", true)
             .with_custom_feature("thinking_mode", true)
+            .with_custom_feature("batch_processing", true)
+            .with_custom_feature("web_search", true)
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -259,6 +605,14 @@ impl LlmClient for AnthropicClient {
     fn clone_box(&self) -> Box<dyn LlmClient> {
         Box::new(self.clone())
     }
+
+    fn as_vision_capability(&self) -> Option<&dyn VisionCapability> {
+        Some(self)
+    }
+
+    fn as_batch_capability(&self) -> Option<&dyn BatchCapability> {
+        Some(self)
+    }
 }
 
 #[cfg(test)]
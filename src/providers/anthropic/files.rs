@@ -0,0 +1,312 @@
+//! Anthropic Files API Implementation
+//!
+//! Implements the `FileManagementCapability` trait on top of the (beta) Anthropic Files API.
+//! Uploaded files can be referenced from messages with [`DocumentSource::FileId`].
+//!
+//! API Reference: <https://docs.anthropic.com/en/docs/build-with-claude/files>
+//!
+//! [`DocumentSource::FileId`]: crate::types::DocumentSource::FileId
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::error::LlmError;
+use crate::rate_limiter::send_request;
+use crate::traits::FileManagementCapability;
+use crate::types::{
+    FileDeleteResponse, FileListQuery, FileListResponse, FileObject, FileUploadRequest,
+};
+
+use super::utils::{FILES_API_BETA, add_beta_feature, build_headers, map_anthropic_error};
+
+/// Anthropic file metadata
+#[derive(Debug, Clone, Deserialize)]
+struct AnthropicFile {
+    /// File ID
+    id: String,
+    /// Original filename
+    filename: String,
+    /// MIME type
+    mime_type: Option<String>,
+    /// File size in bytes
+    size_bytes: u64,
+    /// RFC 3339 creation time
+    created_at: String,
+    /// Whether the file content can be downloaded
+    #[serde(default)]
+    downloadable: bool,
+}
+
+/// Anthropic file list response
+#[derive(Debug, Clone, Deserialize)]
+struct AnthropicFileListResponse {
+    /// Files in this page
+    data: Vec<AnthropicFile>,
+    /// Whether there are more results
+    #[serde(default)]
+    has_more: bool,
+    /// ID of the last file in this page, used as the next `after_id`
+    last_id: Option<String>,
+}
+
+/// Anthropic file deletion response
+#[derive(Debug, Clone, Deserialize)]
+struct AnthropicFileDeleteResponse {
+    /// ID of the deleted file
+    id: String,
+}
+
+/// Anthropic file management capability implementation.
+///
+/// # Supported Operations
+/// - File upload (PDF, plain text, images)
+/// - File listing with cursor pagination
+/// - File metadata retrieval
+/// - File deletion
+/// - File content download (only for files created by tools)
+#[derive(Debug, Clone)]
+pub struct AnthropicFiles {
+    pub api_key: String,
+    pub base_url: String,
+    pub http_client: reqwest::Client,
+    pub http_config: crate::types::HttpConfig,
+}
+
+impl AnthropicFiles {
+    /// Create a new Anthropic files instance
+    pub const fn new(
+        api_key: String,
+        base_url: String,
+        http_client: reqwest::Client,
+        http_config: crate::types::HttpConfig,
+    ) -> Self {
+        Self {
+            api_key,
+            base_url,
+            http_client,
+            http_config,
+        }
+    }
+
+    /// Get maximum file size in bytes.
+    pub const fn get_max_file_size(&self) -> u64 {
+        500 * 1024 * 1024 // 500 MB
+    }
+
+    /// Validate file upload request.
+    fn validate_upload_request(&self, request: &FileUploadRequest) -> Result<(), LlmError> {
+        if request.filename.is_empty() {
+            return Err(LlmError::InvalidInput(
+                "Filename cannot be empty".to_string(),
+            ));
+        }
+
+        if request.content.len() as u64 > self.get_max_file_size() {
+            return Err(LlmError::InvalidInput(format!(
+                "File size {} bytes exceeds maximum allowed size of {} bytes",
+                request.content.len(),
+                self.get_max_file_size()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Convert an Anthropic file to our standard format.
+    fn convert_file(&self, file: AnthropicFile) -> FileObject {
+        let created_at = DateTime::parse_from_rfc3339(&file.created_at)
+            .map(|dt| dt.with_timezone(&Utc).timestamp().max(0) as u64)
+            .unwrap_or(0);
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "downloadable".to_string(),
+            serde_json::Value::Bool(file.downloadable),
+        );
+
+        FileObject {
+            id: file.id,
+            filename: file.filename,
+            bytes: file.size_bytes,
+            created_at,
+            purpose: String::new(),
+            status: "processed".to_string(),
+            mime_type: file.mime_type,
+            metadata,
+        }
+    }
+
+    /// Build the Files API request URL
+    fn url(&self, path: &str) -> String {
+        crate::utils::url::join_url(&self.base_url, path)
+    }
+
+    /// Send a request with authentication and beta headers and return the successful response
+    async fn send(
+        &self,
+        request_builder: reqwest::RequestBuilder,
+        json_body: bool,
+    ) -> Result<reqwest::Response, LlmError> {
+        let mut headers = build_headers(&self.api_key, &self.http_config.headers)?;
+        add_beta_feature(&mut headers, FILES_API_BETA);
+        if !json_body {
+            // Let reqwest set the multipart boundary
+            headers.remove(reqwest::header::CONTENT_TYPE);
+        }
+
+        let response = send_request(
+            request_builder.headers(headers),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "anthropic",
+            "",
+            0,
+        )
+        .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+
+            if let Ok(error_json) = serde_json::from_str::<serde_json::Value>(&error_text)
+                && let Some(error_obj) = error_json.get("error")
+            {
+                let error_type = error_obj
+                    .get("type")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or("unknown");
+                let error_message = error_obj
+                    .get("message")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or("Unknown error");
+
+                return Err(map_anthropic_error(
+                    status.as_u16(),
+                    error_type,
+                    error_message,
+                    error_json.clone(),
+                ));
+            }
+
+            return Err(LlmError::ApiError {
+                code: status.as_u16(),
+                message: format!("Anthropic Files API error: {error_text}"),
+                details: serde_json::from_str(&error_text).ok(),
+            });
+        }
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl FileManagementCapability for AnthropicFiles {
+    /// Upload a file to Anthropic's storage.
+    async fn upload_file(&self, request: FileUploadRequest) -> Result<FileObject, LlmError> {
+        self.validate_upload_request(&request)?;
+
+        let part = reqwest::multipart::Part::bytes(request.content)
+            .file_name(request.filename.clone())
+            .mime_str(
+                request
+                    .mime_type
+                    .as_deref()
+                    .unwrap_or("application/octet-stream"),
+            )
+            .map_err(|e| LlmError::HttpError(format!("Invalid MIME type: {e}")))?;
+        let form = reqwest::multipart::Form::new().part("file", part);
+
+        let request_builder = self.http_client.post(self.url("v1/files")).multipart(form);
+        let file: AnthropicFile = self.send(request_builder, false).await?.json().await?;
+        Ok(self.convert_file(file))
+    }
+
+    /// List files with cursor pagination.
+    async fn list_files(&self, query: Option<FileListQuery>) -> Result<FileListResponse, LlmError> {
+        let mut request_builder = self.http_client.get(self.url("v1/files"));
+        if let Some(query) = query {
+            if let Some(limit) = query.limit {
+                request_builder = request_builder.query(&[("limit", limit.to_string())]);
+            }
+            if let Some(after) = query.after {
+                request_builder = request_builder.query(&[("after_id", after)]);
+            }
+        }
+
+        let list: AnthropicFileListResponse =
+            self.send(request_builder, true).await?.json().await?;
+        Ok(FileListResponse {
+            next_cursor: list.has_more.then_some(list.last_id).flatten(),
+            has_more: list.has_more,
+            files: list
+                .data
+                .into_iter()
+                .map(|file| self.convert_file(file))
+                .collect(),
+        })
+    }
+
+    /// Retrieve file metadata.
+    async fn retrieve_file(&self, file_id: String) -> Result<FileObject, LlmError> {
+        let request_builder = self
+            .http_client
+            .get(self.url(&format!("v1/files/{file_id}")));
+        let file: AnthropicFile = self.send(request_builder, true).await?.json().await?;
+        Ok(self.convert_file(file))
+    }
+
+    /// Delete a file permanently.
+    async fn delete_file(&self, file_id: String) -> Result<FileDeleteResponse, LlmError> {
+        let request_builder = self
+            .http_client
+            .delete(self.url(&format!("v1/files/{file_id}")));
+        let deleted: AnthropicFileDeleteResponse =
+            self.send(request_builder, true).await?.json().await?;
+        Ok(FileDeleteResponse {
+            id: deleted.id,
+            deleted: true,
+        })
+    }
+
+    /// Download file content.
+    async fn get_file_content(&self, file_id: String) -> Result<Vec<u8>, LlmError> {
+        let request_builder = self
+            .http_client
+            .get(self.url(&format!("v1/files/{file_id}/content")));
+        let bytes = self.send(request_builder, true).await?.bytes().await?;
+        Ok(bytes.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_file() {
+        let files = AnthropicFiles::new(
+            "test-key".to_string(),
+            "https://api.anthropic.com".to_string(),
+            reqwest::Client::new(),
+            crate::types::HttpConfig::default(),
+        );
+        let file: AnthropicFile = serde_json::from_value(serde_json::json!({
+            "id": "file_011CNha8iCJcU1wXNR6q4V8w",
+            "type": "file",
+            "filename": "report.pdf",
+            "mime_type": "application/pdf",
+            "size_bytes": 1024,
+            "created_at": "2025-01-01T00:00:00Z",
+            "downloadable": false
+        }))
+        .unwrap();
+
+        let object = files.convert_file(file);
+        assert_eq!(object.id, "file_011CNha8iCJcU1wXNR6q4V8w");
+        assert_eq!(object.bytes, 1024);
+        assert_eq!(object.created_at, 1_735_689_600);
+        assert_eq!(object.mime_type.as_deref(), Some("application/pdf"));
+    }
+}
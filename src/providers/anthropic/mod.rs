@@ -2,9 +2,11 @@
 //!
 //! Modular implementation of Anthropic Claude API client with capability separation.
 
+pub mod batches;
 pub mod cache;
 pub mod chat;
 pub mod client;
+pub mod files;
 pub mod model_constants;
 pub mod models;
 pub mod request;
@@ -18,5 +20,7 @@ pub use client::AnthropicClient;
 pub use types::*;
 
 // Re-export capability implementations
+pub use batches::AnthropicBatches;
 pub use chat::AnthropicChatCapability;
+pub use files::AnthropicFiles;
 pub use models::AnthropicModels;
@@ -7,6 +7,7 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 
 use crate::error::LlmError;
+use crate::rate_limiter::send_request;
 use crate::traits::ModelListingCapability;
 use crate::types::ModelInfo;
 
@@ -65,7 +66,15 @@ impl AnthropicModels {
             url.push_str(&query_params.join("&"));
         }
 
-        let response = self.http_client.get(&url).headers(headers).send().await?;
+        let response = send_request(
+            self.http_client.get(&url).headers(headers),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "anthropic",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -108,7 +117,15 @@ impl AnthropicModels {
         let headers = build_headers(&self.api_key, &self.http_config.headers)?;
         let url = format!("{}/v1/models/{}", self.base_url, model_id);
 
-        let response = self.http_client.get(&url).headers(headers).send().await?;
+        let response = send_request(
+            self.http_client.get(&url).headers(headers),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "anthropic",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -251,7 +268,9 @@ fn determine_model_capabilities(model_id: &str) -> Vec<String> {
 }
 
 /// Estimate model specifications based on model ID
-fn estimate_model_specs(model_id: &str) -> (Option<u32>, Option<u32>, Option<f64>, Option<f64>) {
+pub(crate) fn estimate_model_specs(
+    model_id: &str,
+) -> (Option<u32>, Option<u32>, Option<f64>, Option<f64>) {
     match model_id {
         // Claude Opus 4.1 models (latest flagship)
         id if id.contains("claude-opus-4-1") => (
@@ -5,6 +5,7 @@
 
 use crate::error::LlmError;
 use crate::params::AnthropicParams;
+use crate::rate_limiter::{estimate_request_tokens, send_request};
 use crate::stream::{ChatStream, ChatStreamEvent};
 use crate::types::{ChatResponse, FinishReason, MessageContent, Usage};
 use crate::utils::streaming::{SseEventConverter, StreamFactory};
@@ -41,6 +42,8 @@ struct AnthropicMessage {
     role: Option<String>,
     content: Option<Vec<AnthropicContent>>,
     stop_reason: Option<String>,
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
 }
 
 /// Anthropic content structure
@@ -70,6 +73,8 @@ struct AnthropicDelta {
     stop_reason: Option<String>,
     #[serde(default)]
     stop_sequence: Option<String>,
+    #[serde(default)]
+    citation: Option<serde_json::Value>,
 }
 
 /// Anthropic usage structure
@@ -77,6 +82,8 @@ struct AnthropicDelta {
 struct AnthropicUsage {
     input_tokens: Option<u32>,
     output_tokens: Option<u32>,
+    cache_creation_input_tokens: Option<u32>,
+    cache_read_input_tokens: Option<u32>,
 }
 
 /// Anthropic event converter
@@ -95,18 +102,35 @@ impl AnthropicEventConverter {
     fn convert_anthropic_event(&self, event: AnthropicStreamEvent) -> Option<ChatStreamEvent> {
         match event.r#type.as_str() {
             "message_start" => {
-                // Stream start event
-                None // We don't emit stream start events for now
+                // Prompt and cache usage is only reported at the start of the stream;
+                // output tokens follow in message_delta
+                let usage = event.message.and_then(|message| message.usage)?;
+                let prompt_tokens = usage.input_tokens.unwrap_or(0);
+                Some(ChatStreamEvent::UsageUpdate {
+                    usage: Usage {
+                        prompt_tokens,
+                        completion_tokens: 0,
+                        total_tokens: prompt_tokens,
+                        cached_tokens: usage.cache_read_input_tokens,
+                        cache_write_tokens: usage.cache_creation_input_tokens,
+                        reasoning_tokens: None,
+                    },
+                })
             }
             "content_block_delta" => {
-                if let Some(delta) = event.delta
-                    && let Some(text) = delta.text
-                {
+                let delta = event.delta?;
+                if let Some(text) = delta.text {
                     return Some(ChatStreamEvent::ContentDelta {
                         delta: text,
                         index: None,
                     });
                 }
+                // Streamed citations precede their text, so no response offsets are known
+                if let Some(citation) = delta.citation {
+                    return Some(ChatStreamEvent::CitationDelta {
+                        citation: super::utils::convert_citation(&citation, None),
+                    });
+                }
                 None
             }
             "message_delta" => {
@@ -117,11 +141,11 @@ impl AnthropicEventConverter {
                 // Extract usage information
                 if let Some(usage) = event.usage {
                     usage_info = Some(Usage {
-                        prompt_tokens: usage.input_tokens.unwrap_or(0),
+                        prompt_tokens: 0,
                         completion_tokens: usage.output_tokens.unwrap_or(0),
-                        total_tokens: usage.input_tokens.unwrap_or(0)
-                            + usage.output_tokens.unwrap_or(0),
+                        total_tokens: usage.output_tokens.unwrap_or(0),
                         cached_tokens: None,
+                        cache_write_tokens: None,
                         reasoning_tokens: None,
                     });
                 }
@@ -149,6 +173,8 @@ impl AnthropicEventConverter {
                         finish_reason: Some(reason),
                         tool_calls: None,
                         thinking: None,
+                        citations: Vec::new(),
+                        sources: Vec::new(),
                         metadata: HashMap::new(),
                     };
                     return Some(ChatStreamEvent::StreamEnd { response });
@@ -170,6 +196,8 @@ impl AnthropicEventConverter {
                     finish_reason: Some(FinishReason::Stop),
                     tool_calls: None,
                     thinking: None,
+                    citations: Vec::new(),
+                    sources: Vec::new(),
                     metadata: HashMap::new(),
                 };
                 Some(ChatStreamEvent::StreamEnd { response })
@@ -179,6 +207,22 @@ impl AnthropicEventConverter {
     }
 }
 
+impl AnthropicEventConverter {
+    /// Convert an Anthropic stream event, expanding web search results into sources
+    fn convert_anthropic_events(&self, event: AnthropicStreamEvent) -> Vec<ChatStreamEvent> {
+        if event.r#type == "content_block_start"
+            && let Some(block) = &event.content_block
+            && block["type"].as_str() == Some("web_search_tool_result")
+        {
+            return super::utils::convert_search_results(&block["content"])
+                .into_iter()
+                .map(|source| ChatStreamEvent::SourceDelta { source })
+                .collect();
+        }
+        self.convert_anthropic_event(event).into_iter().collect()
+    }
+}
+
 impl SseEventConverter for AnthropicEventConverter {
     fn convert_event(
         &self,
@@ -231,6 +275,24 @@ impl SseEventConverter for AnthropicEventConverter {
         })
     }
 
+    fn convert_events(
+        &self,
+        event: Event,
+    ) -> Pin<Box<dyn Future<Output = Vec<Result<ChatStreamEvent, LlmError>>> + Send + Sync + '_>>
+    {
+        Box::pin(async move {
+            match serde_json::from_str::<AnthropicStreamEvent>(&event.data) {
+                Ok(anthropic_event) => self
+                    .convert_anthropic_events(anthropic_event)
+                    .into_iter()
+                    .map(Ok)
+                    .collect(),
+                // Unparseable events go through the detailed error handling above
+                Err(_) => self.convert_event(event).await.into_iter().collect(),
+            }
+        })
+    }
+
     fn handle_stream_end(&self) -> Option<Result<ChatStreamEvent, LlmError>> {
         let response = ChatResponse {
             id: None,
@@ -240,6 +302,8 @@ impl SseEventConverter for AnthropicEventConverter {
             finish_reason: Some(FinishReason::Stop),
             tool_calls: None,
             thinking: None,
+            citations: Vec::new(),
+            sources: Vec::new(),
             metadata: HashMap::new(),
         };
 
@@ -298,7 +362,7 @@ impl AnthropicStreaming {
         request: crate::types::ChatRequest,
     ) -> Result<ChatStream, LlmError> {
         // Build request body
-        let (messages, system) = self.convert_messages(&request.messages)?;
+        let (messages, system) = super::utils::convert_messages(&request.messages)?;
         let mut request_body = serde_json::json!({
             "model": request.common_params.model,
             "messages": messages,
@@ -345,11 +409,18 @@ impl AnthropicStreaming {
         // Merge provider-specific params before sending
         Self::merge_provider_params_into_body(&mut request_body, &request);
 
+        // Add the server-side web search tool
+        super::utils::apply_web_search(&mut request_body, &request);
+
+        // Translate prompt caching into cache_control breakpoints
+        super::utils::apply_prompt_cache(&mut request_body, &request);
+
         // Create headers with authentication
-        let headers = crate::utils::http_headers::ProviderHeaders::anthropic(
+        let mut headers = crate::utils::http_headers::ProviderHeaders::anthropic(
             &self.api_key,
             &self.http_config.headers,
         )?;
+        super::utils::add_files_api_beta(&mut headers, &request_body);
 
         // Build the API URL
         let url = crate::utils::url::join_url(&self.base_url, "/v1/messages");
@@ -361,59 +432,18 @@ impl AnthropicStreaming {
             .headers(headers)
             .json(&request_body);
 
-        let converter = AnthropicEventConverter::new(self.config);
-        StreamFactory::create_eventsource_stream(request_builder, converter).await
-    }
+        let response = send_request(
+            request_builder,
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "anthropic",
+            request_body["model"].as_str().unwrap_or_default(),
+            estimate_request_tokens(&request_body),
+        )
+        .await?;
 
-    /// Convert messages to Anthropic format
-    fn convert_messages(
-        &self,
-        messages: &[crate::types::ChatMessage],
-    ) -> Result<(serde_json::Value, Option<String>), LlmError> {
-        let mut anthropic_messages = Vec::new();
-        let mut system_message = None;
-
-        for msg in messages {
-            match msg.role {
-                crate::types::MessageRole::System => {
-                    // Anthropic handles system messages separately
-                    if let Some(text) = msg.content_text() {
-                        system_message = Some(text.to_string());
-                    }
-                }
-                crate::types::MessageRole::User => {
-                    anthropic_messages.push(serde_json::json!({
-                        "role": "user",
-                        "content": msg.content_text().unwrap_or("")
-                    }));
-                }
-                crate::types::MessageRole::Assistant => {
-                    anthropic_messages.push(serde_json::json!({
-                        "role": "assistant",
-                        "content": msg.content_text().unwrap_or("")
-                    }));
-                }
-                crate::types::MessageRole::Developer => {
-                    // Developer messages are treated as system-level instructions
-                    if let Some(text) = msg.content_text() {
-                        let developer_text = format!("Developer instructions: {text}");
-                        system_message = Some(match system_message {
-                            Some(existing) => format!("{existing}\n\n{developer_text}"),
-                            None => developer_text,
-                        });
-                    }
-                }
-                crate::types::MessageRole::Tool => {
-                    // Tool results are handled as user messages in Anthropic
-                    anthropic_messages.push(serde_json::json!({
-                        "role": "user",
-                        "content": msg.content_text().unwrap_or("")
-                    }));
-                }
-            }
-        }
-
-        Ok((serde_json::Value::Array(anthropic_messages), system_message))
+        let converter = AnthropicEventConverter::new(self.config);
+        StreamFactory::create_eventsource_stream_from_response(response, converter).await
     }
 
     /// Convert tools to Anthropic format
@@ -443,6 +473,49 @@ mod tests {
         AnthropicParams::default()
     }
 
+    #[tokio::test]
+    async fn test_anthropic_web_search_sources_and_citations() {
+        let converter = AnthropicEventConverter::new(create_test_config());
+        let event = |data: &str| Event {
+            event: "".to_string(),
+            data: data.to_string(),
+            id: "".to_string(),
+            retry: None,
+        };
+
+        let sources = converter
+            .convert_events(event(
+                r#"{"type":"content_block_start","index":1,"content_block":{
+                    "type":"web_search_tool_result","tool_use_id":"srvtoolu_1",
+                    "content":[
+                        {"type":"web_search_result","url":"https://a.example","title":"A"},
+                        {"type":"web_search_result","url":"https://b.example","title":"B"}
+                    ]}}"#,
+            ))
+            .await;
+        assert_eq!(sources.len(), 2);
+        assert!(matches!(
+            &sources[1],
+            Ok(ChatStreamEvent::SourceDelta { source }) if source.url == "https://b.example"
+        ));
+
+        let citation = converter
+            .convert_events(event(
+                r#"{"type":"content_block_delta","index":2,"delta":{"type":"citations_delta",
+                    "citation":{"type":"web_search_result_location","url":"https://a.example",
+                    "title":"A","cited_text":"quoted"}}}"#,
+            ))
+            .await;
+        match &citation[0] {
+            Ok(ChatStreamEvent::CitationDelta { citation }) => {
+                assert_eq!(citation.url.as_deref(), Some("https://a.example"));
+                assert_eq!(citation.cited_text.as_deref(), Some("quoted"));
+                assert_eq!(citation.start_index, None);
+            }
+            other => panic!("Expected CitationDelta, got {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn test_anthropic_streaming_conversion() {
         let config = create_test_config();
@@ -487,6 +560,8 @@ mod tests {
             http_config: None,
             web_search: None,
             stream: true,
+            prompt_cache: None,
+            output_schema: None,
         };
 
         let mut body = serde_json::json!({
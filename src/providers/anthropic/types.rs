@@ -14,6 +14,12 @@ pub struct AnthropicMessage {
     pub content: serde_json::Value,
 }
 
+/// Anthropic count-tokens response
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnthropicTokenCount {
+    pub input_tokens: u32,
+}
+
 /// Anthropic Chat Response
 #[derive(Debug, Clone, Deserialize)]
 pub struct AnthropicChatResponse {
@@ -44,6 +50,9 @@ pub struct AnthropicContentBlock {
     pub tool_use_id: Option<String>,
     pub content: Option<serde_json::Value>,
     pub is_error: Option<bool>,
+    // Citations supporting a text block
+    #[serde(default)]
+    pub citations: Option<Vec<serde_json::Value>>,
 }
 
 /// Anthropic Usage
@@ -169,6 +178,8 @@ pub enum AnthropicDelta {
     ThinkingDelta { thinking: String },
     #[serde(rename = "signature_delta")]
     SignatureDelta { signature: String },
+    #[serde(rename = "citations_delta")]
+    CitationsDelta { citation: serde_json::Value },
 }
 
 /// Message Delta
@@ -4,6 +4,7 @@
 
 use super::types::*;
 use crate::error::LlmError;
+use crate::multimodal::NormalizedImage;
 use crate::types::*;
 use crate::utils::http_headers::ProviderHeaders;
 use reqwest::header::HeaderMap;
@@ -17,6 +18,102 @@ pub fn build_headers(
     ProviderHeaders::anthropic(api_key, custom_headers)
 }
 
+/// Convert an image reference (URL, `data:` URL or bare base64) to an Anthropic image source
+pub fn convert_image_source(image_url: &str) -> serde_json::Value {
+    match NormalizedImage::parse(image_url) {
+        NormalizedImage::Url(url) => serde_json::json!({
+            "type": "url",
+            "url": url
+        }),
+        NormalizedImage::Inline { media_type, data } => serde_json::json!({
+            "type": "base64",
+            "media_type": media_type,
+            "data": data
+        }),
+    }
+}
+
+/// Beta flag required to reference uploaded files
+pub const FILES_API_BETA: &str = "files-api-2025-04-14";
+
+/// Convert a document to an Anthropic document content block
+/// <https://docs.anthropic.com/en/docs/build-with-claude/pdf-support>
+pub fn convert_document(
+    source: &DocumentSource,
+    media_type: &str,
+    title: Option<&str>,
+    citations: bool,
+) -> serde_json::Value {
+    let source = match source {
+        DocumentSource::Base64(data) => {
+            // Plain-text documents are sent as text so citations use character ranges
+            match source.decoded_text(media_type) {
+                Some(text) => serde_json::json!({
+                    "type": "text",
+                    "media_type": "text/plain",
+                    "data": text
+                }),
+                None => serde_json::json!({
+                    "type": "base64",
+                    "media_type": media_type,
+                    "data": data
+                }),
+            }
+        }
+        DocumentSource::Url(url) => serde_json::json!({
+            "type": "url",
+            "url": url
+        }),
+        DocumentSource::FileId(file_id) => serde_json::json!({
+            "type": "file",
+            "file_id": file_id
+        }),
+    };
+
+    let mut block = serde_json::json!({
+        "type": "document",
+        "source": source
+    });
+    if let Some(title) = title {
+        block["title"] = serde_json::Value::String(title.to_string());
+    }
+    if citations {
+        block["citations"] = serde_json::json!({ "enabled": true });
+    }
+    block
+}
+
+/// Add the Files API beta flag when the request body references an uploaded file
+pub fn add_files_api_beta(headers: &mut HeaderMap, body: &serde_json::Value) {
+    let uses_files = body["messages"].as_array().is_some_and(|messages| {
+        messages.iter().any(|message| {
+            message["content"].as_array().is_some_and(|blocks| {
+                blocks
+                    .iter()
+                    .any(|block| block["source"]["type"].as_str() == Some("file"))
+            })
+        })
+    });
+    if uses_files {
+        add_beta_feature(headers, FILES_API_BETA);
+    }
+}
+
+/// Append a beta flag to the `anthropic-beta` header, keeping flags already set
+pub fn add_beta_feature(headers: &mut HeaderMap, feature: &str) {
+    let value = match headers
+        .get("anthropic-beta")
+        .and_then(|existing| existing.to_str().ok())
+    {
+        Some(existing) if existing.split(',').any(|f| f.trim() == feature) => return,
+        Some(existing) => format!("{existing},{feature}"),
+        None => feature.to_string(),
+    };
+    if let Ok(value) = reqwest::header::HeaderValue::from_str(&value) {
+        headers.insert("anthropic-beta", value);
+    }
+}
+
 /// Convert message content to Anthropic format
 pub fn convert_message_content(content: &MessageContent) -> Result<serde_json::Value, LlmError> {
     match content {
@@ -39,11 +136,7 @@ pub fn convert_message_content(content: &MessageContent) -> Result<serde_json::V
                         // Anthropic uses a different image format
                         content_parts.push(serde_json::json!({
                             "type": "image",
-                            "source": {
-                                "type": "base64",
-                                "media_type": "image/jpeg",
-                                "data": image_url // Base64 encoding needs to be handled here
-                            }
+                            "source": convert_image_source(image_url)
                         }));
                     }
                     ContentPart::Audio {
@@ -56,6 +149,19 @@ pub fn convert_message_content(content: &MessageContent) -> Result<serde_json::V
                             "text": format!("[Audio: {}]", audio_url)
                         }));
                     }
+                    ContentPart::Document {
+                        source,
+                        media_type,
+                        title,
+                        citations,
+                    } => {
+                        content_parts.push(convert_document(
+                            source,
+                            media_type,
+                            title.as_deref(),
+                            *citations,
+                        ));
+                    }
                 }
             }
 
@@ -164,34 +270,22 @@ pub fn parse_response_content(content_blocks: &[AnthropicContentBlock]) -> Messa
 pub fn parse_response_content_and_tools(
     content_blocks: &[AnthropicContentBlock],
 ) -> (MessageContent, Option<Vec<crate::types::ToolCall>>) {
-    let mut text_content = String::new();
+    let (text_content, _) = collect_text_and_citations(content_blocks);
     let mut tool_calls = Vec::new();
 
     for content_block in content_blocks {
-        match content_block.r#type.as_str() {
-            "text" => {
-                if let Some(text) = &content_block.text {
-                    if !text_content.is_empty() {
-                        text_content.push('\n');
-                    }
-                    text_content.push_str(text);
-                }
-            }
-            "tool_use" => {
-                if let (Some(id), Some(name), Some(input)) =
-                    (&content_block.id, &content_block.name, &content_block.input)
-                {
-                    tool_calls.push(crate::types::ToolCall {
-                        id: id.clone(),
-                        r#type: "function".to_string(),
-                        function: Some(crate::types::FunctionCall {
-                            name: name.clone(),
-                            arguments: serde_json::to_string(input).unwrap_or_default(),
-                        }),
-                    });
-                }
-            }
-            _ => {}
+        if content_block.r#type == "tool_use"
+            && let (Some(id), Some(name), Some(input)) =
+                (&content_block.id, &content_block.name, &content_block.input)
+        {
+            tool_calls.push(crate::types::ToolCall {
+                id: id.clone(),
+                r#type: "function".to_string(),
+                function: Some(crate::types::FunctionCall {
+                    name: name.clone(),
+                    arguments: serde_json::to_string(input).unwrap_or_default(),
+                }),
+            });
         }
     }
 
@@ -209,6 +303,118 @@ pub fn parse_response_content_and_tools(
     (content, tools)
 }
 
+/// Parse citations attached to text blocks, with offsets into the joined response text
+pub fn parse_citations(content_blocks: &[AnthropicContentBlock]) -> Vec<Citation> {
+    collect_text_and_citations(content_blocks).1
+}
+
+/// Join text blocks and collect their citations.
+///
+/// Plain text blocks are separated by newlines; blocks produced by citations are
+/// fragments of one answer and are concatenated as-is.
+fn collect_text_and_citations(content_blocks: &[AnthropicContentBlock]) -> (String, Vec<Citation>) {
+    let mut text_content = String::new();
+    let mut citations = Vec::new();
+    let mut previous_cited = false;
+
+    for content_block in content_blocks {
+        if content_block.r#type != "text" {
+            continue;
+        }
+        let Some(text) = &content_block.text else {
+            continue;
+        };
+
+        let cited = content_block
+            .citations
+            .as_ref()
+            .is_some_and(|citations| !citations.is_empty());
+        if !text_content.is_empty() && !cited && !previous_cited {
+            text_content.push('\n');
+        }
+        previous_cited = cited;
+
+        let start = text_content.len();
+        text_content.push_str(text);
+        let end = text_content.len();
+
+        for citation in content_block.citations.iter().flatten() {
+            citations.push(convert_citation(citation, Some((start, end))));
+        }
+    }
+
+    (text_content, citations)
+}
+
+/// Collect the web pages returned by server-side web search
+pub fn parse_search_sources(content_blocks: &[AnthropicContentBlock]) -> Vec<SearchSource> {
+    let mut sources = Vec::new();
+    for content_block in content_blocks {
+        if content_block.r#type == "web_search_tool_result"
+            && let Some(content) = &content_block.content
+        {
+            SearchSource::merge(&mut sources, convert_search_results(content));
+        }
+    }
+    sources
+}
+
+/// Convert the content of a `web_search_tool_result` block
+pub fn convert_search_results(content: &serde_json::Value) -> Vec<SearchSource> {
+    content
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|result| result["type"].as_str() == Some("web_search_result"))
+        .filter_map(|result| {
+            let url = result["url"].as_str()?;
+            let mut source = SearchSource::new(url);
+            source.title = result["title"].as_str().map(str::to_string);
+            Some(source)
+        })
+        .collect()
+}
+
+/// Convert an Anthropic citation object; `span` is its location in the response text
+/// <https://docs.anthropic.com/en/docs/build-with-claude/citations>
+pub fn convert_citation(citation: &serde_json::Value, span: Option<(usize, usize)>) -> Citation {
+    let index = |key: &str| {
+        citation
+            .get(key)
+            .and_then(serde_json::Value::as_u64)
+            .map(|v| v as usize)
+    };
+    let text = |key: &str| {
+        citation
+            .get(key)
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string)
+    };
+
+    let location = match citation.get("type").and_then(serde_json::Value::as_str) {
+        Some("char_location") => index("start_char_index")
+            .zip(index("end_char_index"))
+            .map(|(start, end)| CitationLocation::Chars { start, end }),
+        Some("page_location") => index("start_page_number")
+            .zip(index("end_page_number"))
+            .map(|(start, end)| CitationLocation::Pages { start, end }),
+        Some("content_block_location") => index("start_block_index")
+            .zip(index("end_block_index"))
+            .map(|(start, end)| CitationLocation::ContentBlocks { start, end }),
+        _ => None,
+    };
+
+    Citation {
+        cited_text: text("cited_text"),
+        title: text("document_title").or_else(|| text("title")),
+        url: text("url"),
+        document_index: index("document_index"),
+        location,
+        start_index: span.map(|(start, _)| start),
+        end_index: span.map(|(_, end)| end),
+    }
+}
+
 /// Extract thinking content from Anthropic response
 pub fn extract_thinking_content(content_blocks: &[AnthropicContentBlock]) -> Option<String> {
     for content_block in content_blocks {
@@ -227,6 +433,7 @@ pub fn create_usage_from_response(usage: Option<AnthropicUsage>) -> Option<Usage
         total_tokens: u.input_tokens + u.output_tokens,
         reasoning_tokens: None,
         cached_tokens: u.cache_read_input_tokens,
+        cache_write_tokens: u.cache_creation_input_tokens,
     })
 }
 
@@ -285,6 +492,122 @@ pub fn convert_tools_to_anthropic_format(
     Ok(anthropic_tools)
 }
 
+/// Maximum number of `cache_control` breakpoints accepted per request
+const MAX_CACHE_BREAKPOINTS: usize = 4;
+
+/// Convert a cache control marker to an Anthropic `cache_control` block
+pub fn convert_cache_control(control: &crate::types::CacheControl) -> serde_json::Value {
+    match control {
+        crate::types::CacheControl::Ephemeral => serde_json::json!({ "type": "ephemeral" }),
+        crate::types::CacheControl::Persistent { ttl } => {
+            // Anthropic offers a 5 minute default and a 1 hour extended lifetime
+            let ttl = match ttl {
+                Some(ttl) if ttl.as_secs() <= 300 => "5m",
+                _ => "1h",
+            };
+            serde_json::json!({ "type": "ephemeral", "ttl": ttl })
+        }
+    }
+}
+
+/// Attach a `cache_control` block to the last content block of a message or system value
+fn mark_cached(content: &mut serde_json::Value, cache_control: serde_json::Value) {
+    if let serde_json::Value::String(text) = content {
+        *content = serde_json::json!([{ "type": "text", "text": text }]);
+    }
+    if let Some(serde_json::Value::Object(block)) =
+        content.as_array_mut().and_then(|blocks| blocks.last_mut())
+    {
+        block.insert("cache_control".to_string(), cache_control);
+    }
+}
+
+/// Add the server-side `web_search` tool when the request enables web search
+/// <https://docs.anthropic.com/en/docs/agents-and-tools/tool-use/web-search-tool>
+pub fn apply_web_search(body: &mut serde_json::Value, request: &ChatRequest) {
+    let Some(config) = request.web_search.as_ref().filter(|config| config.enabled) else {
+        return;
+    };
+    let tool = crate::web_search::WebSearchProvider::new("anthropic".to_string(), config.clone())
+        .build_anthropic_tool();
+
+    if let Some(tools) = body.get_mut("tools").and_then(|tools| tools.as_array_mut()) {
+        if !tools
+            .iter()
+            .any(|existing| existing["name"] == tool["name"])
+        {
+            tools.push(tool);
+        }
+    } else {
+        body["tools"] = serde_json::Value::Array(vec![tool]);
+    }
+}
+
+/// Translate the request's prompt caching policy and message-level cache markers
+/// into `cache_control` breakpoints on an already converted request body.
+///
+/// System and developer messages are merged into `system`; every other message maps
+/// one-to-one onto `messages`. Only the last four breakpoints are kept.
+pub fn apply_prompt_cache(body: &mut serde_json::Value, request: &ChatRequest) {
+    let mut messages = request.messages.clone();
+    if let Some(policy) = &request.prompt_cache {
+        policy.apply(&mut messages);
+    }
+
+    let mut system_control = None;
+    let mut message_controls = Vec::new();
+    let mut body_index = 0;
+    for message in &messages {
+        let is_system = matches!(message.role, MessageRole::System | MessageRole::Developer);
+        if let Some(control) = &message.metadata.cache_control {
+            if is_system {
+                system_control = Some(convert_cache_control(control));
+            } else {
+                message_controls.push((body_index, convert_cache_control(control)));
+            }
+        }
+        if !is_system {
+            body_index += 1;
+        }
+    }
+
+    let mut budget = MAX_CACHE_BREAKPOINTS;
+
+    if let Some(policy) = &request.prompt_cache
+        && policy.cache_tools
+        && let Some(tool) = body
+            .get_mut("tools")
+            .and_then(|tools| tools.as_array_mut())
+            .and_then(|tools| tools.last_mut())
+            .and_then(|tool| tool.as_object_mut())
+    {
+        tool.insert(
+            "cache_control".to_string(),
+            convert_cache_control(&policy.cache_control()),
+        );
+        budget -= 1;
+    }
+
+    if let Some(control) = system_control
+        && let Some(system) = body.get_mut("system")
+    {
+        mark_cached(system, control);
+        budget -= 1;
+    }
+
+    let skip = message_controls.len().saturating_sub(budget);
+    if let Some(body_messages) = body.get_mut("messages").and_then(|m| m.as_array_mut()) {
+        for (index, control) in message_controls.into_iter().skip(skip) {
+            if let Some(content) = body_messages
+                .get_mut(index)
+                .and_then(|message| message.get_mut("content"))
+            {
+                mark_cached(content, control);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -304,6 +627,7 @@ mod tests {
                 tool_use_id: None,
                 content: None,
                 is_error: None,
+                citations: None,
             },
             AnthropicContentBlock {
                 r#type: "tool_use".to_string(),
@@ -316,6 +640,7 @@ mod tests {
                 tool_use_id: None,
                 content: None,
                 is_error: None,
+                citations: None,
             },
         ];
 
@@ -352,6 +677,7 @@ mod tests {
             tool_use_id: None,
             content: None,
             is_error: None,
+            citations: None,
         }];
 
         let (content, tool_calls) = parse_response_content_and_tools(&content_blocks);
@@ -365,4 +691,153 @@ mod tests {
         // Check no tool calls
         assert!(tool_calls.is_none());
     }
+
+    #[test]
+    fn test_convert_image_source() {
+        let source = convert_image_source("data:image/png;base64,iVBORw0KGgo=");
+        assert_eq!(source["type"], "base64");
+        assert_eq!(source["media_type"], "image/png");
+        assert_eq!(source["data"], "iVBORw0KGgo=");
+
+        let source = convert_image_source("https://example.com/cat.jpg");
+        assert_eq!(source["type"], "url");
+        assert_eq!(source["url"], "https://example.com/cat.jpg");
+    }
+
+    #[test]
+    fn test_apply_prompt_cache_prefix_policy() {
+        let request = ChatRequest::new(vec![
+            ChatMessage::system("You are helpful").build(),
+            ChatMessage::user("first").build(),
+            ChatMessage::assistant("second").build(),
+            ChatMessage::user("third").build(),
+        ])
+        .with_tools(vec![Tool::function(
+            "lookup".to_string(),
+            "Look something up".to_string(),
+            serde_json::json!({"type": "object"}),
+        )])
+        .with_prompt_cache(PromptCachePolicy::prefix(2));
+
+        let (messages, system) = convert_messages(&request.messages).unwrap();
+        let mut body = serde_json::json!({
+            "messages": messages,
+            "system": system,
+            "tools": convert_tools_to_anthropic_format(request.tools.as_deref().unwrap()).unwrap(),
+        });
+        apply_prompt_cache(&mut body, &request);
+
+        assert_eq!(body["system"][0]["cache_control"]["type"], "ephemeral");
+        assert_eq!(body["tools"][0]["cache_control"]["type"], "ephemeral");
+        assert_eq!(body["messages"][1]["content"][0]["text"], "second");
+        assert_eq!(
+            body["messages"][1]["content"][0]["cache_control"]["type"],
+            "ephemeral"
+        );
+        assert_eq!(body["messages"][0]["content"], "first");
+        assert_eq!(body["messages"][2]["content"], "third");
+    }
+
+    #[test]
+    fn test_apply_web_search_adds_server_tool() {
+        let mut config = WebSearchConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        config
+            .provider_params
+            .insert("max_uses".to_string(), serde_json::json!(2));
+        let request =
+            ChatRequest::new(vec![ChatMessage::user("news?").build()]).with_web_search(config);
+
+        let mut body = serde_json::json!({});
+        apply_web_search(&mut body, &request);
+        apply_web_search(&mut body, &request);
+
+        assert_eq!(
+            body["tools"],
+            serde_json::json!([{
+                "type": "web_search_20250305",
+                "name": "web_search",
+                "max_uses": 2
+            }])
+        );
+    }
+
+    #[test]
+    fn test_parse_citations_offsets_and_locations() {
+        let blocks: Vec<AnthropicContentBlock> = serde_json::from_value(serde_json::json!([
+            {"type": "text", "text": "According to the report, "},
+            {
+                "type": "text",
+                "text": "revenue grew 12%",
+                "citations": [{
+                    "type": "page_location",
+                    "cited_text": "Revenue increased by 12% year over year.",
+                    "document_index": 0,
+                    "document_title": "Annual report",
+                    "start_page_number": 3,
+                    "end_page_number": 4
+                }]
+            },
+            {"type": "text", "text": "."}
+        ]))
+        .unwrap();
+
+        let (content, _) = parse_response_content_and_tools(&blocks);
+        let text = content.text().unwrap().to_string();
+        assert_eq!(text, "According to the report, revenue grew 12%.");
+
+        let citations = parse_citations(&blocks);
+        assert_eq!(citations.len(), 1);
+        let citation = &citations[0];
+        let span = &text[citation.start_index.unwrap()..citation.end_index.unwrap()];
+        assert_eq!(span, "revenue grew 12%");
+        assert_eq!(citation.title.as_deref(), Some("Annual report"));
+        assert_eq!(citation.document_index, Some(0));
+        assert_eq!(
+            citation.location,
+            Some(CitationLocation::Pages { start: 3, end: 4 })
+        );
+    }
+
+    #[test]
+    fn test_convert_document_sources() {
+        let text_doc = convert_document(
+            &DocumentSource::from_bytes(b"The grass is green."),
+            "text/plain",
+            Some("Facts"),
+            true,
+        );
+        assert_eq!(text_doc["source"]["type"], "text");
+        assert_eq!(text_doc["source"]["data"], "The grass is green.");
+        assert_eq!(text_doc["title"], "Facts");
+        assert_eq!(text_doc["citations"]["enabled"], true);
+
+        let pdf_doc = convert_document(
+            &DocumentSource::Base64("JVBERi0=".to_string()),
+            "application/pdf",
+            None,
+            false,
+        );
+        assert_eq!(pdf_doc["source"]["type"], "base64");
+        assert_eq!(pdf_doc["source"]["media_type"], "application/pdf");
+        assert!(pdf_doc.get("citations").is_none());
+
+        let file_doc = convert_document(
+            &DocumentSource::FileId("file_abc".to_string()),
+            "application/pdf",
+            None,
+            false,
+        );
+        assert_eq!(file_doc["source"]["file_id"], "file_abc");
+
+        let body = serde_json::json!({
+            "messages": [{"role": "user", "content": [file_doc]}]
+        });
+        let mut headers = HeaderMap::new();
+        add_files_api_beta(&mut headers, &body);
+        add_files_api_beta(&mut headers, &body);
+        assert_eq!(headers["anthropic-beta"], FILES_API_BETA);
+    }
 }
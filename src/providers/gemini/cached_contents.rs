@@ -0,0 +1,411 @@
+//! Gemini Context Caching Implementation
+//!
+//! This module manages the `cachedContents` resource: large, reusable prompt
+//! prefixes (documents, system instructions, tools) stored server-side so that
+//! subsequent requests only pay for the new turns.
+//!
+//! API Reference: <https://ai.google.dev/api/caching>
+
+use reqwest::Client as HttpClient;
+use std::time::Duration;
+
+use crate::error::LlmError;
+use crate::rate_limiter::send_request;
+use crate::types::{ChatMessage, Tool};
+
+use super::chat::GeminiChatCapability;
+use super::types::{
+    CachedContent, Content, GeminiConfig, GeminiFile, ListCachedContentsResponse, Part,
+};
+
+/// Request to create a cached content from chat messages, files and a system instruction.
+#[derive(Debug, Clone, Default)]
+pub struct CachedContentRequest {
+    /// Model the cache is created for; defaults to the client's model
+    pub model: Option<String>,
+    /// Human-readable display name
+    pub display_name: Option<String>,
+    /// System instruction stored in the cache
+    pub system_instruction: Option<String>,
+    /// Conversation prefix stored in the cache
+    pub messages: Vec<ChatMessage>,
+    /// Uploaded files (URI and MIME type) stored in the cache
+    pub files: Vec<(String, Option<String>)>,
+    /// Tools stored in the cache
+    pub tools: Option<Vec<Tool>>,
+    /// Time to live; the API default (1 hour) applies when unset
+    pub ttl: Option<Duration>,
+}
+
+impl CachedContentRequest {
+    /// Create an empty request
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the model
+    pub fn with_model<S: Into<String>>(mut self, model: S) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Set the display name
+    pub fn with_display_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.display_name = Some(name.into());
+        self
+    }
+
+    /// Set the system instruction
+    pub fn with_system_instruction<S: Into<String>>(mut self, instruction: S) -> Self {
+        self.system_instruction = Some(instruction.into());
+        self
+    }
+
+    /// Add messages to the cached prefix
+    pub fn with_messages(mut self, messages: Vec<ChatMessage>) -> Self {
+        self.messages.extend(messages);
+        self
+    }
+
+    /// Add an uploaded file to the cached prefix
+    pub fn with_file(mut self, file: &GeminiFile) -> Result<Self, LlmError> {
+        let uri = file.uri.clone().ok_or_else(|| {
+            LlmError::InvalidInput("Gemini file has no URI; upload it first".to_string())
+        })?;
+        self.files.push((uri, file.mime_type.clone()));
+        Ok(self)
+    }
+
+    /// Add a file by URI to the cached prefix
+    pub fn with_file_uri<S: Into<String>>(mut self, uri: S, mime_type: Option<String>) -> Self {
+        self.files.push((uri.into(), mime_type));
+        self
+    }
+
+    /// Set the tools stored in the cache
+    pub fn with_tools(mut self, tools: Vec<Tool>) -> Self {
+        self.tools = Some(tools);
+        self
+    }
+
+    /// Set the time to live
+    pub const fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+/// Gemini cached contents capability.
+///
+/// # Supported Operations
+/// - Create a cache from messages, files and a system instruction
+/// - Get, list and delete cached contents
+/// - Update the TTL of a cached content
+///
+/// A cached content is used by setting [`GeminiConfig::with_cached_content`]
+/// (or `GeminiClient::with_cached_content`) to its name.
+#[derive(Debug, Clone)]
+pub struct GeminiCachedContents {
+    /// Gemini configuration
+    config: GeminiConfig,
+    /// HTTP client
+    http_client: HttpClient,
+}
+
+impl GeminiCachedContents {
+    /// Create a new Gemini cached contents capability
+    pub const fn new(config: GeminiConfig, http_client: HttpClient) -> Self {
+        Self {
+            config,
+            http_client,
+        }
+    }
+
+    /// Create a cached content
+    pub async fn create(&self, request: CachedContentRequest) -> Result<CachedContent, LlmError> {
+        let body = self.build_cached_content(request)?;
+        let response = send_request(
+            self.make_request(reqwest::Method::POST, "cachedContents")
+                .json(&body),
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            "",
+            0,
+        )
+        .await?;
+
+        self.parse_response(response).await
+    }
+
+    /// Get a cached content by name (`cachedContents/abc` or `abc`)
+    pub async fn get(&self, name: &str) -> Result<CachedContent, LlmError> {
+        let response = send_request(
+            self.make_request(reqwest::Method::GET, &Self::resource_name(name)),
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            "",
+            0,
+        )
+        .await?;
+
+        self.parse_response(response).await
+    }
+
+    /// List cached contents
+    pub async fn list(
+        &self,
+        page_size: Option<u32>,
+        page_token: Option<String>,
+    ) -> Result<ListCachedContentsResponse, LlmError> {
+        let mut params = Vec::new();
+        if let Some(size) = page_size {
+            params.push(format!("pageSize={size}"));
+        }
+        if let Some(token) = page_token {
+            params.push(format!("pageToken={token}"));
+        }
+
+        let mut endpoint = "cachedContents".to_string();
+        if !params.is_empty() {
+            endpoint.push('?');
+            endpoint.push_str(&params.join("&"));
+        }
+
+        let response = send_request(
+            self.make_request(reqwest::Method::GET, &endpoint),
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            "",
+            0,
+        )
+        .await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_response_error(response).await);
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| LlmError::ParseError(format!("Failed to parse list response: {e}")))
+    }
+
+    /// Extend or shorten the lifetime of a cached content
+    pub async fn update_ttl(&self, name: &str, ttl: Duration) -> Result<CachedContent, LlmError> {
+        let endpoint = format!("{}?updateMask=ttl", Self::resource_name(name));
+        let body = CachedContent {
+            ttl: Some(Self::format_ttl(ttl)),
+            ..Default::default()
+        };
+
+        let response = send_request(
+            self.make_request(reqwest::Method::PATCH, &endpoint)
+                .json(&body),
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            "",
+            0,
+        )
+        .await?;
+
+        self.parse_response(response).await
+    }
+
+    /// Delete a cached content
+    pub async fn delete(&self, name: &str) -> Result<(), LlmError> {
+        let response = send_request(
+            self.make_request(reqwest::Method::DELETE, &Self::resource_name(name)),
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            "",
+            0,
+        )
+        .await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_response_error(response).await);
+        }
+
+        Ok(())
+    }
+
+    /// Convert a creation request into the `CachedContent` payload
+    fn build_cached_content(
+        &self,
+        request: CachedContentRequest,
+    ) -> Result<CachedContent, LlmError> {
+        // Reuse the chat conversion so cached turns match what generation would send
+        let mut config = self.config.clone();
+        config.cached_content = None;
+        let chat = GeminiChatCapability::new(config, self.http_client.clone());
+        let converted = chat.build_request_body(&request.messages, request.tools.as_deref())?;
+
+        let mut contents = Vec::new();
+        if !request.files.is_empty() {
+            let parts = request
+                .files
+                .into_iter()
+                .map(|(uri, mime_type)| Part::file_data(uri, mime_type))
+                .collect();
+            contents.push(Content::new(Some("user".to_string()), parts));
+        }
+        contents.extend(converted.contents);
+
+        if contents.is_empty() && request.system_instruction.is_none() {
+            return Err(LlmError::InvalidInput(
+                "Cached content needs at least one message, file or system instruction".to_string(),
+            ));
+        }
+
+        let model = request.model.unwrap_or_else(|| self.config.model.clone());
+        let model = if model.starts_with("models/") {
+            model
+        } else {
+            format!("models/{model}")
+        };
+
+        Ok(CachedContent {
+            display_name: request.display_name,
+            model: Some(model),
+            system_instruction: request
+                .system_instruction
+                .map(Content::system_text)
+                .or(converted.system_instruction),
+            contents: (!contents.is_empty()).then_some(contents),
+            tools: converted.tools,
+            ttl: request.ttl.map(Self::format_ttl),
+            ..Default::default()
+        })
+    }
+
+    /// Normalize a cached content name to its resource path
+    fn resource_name(name: &str) -> String {
+        if name.starts_with("cachedContents/") {
+            name.to_string()
+        } else {
+            format!("cachedContents/{name}")
+        }
+    }
+
+    /// Format a duration as a protobuf `Duration` string
+    fn format_ttl(ttl: Duration) -> String {
+        format!("{}s", ttl.as_secs())
+    }
+
+    /// Make a request to the Gemini API
+    fn make_request(&self, method: reqwest::Method, endpoint: &str) -> reqwest::RequestBuilder {
+        let url = crate::utils::url::join_url(&self.config.base_url, endpoint);
+
+        self.http_client
+            .request(method, &url)
+            .header("x-goog-api-key", &self.config.api_key)
+            .header("Content-Type", "application/json")
+    }
+
+    /// Parse a single cached content response
+    async fn parse_response(&self, response: reqwest::Response) -> Result<CachedContent, LlmError> {
+        if !response.status().is_success() {
+            return Err(self.handle_response_error(response).await);
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| LlmError::ParseError(format!("Failed to parse cached content: {e}")))
+    }
+
+    /// Handle API response errors
+    async fn handle_response_error(&self, response: reqwest::Response) -> LlmError {
+        let status_code = response.status().as_u16();
+        let error_text = response.text().await.unwrap_or_default();
+
+        LlmError::api_error(
+            status_code,
+            format!("Gemini API error: {status_code} - {error_text}"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capability() -> GeminiCachedContents {
+        let config = GeminiConfig::new("test-key".to_string())
+            .with_model("gemini-1.5-flash-001".to_string());
+        GeminiCachedContents::new(config, HttpClient::new())
+    }
+
+    #[test]
+    fn test_build_cached_content_from_messages_and_files() {
+        let request = CachedContentRequest::new()
+            .with_display_name("contract")
+            .with_system_instruction("Answer questions about the contract")
+            .with_file_uri(
+                "https://generativelanguage.googleapis.com/v1beta/files/abc",
+                Some("application/pdf".to_string()),
+            )
+            .with_messages(vec![ChatMessage::user("Summarize section 2").build()])
+            .with_ttl(Duration::from_secs(3600));
+
+        let cached = capability().build_cached_content(request).unwrap();
+        let json = serde_json::to_value(&cached).unwrap();
+
+        assert_eq!(json["model"], "models/gemini-1.5-flash-001");
+        assert_eq!(json["displayName"], "contract");
+        assert_eq!(json["ttl"], "3600s");
+        assert_eq!(
+            json["systemInstruction"]["parts"][0]["text"],
+            "Answer questions about the contract"
+        );
+        assert_eq!(json["contents"].as_array().unwrap().len(), 2);
+        assert!(json["contents"][0]["parts"][0]["fileData"].is_object());
+    }
+
+    #[test]
+    fn test_build_cached_content_requires_content() {
+        assert!(
+            capability()
+                .build_cached_content(CachedContentRequest::new())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_generate_request_references_cached_content() {
+        let config =
+            GeminiConfig::new("test-key".to_string()).with_cached_content("cachedContents/abc");
+        let chat = GeminiChatCapability::new(config, HttpClient::new());
+        let request = chat
+            .build_request_body(
+                &[
+                    ChatMessage::system("ignored, lives in the cache").build(),
+                    ChatMessage::user("What does clause 4 say?").build(),
+                ],
+                None,
+            )
+            .unwrap();
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["cachedContent"], "cachedContents/abc");
+        assert!(json.get("systemInstruction").is_none());
+        assert_eq!(json["contents"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_resource_name_normalization() {
+        assert_eq!(
+            GeminiCachedContents::resource_name("abc"),
+            "cachedContents/abc"
+        );
+        assert_eq!(
+            GeminiCachedContents::resource_name("cachedContents/abc"),
+            "cachedContents/abc"
+        );
+    }
+}
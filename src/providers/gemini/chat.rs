@@ -8,18 +8,19 @@ use serde_json::json;
 use std::time::Instant;
 
 use crate::error::LlmError;
+use crate::rate_limiter::{estimate_request_tokens, send_request};
 use crate::stream::ChatStream;
 use crate::tracing::ProviderTracer;
 use crate::traits::ChatCapability;
 use crate::types::{
-    ChatMessage, ChatResponse, FinishReason, MessageContent, ResponseMetadata, Tool, ToolCall,
-    Usage,
+    ChatMessage, ChatRequest, ChatResponse, DocumentSource, FinishReason, MessageContent,
+    ResponseMetadata, Tool, ToolCall, Usage,
 };
 
 use super::streaming::GeminiStreaming;
 use super::types::{
-    Content, FunctionCall, FunctionDeclaration, GeminiConfig, GeminiTool, GenerateContentRequest,
-    GenerateContentResponse, Part,
+    Content, CountTokensRequest, CountTokensResponse, FunctionCall, FunctionDeclaration,
+    GeminiConfig, GeminiTool, GenerateContentRequest, GenerateContentResponse, GoogleSearch, Part,
 };
 
 /// Gemini chat capability implementation
@@ -33,7 +34,7 @@ pub struct GeminiChatCapability {
 impl GeminiChatCapability {
     /// Create a new Gemini chat capability
     pub fn new(config: GeminiConfig, http_client: HttpClient) -> Self {
-        let streaming = GeminiStreaming::new(http_client.clone());
+        let streaming = GeminiStreaming::new(http_client.clone()).with_config(config.clone());
         Self {
             config,
             http_client,
@@ -41,6 +42,29 @@ impl GeminiChatCapability {
         }
     }
 
+    /// Convert a document to an inline or file data part
+    fn convert_document(&self, source: &DocumentSource, media_type: &str) -> Part {
+        match source {
+            DocumentSource::Base64(data) => Part::InlineData {
+                inline_data: super::types::Blob {
+                    mime_type: media_type.to_string(),
+                    data: data.clone(),
+                },
+            },
+            DocumentSource::Url(url) => Part::file_data(url.clone(), Some(media_type.to_string())),
+            DocumentSource::FileId(file_id) => {
+                // Files API ids resolve to `{base_url}/files/{id}`
+                let file_uri = if file_id.starts_with("https://") || file_id.starts_with("gs://") {
+                    file_id.clone()
+                } else {
+                    let name = file_id.strip_prefix("files/").unwrap_or(file_id);
+                    crate::utils::url::join_url(&self.config.base_url, &format!("files/{name}"))
+                };
+                Part::file_data(file_uri, Some(media_type.to_string()))
+            }
+        }
+    }
+
     /// Parse data URL to extract MIME type and base64 data
     fn parse_data_url(&self, data_url: &str) -> Option<(String, String)> {
         if let Some(comma_pos) = data_url.find(',') {
@@ -138,6 +162,11 @@ impl GeminiChatCapability {
                                 });
                             }
                         }
+                        crate::types::ContentPart::Document {
+                            source, media_type, ..
+                        } => {
+                            parts.push(self.convert_document(source, media_type));
+                        }
                         crate::types::ContentPart::Audio {
                             audio_url,
                             format: _,
@@ -284,6 +313,22 @@ impl GeminiChatCapability {
             None
         };
 
+        // A cached content already carries the system instruction and tools;
+        // the API rejects requests that set them again
+        let cached_content = self.config.cached_content.clone();
+        if let Some(name) = &cached_content {
+            if gemini_tools.is_some() {
+                return Err(LlmError::InvalidInput(format!(
+                    "Tools cannot be passed with cached content '{name}'; include them when creating the cache"
+                )));
+            }
+            if system_instruction.take().is_some() {
+                tracing::warn!(
+                    "System messages are ignored with cached content '{name}'; the cached system instruction is used"
+                );
+            }
+        }
+
         Ok(GenerateContentRequest {
             model: self.config.model.clone(), // Don't add "models/" prefix here
             contents,
@@ -292,10 +337,39 @@ impl GeminiChatCapability {
             tool_config: None,
             safety_settings: self.config.safety_settings.clone(),
             generation_config: self.config.generation_config.clone(),
-            cached_content: None,
+            cached_content,
         })
     }
 
+    /// Build the request body for a `ChatRequest`, adding Google Search grounding
+    /// when the request enables web search
+    pub fn build_chat_request(
+        &self,
+        request: &ChatRequest,
+    ) -> Result<GenerateContentRequest, LlmError> {
+        let mut body = self.build_request_body(&request.messages, request.tools.as_deref())?;
+
+        if request
+            .web_search
+            .as_ref()
+            .is_some_and(|config| config.enabled)
+        {
+            // Tools cannot be added on top of a cached content
+            if let Some(name) = &body.cached_content {
+                return Err(LlmError::InvalidInput(format!(
+                    "Web search cannot be enabled with cached content '{name}'"
+                )));
+            }
+            body.tools
+                .get_or_insert_with(Vec::new)
+                .push(GeminiTool::GoogleSearch {
+                    google_search: GoogleSearch::default(),
+                });
+        }
+
+        Ok(body)
+    }
+
     /// Convert Gemini response to `ChatResponse`
     fn convert_response(
         &self,
@@ -408,10 +482,16 @@ impl GeminiChatCapability {
                 prompt_tokens: usage_metadata.prompt_token_count.unwrap_or(0) as u32,
                 completion_tokens: usage_metadata.candidates_token_count.unwrap_or(0) as u32,
                 total_tokens: usage_metadata.total_token_count.unwrap_or(0) as u32,
-                cached_tokens: None,
+                cached_tokens: usage_metadata.cached_content_token_count.map(|t| t as u32),
+                cache_write_tokens: None,
                 reasoning_tokens: usage_metadata.thoughts_token_count.map(|t| t as u32),
             });
 
+        let (citations, sources) = super::types::collect_citations(
+            candidate.citation_metadata.as_ref(),
+            candidate.grounding_metadata.as_ref(),
+        );
+
         // Determine finish reason
         let finish_reason = candidate.finish_reason.as_ref().map(|reason| match reason {
             super::types::FinishReason::Stop => FinishReason::Stop,
@@ -457,6 +537,8 @@ impl GeminiChatCapability {
             } else {
                 Some(thinking_content)
             },
+            citations,
+            sources,
             metadata: provider_data,
         })
     }
@@ -489,15 +571,19 @@ impl GeminiChatCapability {
 
         tracer.trace_request_details(&headers, &request_json);
 
-        let response = self
-            .http_client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .header("x-goog-api-key", &self.config.api_key)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(e.to_string()))?;
+        let response = send_request(
+            self.http_client
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .header("x-goog-api-key", &self.config.api_key)
+                .json(&request),
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            &self.config.model,
+            estimate_request_tokens(&request),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status_code = response.status().as_u16();
@@ -532,6 +618,87 @@ impl GeminiChatCapability {
     }
 }
 
+impl GeminiChatCapability {
+    /// Chat with a `ChatRequest`, honoring its web search setting
+    pub async fn chat(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        let request = self.build_chat_request(&request)?;
+        let response = self.make_request(request).await?;
+        self.convert_response(response)
+    }
+
+    /// Chat stream with a `ChatRequest`, honoring its web search setting
+    pub async fn chat_stream_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
+        let request = self.build_chat_request(&request)?;
+        self.stream_request(request).await
+    }
+
+    /// Start a streaming `streamGenerateContent` request
+    async fn stream_request(
+        &self,
+        request: GenerateContentRequest,
+    ) -> Result<ChatStream, LlmError> {
+        let url = crate::utils::url::join_url(
+            &self.config.base_url,
+            &format!("models/{}:streamGenerateContent?alt=sse", self.config.model),
+        );
+
+        // Use the dedicated streaming capability
+        self.streaming
+            .clone()
+            .create_chat_stream(url, self.config.api_key.clone(), request)
+            .await
+    }
+
+    /// Count the input tokens of a conversation with the `countTokens` endpoint
+    ///
+    /// API Reference: <https://ai.google.dev/api/tokens>
+    pub async fn count_tokens(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+    ) -> Result<u32, LlmError> {
+        let mut request = self.build_request_body(messages, tools)?;
+        request.model = format!("models/{}", self.config.model);
+
+        let url = crate::utils::url::join_url(
+            &self.config.base_url,
+            &format!("models/{}:countTokens", self.config.model),
+        );
+
+        let response = send_request(
+            self.http_client
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .header("x-goog-api-key", &self.config.api_key)
+                .json(&CountTokensRequest {
+                    generate_content_request: request,
+                }),
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            "",
+            0,
+        )
+        .await
+        .map_err(|e| LlmError::HttpError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status_code = response.status().as_u16();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(LlmError::api_error(
+                status_code,
+                format!("Gemini API error: {status_code} - {error_text}"),
+            ));
+        }
+
+        let count: CountTokensResponse = response
+            .json()
+            .await
+            .map_err(|e| LlmError::ParseError(format!("Failed to parse response: {e}")))?;
+        Ok(count.total_tokens)
+    }
+}
+
 #[async_trait]
 impl ChatCapability for GeminiChatCapability {
     async fn chat_with_tools(
@@ -550,16 +717,52 @@ impl ChatCapability for GeminiChatCapability {
         tools: Option<Vec<Tool>>,
     ) -> Result<ChatStream, LlmError> {
         let request = self.build_request_body(&messages, tools.as_deref())?;
+        self.stream_request(request).await
+    }
+}
 
-        let url = crate::utils::url::join_url(
-            &self.config.base_url,
-            &format!("models/{}:streamGenerateContent?alt=sse", self.config.model),
-        );
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::WebSearchConfig;
 
-        // Use the dedicated streaming capability
-        self.streaming
-            .clone()
-            .create_chat_stream(url, self.config.api_key.clone(), request)
-            .await
+    #[test]
+    fn test_build_chat_request_adds_google_search() {
+        let chat =
+            GeminiChatCapability::new(GeminiConfig::new("test-key".to_string()), HttpClient::new());
+        let request = ChatRequest::new(vec![ChatMessage::user("news?").build()]);
+
+        let body = chat.build_chat_request(&request).unwrap();
+        assert!(body.tools.is_none());
+
+        let request = request.with_web_search(WebSearchConfig {
+            enabled: true,
+            ..Default::default()
+        });
+        let json = serde_json::to_value(chat.build_chat_request(&request).unwrap()).unwrap();
+        assert_eq!(json["tools"], json!([{ "google_search": {} }]));
+    }
+
+    #[test]
+    fn test_cached_content_rejects_tools_and_web_search() {
+        let config =
+            GeminiConfig::new("test-key".to_string()).with_cached_content("cachedContents/abc");
+        let chat = GeminiChatCapability::new(config, HttpClient::new());
+        let messages = vec![ChatMessage::user("What does clause 4 say?").build()];
+
+        let tools = vec![Tool::function(
+            "lookup".to_string(),
+            "Look up a clause".to_string(),
+            json!({"type": "object"}),
+        )];
+        let result = chat.build_request_body(&messages, Some(&tools));
+        assert!(matches!(result, Err(LlmError::InvalidInput(_))));
+
+        let request = ChatRequest::new(messages).with_web_search(WebSearchConfig {
+            enabled: true,
+            ..Default::default()
+        });
+        let result = chat.build_chat_request(&request);
+        assert!(matches!(result, Err(LlmError::InvalidInput(_))));
     }
 }
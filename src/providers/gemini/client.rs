@@ -4,6 +4,7 @@
 
 use async_trait::async_trait;
 use reqwest::Client as HttpClient;
+use std::collections::HashMap;
 use std::time::Duration;
 
 use crate::client::LlmClient;
@@ -11,7 +12,9 @@ use crate::error::LlmError;
 use crate::stream::ChatStream;
 use crate::traits::*;
 use crate::types::*;
+use crate::web_search::{WebSearchCapability, WebSearchProvider};
 
+use super::cached_contents::GeminiCachedContents;
 use super::chat::GeminiChatCapability;
 use super::embeddings::GeminiEmbeddings;
 use super::files::GeminiFiles;
@@ -37,6 +40,8 @@ pub struct GeminiClient {
     pub models_capability: GeminiModels,
     /// Files capability implementation
     pub files_capability: GeminiFiles,
+    /// Cached contents capability implementation
+    pub cached_contents_capability: GeminiCachedContents,
     /// Tracing configuration
     tracing_config: Option<crate::tracing::TracingConfig>,
     /// Tracing guard to keep tracing system active
@@ -54,6 +59,7 @@ impl Clone for GeminiClient {
             embedding_capability: self.embedding_capability.clone(),
             models_capability: self.models_capability.clone(),
             files_capability: self.files_capability.clone(),
+            cached_contents_capability: self.cached_contents_capability.clone(),
             tracing_config: self.tracing_config.clone(),
             _tracing_guard: None, // Don't clone the tracing guard
         }
@@ -80,6 +86,9 @@ impl GeminiClient {
 
         let files_capability = GeminiFiles::new(config.clone(), http_client.clone());
 
+        let cached_contents_capability =
+            GeminiCachedContents::new(config.clone(), http_client.clone());
+
         // Extract common parameters from config
         let common_params = CommonParams {
             model: config.model.clone(),
@@ -126,6 +135,7 @@ impl GeminiClient {
             embedding_capability,
             models_capability,
             files_capability,
+            cached_contents_capability,
             tracing_config: None,
             _tracing_guard: None,
         })
@@ -165,6 +175,22 @@ impl GeminiClient {
         self
     }
 
+    /// Reference a cached content (`cachedContents/...`) in every generation request
+    ///
+    /// The cache supplies the system instruction and tools, so requests that
+    /// pass tools or enable web search are rejected.
+    pub fn with_cached_content<S: Into<String>>(mut self, name: S) -> Self {
+        self.config.cached_content = Some(name.into());
+        self.chat_capability =
+            GeminiChatCapability::new(self.config.clone(), self.http_client.clone());
+        self
+    }
+
+    /// Get the cached contents capability
+    pub const fn cached_contents(&self) -> &GeminiCachedContents {
+        &self.cached_contents_capability
+    }
+
     /// Set HTTP timeout
     pub const fn with_timeout(mut self, timeout: Duration) -> Self {
         self.config.timeout = Some(timeout.as_secs());
@@ -411,6 +437,10 @@ impl GeminiClient {
 
 #[async_trait]
 impl ChatCapability for GeminiClient {
+    fn default_model(&self) -> Option<String> {
+        Some(self.model().to_string()).filter(|model| !model.is_empty())
+    }
+
     async fn chat_with_tools(
         &self,
         messages: Vec<ChatMessage>,
@@ -426,6 +456,178 @@ impl ChatCapability for GeminiClient {
     ) -> Result<ChatStream, LlmError> {
         self.chat_capability.chat_stream(messages, tools).await
     }
+
+    /// Chat with structured output via `responseMimeType` and `responseSchema`
+    async fn chat_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatResponse, LlmError> {
+        self.chat_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    /// Stream structured output via `responseMimeType` and `responseSchema`
+    async fn chat_stream_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatStream, LlmError> {
+        self.chat_stream_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    /// Chat with parameter overrides
+    ///
+    /// Common parameters are mapped onto the generation config; `seed` is
+    /// not sent. Provider parameters cannot be overridden per request.
+    async fn chat_with_request(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        self.request_capability(&request)?.chat(request).await
+    }
+
+    async fn chat_stream_with_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
+        self.request_capability(&request)?
+            .chat_stream_request(request)
+            .await
+    }
+}
+
+impl GeminiClient {
+    /// Chat capability with a request's common parameters and output schema applied
+    fn request_capability(&self, request: &ChatRequest) -> Result<GeminiChatCapability, LlmError> {
+        if request.provider_params.is_some() {
+            return Err(LlmError::UnsupportedOperation(
+                "Gemini does not support per-request provider parameters".to_string(),
+            ));
+        }
+
+        let params = &request.common_params;
+        let mut config = self.config.clone();
+        if !params.model.is_empty() {
+            config.model = params.model.clone();
+        }
+        let mut generation_config = config.generation_config.unwrap_or_default();
+        if let Some(temperature) = params.temperature {
+            generation_config.temperature = Some(temperature);
+        }
+        if let Some(top_p) = params.top_p {
+            generation_config.top_p = Some(top_p);
+        }
+        if let Some(max_tokens) = params.max_tokens {
+            generation_config.max_output_tokens = Some(max_tokens as i32);
+        }
+        if let Some(stop_sequences) = &params.stop_sequences {
+            generation_config.stop_sequences = Some(stop_sequences.clone());
+        }
+        if let Some(schema) = &request.output_schema {
+            generation_config.response_mime_type = Some("application/json".to_string());
+            generation_config.response_schema = Some(schema.to_gemini_schema());
+        }
+        config.generation_config = Some(generation_config);
+
+        Ok(GeminiChatCapability::new(config, self.http_client.clone()))
+    }
+}
+
+#[async_trait]
+impl VisionCapability for GeminiClient {
+    /// Analyze images using inline data parts
+    ///
+    /// Arbitrary `http(s)` URLs are downloaded first since `fileData` only accepts
+    /// Google-hosted file URIs; `gs://` URIs are passed through.
+    async fn analyze_image(&self, request: VisionRequest) -> Result<VisionResponse, LlmError> {
+        let messages =
+            crate::multimodal::build_vision_messages(&request, Some(&self.http_client)).await?;
+
+        let mut config = self.config.clone();
+        if let Some(model) = &request.model {
+            config.model = model.clone();
+        }
+        if let Some(max_tokens) = request.max_tokens {
+            let mut generation_config = config.generation_config.unwrap_or_default();
+            generation_config.max_output_tokens = Some(max_tokens as i32);
+            config.generation_config = Some(generation_config);
+        }
+
+        GeminiChatCapability::new(config, self.http_client.clone())
+            .chat_with_tools(messages, None)
+            .await
+            .map(Into::into)
+    }
+
+    /// Generate images with an image-capable Gemini model via `responseModalities`
+    async fn generate_image(&self, request: ImageGenRequest) -> Result<ImageResponse, LlmError> {
+        let image_models = super::model_constants::all_image_generation_models();
+        let mut config = self.config.clone();
+        config.model = request.model.clone().unwrap_or_else(|| {
+            if image_models.contains(&config.model.as_str()) {
+                config.model.clone()
+            } else {
+                super::model_constants::gemini_2_0_flash_image_gen::GEMINI_2_0_FLASH_PREVIEW_IMAGE_GENERATION
+                    .to_string()
+            }
+        });
+        let mut generation_config = config.generation_config.unwrap_or_default();
+        generation_config.response_modalities = Some(vec!["TEXT".to_string(), "IMAGE".to_string()]);
+        config.generation_config = Some(generation_config);
+
+        let mut prompt = request.prompt;
+        if let Some(negative_prompt) = request.negative_prompt {
+            prompt.push_str(&format!("\n\nAvoid: {negative_prompt}"));
+        }
+
+        let response = GeminiChatCapability::new(config, self.http_client.clone())
+            .chat_with_tools(vec![ChatMessage::user(prompt).build()], None)
+            .await?;
+
+        let images: Vec<GeneratedImage> = match &response.content {
+            MessageContent::MultiModal(parts) => parts
+                .iter()
+                .filter_map(|part| match part {
+                    ContentPart::Image { image_url, .. } => Some(image_url),
+                    _ => None,
+                })
+                .map(|image_url| {
+                    let (url, b64_json, format) =
+                        match crate::multimodal::NormalizedImage::parse(image_url) {
+                            crate::multimodal::NormalizedImage::Url(url) => (Some(url), None, None),
+                            crate::multimodal::NormalizedImage::Inline { media_type, data } => {
+                                (None, Some(data), Some(media_type))
+                            }
+                        };
+                    GeneratedImage {
+                        url,
+                        b64_json,
+                        format,
+                        width: None,
+                        height: None,
+                        revised_prompt: None,
+                        metadata: HashMap::new(),
+                    }
+                })
+                .collect(),
+            MessageContent::Text(_) => Vec::new(),
+        };
+
+        if images.is_empty() {
+            return Err(LlmError::ParseError(
+                "Gemini response did not contain any images".to_string(),
+            ));
+        }
+
+        let mut metadata = HashMap::new();
+        if let Some(model) = response.model {
+            metadata.insert("model".to_string(), serde_json::Value::String(model));
+        }
+        if let Some(text) = response.content.text() {
+            metadata.insert(
+                "text".to_string(),
+                serde_json::Value::String(text.to_string()),
+            );
+        }
+
+        Ok(ImageGenerationResponse { images, metadata })
+    }
 }
 
 #[async_trait]
@@ -447,6 +649,23 @@ impl EmbeddingCapability for GeminiClient {
     }
 }
 
+#[async_trait]
+impl crate::token_counter::TokenCounter for GeminiClient {
+    async fn count_text(&self, text: &str) -> Result<u32, LlmError> {
+        self.chat_capability
+            .count_tokens(&[ChatMessage::user(text).build()], None)
+            .await
+    }
+
+    async fn count_messages(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+    ) -> Result<u32, LlmError> {
+        self.chat_capability.count_tokens(messages, tools).await
+    }
+}
+
 #[async_trait]
 impl ModelListingCapability for GeminiClient {
     async fn list_models(&self) -> Result<Vec<ModelInfo>, LlmError> {
@@ -481,6 +700,29 @@ impl FileManagementCapability for GeminiClient {
     }
 }
 
+#[async_trait]
+impl WebSearchCapability for GeminiClient {
+    /// Answer the query with Google Search grounding and return the grounding sources
+    async fn web_search(
+        &self,
+        query: String,
+        config: Option<WebSearchConfig>,
+    ) -> Result<Vec<WebSearchResult>, LlmError> {
+        let provider = WebSearchProvider::for_query("gemini", config);
+        let request = provider.search_request(query, self.common_params.clone());
+        let response = self.chat_capability.chat(request).await?;
+        Ok(provider.results_from_response(&response))
+    }
+
+    fn supports_web_search(&self) -> bool {
+        true
+    }
+
+    fn web_search_strategy(&self) -> WebSearchStrategy {
+        WebSearchStrategy::BuiltIn
+    }
+}
+
 impl LlmClient for GeminiClient {
     fn provider_name(&self) -> &'static str {
         "gemini"
@@ -513,6 +755,7 @@ impl LlmClient for GeminiClient {
             .with_custom_feature("json_schema", true)
             .with_custom_feature("image_generation", true)
             .with_custom_feature("enum_output", true)
+            .with_custom_feature("web_search", true)
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -526,6 +769,10 @@ impl LlmClient for GeminiClient {
     fn as_embedding_capability(&self) -> Option<&dyn EmbeddingCapability> {
         Some(self)
     }
+
+    fn as_vision_capability(&self) -> Option<&dyn VisionCapability> {
+        Some(self)
+    }
 }
 
 /// Builder for creating Gemini clients
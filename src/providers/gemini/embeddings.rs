@@ -7,6 +7,7 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 use crate::error::LlmError;
+use crate::rate_limiter::{estimate_request_tokens, send_request};
 use crate::traits::{EmbeddingCapability, EmbeddingExtensions, GeminiEmbeddingCapability};
 use crate::types::{EmbeddingModelInfo, EmbeddingRequest, EmbeddingResponse, EmbeddingTaskType};
 
@@ -208,15 +209,19 @@ impl GeminiEmbeddings {
             &format!("models/{model}:embedContent"),
         );
 
-        let response = self
-            .http_client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .header("x-goog-api-key", &self.config.api_key)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(e.to_string()))?;
+        let response = send_request(
+            self.http_client
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .header("x-goog-api-key", &self.config.api_key)
+                .json(&request),
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            model,
+            estimate_request_tokens(&request),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status_code = response.status().as_u16();
@@ -251,15 +256,19 @@ impl GeminiEmbeddings {
             &format!("models/{model}:batchEmbedContents"),
         );
 
-        let response = self
-            .http_client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .header("x-goog-api-key", &self.config.api_key)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(e.to_string()))?;
+        let response = send_request(
+            self.http_client
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .header("x-goog-api-key", &self.config.api_key)
+                .json(&request),
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            model,
+            estimate_request_tokens(&request),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status_code = response.status().as_u16();
@@ -523,6 +532,9 @@ mod tests {
             generation_config: None,
             safety_settings: None,
             timeout: Some(30),
+            rate_limiter: None,
+            cassette: None,
+            cached_content: None,
         };
         let client = reqwest::Client::new();
         let embeddings = GeminiEmbeddings::new(config, client);
@@ -540,6 +552,9 @@ mod tests {
             generation_config: None,
             safety_settings: None,
             timeout: Some(30),
+            rate_limiter: None,
+            cassette: None,
+            cached_content: None,
         };
         let client = reqwest::Client::new();
         let embeddings = GeminiEmbeddings::new(config, client);
@@ -557,6 +572,9 @@ mod tests {
             generation_config: None,
             safety_settings: None,
             timeout: Some(30),
+            rate_limiter: None,
+            cassette: None,
+            cached_content: None,
         };
         let client = reqwest::Client::new();
         let embeddings = GeminiEmbeddings::new(config, client);
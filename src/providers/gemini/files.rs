@@ -9,6 +9,7 @@ use serde_json::json;
 use std::collections::HashMap;
 
 use crate::error::LlmError;
+use crate::rate_limiter::send_request;
 use crate::traits::FileManagementCapability;
 use crate::types::{
     FileDeleteResponse, FileListQuery, FileListResponse, FileObject, FileUploadRequest,
@@ -223,14 +224,18 @@ impl FileManagementCapability for GeminiFiles {
         }
 
         let url = format!("{}/files", self.config.base_url);
-        let response = self
-            .http_client
-            .post(&url)
-            .header("x-goog-api-key", &self.config.api_key)
-            .multipart(form)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            self.http_client
+                .post(&url)
+                .header("x-goog-api-key", &self.config.api_key)
+                .multipart(form),
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             return Err(self.handle_response_error(response).await);
@@ -269,10 +274,15 @@ impl FileManagementCapability for GeminiFiles {
         }
 
         let request_builder = self.make_request(reqwest::Method::GET, &endpoint).await?;
-        let response = request_builder
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            request_builder,
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             return Err(self.handle_response_error(response).await);
@@ -307,10 +317,15 @@ impl FileManagementCapability for GeminiFiles {
 
         let endpoint = &full_file_name;
         let request_builder = self.make_request(reqwest::Method::GET, endpoint).await?;
-        let response = request_builder
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            request_builder,
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             return Err(self.handle_response_error(response).await);
@@ -335,10 +350,15 @@ impl FileManagementCapability for GeminiFiles {
 
         let endpoint = &full_file_name;
         let request_builder = self.make_request(reqwest::Method::DELETE, endpoint).await?;
-        let response = request_builder
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            request_builder,
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             return Err(self.handle_response_error(response).await);
@@ -388,13 +408,18 @@ impl GeminiFiles {
             })?;
 
         // Download the file content
-        let response = self
-            .http_client
-            .get(download_uri)
-            .header("x-goog-api-key", &self.config.api_key)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Download request failed: {e}")))?;
+        let response = send_request(
+            self.http_client
+                .get(download_uri)
+                .header("x-goog-api-key", &self.config.api_key),
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            "",
+            0,
+        )
+        .await
+        .map_err(|e| LlmError::HttpError(format!("Download request failed: {e}")))?;
 
         if !response.status().is_success() {
             return Err(self.handle_response_error(response).await);
@@ -8,6 +8,7 @@
 //! - `chat.rs` - Chat completion capability implementation
 //! - `models.rs` - Model listing capability implementation
 //! - `files.rs` - File management capability implementation
+//! - `cached_contents.rs` - Context caching (`cachedContents`) implementation
 //! - `code_execution.rs` - Code execution feature implementation
 //! - `streaming.rs` - Streaming functionality with JSON buffering
 //! - `embeddings.rs` - Text embedding capability implementation
@@ -34,6 +35,7 @@
 //! ```
 
 // Core modules
+pub mod cached_contents;
 pub mod chat;
 pub mod client;
 pub mod embeddings;
@@ -48,6 +50,7 @@ pub mod types;
 pub mod code_execution;
 
 // Re-export main types for convenience
+pub use cached_contents::{CachedContentRequest, GeminiCachedContents};
 pub use chat::GeminiChatCapability;
 pub use client::{GeminiBuilder, GeminiClient};
 pub use embeddings::GeminiEmbeddings;
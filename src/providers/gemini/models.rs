@@ -7,6 +7,7 @@ use reqwest::Client as HttpClient;
 use serde::{Deserialize, Serialize};
 
 use crate::error::LlmError;
+use crate::rate_limiter::send_request;
 use crate::traits::ModelListingCapability;
 use crate::types::ModelInfo;
 
@@ -154,13 +155,18 @@ impl GeminiModels {
                 url.push_str(&params.join("&"));
             }
 
-            let response = self
-                .http_client
-                .get(&url)
-                .header("x-goog-api-key", &self.config.api_key)
-                .send()
-                .await
-                .map_err(|e| LlmError::HttpError(e.to_string()))?;
+            let response = send_request(
+                self.http_client
+                    .get(&url)
+                    .header("x-goog-api-key", &self.config.api_key),
+                self.config.rate_limiter.as_deref(),
+                self.config.cassette.as_deref(),
+                "gemini",
+                "",
+                0,
+            )
+            .await
+            .map_err(|e| LlmError::HttpError(e.to_string()))?;
 
             if !response.status().is_success() {
                 let status_code = response.status().as_u16();
@@ -219,13 +225,18 @@ impl ModelListingCapability for GeminiModels {
 
         let url = crate::utils::url::join_url(&self.config.base_url, &full_model_name);
 
-        let response = self
-            .http_client
-            .get(&url)
-            .header("x-goog-api-key", &self.config.api_key)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(e.to_string()))?;
+        let response = send_request(
+            self.http_client
+                .get(&url)
+                .header("x-goog-api-key", &self.config.api_key),
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            "",
+            0,
+        )
+        .await
+        .map_err(|e| LlmError::HttpError(e.to_string()))?;
 
         if !response.status().is_success() {
             let status_code = response.status().as_u16();
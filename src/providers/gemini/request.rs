@@ -230,6 +230,8 @@ impl RequestBuilder for GeminiRequestBuilder {
             http_config: None,
             web_search: None,
             stream,
+            prompt_cache: None,
+            output_schema: None,
         };
 
         // Validate the request
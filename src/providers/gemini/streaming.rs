@@ -4,10 +4,13 @@
 //! eventsource-stream infrastructure for JSON streaming.
 
 use crate::error::LlmError;
-use crate::providers::gemini::types::GeminiConfig;
+use crate::providers::gemini::types::{
+    CitationMetadata, GeminiConfig, GroundingMetadata, collect_citations,
+};
+use crate::rate_limiter::{estimate_request_tokens, send_request};
 use crate::stream::{ChatStream, ChatStreamEvent};
 use crate::types::{ChatResponse, FinishReason, MessageContent, Usage};
-use crate::utils::streaming::{SseEventConverter, StreamFactory};
+use crate::utils::streaming::{SseEventConverter, StreamFactory, with_grounding_events};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::future::Future;
@@ -27,6 +30,10 @@ struct GeminiCandidate {
     content: Option<GeminiContent>,
     #[serde(rename = "finishReason")]
     finish_reason: Option<String>,
+    #[serde(rename = "citationMetadata")]
+    citation_metadata: Option<CitationMetadata>,
+    #[serde(rename = "groundingMetadata")]
+    grounding_metadata: Option<GroundingMetadata>,
 }
 
 /// Gemini content structure
@@ -55,6 +62,8 @@ struct GeminiUsageMetadata {
     candidates_token_count: Option<u32>,
     #[serde(rename = "totalTokenCount")]
     total_token_count: Option<u32>,
+    #[serde(rename = "cachedContentTokenCount")]
+    cached_content_token_count: Option<u32>,
     /// Number of tokens used for thinking (only for thinking models)
     #[serde(rename = "thoughtsTokenCount")]
     thoughts_token_count: Option<u32>,
@@ -114,6 +123,8 @@ impl GeminiEventConverter {
                         finish_reason: Some(reason),
                         tool_calls: None,
                         thinking: None,
+                        citations: Vec::new(),
+                        sources: Vec::new(),
                         metadata: HashMap::new(),
                     };
 
@@ -128,7 +139,8 @@ impl GeminiEventConverter {
                 prompt_tokens: usage.prompt_token_count.unwrap_or(0),
                 completion_tokens: usage.candidates_token_count.unwrap_or(0),
                 total_tokens: usage.total_token_count.unwrap_or(0),
-                cached_tokens: None,
+                cached_tokens: usage.cached_content_token_count,
+                cache_write_tokens: None,
                 reasoning_tokens: usage.thoughts_token_count,
             };
             return Some(ChatStreamEvent::UsageUpdate { usage: usage_info });
@@ -138,6 +150,24 @@ impl GeminiEventConverter {
     }
 }
 
+impl GeminiEventConverter {
+    /// Convert a Gemini stream response, including its citation and grounding metadata
+    fn convert_gemini_events(&self, response: GeminiStreamResponse) -> Vec<ChatStreamEvent> {
+        let mut citations = Vec::new();
+        let mut sources = Vec::new();
+        for candidate in response.candidates.iter().flatten() {
+            let (candidate_citations, candidate_sources) = collect_citations(
+                candidate.citation_metadata.as_ref(),
+                candidate.grounding_metadata.as_ref(),
+            );
+            citations.extend(candidate_citations);
+            sources.extend(candidate_sources);
+        }
+
+        with_grounding_events(self.convert_gemini_response(response), citations, sources)
+    }
+}
+
 impl SseEventConverter for GeminiEventConverter {
     fn convert_event(
         &self,
@@ -159,6 +189,29 @@ impl SseEventConverter for GeminiEventConverter {
             }
         })
     }
+
+    fn convert_events(
+        &self,
+        event: eventsource_stream::Event,
+    ) -> Pin<Box<dyn Future<Output = Vec<Result<ChatStreamEvent, LlmError>>> + Send + Sync + '_>>
+    {
+        Box::pin(async move {
+            if event.data.trim().is_empty() {
+                return Vec::new();
+            }
+
+            match serde_json::from_str::<GeminiStreamResponse>(&event.data) {
+                Ok(gemini_response) => self
+                    .convert_gemini_events(gemini_response)
+                    .into_iter()
+                    .map(Ok)
+                    .collect(),
+                Err(e) => vec![Err(LlmError::ParseError(format!(
+                    "Failed to parse Gemini SSE JSON: {e}"
+                )))],
+            }
+        })
+    }
 }
 
 /// Gemini streaming client
@@ -177,6 +230,12 @@ impl GeminiStreaming {
         }
     }
 
+    /// Use the client configuration (model and rate limiter) for requests
+    pub fn with_config(mut self, config: GeminiConfig) -> Self {
+        self.config = config;
+        self
+    }
+
     /// Create a chat stream from URL, API key, and request
     pub async fn create_chat_stream(
         self,
@@ -185,15 +244,19 @@ impl GeminiStreaming {
         request: crate::providers::gemini::types::GenerateContentRequest,
     ) -> Result<ChatStream, LlmError> {
         // Make the HTTP request
-        let response = self
-            .http_client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .header("x-goog-api-key", &api_key)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            self.http_client
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .header("x-goog-api-key", &api_key)
+                .json(&request),
+            self.config.rate_limiter.as_deref(),
+            self.config.cassette.as_deref(),
+            "gemini",
+            &self.config.model,
+            estimate_request_tokens(&request),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -210,17 +273,9 @@ impl GeminiStreaming {
 
         // Create the stream using SSE infrastructure (Gemini uses SSE format)
         let mut config = self.config;
-        config.api_key = api_key.clone();
+        config.api_key = api_key;
         let converter = GeminiEventConverter::new(config);
-        StreamFactory::create_eventsource_stream(
-            self.http_client
-                .post(&url)
-                .header("Content-Type", "application/json")
-                .header("x-goog-api-key", &api_key)
-                .json(&request),
-            converter,
-        )
-        .await
+        StreamFactory::create_eventsource_stream_from_response(response, converter).await
     }
 }
 
@@ -284,4 +339,52 @@ mod tests {
             panic!("Expected StreamEnd event");
         }
     }
+
+    #[tokio::test]
+    async fn test_gemini_grounding_events() {
+        let converter = GeminiEventConverter::new(create_test_config());
+
+        let json_data = r#"{"candidates":[{
+            "content":{"parts":[{"text":"Spain won Euro 2024."}]},
+            "finishReason":"STOP",
+            "groundingMetadata":{
+                "webSearchQueries":["euro 2024 winner"],
+                "groundingChunks":[{"web":{"uri":"https://uefa.example/euro","title":"uefa.com"}}],
+                "groundingSupports":[{
+                    "segment":{"startIndex":0,"endIndex":19,"text":"Spain won Euro 2024"},
+                    "groundingChunkIndices":[0]
+                }]
+            }
+        }]}"#;
+        let event = eventsource_stream::Event {
+            event: "".to_string(),
+            data: json_data.to_string(),
+            id: "".to_string(),
+            retry: None,
+        };
+
+        let events: Vec<ChatStreamEvent> = converter
+            .convert_events(event)
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+
+        assert!(matches!(events[0], ChatStreamEvent::ContentDelta { .. }));
+        match &events[1] {
+            ChatStreamEvent::SourceDelta { source } => {
+                assert_eq!(source.url, "https://uefa.example/euro");
+                assert_eq!(source.title.as_deref(), Some("uefa.com"));
+            }
+            other => panic!("Expected SourceDelta, got {other:?}"),
+        }
+        match &events[2] {
+            ChatStreamEvent::CitationDelta { citation } => {
+                assert_eq!(citation.start_index, Some(0));
+                assert_eq!(citation.end_index, Some(19));
+                assert_eq!(citation.url.as_deref(), Some("https://uefa.example/euro"));
+            }
+            other => panic!("Expected CitationDelta, got {other:?}"),
+        }
+    }
 }
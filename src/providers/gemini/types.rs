@@ -32,6 +32,22 @@ pub struct GenerateContentRequest {
     pub cached_content: Option<String>,
 }
 
+/// Gemini Count Tokens Request
+#[derive(Debug, Clone, Serialize)]
+pub struct CountTokensRequest {
+    /// The full generation request whose input is counted
+    #[serde(rename = "generateContentRequest")]
+    pub generate_content_request: GenerateContentRequest,
+}
+
+/// Gemini Count Tokens Response
+#[derive(Debug, Clone, Deserialize)]
+pub struct CountTokensResponse {
+    /// Total number of tokens in the prompt
+    #[serde(rename = "totalTokens", default)]
+    pub total_tokens: u32,
+}
+
 /// Gemini Generate Content Response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerateContentResponse {
@@ -42,7 +58,7 @@ pub struct GenerateContentResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prompt_feedback: Option<PromptFeedback>,
     /// Output only. Metadata on the generation requests' token usage.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", alias = "usageMetadata")]
     pub usage_metadata: Option<UsageMetadata>,
     /// Output only. The model version used to generate the response.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -197,8 +213,11 @@ pub struct Candidate {
     #[serde(default)]
     pub safety_ratings: Vec<SafetyRating>,
     /// Output only. Citation information for model-generated candidate.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", alias = "citationMetadata")]
     pub citation_metadata: Option<CitationMetadata>,
+    /// Output only. Grounding metadata, returned when search grounding is enabled.
+    #[serde(skip_serializing_if = "Option::is_none", alias = "groundingMetadata")]
+    pub grounding_metadata: Option<GroundingMetadata>,
     /// Output only. Token count for this candidate.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub token_count: Option<i32>,
@@ -236,14 +255,31 @@ pub enum FinishReason {
 
 /// A collection of source attributions for a piece of content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CitationMetadata {
     /// Citations to sources for a specific response.
-    #[serde(default)]
+    #[serde(default, alias = "citation_sources")]
     pub citation_sources: Vec<CitationSource>,
 }
 
+impl CitationMetadata {
+    /// Convert the recitation sources to citations
+    pub fn citations(&self) -> Vec<crate::types::Citation> {
+        self.citation_sources
+            .iter()
+            .map(|source| crate::types::Citation {
+                url: source.uri.clone(),
+                start_index: source.start_index.map(|i| i.max(0) as usize),
+                end_index: source.end_index.map(|i| i.max(0) as usize),
+                ..Default::default()
+            })
+            .collect()
+    }
+}
+
 /// A citation to a source for a portion of a specific response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CitationSource {
     /// Optional. Start of segment of the response that is attributed to this source.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -259,6 +295,127 @@ pub struct CitationSource {
     pub license: Option<String>,
 }
 
+/// Metadata returned when grounding with Google Search is enabled.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroundingMetadata {
+    /// Supporting references retrieved from the grounding source.
+    #[serde(default)]
+    pub grounding_chunks: Vec<GroundingChunk>,
+    /// Segments of the response supported by grounding chunks.
+    #[serde(default)]
+    pub grounding_supports: Vec<GroundingSupport>,
+    /// Web search queries used for grounding.
+    #[serde(default)]
+    pub web_search_queries: Vec<String>,
+    /// Google Search entry point to display alongside grounded answers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_entry_point: Option<serde_json::Value>,
+}
+
+impl GroundingMetadata {
+    /// Web pages retrieved for grounding
+    pub fn sources(&self) -> Vec<crate::types::SearchSource> {
+        self.grounding_chunks
+            .iter()
+            .filter_map(|chunk| chunk.web.as_ref())
+            .map(|web| crate::types::SearchSource {
+                url: web.uri.clone(),
+                title: web.title.clone(),
+                snippet: None,
+            })
+            .collect()
+    }
+
+    /// One citation per (supported segment, grounding chunk) pair
+    pub fn citations(&self) -> Vec<crate::types::Citation> {
+        let mut citations = Vec::new();
+        for support in &self.grounding_supports {
+            for &chunk_index in &support.grounding_chunk_indices {
+                let web = self
+                    .grounding_chunks
+                    .get(chunk_index)
+                    .and_then(|chunk| chunk.web.as_ref());
+                citations.push(crate::types::Citation {
+                    cited_text: support.segment.text.clone(),
+                    title: web.and_then(|web| web.title.clone()),
+                    url: web.map(|web| web.uri.clone()),
+                    start_index: support.segment.start_index,
+                    end_index: support.segment.end_index,
+                    ..Default::default()
+                });
+            }
+        }
+        citations
+    }
+}
+
+/// Collect the citations and search sources of a candidate
+pub fn collect_citations(
+    citation_metadata: Option<&CitationMetadata>,
+    grounding_metadata: Option<&GroundingMetadata>,
+) -> (Vec<crate::types::Citation>, Vec<crate::types::SearchSource>) {
+    let mut citations = Vec::new();
+    let mut sources = Vec::new();
+    if let Some(grounding) = grounding_metadata {
+        citations.extend(grounding.citations());
+        sources = grounding.sources();
+    }
+    if let Some(metadata) = citation_metadata {
+        let recitations = metadata.citations();
+        crate::types::SearchSource::merge(
+            &mut sources,
+            crate::types::SearchSource::from_citations(&recitations),
+        );
+        citations.extend(recitations);
+    }
+    (citations, sources)
+}
+
+/// A grounding reference.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroundingChunk {
+    /// Web page the chunk comes from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub web: Option<WebChunk>,
+}
+
+/// A web page used for grounding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebChunk {
+    /// URI of the page.
+    pub uri: String,
+    /// Title of the page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// A response segment supported by grounding chunks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroundingSupport {
+    /// Segment of the response text.
+    pub segment: GroundingSegment,
+    /// Indices into `grounding_chunks` supporting the segment.
+    #[serde(default)]
+    pub grounding_chunk_indices: Vec<usize>,
+}
+
+/// A segment of the response text (byte offsets, end exclusive).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroundingSegment {
+    /// Start byte offset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_index: Option<usize>,
+    /// End byte offset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_index: Option<usize>,
+    /// Text of the segment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
 /// Safety rating for a piece of content.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SafetyRating {
@@ -425,6 +582,7 @@ pub enum BlockReason {
 
 /// Metadata on the generation requests' token usage.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct UsageMetadata {
     /// Number of tokens in the prompt.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -453,6 +611,8 @@ pub enum GeminiTool {
     },
     /// Code execution tool
     CodeExecution { code_execution: CodeExecution },
+    /// Grounding with Google Search
+    GoogleSearch { google_search: GoogleSearch },
 }
 
 /// Structured representation of a function declaration.
@@ -476,6 +636,12 @@ pub struct CodeExecution {
     // This is an empty object in the API spec
 }
 
+/// Tool that grounds responses with Google Search results.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GoogleSearch {
+    // This is an empty object in the API spec
+}
+
 /// Tool configuration for any Tool specified in the request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolConfig {
@@ -526,6 +692,15 @@ pub struct GeminiConfig {
     /// HTTP timeout in seconds
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<u64>,
+    /// Client-side rate limiter shared by every client using this configuration
+    #[serde(skip)]
+    pub rate_limiter: Option<std::sync::Arc<crate::rate_limiter::RateLimiter>>,
+    /// Record/replay cassette
+    #[serde(skip)]
+    pub cassette: Option<std::sync::Arc<crate::cassette::Cassette>>,
+    /// Cached content (`cachedContents/...`) referenced by generation requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cached_content: Option<String>,
 }
 
 impl Default for GeminiConfig {
@@ -537,6 +712,9 @@ impl Default for GeminiConfig {
             generation_config: None,
             safety_settings: None,
             timeout: Some(30),
+            rate_limiter: None,
+            cassette: None,
+            cached_content: None,
         }
     }
 }
@@ -579,6 +757,30 @@ impl GeminiConfig {
         self.timeout = Some(timeout);
         self
     }
+
+    /// Set the client-side rate limiter
+    pub fn with_rate_limiter(
+        mut self,
+        rate_limiter: std::sync::Arc<crate::rate_limiter::RateLimiter>,
+    ) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Record or replay requests with a cassette
+    pub fn with_cassette(mut self, cassette: std::sync::Arc<crate::cassette::Cassette>) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Reference a cached content by name in every generation request
+    ///
+    /// The cache supplies the system instruction and tools, so requests that
+    /// pass tools or enable web search are rejected.
+    pub fn with_cached_content<S: Into<String>>(mut self, name: S) -> Self {
+        self.cached_content = Some(name.into());
+        self
+    }
 }
 
 impl GenerationConfig {
@@ -728,6 +930,62 @@ impl Default for ThinkingConfig {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_usage_metadata_deserializes_cached_content_tokens() {
+        let response: GenerateContentResponse = serde_json::from_value(serde_json::json!({
+            "candidates": [],
+            "usageMetadata": {
+                "promptTokenCount": 4096,
+                "cachedContentTokenCount": 4000,
+                "candidatesTokenCount": 12,
+                "totalTokenCount": 4108
+            }
+        }))
+        .unwrap();
+
+        let usage = response.usage_metadata.unwrap();
+        assert_eq!(usage.prompt_token_count, Some(4096));
+        assert_eq!(usage.cached_content_token_count, Some(4000));
+    }
+
+    #[test]
+    fn test_candidate_citations_and_grounding() {
+        let candidate: Candidate = serde_json::from_value(serde_json::json!({
+            "citationMetadata": {
+                "citationSources": [
+                    {"startIndex": 40, "endIndex": 80, "uri": "https://github.com/a/b"}
+                ]
+            },
+            "groundingMetadata": {
+                "groundingChunks": [
+                    {"web": {"uri": "https://example.com/1", "title": "example.com"}},
+                    {"web": {"uri": "https://example.com/2", "title": "example.com"}}
+                ],
+                "groundingSupports": [{
+                    "segment": {"startIndex": 0, "endIndex": 10, "text": "First fact"},
+                    "groundingChunkIndices": [0, 1]
+                }]
+            }
+        }))
+        .unwrap();
+
+        let (citations, sources) = collect_citations(
+            candidate.citation_metadata.as_ref(),
+            candidate.grounding_metadata.as_ref(),
+        );
+        assert_eq!(citations.len(), 3);
+        assert_eq!(citations[1].url.as_deref(), Some("https://example.com/2"));
+        assert_eq!(citations[2].start_index, Some(40));
+        assert_eq!(
+            sources.iter().map(|s| s.url.as_str()).collect::<Vec<_>>(),
+            vec![
+                "https://example.com/1",
+                "https://example.com/2",
+                "https://github.com/a/b"
+            ]
+        );
+    }
+
     #[test]
     fn test_thinking_config_json_serialization() {
         let thinking_config = ThinkingConfig {
@@ -1008,3 +1266,68 @@ pub struct ListFilesResponse {
 pub struct DownloadFileResponse {
     // This is typically just raw bytes, but we'll handle it in the implementation
 }
+
+// Context caching types
+
+/// Cached content resource (`cachedContents/...`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedContent {
+    /// Output only. Identifier. The cached content resource name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Optional. Immutable. The user-generated meaningful display name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Required. Immutable. The name of the Model to use, e.g. `models/gemini-1.5-flash-001`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Optional. Input only. Immutable. Developer set system instruction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_instruction: Option<Content>,
+    /// Optional. Input only. Immutable. The content to cache.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contents: Option<Vec<Content>>,
+    /// Optional. Input only. Immutable. A list of Tools the model may use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<GeminiTool>>,
+    /// Optional. Input only. Immutable. Tool config, shared for all tools.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_config: Option<ToolConfig>,
+    /// Output only. Creation time of the cache entry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub create_time: Option<String>,
+    /// Output only. When the cache entry was last updated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_time: Option<String>,
+    /// Timestamp in UTC of when this resource is considered expired.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expire_time: Option<String>,
+    /// Input only. New TTL for this resource, e.g. `"300s"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<String>,
+    /// Output only. Metadata on the usage of the cached content.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage_metadata: Option<CachedContentUsageMetadata>,
+}
+
+/// Metadata on the usage of a cached content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedContentUsageMetadata {
+    /// Total number of tokens that the cached content consumes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_token_count: Option<i32>,
+}
+
+/// Response for `ListCachedContents`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListCachedContentsResponse {
+    /// The list of cached contents.
+    #[serde(default)]
+    pub cached_contents: Vec<CachedContent>,
+    /// A token that can be sent as `page_token` into a subsequent call.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
+}
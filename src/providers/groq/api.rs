@@ -3,6 +3,7 @@
 //! Implements model listing and information capabilities for Groq.
 
 use crate::error::LlmError;
+use crate::rate_limiter::send_request;
 use crate::traits::ModelListingCapability;
 use crate::types::{HttpConfig, ModelInfo};
 use async_trait::async_trait;
@@ -100,7 +101,15 @@ impl GroqModels {
         let url = format!("{}/models", self.base_url);
         let headers = build_headers(&self.api_key, &self.http_config.headers)?;
 
-        let response = self.http_client.get(&url).headers(headers).send().await?;
+        let response = send_request(
+            self.http_client.get(&url).headers(headers),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "groq",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -128,7 +137,15 @@ impl GroqModels {
         let url = format!("{}/models/{}", self.base_url, model_id);
         let headers = build_headers(&self.api_key, &self.http_config.headers)?;
 
-        let response = self.http_client.get(&url).headers(headers).send().await?;
+        let response = send_request(
+            self.http_client.get(&url).headers(headers),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "groq",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -18,15 +18,34 @@ pub struct AudioTranslationResponse {
     pub duration: Option<f32>,
     pub segments: Option<Vec<serde_json::Value>>,
 }
+use std::collections::HashMap;
+
+use async_trait::async_trait;
 use reqwest::multipart::{Form, Part};
+use serde::de::DeserializeOwned;
 
 use crate::error::LlmError;
-use crate::types::HttpConfig;
+use crate::rate_limiter::send_request;
+use crate::traits::AudioCapability;
+use crate::types::{
+    AudioFeature, AudioStream, AudioTranslationRequest, HttpConfig, SttRequest, SttResponse,
+    TtsRequest, TtsResponse,
+};
+use crate::utils::streaming::StreamFactory;
 
 use super::types::*;
 use super::utils::*;
 
+/// Audio features supported by Groq
+const GROQ_AUDIO_FEATURES: &[AudioFeature] = &[
+    AudioFeature::TextToSpeech,
+    AudioFeature::StreamingTTS,
+    AudioFeature::SpeechToText,
+    AudioFeature::AudioTranslation,
+];
+
 /// `Groq` Audio Capability Implementation
+#[derive(Debug, Clone)]
 pub struct GroqAudio {
     pub api_key: String,
     pub base_url: String,
@@ -54,13 +73,17 @@ impl GroqAudio {
     fn create_audio_form(
         &self,
         audio_data: Vec<u8>,
+        file_name: &str,
         model: &str,
         language: Option<&str>,
         prompt: Option<&str>,
         response_format: Option<&str>,
     ) -> Result<Form, LlmError> {
         let mut form = Form::new()
-            .part("file", Part::bytes(audio_data).file_name("audio.wav"))
+            .part(
+                "file",
+                Part::bytes(audio_data).file_name(file_name.to_string()),
+            )
             .text("model", model.to_string());
 
         if let Some(lang) = language {
@@ -77,6 +100,66 @@ impl GroqAudio {
 
         Ok(form)
     }
+
+    /// Send a multipart audio request and parse the JSON response
+    async fn send_audio_form<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        form: Form,
+        operation: &str,
+    ) -> Result<T, LlmError> {
+        let url = format!("{}/{endpoint}", self.base_url);
+        let headers = build_headers(&self.api_key, &self.http_config.headers)?;
+
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).multipart(form),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "groq",
+            "",
+            0,
+        )
+        .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            let error_message = extract_error_message(&error_text);
+
+            return Err(LlmError::ApiError {
+                code: status.as_u16(),
+                message: format!("Groq {operation} error: {error_message}"),
+                details: serde_json::from_str(&error_text).ok(),
+            });
+        }
+
+        Ok(response.json().await?)
+    }
+}
+
+/// Resolve audio bytes and a file name from in-memory data or a file path
+fn load_audio(
+    audio_data: Option<Vec<u8>>,
+    file_path: Option<String>,
+    format: Option<&str>,
+) -> Result<(Vec<u8>, String), LlmError> {
+    if let Some(data) = audio_data {
+        let file_name = format!("audio.{}", format.unwrap_or("wav"));
+        Ok((data, file_name))
+    } else if let Some(path) = file_path {
+        let data = std::fs::read(&path)
+            .map_err(|e| LlmError::IoError(format!("Failed to read audio file: {e}")))?;
+        let file_name = std::path::Path::new(&path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("audio.wav")
+            .to_string();
+        Ok((data, file_name))
+    } else {
+        Err(LlmError::InvalidInput(
+            "Either audio_data or file_path must be provided".to_string(),
+        ))
+    }
 }
 
 impl GroqAudio {
@@ -89,40 +172,20 @@ impl GroqAudio {
         prompt: Option<String>,
     ) -> Result<AudioTranscriptionResponse, LlmError> {
         let model = model.unwrap_or_else(|| "whisper-large-v3".to_string());
-        let url = format!("{}/audio/transcriptions", self.base_url);
 
         let form = self.create_audio_form(
             audio_data,
+            "audio.wav",
             &model,
             language.as_deref(),
             prompt.as_deref(),
             Some("json"),
         )?;
 
-        let headers = build_headers(&self.api_key, &self.http_config.headers)?;
-
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .multipart(form)
-            .send()
+        let groq_response: GroqTranscriptionResponse = self
+            .send_audio_form("audio/transcriptions", form, "transcription")
             .await?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            let error_message = extract_error_message(&error_text);
-
-            return Err(LlmError::ApiError {
-                code: status.as_u16(),
-                message: format!("Groq transcription error: {error_message}"),
-                details: serde_json::from_str(&error_text).ok(),
-            });
-        }
-
-        let groq_response: GroqTranscriptionResponse = response.json().await?;
-
         Ok(AudioTranscriptionResponse {
             text: groq_response.text,
             language: None, // Groq doesn't return detected language
@@ -139,40 +202,20 @@ impl GroqAudio {
         prompt: Option<String>,
     ) -> Result<AudioTranslationResponse, LlmError> {
         let model = model.unwrap_or_else(|| "whisper-large-v3".to_string());
-        let url = format!("{}/audio/translations", self.base_url);
 
         let form = self.create_audio_form(
             audio_data,
+            "audio.wav",
             &model,
             None, // Translation doesn't use language parameter
             prompt.as_deref(),
             Some("json"),
         )?;
 
-        let headers = build_headers(&self.api_key, &self.http_config.headers)?;
-
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .multipart(form)
-            .send()
+        let groq_response: GroqTranslationResponse = self
+            .send_audio_form("audio/translations", form, "translation")
             .await?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            let error_message = extract_error_message(&error_text);
-
-            return Err(LlmError::ApiError {
-                code: status.as_u16(),
-                message: format!("Groq translation error: {error_message}"),
-                details: serde_json::from_str(&error_text).ok(),
-            });
-        }
-
-        let groq_response: GroqTranslationResponse = response.json().await?;
-
         Ok(AudioTranslationResponse {
             text: groq_response.text,
             language: Some("en".to_string()), // Groq translates to English
@@ -190,6 +233,23 @@ impl GroqAudio {
         response_format: Option<String>,
         speed: Option<f32>,
     ) -> Result<Vec<u8>, LlmError> {
+        let response = self
+            .send_speech_request(text, model, voice, response_format, speed)
+            .await?;
+
+        let audio_data = response.bytes().await?;
+        Ok(audio_data.to_vec())
+    }
+
+    /// Send a speech synthesis request and return the response once headers arrive
+    async fn send_speech_request(
+        &self,
+        text: String,
+        model: Option<String>,
+        voice: Option<String>,
+        response_format: Option<String>,
+        speed: Option<f32>,
+    ) -> Result<reqwest::Response, LlmError> {
         let model = model.unwrap_or_else(|| "playai-tts".to_string());
         let voice = voice.unwrap_or_else(|| "Fritz-PlayAI".to_string());
         let response_format = response_format.unwrap_or_else(|| "wav".to_string());
@@ -207,13 +267,18 @@ impl GroqAudio {
 
         let headers = build_headers(&self.api_key, &self.http_config.headers)?;
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&request_body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client
+                .post(&url)
+                .headers(headers)
+                .json(&request_body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "groq",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -227,8 +292,7 @@ impl GroqAudio {
             });
         }
 
-        let audio_data = response.bytes().await?;
-        Ok(audio_data.to_vec())
+        Ok(response)
     }
 
     /// Check if transcription is supported
@@ -257,6 +321,135 @@ impl GroqAudio {
     }
 }
 
+#[async_trait]
+impl AudioCapability for GroqAudio {
+    fn supported_features(&self) -> &[AudioFeature] {
+        GROQ_AUDIO_FEATURES
+    }
+
+    /// Convert text to speech with PlayAI voices
+    async fn text_to_speech(&self, request: TtsRequest) -> Result<TtsResponse, LlmError> {
+        let format = request.format.unwrap_or_else(|| "wav".to_string());
+        let audio_data = self
+            .speech(
+                request.text,
+                request.model,
+                request.voice,
+                Some(format.clone()),
+                request.speed,
+            )
+            .await?;
+
+        Ok(TtsResponse {
+            audio_data,
+            format,
+            duration: None,
+            sample_rate: None,
+            metadata: HashMap::new(),
+        })
+    }
+
+    /// Convert text to speech, yielding audio chunks as they arrive
+    async fn text_to_speech_stream(&self, request: TtsRequest) -> Result<AudioStream, LlmError> {
+        let format = request.format.unwrap_or_else(|| "wav".to_string());
+        let mut metadata = HashMap::new();
+        if let Some(model) = &request.model {
+            metadata.insert("model".to_string(), serde_json::json!(model));
+        }
+        if let Some(voice) = &request.voice {
+            metadata.insert("voice".to_string(), serde_json::json!(voice));
+        }
+
+        let response = self
+            .send_speech_request(
+                request.text,
+                request.model,
+                request.voice,
+                Some(format.clone()),
+                request.speed,
+            )
+            .await?;
+
+        Ok(StreamFactory::create_audio_stream(
+            response, format, None, metadata,
+        ))
+    }
+
+    /// Transcribe audio with Whisper
+    async fn speech_to_text(&self, request: SttRequest) -> Result<SttResponse, LlmError> {
+        let (audio_data, file_name) = load_audio(
+            request.audio_data,
+            request.file_path,
+            request.format.as_deref(),
+        )?;
+        let model = request
+            .model
+            .unwrap_or_else(|| "whisper-large-v3".to_string());
+        let prompt = request.extra_params.get("prompt").and_then(|v| v.as_str());
+
+        let form = self.create_audio_form(
+            audio_data,
+            &file_name,
+            &model,
+            request.language.as_deref(),
+            prompt,
+            Some("json"),
+        )?;
+        let groq_response: GroqTranscriptionResponse = self
+            .send_audio_form("audio/transcriptions", form, "transcription")
+            .await?;
+
+        Ok(SttResponse {
+            text: groq_response.text,
+            language: request.language,
+            confidence: None,
+            words: None,
+            duration: None,
+            metadata: HashMap::new(),
+        })
+    }
+
+    /// Translate audio to English with Whisper
+    async fn translate_audio(
+        &self,
+        request: AudioTranslationRequest,
+    ) -> Result<SttResponse, LlmError> {
+        let (audio_data, file_name) = load_audio(
+            request.audio_data,
+            request.file_path,
+            request.format.as_deref(),
+        )?;
+        let model = request
+            .model
+            .unwrap_or_else(|| "whisper-large-v3".to_string());
+        let prompt = request.extra_params.get("prompt").and_then(|v| v.as_str());
+
+        let form =
+            self.create_audio_form(audio_data, &file_name, &model, None, prompt, Some("json"))?;
+        let groq_response: GroqTranslationResponse = self
+            .send_audio_form("audio/translations", form, "translation")
+            .await?;
+
+        Ok(SttResponse {
+            text: groq_response.text,
+            language: Some("en".to_string()),
+            confidence: None,
+            words: None,
+            duration: None,
+            metadata: HashMap::new(),
+        })
+    }
+
+    fn get_supported_audio_formats(&self) -> Vec<String> {
+        [
+            "flac", "mp3", "mp4", "mpeg", "mpga", "m4a", "ogg", "wav", "webm",
+        ]
+        .iter()
+        .map(|f| f.to_string())
+        .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -278,6 +471,7 @@ mod tests {
 
         let form = audio.create_audio_form(
             audio_data,
+            "audio.wav",
             "whisper-large-v3",
             Some("en"),
             Some("Test prompt"),
@@ -297,6 +491,15 @@ mod tests {
         assert!(models.contains(&"playai-tts".to_string()));
     }
 
+    #[test]
+    fn test_load_audio() {
+        let (data, file_name) = load_audio(Some(vec![1, 2, 3]), None, Some("mp3")).unwrap();
+        assert_eq!(data, vec![1, 2, 3]);
+        assert_eq!(file_name, "audio.mp3");
+
+        assert!(load_audio(None, None, None).is_err());
+    }
+
     #[test]
     fn test_capability_support() {
         let audio = create_test_audio();
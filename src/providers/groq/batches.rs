@@ -0,0 +1,139 @@
+//! `Groq` Batch Capability Implementation
+//!
+//! Groq's Batch API is OpenAI-compatible, so batches are handled by the
+//! `OpenAI` implementation pointed at Groq's base URL with Groq's credentials.
+
+use async_trait::async_trait;
+
+use crate::error::LlmError;
+use crate::providers::openai::batches::OpenAiBatches;
+use crate::providers::openai::config::OpenAiConfig;
+use crate::traits::BatchCapability;
+use crate::types::*;
+
+use super::config::GroqConfig;
+
+/// `Groq` Batch Capability Implementation
+#[derive(Debug, Clone)]
+pub struct GroqBatches {
+    inner: OpenAiBatches,
+}
+
+impl GroqBatches {
+    /// Create a new `Groq` batch capability instance
+    pub fn new(config: GroqConfig, http_client: reqwest::Client) -> Self {
+        let mut openai_config = OpenAiConfig::new(config.api_key)
+            .with_base_url(config.base_url)
+            .with_provider_id("groq");
+        openai_config.common_params = config.common_params;
+        openai_config.http_config = config.http_config;
+
+        Self {
+            inner: OpenAiBatches::new(openai_config, http_client),
+        }
+    }
+}
+
+#[async_trait]
+impl BatchCapability for GroqBatches {
+    async fn create_batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResponse, LlmError> {
+        self.inner.create_batch(requests).await
+    }
+
+    async fn retrieve_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError> {
+        self.inner.retrieve_batch(batch_id).await
+    }
+
+    async fn list_batches(
+        &self,
+        query: Option<BatchListQuery>,
+    ) -> Result<BatchListResponse, LlmError> {
+        self.inner.list_batches(query).await
+    }
+
+    async fn cancel_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError> {
+        self.inner.cancel_batch(batch_id).await
+    }
+
+    async fn get_batch_results(&self, batch_id: String) -> Result<Vec<BatchResult>, LlmError> {
+        self.inner.get_batch_results(batch_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn create_test_batches(base_url: &str) -> GroqBatches {
+        GroqBatches::new(
+            GroqConfig::new("test-api-key")
+                .with_base_url(base_url)
+                .with_model("llama-3.1-8b-instant"),
+            reqwest::Client::new(),
+        )
+    }
+
+    #[test]
+    fn test_build_input_file() {
+        let batches = create_test_batches(GroqConfig::DEFAULT_BASE_URL);
+        let requests = vec![BatchRequest::new(
+            "req-1",
+            vec![ChatMessage::user("Hello").build()],
+        )];
+
+        let content =
+            String::from_utf8(batches.inner.build_input_file(&requests).unwrap()).unwrap();
+        let line: serde_json::Value = serde_json::from_str(content.trim()).unwrap();
+
+        assert_eq!(line["custom_id"], "req-1");
+        assert_eq!(line["method"], "POST");
+        assert_eq!(line["body"]["model"], "llama-3.1-8b-instant");
+        assert!(line["body"].get("stream").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retrieve_batch_uses_groq_endpoint() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/openai/v1/batches/batch_123"))
+            .and(header("authorization", "Bearer test-api-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "batch_123",
+                "object": "batch",
+                "endpoint": "/v1/chat/completions",
+                "errors": null,
+                "input_file_id": "file_123",
+                "completion_window": "24h",
+                "status": "completed",
+                "output_file_id": "file_456",
+                "error_file_id": null,
+                "finalizing_at": null,
+                "failed_at": null,
+                "expired_at": null,
+                "cancelled_at": null,
+                "request_counts": {"total": 2, "completed": 2, "failed": 0},
+                "metadata": null,
+                "created_at": 1_700_000_000,
+                "expires_at": 1_700_086_400,
+                "cancelling_at": null,
+                "completed_at": 1_700_000_600,
+                "in_progress_at": null
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let batches = create_test_batches(&format!("{}/openai/v1", server.uri()));
+        let batch = batches
+            .retrieve_batch("batch_123".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(batch.status, BatchStatus::Completed);
+        assert_eq!(batch.request_counts.completed, 2);
+        assert_eq!(batch.ended_at, Some(1_700_000_600));
+        assert_eq!(batch.output_file_id.as_deref(), Some("file_456"));
+    }
+}
@@ -8,6 +8,7 @@ use std::time::Instant;
 
 use crate::error::LlmError;
 use crate::params::{OpenAiParameterMapper, ParameterMapper};
+use crate::rate_limiter::{estimate_request_tokens, send_request};
 use crate::stream::ChatStream;
 use crate::tracing::ProviderTracer;
 use crate::traits::ChatCapability;
@@ -63,6 +64,11 @@ impl GroqChatCapability {
                 .merge_provider_params(body, provider_params);
         }
 
+        // Constrain the output to the requested schema
+        if let Some(ref schema) = request.output_schema {
+            body["response_format"] = schema.to_openai_response_format();
+        }
+
         // Validate parameters with Groq-specific validation
         validate_groq_params(&body)?;
 
@@ -84,7 +90,10 @@ impl GroqChatCapability {
     }
 
     /// Parse the `Groq` response
-    fn parse_chat_response(&self, response: GroqChatResponse) -> Result<ChatResponse, LlmError> {
+    pub(crate) fn parse_chat_response(
+        &self,
+        response: GroqChatResponse,
+    ) -> Result<ChatResponse, LlmError> {
         let choice = response
             .choices
             .into_iter()
@@ -136,7 +145,10 @@ impl GroqChatCapability {
             completion_tokens: u.completion_tokens.unwrap_or(0),
             total_tokens: u.total_tokens.unwrap_or(0),
             reasoning_tokens: None, // Groq doesn't provide reasoning tokens
-            cached_tokens: None,
+            cached_tokens: u
+                .prompt_tokens_details
+                .and_then(|details| details.cached_tokens),
+            cache_write_tokens: None,
         });
 
         Ok(ChatResponse {
@@ -147,6 +159,8 @@ impl GroqChatCapability {
             finish_reason: Some(finish_reason),
             tool_calls,
             thinking: None, // Groq doesn't support thinking content like OpenAI o1
+            citations: Vec::new(),
+            sources: Vec::new(),
             metadata: HashMap::new(),
         })
     }
@@ -170,6 +184,8 @@ impl ChatCapability for GroqChatCapability {
             http_config: None,
             web_search: None,
             stream: false,
+            prompt_cache: None,
+            output_schema: None,
         };
 
         // Extract model name for tracing
@@ -183,13 +199,15 @@ impl ChatCapability for GroqChatCapability {
         tracer.trace_request_start("POST", &url);
         tracer.trace_request_details(&headers, &body);
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "groq",
+            body["model"].as_str().unwrap_or_default(),
+            estimate_request_tokens(&body),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -233,6 +251,8 @@ impl ChatCapability for GroqChatCapability {
             http_config: None,
             web_search: None,
             stream: true,
+            prompt_cache: None,
+            output_schema: None,
         };
 
         // Create streaming client
@@ -259,13 +279,15 @@ impl GroqChatCapability {
         let body = self.build_chat_request_body(&request)?;
         let url = format!("{}/chat/completions", self.base_url);
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "groq",
+            body["model"].as_str().unwrap_or_default(),
+            estimate_request_tokens(&body),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -7,10 +7,14 @@ use async_trait::async_trait;
 use crate::client::LlmClient;
 use crate::error::LlmError;
 use crate::stream::ChatStream;
-use crate::traits::{ChatCapability, ModelListingCapability, ProviderCapabilities};
+use crate::traits::{
+    AudioCapability, BatchCapability, ChatCapability, ModelListingCapability, ProviderCapabilities,
+};
 use crate::types::*;
 
 use super::api::GroqModels;
+use super::audio::GroqAudio;
+use super::batches::GroqBatches;
 use super::chat::GroqChatCapability;
 use super::config::GroqConfig;
 
@@ -24,6 +28,8 @@ pub struct GroqClient {
     chat_capability: GroqChatCapability,
     /// Models capability
     models_capability: GroqModels,
+    /// Audio capability
+    audio_capability: GroqAudio,
     /// Tracing configuration
     tracing_config: Option<crate::tracing::TracingConfig>,
     /// Tracing guard to keep tracing system active
@@ -37,6 +43,7 @@ impl Clone for GroqClient {
             http_client: self.http_client.clone(),
             chat_capability: self.chat_capability.clone(),
             models_capability: self.models_capability.clone(),
+            audio_capability: self.audio_capability.clone(),
             tracing_config: self.tracing_config.clone(),
             _tracing_guard: None, // Don't clone the tracing guard
         }
@@ -61,11 +68,19 @@ impl GroqClient {
             config.http_config.clone(),
         );
 
+        let audio_capability = GroqAudio::new(
+            config.api_key.clone(),
+            config.base_url.clone(),
+            http_client.clone(),
+            config.http_config.clone(),
+        );
+
         Self {
             config,
             http_client,
             chat_capability,
             models_capability,
+            audio_capability,
             tracing_config: None,
             _tracing_guard: None,
         }
@@ -85,6 +100,16 @@ impl GroqClient {
     pub fn chat_capability(&self) -> &GroqChatCapability {
         &self.chat_capability
     }
+
+    /// Get audio capability
+    pub fn audio_capability(&self) -> &GroqAudio {
+        &self.audio_capability
+    }
+
+    /// Create a batch capability using the client's configuration
+    pub fn batches(&self) -> GroqBatches {
+        GroqBatches::new(self.config.clone(), self.http_client.clone())
+    }
 }
 
 #[async_trait]
@@ -105,6 +130,8 @@ impl LlmClient for GroqClient {
             .with_chat()
             .with_streaming()
             .with_tools()
+            .with_audio()
+            .with_custom_feature("batch_processing", true)
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -114,10 +141,49 @@ impl LlmClient for GroqClient {
     fn clone_box(&self) -> Box<dyn LlmClient> {
         Box::new(self.clone())
     }
+
+    fn as_audio_capability(&self) -> Option<&dyn AudioCapability> {
+        Some(self)
+    }
+
+    fn as_batch_capability(&self) -> Option<&dyn BatchCapability> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl BatchCapability for GroqClient {
+    async fn create_batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResponse, LlmError> {
+        self.batches().create_batch(requests).await
+    }
+
+    async fn retrieve_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError> {
+        self.batches().retrieve_batch(batch_id).await
+    }
+
+    async fn list_batches(
+        &self,
+        query: Option<BatchListQuery>,
+    ) -> Result<BatchListResponse, LlmError> {
+        self.batches().list_batches(query).await
+    }
+
+    async fn cancel_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError> {
+        self.batches().cancel_batch(batch_id).await
+    }
+
+    async fn get_batch_results(&self, batch_id: String) -> Result<Vec<BatchResult>, LlmError> {
+        self.batches().get_batch_results(batch_id).await
+    }
 }
 
 #[async_trait]
 impl ChatCapability for GroqClient {
+    fn default_model(&self) -> Option<String> {
+        let model = &self.chat_capability.common_params.model;
+        Some(model.clone()).filter(|model| !model.is_empty())
+    }
+
     async fn chat_with_tools(
         &self,
         messages: Vec<ChatMessage>,
@@ -133,6 +199,52 @@ impl ChatCapability for GroqClient {
     ) -> Result<ChatStream, LlmError> {
         self.chat_capability.chat_stream(messages, tools).await
     }
+
+    /// Chat with structured output via the OpenAI-compatible `response_format`
+    async fn chat_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatResponse, LlmError> {
+        self.chat_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    /// Stream structured output via the OpenAI-compatible `response_format`
+    async fn chat_stream_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatStream, LlmError> {
+        self.chat_stream_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    async fn chat_with_request(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        let request = ChatRequest {
+            common_params: self
+                .chat_capability
+                .common_params
+                .merged_with(&request.common_params),
+            http_config: None,
+            stream: false,
+            ..request
+        };
+        self.chat_capability.chat(request).await
+    }
+
+    async fn chat_stream_with_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
+        let request = ChatRequest {
+            common_params: self
+                .chat_capability
+                .common_params
+                .merged_with(&request.common_params),
+            http_config: None,
+            stream: true,
+            ..request
+        };
+        self.chat_capability.chat_stream_request(request).await
+    }
 }
 
 impl GroqClient {
@@ -150,6 +262,36 @@ impl GroqClient {
     }
 }
 
+#[async_trait]
+impl AudioCapability for GroqClient {
+    fn supported_features(&self) -> &[AudioFeature] {
+        self.audio_capability.supported_features()
+    }
+
+    async fn text_to_speech(&self, request: TtsRequest) -> Result<TtsResponse, LlmError> {
+        self.audio_capability.text_to_speech(request).await
+    }
+
+    async fn text_to_speech_stream(&self, request: TtsRequest) -> Result<AudioStream, LlmError> {
+        self.audio_capability.text_to_speech_stream(request).await
+    }
+
+    async fn speech_to_text(&self, request: SttRequest) -> Result<SttResponse, LlmError> {
+        self.audio_capability.speech_to_text(request).await
+    }
+
+    async fn translate_audio(
+        &self,
+        request: AudioTranslationRequest,
+    ) -> Result<SttResponse, LlmError> {
+        self.audio_capability.translate_audio(request).await
+    }
+
+    fn get_supported_audio_formats(&self) -> Vec<String> {
+        self.audio_capability.get_supported_audio_formats()
+    }
+}
+
 #[async_trait]
 impl ModelListingCapability for GroqClient {
     async fn list_models(&self) -> Result<Vec<ModelInfo>, LlmError> {
@@ -2,10 +2,16 @@
 //!
 //! Implements file management capabilities for Groq.
 
+use async_trait::async_trait;
 use reqwest::multipart::{Form, Part};
+use std::collections::HashMap;
 
 use crate::error::LlmError;
-use crate::types::HttpConfig;
+use crate::rate_limiter::send_request;
+use crate::traits::FileManagementCapability;
+use crate::types::{
+    FileDeleteResponse, FileListQuery, FileListResponse, FileObject, FileUploadRequest, HttpConfig,
+};
 
 // File response type for Groq
 #[derive(Debug, Clone)]
@@ -47,7 +53,6 @@ impl GroqFiles {
     }
 
     /// Convert Groq file to our FileResponse
-    #[allow(dead_code)]
     fn convert_groq_file(&self, groq_file: GroqFile) -> FileResponse {
         FileResponse {
             id: groq_file.id,
@@ -62,29 +67,44 @@ impl GroqFiles {
     }
 }
 
-#[allow(dead_code)]
-impl GroqFiles {
-    async fn upload_file(
-        &self,
-        file_data: Vec<u8>,
-        filename: String,
-        purpose: String,
-    ) -> Result<FileResponse, LlmError> {
+impl From<FileResponse> for FileObject {
+    fn from(file: FileResponse) -> Self {
+        Self {
+            id: file.id,
+            filename: file.filename,
+            bytes: file.size,
+            created_at: file.created_at.timestamp() as u64,
+            purpose: file.purpose,
+            status: file.status.unwrap_or_else(|| "uploaded".to_string()),
+            mime_type: None,
+            metadata: HashMap::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl FileManagementCapability for GroqFiles {
+    async fn upload_file(&self, request: FileUploadRequest) -> Result<FileObject, LlmError> {
         let url = format!("{}/files", self.base_url);
 
         let form = Form::new()
-            .part("file", Part::bytes(file_data).file_name(filename.clone()))
-            .text("purpose", purpose);
+            .part(
+                "file",
+                Part::bytes(request.content).file_name(request.filename),
+            )
+            .text("purpose", request.purpose);
 
         let headers = build_headers(&self.api_key, &self.http_config.headers)?;
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .multipart(form)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).multipart(form),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "groq",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -99,14 +119,22 @@ impl GroqFiles {
         }
 
         let groq_file: GroqFile = response.json().await?;
-        Ok(self.convert_groq_file(groq_file))
+        Ok(self.convert_groq_file(groq_file).into())
     }
 
-    async fn list_files(&self) -> Result<Vec<FileResponse>, LlmError> {
+    async fn list_files(&self, query: Option<FileListQuery>) -> Result<FileListResponse, LlmError> {
         let url = format!("{}/files", self.base_url);
         let headers = build_headers(&self.api_key, &self.http_config.headers)?;
 
-        let response = self.http_client.get(&url).headers(headers).send().await?;
+        let response = send_request(
+            self.http_client.get(&url).headers(headers),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "groq",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -120,21 +148,36 @@ impl GroqFiles {
             });
         }
 
+        // Groq does not paginate or filter files, so the purpose filter is applied here
+        let purpose = query.and_then(|q| q.purpose);
         let groq_response: GroqFilesResponse = response.json().await?;
         let files = groq_response
             .data
             .into_iter()
-            .map(|f| self.convert_groq_file(f))
+            .filter(|f| purpose.as_ref().is_none_or(|p| &f.purpose == p))
+            .map(|f| self.convert_groq_file(f).into())
             .collect();
 
-        Ok(files)
+        Ok(FileListResponse {
+            files,
+            has_more: false,
+            next_cursor: None,
+        })
     }
 
-    async fn get_file(&self, file_id: String) -> Result<FileResponse, LlmError> {
+    async fn retrieve_file(&self, file_id: String) -> Result<FileObject, LlmError> {
         let url = format!("{}/files/{}", self.base_url, file_id);
         let headers = build_headers(&self.api_key, &self.http_config.headers)?;
 
-        let response = self.http_client.get(&url).headers(headers).send().await?;
+        let response = send_request(
+            self.http_client.get(&url).headers(headers),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "groq",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -149,19 +192,22 @@ impl GroqFiles {
         }
 
         let groq_file: GroqFile = response.json().await?;
-        Ok(self.convert_groq_file(groq_file))
+        Ok(self.convert_groq_file(groq_file).into())
     }
 
-    async fn delete_file(&self, file_id: String) -> Result<bool, LlmError> {
+    async fn delete_file(&self, file_id: String) -> Result<FileDeleteResponse, LlmError> {
         let url = format!("{}/files/{}", self.base_url, file_id);
         let headers = build_headers(&self.api_key, &self.http_config.headers)?;
 
-        let response = self
-            .http_client
-            .delete(&url)
-            .headers(headers)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.delete(&url).headers(headers),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "groq",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -176,14 +222,25 @@ impl GroqFiles {
         }
 
         let delete_response: GroqDeleteFileResponse = response.json().await?;
-        Ok(delete_response.deleted)
+        Ok(FileDeleteResponse {
+            id: delete_response.id,
+            deleted: delete_response.deleted,
+        })
     }
 
-    async fn download_file(&self, file_id: String) -> Result<Vec<u8>, LlmError> {
+    async fn get_file_content(&self, file_id: String) -> Result<Vec<u8>, LlmError> {
         let url = format!("{}/files/{}/content", self.base_url, file_id);
         let headers = build_headers(&self.api_key, &self.http_config.headers)?;
 
-        let response = self.http_client.get(&url).headers(headers).send().await?;
+        let response = send_request(
+            self.http_client.get(&url).headers(headers),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "groq",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -200,7 +257,10 @@ impl GroqFiles {
         let file_data = response.bytes().await?;
         Ok(file_data.to_vec())
     }
+}
 
+#[allow(dead_code)]
+impl GroqFiles {
     fn supports_file_upload(&self) -> bool {
         true
     }
@@ -12,6 +12,7 @@
 //! - `builder.rs` - Builder pattern implementation for client creation
 //! - `chat.rs` - Chat completion capability implementation
 //! - `audio.rs` - Audio processing (TTS/STT) capability implementation
+//! - `batches.rs` - Batch processing capability implementation
 //! - `files.rs` - File management capability implementation
 //! - `models.rs` - Model listing capability implementation
 //! - `types.rs` - Groq-specific type definitions
@@ -35,8 +36,8 @@
 //!     let messages = vec![user!("Hello, world!")];
 //!     let response = client.chat(messages).await?;
 //!
-//!     // Use audio capability (if available)
-//!     // let audio_data = client.speech("Hello, world!").await?;
+//!     // Use audio capability (Whisper transcription, PlayAI speech)
+//!     let audio_data = client.speech("Hello, world!".to_string()).await?;
 //!
 //!     Ok(())
 //! }
@@ -52,6 +53,7 @@ pub mod utils;
 // Capability modules
 pub mod api;
 pub mod audio;
+pub mod batches;
 pub mod chat;
 pub mod files;
 pub mod models;
@@ -66,6 +68,7 @@ pub use types::*;
 // Re-export capability implementations
 pub use api::GroqModels;
 pub use audio::GroqAudio;
+pub use batches::GroqBatches;
 pub use chat::GroqChatCapability;
 pub use files::GroqFiles;
 
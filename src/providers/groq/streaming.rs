@@ -3,6 +3,7 @@
 //! This module provides Groq-specific streaming functionality for chat completions.
 
 use crate::error::LlmError;
+use crate::rate_limiter::{estimate_request_tokens, send_request};
 use crate::stream::{ChatStream, ChatStreamEvent};
 use crate::types::{ChatRequest, Usage};
 use crate::types::{ChatResponse, FinishReason, MessageContent};
@@ -38,7 +39,10 @@ impl GroqEventConverter {
                     completion_tokens: usage.completion_tokens.unwrap_or(0),
                     total_tokens: usage.total_tokens.unwrap_or(0),
                     reasoning_tokens: None, // Groq doesn't provide reasoning tokens
-                    cached_tokens: None,
+                    cached_tokens: usage
+                        .prompt_tokens_details
+                        .and_then(|details| details.cached_tokens),
+                    cache_write_tokens: None,
                 },
             };
         }
@@ -66,6 +70,8 @@ impl GroqEventConverter {
                         finish_reason: Some(finish_reason_enum),
                         tool_calls: None,
                         thinking: None,
+                        citations: Vec::new(),
+                        sources: Vec::new(),
                         metadata: std::collections::HashMap::new(),
                     },
                 };
@@ -129,6 +135,8 @@ impl SseEventConverter for GroqEventConverter {
             finish_reason: Some(FinishReason::Stop),
             tool_calls: None,
             thinking: None,
+            citations: Vec::new(),
+            sources: Vec::new(),
             metadata: std::collections::HashMap::new(),
         };
         Some(Ok(ChatStreamEvent::StreamEnd { response }))
@@ -187,7 +195,17 @@ impl GroqStreaming {
             .headers(headers)
             .json(&request_body);
 
+        let response = send_request(
+            request_builder,
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "groq",
+            request_body["model"].as_str().unwrap_or_default(),
+            estimate_request_tokens(&request_body),
+        )
+        .await?;
+
         let converter = GroqEventConverter::new(self.config.clone());
-        StreamFactory::create_eventsource_stream(request_builder, converter).await
+        StreamFactory::create_eventsource_stream_from_response(response, converter).await
     }
 }
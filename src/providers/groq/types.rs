@@ -60,6 +60,13 @@ pub struct GroqUsage {
     pub completion_time: Option<f64>,
     pub total_tokens: Option<u32>,
     pub total_time: Option<f64>,
+    pub prompt_tokens_details: Option<GroqPromptTokensDetails>,
+}
+
+/// Groq prompt tokens details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroqPromptTokensDetails {
+    pub cached_tokens: Option<u32>,
 }
 
 /// Groq Metadata
@@ -62,6 +62,20 @@ pub fn convert_messages(messages: &[ChatMessage]) -> Result<Vec<serde_json::Valu
                                 "format": format
                             }));
                         }
+                        crate::types::ContentPart::Document {
+                            source, media_type, ..
+                        } => {
+                            let text = source.decoded_text(media_type).ok_or_else(|| {
+                                LlmError::UnsupportedOperation(
+                                    "Only inline plain-text documents are supported by Groq"
+                                        .to_string(),
+                                )
+                            })?;
+                            content_parts.push(serde_json::json!({
+                                "type": "text",
+                                "text": text
+                            }));
+                        }
                     }
                 }
                 serde_json::Value::Array(content_parts)
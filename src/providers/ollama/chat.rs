@@ -6,6 +6,7 @@ use async_trait::async_trait;
 use std::time::Instant;
 
 use crate::error::LlmError;
+use crate::rate_limiter::{estimate_request_tokens, send_request};
 use crate::stream::ChatStream;
 use crate::tracing::ProviderTracer;
 use crate::traits::ChatCapability;
@@ -75,8 +76,10 @@ impl OllamaChatCapability {
             self.ollama_params.options.as_ref(),
         );
 
-        // Build format if specified
-        let format = if let Some(format_str) = &self.ollama_params.format {
+        // Build format from the output schema or the configured format
+        let format = if let Some(schema) = &request.output_schema {
+            Some(schema.resolved_schema())
+        } else if let Some(format_str) = &self.ollama_params.format {
             if format_str == "json" {
                 Some(serde_json::Value::String("json".to_string()))
             } else {
@@ -128,6 +131,7 @@ impl OllamaChatCapability {
                 total_tokens: response.prompt_eval_count.unwrap_or(0)
                     + response.eval_count.unwrap_or(0),
                 cached_tokens: None,
+                cache_write_tokens: None,
                 reasoning_tokens: None,
             })
         } else {
@@ -179,6 +183,8 @@ impl OllamaChatCapability {
             finish_reason,
             tool_calls: message.tool_calls,
             thinking: response.message.thinking,
+            citations: Vec::new(),
+            sources: Vec::new(),
             metadata,
         }
     }
@@ -204,6 +210,8 @@ impl ChatCapability for OllamaChatCapability {
             http_config: None,
             web_search: None,
             stream: false,
+            prompt_cache: None,
+            output_schema: None,
         };
 
         self.chat(request).await
@@ -226,10 +234,14 @@ impl ChatCapability for OllamaChatCapability {
             http_config: None,
             web_search: None,
             stream: true,
+            prompt_cache: None,
+            output_schema: None,
         };
 
         // Create streaming capability
-        let streaming = super::streaming::OllamaStreaming::new(reqwest::Client::new());
+        let streaming = super::streaming::OllamaStreaming::new(reqwest::Client::new())
+            .with_rate_limiter(self.http_config.rate_limiter.clone())
+            .with_cassette(self.http_config.cassette.clone());
 
         let headers = super::utils::build_headers(&self.http_config.headers)?;
         let body = self.build_chat_request_body(&request)?;
@@ -258,13 +270,15 @@ impl OllamaChatCapability {
         let body_json = serde_json::to_value(&body)?;
         tracer.trace_request_details(&headers, &body_json);
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "ollama",
+            body_json["model"].as_str().unwrap_or_default(),
+            estimate_request_tokens(&body_json),
+        )
+        .await?;
 
         let status = response.status();
         if !status.is_success() {
@@ -324,6 +338,8 @@ mod tests {
             http_config: None,
             web_search: None,
             stream: false,
+            prompt_cache: None,
+            output_schema: None,
         };
 
         let body = capability.build_chat_request_body(&request).unwrap();
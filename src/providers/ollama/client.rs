@@ -6,9 +6,11 @@ use async_trait::async_trait;
 
 use crate::client::LlmClient;
 use crate::error::LlmError;
+use crate::rate_limiter::send_request;
 use crate::stream::ChatStream;
 use crate::traits::{
-    ChatCapability, EmbeddingCapability, LlmProvider, ModelListingCapability, ProviderCapabilities,
+    ChatCapability, CompletionCapability, EmbeddingCapability, LlmProvider, ModelListingCapability,
+    ProviderCapabilities, VisionCapability,
 };
 use crate::types::*;
 
@@ -100,7 +102,9 @@ impl OllamaClient {
             config.http_config.clone(),
         );
 
-        let streaming_capability = OllamaStreaming::new(http_client.clone());
+        let streaming_capability = OllamaStreaming::new(http_client.clone())
+            .with_rate_limiter(config.http_config.rate_limiter.clone())
+            .with_cassette(config.http_config.cassette.clone());
 
         Self {
             chat_capability,
@@ -271,7 +275,17 @@ impl OllamaClient {
     pub async fn health_check(&self) -> Result<bool, LlmError> {
         let url = format!("{}/api/version", self.base_url);
 
-        match self.http_client.get(&url).send().await {
+        let http_config = &self.chat_capability.http_config;
+        match send_request(
+            self.http_client.get(&url),
+            http_config.rate_limiter.as_deref(),
+            http_config.cassette.as_deref(),
+            "ollama",
+            "",
+            0,
+        )
+        .await
+        {
             Ok(response) => Ok(response.status().is_success()),
             Err(_) => Ok(false),
         }
@@ -281,7 +295,16 @@ impl OllamaClient {
     pub async fn version(&self) -> Result<String, LlmError> {
         let url = format!("{}/api/version", self.base_url);
 
-        let response = self.http_client.get(&url).send().await?;
+        let http_config = &self.chat_capability.http_config;
+        let response = send_request(
+            self.http_client.get(&url),
+            http_config.rate_limiter.as_deref(),
+            http_config.cassette.as_deref(),
+            "ollama",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             return Err(LlmError::HttpError(format!(
@@ -297,6 +320,10 @@ impl OllamaClient {
 
 #[async_trait]
 impl ChatCapability for OllamaClient {
+    fn default_model(&self) -> Option<String> {
+        Some(self.common_params.model.clone()).filter(|model| !model.is_empty())
+    }
+
     /// Chat with tools implementation
     async fn chat_with_tools(
         &self,
@@ -312,6 +339,8 @@ impl ChatCapability for OllamaClient {
             http_config: None,
             web_search: None,
             stream: false,
+            prompt_cache: None,
+            output_schema: None,
         };
         self.chat_capability.chat(request).await
     }
@@ -331,8 +360,69 @@ impl ChatCapability for OllamaClient {
             http_config: None,
             web_search: None,
             stream: true,
+            prompt_cache: None,
+            output_schema: None,
         };
+        self.stream_request(request).await
+    }
+
+    /// Chat with structured output via the `format` JSON schema
+    async fn chat_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatResponse, LlmError> {
+        self.chat_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    /// Stream structured output via the `format` JSON schema
+    async fn chat_stream_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatStream, LlmError> {
+        self.chat_stream_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
 
+    /// Chat with parameter overrides
+    ///
+    /// Ollama options are configured on the client, so provider parameters
+    /// cannot be overridden per request.
+    async fn chat_with_request(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        let request = self.override_request(request, false)?;
+        self.chat_capability.chat(request).await
+    }
+
+    async fn chat_stream_with_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
+        let request = self.override_request(request, true)?;
+        self.stream_request(request).await
+    }
+}
+
+impl OllamaClient {
+    /// Apply a request's common parameter overrides to the client's
+    fn override_request(
+        &self,
+        request: ChatRequest,
+        stream: bool,
+    ) -> Result<ChatRequest, LlmError> {
+        if request.provider_params.is_some() {
+            return Err(LlmError::UnsupportedOperation(
+                "Ollama does not support per-request provider parameters".to_string(),
+            ));
+        }
+        Ok(ChatRequest {
+            common_params: self.common_params.merged_with(&request.common_params),
+            http_config: None,
+            stream,
+            ..request
+        })
+    }
+
+    /// Start a streaming `/api/chat` request
+    async fn stream_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
         let headers = crate::providers::ollama::utils::build_headers(
             &self.chat_capability.http_config.headers,
         )?;
@@ -347,6 +437,66 @@ impl ChatCapability for OllamaClient {
     }
 }
 
+#[async_trait]
+impl VisionCapability for OllamaClient {
+    /// Analyze images with a multimodal model such as `llava`
+    ///
+    /// Ollama only accepts base64 image data, so remote images are downloaded first.
+    async fn analyze_image(&self, request: VisionRequest) -> Result<VisionResponse, LlmError> {
+        let messages =
+            crate::multimodal::build_vision_messages(&request, Some(&self.http_client)).await?;
+        let chat_request = ChatRequest {
+            messages,
+            tools: None,
+            common_params: request.common_params(&self.common_params),
+            provider_params: None,
+            http_config: None,
+            web_search: None,
+            stream: false,
+            prompt_cache: None,
+            output_schema: None,
+        };
+
+        self.chat_capability
+            .chat(chat_request)
+            .await
+            .map(Into::into)
+    }
+
+    async fn generate_image(&self, _request: ImageGenRequest) -> Result<ImageResponse, LlmError> {
+        Err(LlmError::UnsupportedOperation(
+            "Ollama does not support image generation".to_string(),
+        ))
+    }
+
+    fn get_supported_output_formats(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+#[async_trait]
+impl CompletionCapability for OllamaClient {
+    async fn complete(
+        &self,
+        mut request: CompletionRequest,
+    ) -> Result<CompletionResponse, LlmError> {
+        if request.model.is_none() {
+            request.model = Some(self.common_params.model.clone());
+        }
+        self.completion_capability.complete(request).await
+    }
+
+    async fn complete_stream(
+        &self,
+        mut request: CompletionRequest,
+    ) -> Result<CompletionStream, LlmError> {
+        if request.model.is_none() {
+            request.model = Some(self.common_params.model.clone());
+        }
+        self.completion_capability.complete_stream(request).await
+    }
+}
+
 #[async_trait]
 impl EmbeddingCapability for OllamaClient {
     async fn embed(&self, texts: Vec<String>) -> Result<EmbeddingResponse, LlmError> {
@@ -401,6 +551,14 @@ impl LlmClient for OllamaClient {
     fn as_embedding_capability(&self) -> Option<&dyn EmbeddingCapability> {
         Some(self)
     }
+
+    fn as_completion_capability(&self) -> Option<&dyn CompletionCapability> {
+        Some(self)
+    }
+
+    fn as_vision_capability(&self) -> Option<&dyn VisionCapability> {
+        Some(self)
+    }
 }
 
 impl LlmProvider for OllamaClient {
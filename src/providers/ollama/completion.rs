@@ -2,8 +2,13 @@
 //!
 //! Implements text completion using the /api/generate endpoint.
 
+use async_trait::async_trait;
+use futures_util::StreamExt;
+
 use crate::error::LlmError;
-use crate::stream::ChatStream;
+use crate::rate_limiter::{estimate_request_tokens, send_request};
+use crate::stream::{ChatStream, ChatStreamEvent};
+use crate::traits::CompletionCapability;
 use crate::types::*;
 
 use super::config::OllamaParams;
@@ -29,7 +34,9 @@ impl OllamaCompletionCapability {
         http_config: HttpConfig,
         ollama_params: OllamaParams,
     ) -> Self {
-        let streaming = OllamaStreaming::new(http_client.clone());
+        let streaming = OllamaStreaming::new(http_client.clone())
+            .with_rate_limiter(http_config.rate_limiter.clone())
+            .with_cassette(http_config.cassette.clone());
         Self {
             base_url,
             http_client,
@@ -102,24 +109,109 @@ impl OllamaCompletionCapability {
         })
     }
 
+    /// Build generate request body from a completion request
+    fn build_completion_request_body(
+        &self,
+        request: &CompletionRequest,
+        stream: bool,
+    ) -> Result<OllamaGenerateRequest, LlmError> {
+        let mut body =
+            self.build_generate_request_body(&request.prompt, request.model.as_deref(), stream)?;
+        body.suffix = request.suffix.clone();
+
+        let mut options = build_model_options(
+            request.temperature,
+            request.max_tokens,
+            request.top_p,
+            None, // frequency_penalty
+            None, // presence_penalty
+            self.ollama_params.options.as_ref(),
+        );
+        if let Some(top_k) = request.top_k {
+            options.insert("top_k".to_string(), serde_json::json!(top_k));
+        }
+        if let Some(stop) = &request.stop {
+            options.insert("stop".to_string(), serde_json::json!(stop));
+        }
+        options.extend(request.extra_params.clone());
+
+        body.options = if options.is_empty() {
+            None
+        } else {
+            Some(options)
+        };
+
+        Ok(body)
+    }
+
     /// Parse generate response
     fn parse_generate_response(&self, response: OllamaGenerateResponse) -> String {
         response.response
     }
 
+    /// Parse generate response into a completion response
+    fn parse_completion_response(&self, response: OllamaGenerateResponse) -> CompletionResponse {
+        let usage = if response.prompt_eval_count.is_some() || response.eval_count.is_some() {
+            Some(Usage::new(
+                response.prompt_eval_count.unwrap_or(0),
+                response.eval_count.unwrap_or(0),
+            ))
+        } else {
+            None
+        };
+
+        CompletionResponse {
+            text: response.response,
+            finish_reason: response.done.then(|| "stop".to_string()),
+            usage,
+            model: Some(response.model),
+        }
+    }
+
+    /// Send a generate request and return the raw response
+    async fn send_generate_request(
+        &self,
+        body: &OllamaGenerateRequest,
+    ) -> Result<OllamaGenerateResponse, LlmError> {
+        let headers = build_headers(&self.http_config.headers)?;
+        let url = format!("{}/api/generate", self.base_url);
+
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "ollama",
+            &body.model,
+            estimate_request_tokens(&body),
+        )
+        .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(LlmError::HttpError(format!(
+                "Generate request failed: {status} - {error_text}"
+            )));
+        }
+
+        Ok(response.json().await?)
+    }
+
     /// Generate text completion
     pub async fn generate(&self, prompt: String) -> Result<String, LlmError> {
         let headers = build_headers(&self.http_config.headers)?;
         let body = self.build_generate_request_body(&prompt, None, false)?;
         let url = format!("{}/api/generate", self.base_url);
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "ollama",
+            &body.model,
+            estimate_request_tokens(&body),
+        )
+        .await?;
 
         let status = response.status();
         if !status.is_success() {
@@ -156,13 +248,15 @@ impl OllamaCompletionCapability {
         let body = self.build_generate_request_body(&prompt, Some(&model), false)?;
         let url = crate::utils::url::join_url(&self.base_url, "api/generate");
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "ollama",
+            &body.model,
+            estimate_request_tokens(&body),
+        )
+        .await?;
 
         let status = response.status();
         if !status.is_success() {
@@ -188,13 +282,15 @@ impl OllamaCompletionCapability {
         body.suffix = Some(suffix);
         let url = format!("{}/api/generate", self.base_url);
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "ollama",
+            &body.model,
+            estimate_request_tokens(&body),
+        )
+        .await?;
 
         let status = response.status();
         if !status.is_success() {
@@ -209,6 +305,57 @@ impl OllamaCompletionCapability {
     }
 }
 
+#[async_trait]
+impl CompletionCapability for OllamaCompletionCapability {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        let body = self.build_completion_request_body(&request, false)?;
+        let response = self.send_generate_request(&body).await?;
+        Ok(self.parse_completion_response(response))
+    }
+
+    async fn complete_stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionStream, LlmError> {
+        let headers = build_headers(&self.http_config.headers)?;
+        let body = self.build_completion_request_body(&request, true)?;
+        let url = format!("{}/api/generate", self.base_url);
+
+        let chat_stream = self
+            .streaming
+            .clone()
+            .create_completion_stream(url, headers, body)
+            .await?;
+
+        Ok(Box::pin(chat_stream.filter_map(|event| {
+            futures_util::future::ready(convert_stream_event(event))
+        })))
+    }
+}
+
+/// Convert a generate stream event into a completion stream event
+fn convert_stream_event(
+    event: Result<ChatStreamEvent, LlmError>,
+) -> Option<Result<CompletionStreamEvent, LlmError>> {
+    let event = match event {
+        Ok(event) => event,
+        Err(e) => return Some(Err(e)),
+    };
+
+    match event {
+        ChatStreamEvent::ContentDelta { delta, index } => {
+            Some(Ok(CompletionStreamEvent::TextDelta { text: delta, index }))
+        }
+        ChatStreamEvent::UsageUpdate { usage } => Some(Ok(CompletionStreamEvent::Usage { usage })),
+        ChatStreamEvent::StreamEnd { response } => Some(Ok(CompletionStreamEvent::Done {
+            finish_reason: response.finish_reason,
+            usage: response.usage,
+        })),
+        ChatStreamEvent::Error { error } => Some(Ok(CompletionStreamEvent::Error { error })),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -256,4 +403,30 @@ mod tests {
         let response = capability.parse_generate_response(ollama_response);
         assert_eq!(response, "Hello there!");
     }
+
+    #[test]
+    fn test_build_completion_request_body_with_suffix() {
+        let capability = OllamaCompletionCapability::new(
+            "http://localhost:11434".to_string(),
+            reqwest::Client::new(),
+            HttpConfig::default(),
+            OllamaParams::default(),
+        );
+
+        let request = CompletionRequest::new("def add(a, b):")
+            .with_suffix("    return result")
+            .with_model("codellama:code")
+            .with_max_tokens(32)
+            .with_stop(vec!["\n\n".to_string()]);
+
+        let body = capability
+            .build_completion_request_body(&request, false)
+            .unwrap();
+        assert_eq!(body.model, "codellama:code");
+        assert_eq!(body.suffix.as_deref(), Some("    return result"));
+
+        let options = body.options.unwrap();
+        assert_eq!(options["num_predict"], 32);
+        assert_eq!(options["stop"], serde_json::json!(["\n\n"]));
+    }
 }
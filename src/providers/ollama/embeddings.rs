@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::error::LlmError;
+use crate::rate_limiter::{estimate_request_tokens, send_request};
 use crate::traits::{
     EmbeddingCapability, EmbeddingExtensions, OllamaEmbeddingCapability as OllamaEmbeddingTrait,
 };
@@ -161,14 +162,15 @@ impl OllamaEmbeddings {
         let headers = build_headers(&self.http_config.headers)?;
         let url = crate::utils::url::join_url(&self.base_url, "api/embed");
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(e.to_string()))?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&request),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "ollama",
+            &request.model,
+            estimate_request_tokens(&request),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status_code = response.status().as_u16();
@@ -7,6 +7,7 @@ use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 
 use crate::error::LlmError;
+use crate::rate_limiter::send_request;
 use crate::traits::ModelListingCapability;
 use crate::types::*;
 
@@ -63,13 +64,15 @@ impl OllamaModelsCapability {
             "stream": false
         });
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "ollama",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -97,13 +100,15 @@ impl OllamaModelsCapability {
             "stream": true
         });
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "ollama",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -149,13 +154,15 @@ impl OllamaModelsCapability {
             "model": model_name
         });
 
-        let response = self
-            .http_client
-            .delete(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.delete(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "ollama",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -181,13 +188,15 @@ impl OllamaModelsCapability {
             "destination": destination
         });
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "ollama",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -211,13 +220,15 @@ impl OllamaModelsCapability {
             "model": model_name
         });
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "ollama",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -257,7 +268,15 @@ impl OllamaModelsCapability {
         let headers = build_headers(&self.http_config.headers)?;
         let url = format!("{}/api/ps", self.base_url);
 
-        let response = self.http_client.get(&url).headers(headers).send().await?;
+        let response = send_request(
+            self.http_client.get(&url).headers(headers),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "ollama",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -292,7 +311,15 @@ impl ModelListingCapability for OllamaModelsCapability {
         let headers = build_headers(&self.http_config.headers)?;
         let url = crate::utils::url::join_url(&self.base_url, "api/tags");
 
-        let response = self.http_client.get(&url).headers(headers).send().await?;
+        let response = send_request(
+            self.http_client.get(&url).headers(headers),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "ollama",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -3,7 +3,9 @@
 //! This module provides Ollama streaming functionality using the
 //! eventsource-stream infrastructure for JSON streaming.
 
+use crate::cassette::Cassette;
 use crate::error::LlmError;
+use crate::rate_limiter::{RateLimiter, estimate_request_tokens, send_request};
 use crate::stream::{ChatStream, ChatStreamEvent};
 use crate::types::{ChatResponse, FinishReason, MessageContent, Usage};
 use crate::utils::streaming::{JsonEventConverter, StreamFactory};
@@ -11,6 +13,7 @@ use serde::Deserialize;
 use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::Arc;
 
 /// Ollama stream response structure
 #[derive(Debug, Clone, Deserialize)]
@@ -63,6 +66,7 @@ impl OllamaEventConverter {
                     completion_tokens,
                     total_tokens: prompt_tokens + completion_tokens,
                     cached_tokens: None,
+                    cache_write_tokens: None,
                     reasoning_tokens: None,
                 };
                 return Some(ChatStreamEvent::UsageUpdate { usage: usage_info });
@@ -77,6 +81,8 @@ impl OllamaEventConverter {
                 finish_reason: Some(FinishReason::Stop),
                 tool_calls: None,
                 thinking: None,
+                citations: Vec::new(),
+                sources: Vec::new(),
                 metadata: HashMap::new(),
             };
             return Some(ChatStreamEvent::StreamEnd { response });
@@ -155,12 +161,30 @@ impl JsonEventConverter for OllamaEventConverter {
 #[derive(Clone)]
 pub struct OllamaStreaming {
     http_client: reqwest::Client,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    cassette: Option<Arc<Cassette>>,
 }
 
 impl OllamaStreaming {
     /// Create a new Ollama streaming client
     pub fn new(http_client: reqwest::Client) -> Self {
-        Self { http_client }
+        Self {
+            http_client,
+            rate_limiter: None,
+            cassette: None,
+        }
+    }
+
+    /// Set the rate limiter requests wait on
+    pub fn with_rate_limiter(mut self, rate_limiter: Option<Arc<RateLimiter>>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Set the cassette requests are recorded into or replayed from
+    pub fn with_cassette(mut self, cassette: Option<Arc<Cassette>>) -> Self {
+        self.cassette = cassette;
+        self
     }
 
     /// Create a chat stream from URL, headers, and body
@@ -171,14 +195,15 @@ impl OllamaStreaming {
         body: crate::providers::ollama::types::OllamaChatRequest,
     ) -> Result<ChatStream, LlmError> {
         // Make the HTTP request
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.rate_limiter.as_deref(),
+            self.cassette.as_deref(),
+            "ollama",
+            &body.model,
+            estimate_request_tokens(&body),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -206,14 +231,15 @@ impl OllamaStreaming {
         body: crate::providers::ollama::types::OllamaGenerateRequest,
     ) -> Result<ChatStream, LlmError> {
         // Make the HTTP request
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.rate_limiter.as_deref(),
+            self.cassette.as_deref(),
+            "ollama",
+            &body.model,
+            estimate_request_tokens(&body),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
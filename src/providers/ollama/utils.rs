@@ -4,6 +4,7 @@
 
 use super::types::*;
 use crate::error::LlmError;
+use crate::multimodal::NormalizedImage;
 use crate::types::{ChatMessage, Tool, ToolCall};
 use crate::utils::http_headers::ProviderHeaders;
 use reqwest::header::HeaderMap;
@@ -57,7 +58,11 @@ pub fn convert_chat_message(message: &ChatMessage) -> OllamaChatMessage {
             .iter()
             .filter_map(|part| {
                 if let crate::types::ContentPart::Image { image_url, .. } = part {
-                    Some(image_url.clone())
+                    // Ollama expects bare base64 data rather than data URLs
+                    match NormalizedImage::parse(image_url) {
+                        NormalizedImage::Inline { data, .. } => Some(data),
+                        NormalizedImage::Url(url) => Some(url),
+                    }
                 } else {
                     None
                 }
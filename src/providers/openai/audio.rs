@@ -8,11 +8,13 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::error::LlmError;
+use crate::rate_limiter::send_request;
 use crate::traits::AudioCapability;
 use crate::types::{
-    AudioFeature, AudioTranslationRequest, LanguageInfo, SttRequest, SttResponse, TtsRequest,
-    TtsResponse, VoiceInfo, WordTimestamp,
+    AudioFeature, AudioStream, AudioTranslationRequest, LanguageInfo, SttRequest, SttResponse,
+    TtsRequest, TtsResponse, VoiceInfo, WordTimestamp,
 };
+use crate::utils::streaming::StreamFactory;
 
 use super::config::OpenAiConfig;
 
@@ -117,6 +119,7 @@ impl OpenAiAudio {
     pub fn new(config: OpenAiConfig, http_client: reqwest::Client) -> Self {
         let features = vec![
             AudioFeature::TextToSpeech,
+            AudioFeature::StreamingTTS,
             AudioFeature::SpeechToText,
             AudioFeature::AudioTranslation,
             AudioFeature::CharacterTiming,
@@ -223,8 +226,62 @@ impl OpenAiAudio {
         ]
     }
 
+    /// Build and validate a TTS API request.
+    fn build_tts_request(&self, request: TtsRequest) -> Result<OpenAiTtsRequest, LlmError> {
+        let voice = request.voice.unwrap_or_else(|| "alloy".to_string());
+        let format = request.format.unwrap_or_else(|| "mp3".to_string());
+        let model = request.model.unwrap_or_else(|| "tts-1".to_string());
+
+        // Extract instructions from extra_params
+        let instructions = request
+            .extra_params
+            .get("instructions")
+            .and_then(|v| v.as_str())
+            .map(std::string::ToString::to_string);
+
+        // Validate voice
+        if !self.is_voice_supported(&voice) {
+            return Err(LlmError::InvalidInput(format!(
+                "Unsupported voice: {}. Supported voices: {}",
+                voice,
+                self.get_tts_voices()
+                    .iter()
+                    .map(|v| v.id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+
+        // Validate request parameters
+        self.validate_tts_request(&model, &instructions)?;
+
+        Ok(OpenAiTtsRequest {
+            model,
+            input: request.text,
+            voice,
+            response_format: Some(format),
+            speed: request.speed,
+            instructions,
+        })
+    }
+
     /// Make a TTS API request.
     async fn make_tts_request(&self, request: OpenAiTtsRequest) -> Result<Vec<u8>, LlmError> {
+        let response = self.send_tts_request(&request).await?;
+
+        let audio_data = response
+            .bytes()
+            .await
+            .map_err(|e| LlmError::HttpError(format!("Failed to read audio data: {e}")))?;
+
+        Ok(audio_data.to_vec())
+    }
+
+    /// Send a TTS API request and return the response once headers arrive.
+    async fn send_tts_request(
+        &self,
+        request: &OpenAiTtsRequest,
+    ) -> Result<reqwest::Response, LlmError> {
         let url = format!("{}/audio/speech", self.config.base_url);
 
         let mut headers = reqwest::header::HeaderMap::new();
@@ -236,14 +293,15 @@ impl OpenAiAudio {
             headers.insert(header_name, header_value);
         }
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("TTS request failed: {e}")))?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(request),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            &self.config.provider_id,
+            &request.model,
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -258,12 +316,7 @@ impl OpenAiAudio {
             });
         }
 
-        let audio_data = response
-            .bytes()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Failed to read audio data: {e}")))?;
-
-        Ok(audio_data.to_vec())
+        Ok(response)
     }
 
     /// Make an STT API request.
@@ -284,7 +337,7 @@ impl OpenAiAudio {
         form = form.part("file", file_part);
 
         // Add other fields
-        form = form.text("model", request.model);
+        form = form.text("model", request.model.clone());
 
         if let Some(language) = request.language {
             form = form.text("language", language);
@@ -321,14 +374,15 @@ impl OpenAiAudio {
             headers.insert(header_name, header_value);
         }
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .multipart(form)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("STT request failed: {e}")))?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).multipart(form),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            &self.config.provider_id,
+            &request.model,
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -434,41 +488,11 @@ impl AudioCapability for OpenAiAudio {
 
     /// Convert text to speech.
     async fn text_to_speech(&self, request: TtsRequest) -> Result<TtsResponse, LlmError> {
-        let voice = request.voice.unwrap_or_else(|| "alloy".to_string());
-        let format = request.format.unwrap_or_else(|| "mp3".to_string());
-        let model = request.model.unwrap_or_else(|| "tts-1".to_string());
-
-        // Extract instructions from extra_params
-        let instructions = request
-            .extra_params
-            .get("instructions")
-            .and_then(|v| v.as_str())
-            .map(std::string::ToString::to_string);
-
-        // Validate voice
-        if !self.is_voice_supported(&voice) {
-            return Err(LlmError::InvalidInput(format!(
-                "Unsupported voice: {}. Supported voices: {}",
-                voice,
-                self.get_tts_voices()
-                    .iter()
-                    .map(|v| v.id.as_str())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            )));
-        }
-
-        // Validate request parameters
-        self.validate_tts_request(&model, &instructions)?;
-
-        let openai_request = OpenAiTtsRequest {
-            model,
-            input: request.text,
-            voice,
-            response_format: Some(format.clone()),
-            speed: request.speed,
-            instructions,
-        };
+        let openai_request = self.build_tts_request(request)?;
+        let format = openai_request
+            .response_format
+            .clone()
+            .unwrap_or_else(|| "mp3".to_string());
 
         let audio_data = self.make_tts_request(openai_request).await?;
 
@@ -481,6 +505,31 @@ impl AudioCapability for OpenAiAudio {
         })
     }
 
+    /// Convert text to speech, yielding audio chunks as they are generated.
+    ///
+    /// Use the `pcm` format for the lowest latency; raw PCM is 24kHz 16-bit mono.
+    async fn text_to_speech_stream(&self, request: TtsRequest) -> Result<AudioStream, LlmError> {
+        let openai_request = self.build_tts_request(request)?;
+        let format = openai_request
+            .response_format
+            .clone()
+            .unwrap_or_else(|| "mp3".to_string());
+
+        let mut metadata = HashMap::new();
+        metadata.insert("model".to_string(), serde_json::json!(openai_request.model));
+        metadata.insert("voice".to_string(), serde_json::json!(openai_request.voice));
+
+        let response = self.send_tts_request(&openai_request).await?;
+        let sample_rate = (format == "pcm").then_some(24000);
+
+        Ok(StreamFactory::create_audio_stream(
+            response,
+            format,
+            sample_rate,
+            metadata,
+        ))
+    }
+
     /// Convert speech to text.
     async fn speech_to_text(&self, request: SttRequest) -> Result<SttResponse, LlmError> {
         let (file_data, filename) = if let Some(data) = request.audio_data {
@@ -551,7 +600,7 @@ impl AudioCapability for OpenAiAudio {
         form = form.part("file", file_part);
 
         let model = request.model.unwrap_or_else(|| "whisper-1".to_string());
-        form = form.text("model", model);
+        form = form.text("model", model.clone());
         form = form.text("response_format", "json");
 
         let mut headers = reqwest::header::HeaderMap::new();
@@ -566,14 +615,15 @@ impl AudioCapability for OpenAiAudio {
             headers.insert(header_name, header_value);
         }
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .multipart(form)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Translation request failed: {e}")))?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).multipart(form),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            &self.config.provider_id,
+            &model,
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -0,0 +1,513 @@
+//! `OpenAI` Batch API Implementation
+//!
+//! This module provides the `OpenAI` implementation of the `BatchCapability` trait.
+//! Requests are written as JSONL, uploaded through the Files API and processed
+//! against the Chat Completions endpoint.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::error::LlmError;
+use crate::rate_limiter::send_request;
+use crate::traits::{BatchCapability, FileManagementCapability};
+use crate::types::*;
+
+use super::chat::OpenAiChatCapability;
+use super::config::OpenAiConfig;
+use super::files::OpenAiFiles;
+use super::types::OpenAiChatResponse;
+
+/// Endpoint batch requests are processed against
+const BATCH_ENDPOINT: &str = "/v1/chat/completions";
+
+/// Completion window for new batches
+const COMPLETION_WINDOW: &str = "24h";
+
+/// `OpenAI` batch object
+#[derive(Debug, Clone, Deserialize)]
+struct OpenAiBatch {
+    id: String,
+    endpoint: Option<String>,
+    errors: Option<serde_json::Value>,
+    input_file_id: Option<String>,
+    status: String,
+    output_file_id: Option<String>,
+    error_file_id: Option<String>,
+    created_at: u64,
+    expires_at: Option<u64>,
+    completed_at: Option<u64>,
+    failed_at: Option<u64>,
+    expired_at: Option<u64>,
+    cancelled_at: Option<u64>,
+    request_counts: Option<OpenAiBatchRequestCounts>,
+    metadata: Option<serde_json::Value>,
+}
+
+/// `OpenAI` batch request counts
+#[derive(Debug, Clone, Deserialize)]
+struct OpenAiBatchRequestCounts {
+    total: u32,
+    completed: u32,
+    failed: u32,
+}
+
+/// `OpenAI` batch list response
+#[derive(Debug, Clone, Deserialize)]
+struct OpenAiBatchList {
+    data: Vec<OpenAiBatch>,
+    has_more: Option<bool>,
+    last_id: Option<String>,
+}
+
+/// A line of the batch input file
+#[derive(Debug, Serialize)]
+struct OpenAiBatchInputLine<'a> {
+    custom_id: &'a str,
+    method: &'static str,
+    url: &'static str,
+    body: serde_json::Value,
+}
+
+/// A line of the batch output or error file
+#[derive(Debug, Deserialize)]
+struct OpenAiBatchOutputLine {
+    custom_id: String,
+    response: Option<OpenAiBatchOutputResponse>,
+    error: Option<OpenAiBatchError>,
+}
+
+/// The HTTP response recorded for a batch request
+#[derive(Debug, Deserialize)]
+struct OpenAiBatchOutputResponse {
+    status_code: u16,
+    body: serde_json::Value,
+}
+
+/// The error recorded for a batch request that was not sent
+#[derive(Debug, Deserialize)]
+struct OpenAiBatchError {
+    code: Option<String>,
+    message: Option<String>,
+}
+
+/// `OpenAI` batch capability implementation.
+///
+/// # API Reference
+/// <https://platform.openai.com/docs/api-reference/batch>
+#[derive(Debug, Clone)]
+pub struct OpenAiBatches {
+    /// `OpenAI` configuration
+    config: OpenAiConfig,
+    /// HTTP client
+    http_client: reqwest::Client,
+}
+
+impl OpenAiBatches {
+    /// Create a new `OpenAI` batches instance.
+    ///
+    /// # Arguments
+    /// * `config` - `OpenAI` configuration
+    /// * `http_client` - HTTP client for making requests
+    pub const fn new(config: OpenAiConfig, http_client: reqwest::Client) -> Self {
+        Self {
+            config,
+            http_client,
+        }
+    }
+
+    fn files(&self) -> OpenAiFiles {
+        OpenAiFiles::new(self.config.clone(), self.http_client.clone())
+    }
+
+    fn chat(&self) -> OpenAiChatCapability {
+        OpenAiChatCapability::new(
+            self.config.api_key.clone(),
+            self.config.base_url.clone(),
+            self.http_client.clone(),
+            self.config.organization.clone(),
+            self.config.project.clone(),
+            self.config.http_config.clone(),
+            self.config.common_params.clone(),
+        )
+    }
+
+    /// Build the JSONL input file for a batch.
+    pub(crate) fn build_input_file(&self, requests: &[BatchRequest]) -> Result<Vec<u8>, LlmError> {
+        if requests.is_empty() {
+            return Err(LlmError::InvalidInput(
+                "A batch must contain at least one request".to_string(),
+            ));
+        }
+
+        let chat = self.chat();
+        let mut content = Vec::new();
+        for request in requests {
+            let line = OpenAiBatchInputLine {
+                custom_id: &request.custom_id,
+                method: "POST",
+                url: BATCH_ENDPOINT,
+                body: chat.build_chat_request_body(&request.resolve(&self.config.common_params))?,
+            };
+            serde_json::to_writer(&mut content, &line)?;
+            content.push(b'\n');
+        }
+        Ok(content)
+    }
+
+    /// Parse an output or error file into results.
+    fn parse_results(&self, content: &[u8]) -> Result<Vec<BatchResult>, LlmError> {
+        let chat = self.chat();
+        let text = String::from_utf8_lossy(content);
+        let mut results = Vec::new();
+
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let line: OpenAiBatchOutputLine = serde_json::from_str(line)?;
+            let result = match (line.response, line.error) {
+                (Some(response), _) if response.status_code == 200 => {
+                    serde_json::from_value::<OpenAiChatResponse>(response.body)
+                        .map_err(LlmError::from)
+                        .and_then(|body| chat.parse_chat_response(body))
+                }
+                (Some(response), _) => Err(LlmError::ApiError {
+                    code: response.status_code,
+                    message: response
+                        .body
+                        .pointer("/error/message")
+                        .and_then(|m| m.as_str())
+                        .unwrap_or("Batch request failed")
+                        .to_string(),
+                    details: Some(response.body),
+                }),
+                (None, Some(error)) => Err(LlmError::ProviderError {
+                    provider: self.config.provider_id.clone(),
+                    message: error
+                        .message
+                        .unwrap_or_else(|| "Batch request failed".to_string()),
+                    error_code: error.code,
+                }),
+                (None, None) => Err(LlmError::ParseError(format!(
+                    "Batch result for '{}' has neither a response nor an error",
+                    line.custom_id
+                ))),
+            };
+            results.push(BatchResult {
+                custom_id: line.custom_id,
+                result,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Convert `OpenAI` batch to our standard format.
+    fn convert_batch(&self, batch: OpenAiBatch) -> Result<BatchResponse, LlmError> {
+        let mut metadata = HashMap::new();
+        if let Some(endpoint) = batch.endpoint {
+            metadata.insert("endpoint".to_string(), serde_json::Value::String(endpoint));
+        }
+        if let Some(input_file_id) = batch.input_file_id {
+            metadata.insert(
+                "input_file_id".to_string(),
+                serde_json::Value::String(input_file_id),
+            );
+        }
+        if let Some(errors) = batch.errors {
+            metadata.insert("errors".to_string(), errors);
+        }
+        if let Some(user_metadata) = batch.metadata {
+            metadata.insert("metadata".to_string(), user_metadata);
+        }
+
+        let request_counts = batch
+            .request_counts
+            .map(|counts| BatchRequestCounts {
+                total: counts.total,
+                completed: counts.completed,
+                failed: counts.failed,
+            })
+            .unwrap_or_default();
+
+        Ok(BatchResponse {
+            id: batch.id,
+            status: batch.status.parse()?,
+            request_counts,
+            created_at: Some(batch.created_at),
+            ended_at: batch
+                .completed_at
+                .or(batch.failed_at)
+                .or(batch.expired_at)
+                .or(batch.cancelled_at),
+            expires_at: batch.expires_at,
+            output_file_id: batch.output_file_id,
+            error_file_id: batch.error_file_id,
+            results_url: None,
+            metadata,
+        })
+    }
+
+    /// Make HTTP request with proper headers.
+    fn make_request(
+        &self,
+        method: reqwest::Method,
+        endpoint: &str,
+    ) -> Result<reqwest::RequestBuilder, LlmError> {
+        let url = format!("{}/{}", self.config.base_url, endpoint);
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (key, value) in self.config.get_headers() {
+            let header_name = reqwest::header::HeaderName::from_bytes(key.as_bytes())
+                .map_err(|e| LlmError::HttpError(format!("Invalid header name: {e}")))?;
+            let header_value = reqwest::header::HeaderValue::from_str(&value)
+                .map_err(|e| LlmError::HttpError(format!("Invalid header value: {e}")))?;
+            headers.insert(header_name, header_value);
+        }
+
+        Ok(self.http_client.request(method, &url).headers(headers))
+    }
+
+    /// Send a request and parse the batch object in the response.
+    async fn send_batch_request(
+        &self,
+        request_builder: reqwest::RequestBuilder,
+    ) -> Result<OpenAiBatch, LlmError> {
+        let response = send_request(
+            request_builder,
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            &self.config.provider_id,
+            "",
+            0,
+        )
+        .await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_response_error(response).await);
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| LlmError::ParseError(format!("Failed to parse response: {e}")))
+    }
+
+    /// Handle API response errors.
+    async fn handle_response_error(&self, response: reqwest::Response) -> LlmError {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+
+        match status.as_u16() {
+            404 => LlmError::NotFound(format!("Batch not found: {error_text}")),
+            _ => LlmError::ApiError {
+                code: status.as_u16(),
+                message: format!("OpenAI Batch API error {status}: {error_text}"),
+                details: serde_json::from_str(&error_text).ok(),
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl BatchCapability for OpenAiBatches {
+    /// Upload the requests as a JSONL file and create a batch from it.
+    async fn create_batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResponse, LlmError> {
+        let content = self.build_input_file(&requests)?;
+
+        let file = self
+            .files()
+            .upload_file(FileUploadRequest {
+                content,
+                filename: "batch.jsonl".to_string(),
+                mime_type: Some("application/jsonl".to_string()),
+                purpose: "batch".to_string(),
+                metadata: HashMap::new(),
+            })
+            .await?;
+
+        let body = serde_json::json!({
+            "input_file_id": file.id,
+            "endpoint": BATCH_ENDPOINT,
+            "completion_window": COMPLETION_WINDOW,
+        });
+        let request_builder = self
+            .make_request(reqwest::Method::POST, "batches")?
+            .json(&body);
+        let batch = self.send_batch_request(request_builder).await?;
+
+        self.convert_batch(batch)
+    }
+
+    async fn retrieve_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError> {
+        let request_builder =
+            self.make_request(reqwest::Method::GET, &format!("batches/{batch_id}"))?;
+        let batch = self.send_batch_request(request_builder).await?;
+
+        self.convert_batch(batch)
+    }
+
+    async fn list_batches(
+        &self,
+        query: Option<BatchListQuery>,
+    ) -> Result<BatchListResponse, LlmError> {
+        let mut endpoint = "batches".to_string();
+
+        if let Some(q) = query {
+            let mut params = Vec::new();
+
+            if let Some(limit) = q.limit {
+                params.push(format!("limit={limit}"));
+            }
+            if let Some(after) = q.after {
+                params.push(format!("after={}", urlencoding::encode(&after)));
+            }
+
+            if !params.is_empty() {
+                endpoint.push('?');
+                endpoint.push_str(&params.join("&"));
+            }
+        }
+
+        let response = send_request(
+            self.make_request(reqwest::Method::GET, &endpoint)?,
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            &self.config.provider_id,
+            "",
+            0,
+        )
+        .await?;
+
+        if !response.status().is_success() {
+            return Err(self.handle_response_error(response).await);
+        }
+
+        let list: OpenAiBatchList = response
+            .json()
+            .await
+            .map_err(|e| LlmError::ParseError(format!("Failed to parse response: {e}")))?;
+
+        Ok(BatchListResponse {
+            batches: list
+                .data
+                .into_iter()
+                .map(|batch| self.convert_batch(batch))
+                .collect::<Result<_, _>>()?,
+            has_more: list.has_more.unwrap_or(false),
+            next_cursor: list.last_id,
+        })
+    }
+
+    async fn cancel_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError> {
+        let request_builder =
+            self.make_request(reqwest::Method::POST, &format!("batches/{batch_id}/cancel"))?;
+        let batch = self.send_batch_request(request_builder).await?;
+
+        self.convert_batch(batch)
+    }
+
+    /// Download the output and error files and parse them into results.
+    async fn get_batch_results(&self, batch_id: String) -> Result<Vec<BatchResult>, LlmError> {
+        let batch = self.retrieve_batch(batch_id).await?;
+        let files = self.files();
+
+        let mut results = Vec::new();
+        for file_id in [batch.output_file_id, batch.error_file_id]
+            .into_iter()
+            .flatten()
+        {
+            let content = files.get_file_content(file_id).await?;
+            results.extend(self.parse_results(&content)?);
+        }
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_batches() -> OpenAiBatches {
+        OpenAiBatches::new(
+            OpenAiConfig::new("test-key").with_model("gpt-4o-mini"),
+            reqwest::Client::new(),
+        )
+    }
+
+    #[test]
+    fn test_build_input_file() {
+        let batches = create_test_batches();
+        let requests = vec![
+            BatchRequest::new("req-1", vec![ChatMessage::user("Hello").build()]),
+            BatchRequest::new("req-2", vec![ChatMessage::user("Hi").build()]).with_model("gpt-4o"),
+        ];
+
+        let content = String::from_utf8(batches.build_input_file(&requests).unwrap()).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["custom_id"], "req-1");
+        assert_eq!(lines[0]["url"], BATCH_ENDPOINT);
+        assert_eq!(lines[0]["body"]["model"], "gpt-4o-mini");
+        assert_eq!(lines[1]["body"]["model"], "gpt-4o");
+        assert!(batches.build_input_file(&[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_results() {
+        let batches = create_test_batches();
+        let content = [
+            serde_json::json!({
+                "id": "batch_req_1",
+                "custom_id": "req-1",
+                "response": {
+                    "status_code": 200,
+                    "body": {
+                        "id": "chatcmpl-1",
+                        "object": "chat.completion",
+                        "created": 1_700_000_000,
+                        "model": "gpt-4o-mini",
+                        "choices": [{
+                            "index": 0,
+                            "message": {"role": "assistant", "content": "Hello!"},
+                            "finish_reason": "stop"
+                        }]
+                    }
+                },
+                "error": null
+            }),
+            serde_json::json!({
+                "id": "batch_req_2",
+                "custom_id": "req-2",
+                "response": {
+                    "status_code": 400,
+                    "body": {"error": {"message": "Invalid model"}}
+                },
+                "error": null
+            }),
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        let results = batches.parse_results(content.as_bytes()).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].custom_id, "req-1");
+        assert_eq!(
+            results[0].result.as_ref().unwrap().content_text(),
+            Some("Hello!")
+        );
+        assert!(matches!(
+            results[1].result,
+            Err(LlmError::ApiError { code: 400, .. })
+        ));
+    }
+}
@@ -63,6 +63,8 @@ pub struct OpenAiBuilder {
     built_in_tools: Vec<OpenAiBuiltInTool>,
     /// Web search config
     web_search_config: WebSearchConfig,
+    /// Provider served through the `OpenAI` API
+    provider_id: String,
 }
 
 impl OpenAiBuilder {
@@ -86,6 +88,7 @@ impl OpenAiBuilder {
             previous_response_id: None,
             built_in_tools: Vec::new(),
             web_search_config: WebSearchConfig::default(),
+            provider_id: "openai".to_string(),
         }
     }
 
@@ -103,6 +106,15 @@ impl OpenAiBuilder {
         self
     }
 
+    /// Set the provider served through the `OpenAI` API (e.g. "openrouter").
+    ///
+    /// Provider-specific request features are selected by this ID rather than
+    /// by the base URL.
+    pub fn provider_id<S: Into<String>>(mut self, provider_id: S) -> Self {
+        self.provider_id = provider_id.into();
+        self
+    }
+
     /// Set a custom base URL for the `OpenAI` API.
     ///
     /// This is useful for using OpenAI-compatible APIs or proxies.
@@ -453,6 +465,11 @@ impl OpenAiBuilder {
         // Build HTTP client using the base builder
         let http_client = self.base.build_http_client()?;
 
+        let mut http_config = self.http_config;
+        if http_config.cassette.is_none() {
+            http_config.cassette = self.base.cassette.clone();
+        }
+
         // Create OpenAI configuration
         let config = OpenAiConfig {
             api_key: secrecy::SecretString::from(api_key),
@@ -461,11 +478,12 @@ impl OpenAiBuilder {
             project: self.project,
             common_params: self.common_params,
             openai_params: self.openai_params,
-            http_config: self.http_config,
+            http_config,
             web_search_config: self.web_search_config,
             use_responses_api: self.use_responses_api,
             previous_response_id: self.previous_response_id,
             built_in_tools: self.built_in_tools,
+            provider_id: self.provider_id,
         };
 
         // Create client and store tracing guard to keep tracing active
@@ -9,6 +9,7 @@ use std::time::Instant;
 
 use crate::error::LlmError;
 use crate::params::{OpenAiParameterMapper, OpenAiParams, ParameterMapper};
+use crate::rate_limiter::{estimate_request_tokens, send_request};
 use crate::stream::ChatStream;
 use crate::traits::ChatCapability;
 use crate::types::*;
@@ -112,6 +113,8 @@ pub struct OpenAiChatCapability {
     pub parameter_mapper: OpenAiParameterMapper,
     pub common_params: CommonParams,
     pub request_builder: OpenAiRequestBuilder,
+    /// Provider served through the `OpenAI` API
+    pub provider_id: String,
 }
 
 impl OpenAiChatCapability {
@@ -140,9 +143,16 @@ impl OpenAiChatCapability {
             parameter_mapper: OpenAiParameterMapper,
             common_params,
             request_builder,
+            provider_id: "openai".to_string(),
         }
     }
 
+    /// Set the provider served through the `OpenAI` API
+    pub fn with_provider_id<S: Into<String>>(mut self, provider_id: S) -> Self {
+        self.provider_id = provider_id.into();
+        self
+    }
+
     /// Build the chat request body
     pub fn build_chat_request_body(
         &self,
@@ -174,6 +184,31 @@ impl OpenAiChatCapability {
             body["tools"] = serde_json::to_value(tools)?;
         }
 
+        // OpenRouter grounds any model through its `web` plugin
+        if let Some(config) = request.web_search.as_ref().filter(|config| config.enabled)
+            && self.provider_id == "openrouter"
+        {
+            let plugin =
+                crate::web_search::WebSearchProvider::new("openrouter".to_string(), config.clone())
+                    .build_openrouter_plugin();
+            match body
+                .get_mut("plugins")
+                .and_then(|plugins| plugins.as_array_mut())
+            {
+                Some(plugins) => plugins.push(plugin),
+                None => body["plugins"] = serde_json::Value::Array(vec![plugin]),
+            }
+        }
+
+        // Prefix caching is automatic; the policy's key only routes requests to a warm cache
+        if let Some(key) = request
+            .prompt_cache
+            .as_ref()
+            .and_then(|policy| policy.cache_key.as_ref())
+        {
+            body["prompt_cache_key"] = serde_json::Value::String(key.clone());
+        }
+
         // Clean up null values that might cause API errors
         self.clean_null_values(&mut body);
 
@@ -202,7 +237,10 @@ impl OpenAiChatCapability {
     }
 
     /// Parse the `OpenAI` response
-    fn parse_chat_response(&self, response: OpenAiChatResponse) -> Result<ChatResponse, LlmError> {
+    pub(crate) fn parse_chat_response(
+        &self,
+        response: OpenAiChatResponse,
+    ) -> Result<ChatResponse, LlmError> {
         let choice = response
             .choices
             .into_iter()
@@ -260,6 +298,15 @@ impl OpenAiChatCapability {
             MessageContent::Text(String::new())
         };
 
+        let citations: Vec<Citation> = choice
+            .message
+            .annotations
+            .iter()
+            .flatten()
+            .filter_map(|annotation| convert_url_citation(annotation, content.text(), 0))
+            .collect();
+        let sources = SearchSource::from_citations(&citations);
+
         let tool_calls = choice.message.tool_calls.map(|calls| {
             calls
                 .into_iter()
@@ -281,7 +328,10 @@ impl OpenAiChatCapability {
             completion_tokens: u.completion_tokens.unwrap_or(0),
             total_tokens: u.total_tokens.unwrap_or(0),
             reasoning_tokens: None, // Specific to OpenAI o1, requires special handling
-            cached_tokens: None,
+            cached_tokens: u
+                .prompt_tokens_details
+                .and_then(|details| details.cached_tokens),
+            cache_write_tokens: None,
         });
 
         let _metadata = ResponseMetadata {
@@ -303,6 +353,8 @@ impl OpenAiChatCapability {
             finish_reason,
             tool_calls,
             thinking: thinking_content, // Now includes extracted <think> content
+            citations,
+            sources,
             metadata: HashMap::new(),
         })
     }
@@ -341,13 +393,15 @@ impl ChatCapability for OpenAiChatCapability {
             "Sending OpenAI API request"
         );
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "openai",
+            body["model"].as_str().unwrap_or_default(),
+            estimate_request_tokens(&body),
+        )
+        .await?;
 
         let duration = start_time.elapsed();
 
@@ -419,6 +473,7 @@ impl ChatCapability for OpenAiChatCapability {
             use_responses_api: false,
             previous_response_id: None,
             built_in_tools: Vec::new(),
+            provider_id: self.provider_id.clone(),
         };
 
         let streaming = super::streaming::OpenAiStreaming::new(config, self.http_client.clone());
@@ -451,13 +506,15 @@ impl OpenAiChatCapability {
             "Sending OpenAI API request"
         );
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "openai",
+            body["model"].as_str().unwrap_or_default(),
+            estimate_request_tokens(&body),
+        )
+        .await?;
 
         let duration = start_time.elapsed();
 
@@ -521,6 +578,7 @@ impl OpenAiChatCapability {
             use_responses_api: false,
             previous_response_id: None,
             built_in_tools: Vec::new(),
+            provider_id: self.provider_id.clone(),
         };
 
         let streaming = super::streaming::OpenAiStreaming::new(config, self.http_client.clone());
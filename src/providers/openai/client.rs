@@ -8,6 +8,7 @@ use crate::client::LlmClient;
 use crate::error::LlmError;
 use crate::params::OpenAiParams;
 use crate::stream::ChatStream;
+use crate::stream_handle::ChatStreamHandle;
 use crate::traits::*;
 use crate::types::*;
 
@@ -83,7 +84,8 @@ impl OpenAiClient {
             config.project.clone(),
             config.http_config.clone(),
             config.common_params.clone(),
-        );
+        )
+        .with_provider_id(config.provider_id.clone());
 
         let models_capability = OpenAiModels::new(
             config.api_key.clone(),
@@ -128,9 +130,9 @@ impl OpenAiClient {
         let http_client = reqwest::Client::new();
         Self::new(config, http_client)
     }
-    /// Decide whether to use Responses API for current client config (auto routes gpt-5*)
-    pub(crate) fn should_use_responses(&self) -> bool {
-        let cfg = super::config::OpenAiConfig {
+    /// The configuration this client was built from
+    fn config(&self) -> super::config::OpenAiConfig {
+        super::config::OpenAiConfig {
             api_key: self.chat_capability.api_key.clone(),
             base_url: self.chat_capability.base_url.clone(),
             organization: self.chat_capability.organization.clone(),
@@ -142,8 +144,134 @@ impl OpenAiClient {
             use_responses_api: self.use_responses_api,
             previous_response_id: self.previous_response_id.clone(),
             built_in_tools: self.built_in_tools.clone(),
+            provider_id: self.chat_capability.provider_id.clone(),
+        }
+    }
+
+    /// Apply a request's parameter overrides to the client configuration
+    fn request_config(&self, request: &ChatRequest) -> super::config::OpenAiConfig {
+        super::config::OpenAiConfig {
+            common_params: self.common_params.merged_with(&request.common_params),
+            web_search_config: request
+                .web_search
+                .clone()
+                .unwrap_or_else(|| self.web_search_config.clone()),
+            ..self.config()
+        }
+    }
+
+    /// Route a request with parameter overrides to the Responses API or Chat Completions
+    ///
+    /// An output schema is sent as `response_format`, and a prompt cache key as
+    /// `prompt_cache_key`.
+    fn route_request(&self, request: ChatRequest, stream: bool) -> Result<RequestRoute, LlmError> {
+        let mut config = self.request_config(&request);
+        if super::utils::should_route_responses(&config) {
+            if request.provider_params.is_some() {
+                return Err(LlmError::UnsupportedOperation(
+                    "Provider parameters cannot be overridden on the Responses API".to_string(),
+                ));
+            }
+            if let Some(key) = request
+                .prompt_cache
+                .as_ref()
+                .and_then(|policy| policy.cache_key.clone())
+            {
+                config.openai_params.prompt_cache_key = Some(key);
+            }
+            if let Some(schema) = &request.output_schema {
+                config.openai_params.response_format =
+                    Some(crate::params::openai::ResponseFormat::JsonSchema {
+                        schema: schema.strict_schema(),
+                    });
+            }
+            let config = super::config::OpenAiConfig {
+                use_responses_api: true,
+                ..config
+            };
+            return Ok(RequestRoute::Responses(
+                Box::new(OpenAiResponses::new(self.http_client.clone(), config)),
+                request,
+            ));
+        }
+
+        let mut provider_params = ProviderParams::from_openai(self.openai_params.clone());
+        if let Some(schema) = &request.output_schema {
+            provider_params =
+                provider_params.with_param("response_format", schema.to_openai_response_format());
+        }
+        Ok(RequestRoute::ChatCompletions(ChatRequest {
+            common_params: config.common_params,
+            provider_params: ProviderParams::overlay(
+                Some(&provider_params),
+                request.provider_params.as_ref(),
+            ),
+            http_config: None,
+            stream,
+            ..request
+        }))
+    }
+
+    /// Build a standalone configuration for endpoint-specific capabilities
+    fn endpoint_config(&self) -> super::config::OpenAiConfig {
+        super::config::OpenAiConfig {
+            web_search_config: crate::types::WebSearchConfig::default(),
+            use_responses_api: false,
+            previous_response_id: None,
+            built_in_tools: Vec::new(),
+            ..self.config()
+        }
+    }
+
+    /// Create a legacy completions capability using the client's configuration
+    fn completions(&self) -> super::completions::OpenAiCompletions {
+        super::completions::OpenAiCompletions::new(self.endpoint_config(), self.http_client.clone())
+    }
+
+    /// Create an audio capability using the client's configuration
+    fn audio(&self) -> super::audio::OpenAiAudio {
+        super::audio::OpenAiAudio::new(self.endpoint_config(), self.http_client.clone())
+    }
+
+    /// Create an image generation capability using the client's configuration
+    fn images(&self) -> super::images::OpenAiImages {
+        super::images::OpenAiImages::new(self.endpoint_config(), self.http_client.clone())
+    }
+
+    /// Create a batch capability using the client's configuration
+    fn batches(&self) -> super::batches::OpenAiBatches {
+        super::batches::OpenAiBatches::new(self.endpoint_config(), self.http_client.clone())
+    }
+
+    /// Stream through the Responses API so that a dropped connection resumes
+    /// where it stopped.
+    ///
+    /// The response runs in background mode; see
+    /// [`OpenAiResponses::chat_stream_resumable`].
+    pub async fn chat_stream_resumable(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+    ) -> Result<ChatStreamHandle, LlmError> {
+        let config = super::config::OpenAiConfig {
+            use_responses_api: true,
+            ..self.config()
         };
-        super::utils::should_route_responses(&cfg)
+        let responses = OpenAiResponses::new(self.http_client.clone(), config);
+        responses.chat_stream_resumable(messages, tools).await
+    }
+
+    /// Decide whether to use Responses API for current client config (auto routes gpt-5*)
+    pub(crate) fn should_use_responses(&self) -> bool {
+        super::utils::should_route_responses(&self.config())
+    }
+
+    /// Whether this client serves `OpenAI` itself rather than an OpenAI-compatible provider.
+    ///
+    /// Compatible providers only expose chat, so endpoints such as audio,
+    /// completions and batches are not advertised for them.
+    fn is_openai(&self) -> bool {
+        self.chat_capability.provider_id == "openai"
     }
 
     /// Creates a new `OpenAI` client (legacy constructor for backward compatibility)
@@ -170,6 +298,7 @@ impl OpenAiClient {
             use_responses_api: false,
             previous_response_id: None,
             built_in_tools: Vec::new(),
+            provider_id: "openai".to_string(),
         };
 
         Self::new(config, http_client)
@@ -246,8 +375,18 @@ impl OpenAiClient {
     }
 }
 
+/// Where a chat request with parameter overrides is sent
+enum RequestRoute {
+    Responses(Box<OpenAiResponses>, ChatRequest),
+    ChatCompletions(ChatRequest),
+}
+
 #[async_trait]
 impl ChatCapability for OpenAiClient {
+    fn default_model(&self) -> Option<String> {
+        Some(self.common_params.model.clone()).filter(|model| !model.is_empty())
+    }
+
     /// Chat with tools implementation
     async fn chat_with_tools(
         &self,
@@ -256,17 +395,8 @@ impl ChatCapability for OpenAiClient {
     ) -> Result<ChatResponse, LlmError> {
         if self.should_use_responses() {
             let config = super::config::OpenAiConfig {
-                api_key: self.chat_capability.api_key.clone(),
-                base_url: self.chat_capability.base_url.clone(),
-                organization: self.chat_capability.organization.clone(),
-                project: self.chat_capability.project.clone(),
-                common_params: self.common_params.clone(),
-                openai_params: self.openai_params.clone(),
-                http_config: self.chat_capability.http_config.clone(),
-                web_search_config: self.web_search_config.clone(),
                 use_responses_api: true,
-                previous_response_id: self.previous_response_id.clone(),
-                built_in_tools: self.built_in_tools.clone(),
+                ..self.config()
             };
             let responses = OpenAiResponses::new(self.http_client.clone(), config);
             responses.chat_with_tools(messages, tools).await
@@ -280,6 +410,8 @@ impl ChatCapability for OpenAiClient {
                 http_config: None,
                 web_search: None,
                 stream: false,
+                prompt_cache: None,
+                output_schema: None,
             };
             self.chat_capability.chat(request).await
         }
@@ -293,17 +425,8 @@ impl ChatCapability for OpenAiClient {
     ) -> Result<ChatStream, LlmError> {
         if self.should_use_responses() {
             let config = super::config::OpenAiConfig {
-                api_key: self.chat_capability.api_key.clone(),
-                base_url: self.chat_capability.base_url.clone(),
-                organization: self.chat_capability.organization.clone(),
-                project: self.chat_capability.project.clone(),
-                common_params: self.common_params.clone(),
-                openai_params: self.openai_params.clone(),
-                http_config: self.chat_capability.http_config.clone(),
-                web_search_config: self.web_search_config.clone(),
                 use_responses_api: true,
-                previous_response_id: self.previous_response_id.clone(),
-                built_in_tools: self.built_in_tools.clone(),
+                ..self.config()
             };
             let responses = OpenAiResponses::new(self.http_client.clone(), config);
             responses.chat_stream(messages, tools).await
@@ -312,6 +435,48 @@ impl ChatCapability for OpenAiClient {
             self.chat_capability.chat_stream(messages, tools).await
         }
     }
+
+    /// Chat with structured output via `response_format: json_schema`
+    async fn chat_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatResponse, LlmError> {
+        self.chat_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    /// Stream structured output via `response_format: json_schema`
+    async fn chat_stream_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatStream, LlmError> {
+        self.chat_stream_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    async fn chat_with_request(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        match self.route_request(request, false)? {
+            RequestRoute::Responses(responses, request) => {
+                responses
+                    .chat_with_tools(request.messages, request.tools)
+                    .await
+            }
+            RequestRoute::ChatCompletions(request) => self.chat_capability.chat(request).await,
+        }
+    }
+
+    async fn chat_stream_with_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
+        match self.route_request(request, true)? {
+            RequestRoute::Responses(responses, request) => {
+                responses.chat_stream(request.messages, request.tools).await
+            }
+            RequestRoute::ChatCompletions(request) => {
+                self.chat_capability.chat_stream_request(request).await
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -325,23 +490,108 @@ impl ModelListingCapability for OpenAiClient {
     }
 }
 
+#[async_trait]
+impl CompletionCapability for OpenAiClient {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        self.completions().complete(request).await
+    }
+
+    async fn complete_stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionStream, LlmError> {
+        self.completions().complete_stream(request).await
+    }
+}
+
+/// Audio features supported by `OpenAI`
+const OPENAI_AUDIO_FEATURES: &[AudioFeature] = &[
+    AudioFeature::TextToSpeech,
+    AudioFeature::StreamingTTS,
+    AudioFeature::SpeechToText,
+    AudioFeature::AudioTranslation,
+    AudioFeature::CharacterTiming,
+];
+
+#[async_trait]
+impl AudioCapability for OpenAiClient {
+    fn supported_features(&self) -> &[AudioFeature] {
+        OPENAI_AUDIO_FEATURES
+    }
+
+    async fn text_to_speech(&self, request: TtsRequest) -> Result<TtsResponse, LlmError> {
+        self.audio().text_to_speech(request).await
+    }
+
+    async fn text_to_speech_stream(&self, request: TtsRequest) -> Result<AudioStream, LlmError> {
+        self.audio().text_to_speech_stream(request).await
+    }
+
+    async fn speech_to_text(&self, request: SttRequest) -> Result<SttResponse, LlmError> {
+        self.audio().speech_to_text(request).await
+    }
+
+    async fn translate_audio(
+        &self,
+        request: AudioTranslationRequest,
+    ) -> Result<SttResponse, LlmError> {
+        self.audio().translate_audio(request).await
+    }
+
+    async fn get_voices(&self) -> Result<Vec<VoiceInfo>, LlmError> {
+        self.audio().get_voices().await
+    }
+
+    async fn get_supported_languages(&self) -> Result<Vec<LanguageInfo>, LlmError> {
+        self.audio().get_supported_languages().await
+    }
+
+    fn get_supported_audio_formats(&self) -> Vec<String> {
+        self.audio().get_supported_audio_formats()
+    }
+}
+
+#[async_trait]
+impl VisionCapability for OpenAiClient {
+    async fn analyze_image(&self, request: VisionRequest) -> Result<VisionResponse, LlmError> {
+        let messages = crate::multimodal::build_vision_messages(&request, None).await?;
+        let chat_request = ChatRequest {
+            messages,
+            tools: None,
+            common_params: request.common_params(&self.common_params),
+            provider_params: Some(ProviderParams::from_openai(self.openai_params.clone())),
+            http_config: None,
+            web_search: None,
+            stream: false,
+            prompt_cache: None,
+            output_schema: None,
+        };
+
+        self.chat_capability
+            .chat(chat_request)
+            .await
+            .map(Into::into)
+    }
+
+    async fn generate_image(&self, request: ImageGenRequest) -> Result<ImageResponse, LlmError> {
+        self.images().generate_images(request).await
+    }
+
+    fn get_supported_input_formats(&self) -> Vec<String> {
+        vec![
+            "jpeg".to_string(),
+            "png".to_string(),
+            "gif".to_string(),
+            "webp".to_string(),
+        ]
+    }
+}
+
 #[async_trait]
 impl EmbeddingCapability for OpenAiClient {
     async fn embed(&self, texts: Vec<String>) -> Result<EmbeddingResponse, LlmError> {
         // Create an OpenAiEmbeddings instance using the client's configuration
-        let config = super::config::OpenAiConfig {
-            api_key: self.chat_capability.api_key.clone(),
-            base_url: self.chat_capability.base_url.clone(),
-            organization: self.chat_capability.organization.clone(),
-            project: self.chat_capability.project.clone(),
-            common_params: self.common_params.clone(),
-            openai_params: self.openai_params.clone(),
-            http_config: self.chat_capability.http_config.clone(),
-            web_search_config: crate::types::WebSearchConfig::default(),
-            use_responses_api: false,
-            previous_response_id: None,
-            built_in_tools: Vec::new(),
-        };
+        let config = self.endpoint_config();
 
         let embeddings = super::embeddings::OpenAiEmbeddings::new(config, self.http_client.clone());
         embeddings.embed(texts).await
@@ -376,6 +626,32 @@ impl EmbeddingCapability for OpenAiClient {
     }
 }
 
+#[async_trait]
+impl BatchCapability for OpenAiClient {
+    async fn create_batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResponse, LlmError> {
+        self.batches().create_batch(requests).await
+    }
+
+    async fn retrieve_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError> {
+        self.batches().retrieve_batch(batch_id).await
+    }
+
+    async fn list_batches(
+        &self,
+        query: Option<BatchListQuery>,
+    ) -> Result<BatchListResponse, LlmError> {
+        self.batches().list_batches(query).await
+    }
+
+    async fn cancel_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError> {
+        self.batches().cancel_batch(batch_id).await
+    }
+
+    async fn get_batch_results(&self, batch_id: String) -> Result<Vec<BatchResult>, LlmError> {
+        self.batches().get_batch_results(batch_id).await
+    }
+}
+
 impl LlmProvider for OpenAiClient {
     fn provider_name(&self) -> &'static str {
         "openai"
@@ -395,6 +671,7 @@ impl LlmProvider for OpenAiClient {
             .with_embedding()
             .with_custom_feature("structured_output", true)
             .with_custom_feature("batch_processing", true)
+            .with_custom_feature("completion", true)
     }
 
     fn http_client(&self) -> &reqwest::Client {
@@ -427,10 +704,16 @@ impl LlmClient for OpenAiClient {
         Some(self)
     }
 
+    fn as_completion_capability(&self) -> Option<&dyn CompletionCapability> {
+        if self.is_openai() { Some(self) } else { None }
+    }
+
+    fn as_vision_capability(&self) -> Option<&dyn VisionCapability> {
+        if self.is_openai() { Some(self) } else { None }
+    }
+
     fn as_audio_capability(&self) -> Option<&dyn AudioCapability> {
-        // OpenAI client doesn't directly implement AudioCapability
-        // Audio is handled through separate OpenAiAudio struct
-        None
+        if self.is_openai() { Some(self) } else { None }
     }
 
     fn as_image_generation_capability(&self) -> Option<&dyn ImageGenerationCapability> {
@@ -438,6 +721,37 @@ impl LlmClient for OpenAiClient {
         // Image generation is handled through separate OpenAiImages struct
         None
     }
+
+    fn as_batch_capability(&self) -> Option<&dyn BatchCapability> {
+        if self.is_openai() { Some(self) } else { None }
+    }
+
+    fn as_conversation_state_capability(&self) -> Option<&dyn ConversationStateCapability> {
+        // Only the Responses API keeps conversation state on the server
+        if self.should_use_responses() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl ConversationStateCapability for OpenAiClient {
+    async fn chat_with_previous_response(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+        previous_response_id: Option<String>,
+    ) -> Result<ChatResponse, LlmError> {
+        let config = super::config::OpenAiConfig {
+            use_responses_api: true,
+            previous_response_id,
+            ..self.config()
+        };
+        let responses = OpenAiResponses::new(self.http_client.clone(), config);
+        responses.chat_with_tools(messages, tools).await
+    }
 }
 
 #[cfg(test)]
@@ -454,6 +768,77 @@ mod tests {
         assert!(!LlmProvider::supported_models(&client).is_empty());
     }
 
+    #[test]
+    fn test_request_overrides_client_params() {
+        let config = OpenAiConfig::new("test-key")
+            .with_model("gpt-4o")
+            .with_temperature(0.7);
+        let client = OpenAiClient::new(config, reqwest::Client::new());
+
+        let request = ChatRequest::new(vec![ChatMessage::user("hi").build()])
+            .with_common_params(CommonParams {
+                model: "gpt-4o-mini".to_string(),
+                ..Default::default()
+            })
+            .with_provider_params(ProviderParams::new().with_param("user", "tester"));
+        match client.route_request(request, false).unwrap() {
+            RequestRoute::ChatCompletions(request) => {
+                assert_eq!(request.common_params.model, "gpt-4o-mini");
+                assert_eq!(request.common_params.temperature, Some(0.7));
+                let params = request.provider_params.unwrap();
+                assert_eq!(params.get::<String>("user").as_deref(), Some("tester"));
+            }
+            RequestRoute::Responses(..) => panic!("Expected Chat Completions"),
+        }
+
+        // A gpt-5 override routes to the Responses API, which takes no provider params
+        let request = ChatRequest::new(vec![ChatMessage::user("hi").build()]).with_common_params(
+            CommonParams {
+                model: "gpt-5".to_string(),
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            client.route_request(request.clone(), false).unwrap(),
+            RequestRoute::Responses(..)
+        ));
+        let request = request.with_provider_params(ProviderParams::new().with_param("user", "x"));
+        assert!(matches!(
+            client.route_request(request, false),
+            Err(LlmError::UnsupportedOperation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_responses_requests_send_prompt_cache_key() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/responses"))
+            .and(body_partial_json(
+                serde_json::json!({ "prompt_cache_key": "tenant-1" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "resp_1",
+                "output": [{"content": [{"type": "output_text", "text": "ok"}]}]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = OpenAiConfig::new("test-key")
+            .with_base_url(server.uri())
+            .with_model("gpt-5");
+        let client = OpenAiClient::new(config, reqwest::Client::new());
+        let request = ChatRequest::new(vec![ChatMessage::user("hi").build()])
+            .with_prompt_cache(PromptCachePolicy::system_prompt().with_cache_key("tenant-1"));
+
+        let response = client.chat_with_request(request).await.unwrap();
+        assert_eq!(response.content_text(), Some("ok"));
+    }
+
     #[test]
     fn test_openai_client_with_specific_params() {
         let config = OpenAiConfig::new("test-key")
@@ -526,6 +911,8 @@ mod tests {
             http_config: None,
             web_search: None,
             stream: false,
+            prompt_cache: None,
+            output_schema: None,
         };
 
         // Test that the request body includes the correct model
@@ -535,4 +922,58 @@ mod tests {
             .unwrap();
         assert_eq!(body["model"], "gpt-4-test");
     }
+
+    #[test]
+    fn test_openrouter_web_search_plugin() {
+        let config = OpenAiConfig::new("test-key")
+            .with_provider_id("openrouter")
+            .with_base_url("https://openrouter.ai/api/v1")
+            .with_model("anthropic/claude-3.5-sonnet");
+        let client = OpenAiClient::new(config, reqwest::Client::new());
+
+        let request = ChatRequest::new(vec![crate::types::ChatMessage::user("news?").build()])
+            .with_common_params(client.common_params.clone())
+            .with_web_search(crate::types::WebSearchConfig {
+                enabled: true,
+                max_results: Some(3),
+                ..Default::default()
+            });
+
+        let body = client
+            .chat_capability
+            .build_chat_request_body(&request)
+            .unwrap();
+        assert_eq!(
+            body["plugins"],
+            serde_json::json!([{ "id": "web", "max_results": 3 }])
+        );
+
+        // Plain OpenAI chat completions are left untouched
+        let openai = OpenAiClient::new(OpenAiConfig::new("test-key"), reqwest::Client::new());
+        let body = openai
+            .chat_capability
+            .build_chat_request_body(&request)
+            .unwrap();
+        assert!(body.get("plugins").is_none());
+    }
+
+    #[test]
+    fn test_openai_only_capabilities_are_not_advertised_for_compatible_providers() {
+        let openai = OpenAiClient::new(OpenAiConfig::new("test-key"), reqwest::Client::new());
+        assert!(openai.as_batch_capability().is_some());
+        assert!(openai.as_audio_capability().is_some());
+        assert!(openai.as_completion_capability().is_some());
+        assert!(openai.as_vision_capability().is_some());
+
+        let deepseek = OpenAiClient::new(
+            OpenAiConfig::new("test-key")
+                .with_provider_id("deepseek")
+                .with_base_url("https://api.deepseek.com"),
+            reqwest::Client::new(),
+        );
+        assert!(deepseek.as_batch_capability().is_none());
+        assert!(deepseek.as_audio_capability().is_none());
+        assert!(deepseek.as_completion_capability().is_none());
+        assert!(deepseek.as_vision_capability().is_none());
+    }
 }
@@ -0,0 +1,375 @@
+//! OpenAI Legacy Completions Implementation
+//!
+//! This module provides the OpenAI implementation of the text completion capability
+//! using the `/v1/completions` endpoint, including suffix (fill-in-the-middle) support
+//! and streaming.
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::error::LlmError;
+use crate::rate_limiter::{estimate_request_tokens, send_request};
+use crate::traits::CompletionCapability;
+use crate::types::{
+    CompletionRequest, CompletionResponse, CompletionStream, CompletionStreamEvent, FinishReason,
+    Usage,
+};
+use crate::utils::sse_stream::SseStreamExt;
+
+use super::config::OpenAiConfig;
+
+/// `OpenAI` completions API request structure
+#[derive(Debug, Clone, Serialize)]
+struct OpenAiCompletionRequest {
+    /// Model to use
+    model: String,
+    /// Input prompt
+    prompt: String,
+    /// Text after the insertion point
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suffix: Option<String>,
+    /// Maximum tokens to generate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    /// Sampling temperature
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    /// Top-p sampling
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    /// Stop sequences
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
+    /// Number of completions to generate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    n: Option<u32>,
+    /// Whether to stream the response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+    /// Additional parameters
+    #[serde(flatten)]
+    extra_params: serde_json::Map<String, serde_json::Value>,
+}
+
+/// `OpenAI` completions API response structure
+#[derive(Debug, Clone, Deserialize)]
+struct OpenAiCompletionResponse {
+    /// Model used
+    model: Option<String>,
+    /// Generated choices
+    choices: Vec<OpenAiCompletionChoice>,
+    /// Usage information
+    usage: Option<OpenAiCompletionUsage>,
+}
+
+/// Individual completion choice
+#[derive(Debug, Clone, Deserialize)]
+struct OpenAiCompletionChoice {
+    /// Generated text
+    text: String,
+    /// Index of the choice
+    #[serde(default)]
+    index: usize,
+    /// Finish reason
+    finish_reason: Option<String>,
+}
+
+/// Usage information for completions
+#[derive(Debug, Clone, Deserialize)]
+struct OpenAiCompletionUsage {
+    /// Number of prompt tokens
+    prompt_tokens: u32,
+    /// Number of completion tokens
+    #[serde(default)]
+    completion_tokens: u32,
+}
+
+impl From<OpenAiCompletionUsage> for Usage {
+    fn from(usage: OpenAiCompletionUsage) -> Self {
+        Usage::new(usage.prompt_tokens, usage.completion_tokens)
+    }
+}
+
+/// OpenAI legacy completions capability implementation.
+///
+/// # Supported Models
+/// - gpt-3.5-turbo-instruct
+/// - davinci-002 / babbage-002
+///
+/// # API Reference
+/// <https://platform.openai.com/docs/api-reference/completions>
+#[derive(Debug, Clone)]
+pub struct OpenAiCompletions {
+    /// OpenAI configuration
+    config: OpenAiConfig,
+    /// HTTP client
+    http_client: reqwest::Client,
+}
+
+/// Whether a model is served by the legacy completions endpoint
+fn is_completion_model(model: &str) -> bool {
+    model.contains("-instruct") || model.starts_with("davinci") || model.starts_with("babbage")
+}
+
+impl OpenAiCompletions {
+    /// Create a new OpenAI completions instance
+    pub const fn new(config: OpenAiConfig, http_client: reqwest::Client) -> Self {
+        Self {
+            config,
+            http_client,
+        }
+    }
+
+    /// Get the default completion model
+    fn default_model(&self) -> String {
+        "gpt-3.5-turbo-instruct".to_string()
+    }
+
+    /// Build the request body for OpenAI API
+    ///
+    /// `/v1/completions` rejects chat models, so the client's model is only used
+    /// when it is a completion model; otherwise the default completion model is.
+    fn build_request(&self, request: &CompletionRequest, stream: bool) -> OpenAiCompletionRequest {
+        let common = &self.config.common_params;
+        let model = request
+            .model
+            .clone()
+            .or_else(|| is_completion_model(&common.model).then(|| common.model.clone()))
+            .unwrap_or_else(|| self.default_model());
+
+        OpenAiCompletionRequest {
+            model,
+            prompt: request.prompt.clone(),
+            suffix: request.suffix.clone(),
+            max_tokens: request.max_tokens.or(common.max_tokens),
+            temperature: request.temperature.or(common.temperature),
+            top_p: request.top_p.or(common.top_p),
+            stop: request
+                .stop
+                .clone()
+                .or_else(|| common.stop_sequences.clone()),
+            n: request.n,
+            stream: stream.then_some(true),
+            extra_params: request
+                .extra_params
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        }
+    }
+
+    /// Build request headers from the configuration
+    fn build_headers(&self) -> Result<reqwest::header::HeaderMap, LlmError> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (key, value) in self.config.get_headers() {
+            let header_name = reqwest::header::HeaderName::from_bytes(key.as_bytes())
+                .map_err(|e| LlmError::HttpError(format!("Invalid header name: {e}")))?;
+            let header_value = reqwest::header::HeaderValue::from_str(&value)
+                .map_err(|e| LlmError::HttpError(format!("Invalid header value: {e}")))?;
+            headers.insert(header_name, header_value);
+        }
+        Ok(headers)
+    }
+
+    /// Send a completions API request
+    async fn send_request(
+        &self,
+        request: &OpenAiCompletionRequest,
+    ) -> Result<reqwest::Response, LlmError> {
+        let url = format!("{}/completions", self.config.base_url);
+
+        let response = send_request(
+            self.http_client
+                .post(&url)
+                .headers(self.build_headers()?)
+                .json(request),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            &request.model,
+            estimate_request_tokens(&request),
+        )
+        .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(LlmError::ApiError {
+                code: status.as_u16(),
+                message: format!("OpenAI API error {status}: {error_text}"),
+                details: serde_json::from_str(&error_text).ok(),
+            });
+        }
+
+        Ok(response)
+    }
+
+    /// Convert OpenAI response to our standard format
+    fn convert_response(&self, response: OpenAiCompletionResponse) -> CompletionResponse {
+        let first = response.choices.into_iter().min_by_key(|c| c.index);
+
+        CompletionResponse {
+            text: first.as_ref().map(|c| c.text.clone()).unwrap_or_default(),
+            finish_reason: first.and_then(|c| c.finish_reason),
+            usage: response.usage.map(Into::into),
+            model: response.model,
+        }
+    }
+
+    /// Convert a streaming chunk into completion stream events
+    fn convert_chunk(data: &str) -> Vec<Result<CompletionStreamEvent, LlmError>> {
+        let chunk: OpenAiCompletionResponse = match serde_json::from_str(data) {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                return vec![Err(LlmError::ParseError(format!(
+                    "Failed to parse completion chunk: {e}"
+                )))];
+            }
+        };
+
+        let mut events = Vec::new();
+        let mut finish_reason = None;
+
+        for choice in chunk.choices {
+            if !choice.text.is_empty() {
+                events.push(Ok(CompletionStreamEvent::TextDelta {
+                    text: choice.text,
+                    index: Some(choice.index),
+                }));
+            }
+            if let Some(reason) = choice.finish_reason {
+                finish_reason = Some(parse_finish_reason(&reason));
+            }
+        }
+
+        let usage: Option<Usage> = chunk.usage.map(Into::into);
+        if let Some(finish_reason) = finish_reason {
+            events.push(Ok(CompletionStreamEvent::Done {
+                finish_reason: Some(finish_reason),
+                usage,
+            }));
+        } else if let Some(usage) = usage {
+            events.push(Ok(CompletionStreamEvent::Usage { usage }));
+        }
+
+        events
+    }
+}
+
+fn parse_finish_reason(reason: &str) -> FinishReason {
+    match reason {
+        "stop" => FinishReason::Stop,
+        "length" => FinishReason::Length,
+        "content_filter" => FinishReason::ContentFilter,
+        other => FinishReason::Other(other.to_string()),
+    }
+}
+
+#[async_trait]
+impl CompletionCapability for OpenAiCompletions {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        let body = self.build_request(&request, false);
+        let response = self.send_request(&body).await?;
+
+        let openai_response: OpenAiCompletionResponse = response
+            .json()
+            .await
+            .map_err(|e| LlmError::ParseError(format!("Failed to parse response: {e}")))?;
+
+        Ok(self.convert_response(openai_response))
+    }
+
+    async fn complete_stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Result<CompletionStream, LlmError> {
+        let body = self.build_request(&request, true);
+        let response = self.send_request(&body).await?;
+
+        let byte_stream = response
+            .bytes_stream()
+            .map(|chunk| chunk.map_err(|e| LlmError::HttpError(format!("Stream error: {e}"))));
+
+        let stream = byte_stream
+            .into_sse_stream()
+            .map(|event_result| match event_result {
+                Ok(event) => {
+                    let data = event.data.trim();
+                    if data.is_empty() || data == "[DONE]" {
+                        Vec::new()
+                    } else {
+                        Self::convert_chunk(data)
+                    }
+                }
+                Err(e) => vec![Err(LlmError::StreamError(format!(
+                    "SSE parsing error: {e}"
+                )))],
+            })
+            .flat_map(futures_util::stream::iter);
+
+        Ok(Box::pin(stream))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_completions() -> OpenAiCompletions {
+        let config = OpenAiConfig::new("test-key")
+            .with_model("gpt-3.5-turbo-instruct")
+            .with_max_tokens(64);
+        OpenAiCompletions::new(config, reqwest::Client::new())
+    }
+
+    #[test]
+    fn test_build_request_with_suffix() {
+        let completions = create_completions();
+        let request = CompletionRequest::new("fn add(a: i32, b: i32) -> i32 {")
+            .with_suffix("}")
+            .with_temperature(0.2);
+
+        let body = serde_json::to_value(completions.build_request(&request, false)).unwrap();
+        assert_eq!(body["model"], "gpt-3.5-turbo-instruct");
+        assert_eq!(body["suffix"], "}");
+        assert_eq!(body["max_tokens"], 64);
+        assert!(body.get("stream").is_none());
+    }
+
+    #[test]
+    fn test_chat_model_not_used_for_completions() {
+        let config = OpenAiConfig::new("test-key").with_model("gpt-4o");
+        let completions = OpenAiCompletions::new(config, reqwest::Client::new());
+        let request = CompletionRequest::new("Once upon a time");
+
+        let body = serde_json::to_value(completions.build_request(&request, false)).unwrap();
+        assert_eq!(body["model"], "gpt-3.5-turbo-instruct");
+
+        let request = request.with_model("davinci-002");
+        let body = serde_json::to_value(completions.build_request(&request, false)).unwrap();
+        assert_eq!(body["model"], "davinci-002");
+    }
+
+    #[test]
+    fn test_convert_stream_chunk() {
+        let events = OpenAiCompletions::convert_chunk(
+            r#"{"model":"gpt-3.5-turbo-instruct","choices":[{"text":" a + b","index":0,"finish_reason":"stop"}]}"#,
+        );
+
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            Ok(CompletionStreamEvent::TextDelta { text, .. }) => assert_eq!(text, " a + b"),
+            other => panic!("Expected TextDelta, got {other:?}"),
+        }
+        match &events[1] {
+            Ok(CompletionStreamEvent::Done { finish_reason, .. }) => {
+                assert_eq!(finish_reason, &Some(FinishReason::Stop))
+            }
+            other => panic!("Expected Done, got {other:?}"),
+        }
+    }
+}
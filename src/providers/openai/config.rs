@@ -30,6 +30,7 @@ use crate::types::{CommonParams, HttpConfig, WebSearchConfig};
 ///     use_responses_api: false,
 ///     previous_response_id: None,
 ///     built_in_tools: Vec::new(),
+///     provider_id: "openai".to_string(),
 /// };
 /// ```
 #[derive(Debug, Clone)]
@@ -66,6 +67,9 @@ pub struct OpenAiConfig {
 
     /// Built-in tools for Responses API
     pub built_in_tools: Vec<crate::types::OpenAiBuiltInTool>,
+
+    /// Provider served through the `OpenAI` API (e.g. "openai", "openrouter")
+    pub provider_id: String,
 }
 
 impl OpenAiConfig {
@@ -89,9 +93,19 @@ impl OpenAiConfig {
             use_responses_api: false,
             previous_response_id: None,
             built_in_tools: Vec::new(),
+            provider_id: "openai".to_string(),
         }
     }
 
+    /// Set the provider served through the `OpenAI` API.
+    ///
+    /// Provider-specific request features, such as the `OpenRouter` `web`
+    /// plugin, are selected by this ID.
+    pub fn with_provider_id<S: Into<String>>(mut self, provider_id: S) -> Self {
+        self.provider_id = provider_id.into();
+        self
+    }
+
     /// Set the base URL for the `OpenAI` API.
     ///
     /// # Arguments
@@ -307,6 +321,7 @@ impl Default for OpenAiConfig {
             use_responses_api: false,
             previous_response_id: None,
             built_in_tools: Vec::new(),
+            provider_id: "openai".to_string(),
         }
     }
 }
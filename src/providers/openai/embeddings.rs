@@ -7,6 +7,7 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 use crate::error::LlmError;
+use crate::rate_limiter::{estimate_request_tokens, send_request};
 use crate::traits::{EmbeddingCapability, EmbeddingExtensions, OpenAiEmbeddingCapability};
 use crate::types::{
     EmbeddingFormat, EmbeddingModelInfo, EmbeddingRequest, EmbeddingResponse, EmbeddingUsage,
@@ -142,14 +143,15 @@ impl OpenAiEmbeddings {
             headers.insert(header_name, header_value);
         }
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&request),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            &request.model,
+            estimate_request_tokens(&request),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
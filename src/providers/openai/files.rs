@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::error::LlmError;
+use crate::rate_limiter::send_request;
 use crate::traits::FileManagementCapability;
 use crate::types::{
     FileDeleteResponse, FileListQuery, FileListResponse, FileObject, FileUploadRequest,
@@ -283,11 +284,15 @@ impl FileManagementCapability for OpenAiFiles {
             );
 
         let request_builder = self.make_request(reqwest::Method::POST, "files").await?;
-        let response = request_builder
-            .multipart(form)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            request_builder.multipart(form),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            &self.config.provider_id,
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             return Err(self.handle_response_error(response).await);
@@ -329,10 +334,15 @@ impl FileManagementCapability for OpenAiFiles {
         }
 
         let request_builder = self.make_request(reqwest::Method::GET, &endpoint).await?;
-        let response = request_builder
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            request_builder,
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            &self.config.provider_id,
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             return Err(self.handle_response_error(response).await);
@@ -361,10 +371,15 @@ impl FileManagementCapability for OpenAiFiles {
         let endpoint = format!("files/{file_id}");
 
         let request_builder = self.make_request(reqwest::Method::GET, &endpoint).await?;
-        let response = request_builder
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            request_builder,
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            &self.config.provider_id,
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             return Err(self.handle_response_error(response).await);
@@ -385,10 +400,15 @@ impl FileManagementCapability for OpenAiFiles {
         let request_builder = self
             .make_request(reqwest::Method::DELETE, &endpoint)
             .await?;
-        let response = request_builder
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            request_builder,
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            &self.config.provider_id,
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             return Err(self.handle_response_error(response).await);
@@ -410,10 +430,15 @@ impl FileManagementCapability for OpenAiFiles {
         let endpoint = format!("files/{file_id}/content");
 
         let request_builder = self.make_request(reqwest::Method::GET, &endpoint).await?;
-        let response = request_builder
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            request_builder,
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            &self.config.provider_id,
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             return Err(self.handle_response_error(response).await);
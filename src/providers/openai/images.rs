@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::error::LlmError;
+use crate::rate_limiter::send_request;
 use crate::traits::ImageGenerationCapability;
 use crate::types::{
     GeneratedImage, ImageEditRequest, ImageGenerationRequest, ImageGenerationResponse,
@@ -129,14 +130,15 @@ impl OpenAiImages {
             headers.insert(header_name, header_value);
         }
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&request),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -375,14 +377,15 @@ impl ImageGenerationCapability for OpenAiImages {
             form = form.text("response_format", response_format);
         }
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .multipart(form)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).multipart(form),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -444,14 +447,15 @@ impl ImageGenerationCapability for OpenAiImages {
             form = form.text("response_format", response_format);
         }
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .multipart(form)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).multipart(form),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -10,6 +10,7 @@
 //! - `builder.rs` - Builder pattern implementation for client creation
 //! - `chat.rs` - Chat completion capability implementation
 //! - `audio.rs` - Audio processing (TTS/STT) capability implementation
+//! - `batches.rs` - Batch processing capability implementation
 //! - `embeddings.rs` - Text embedding capability implementation
 //! - `images.rs` - Image generation capability implementation
 //! - `files.rs` - File management capability implementation
@@ -57,7 +58,9 @@ mod thinking_utils_test;
 
 // Capability modules
 pub mod audio;
+pub mod batches;
 pub mod chat;
+pub mod completions;
 pub mod embeddings;
 pub mod files;
 pub mod images;
@@ -83,7 +86,9 @@ pub use types::*;
 
 // Re-export capability implementations
 pub use audio::OpenAiAudio;
+pub use batches::OpenAiBatches;
 pub use chat::OpenAiChatCapability;
+pub use completions::OpenAiCompletions;
 pub use embeddings::OpenAiEmbeddings;
 pub use files::OpenAiFiles;
 pub use images::OpenAiImages;
@@ -10,6 +10,7 @@ use reqwest::header::HeaderMap;
 use secrecy::{ExposeSecret, SecretString};
 
 use crate::error::LlmError;
+use crate::rate_limiter::send_request;
 use crate::traits::ModelListingCapability;
 use crate::types::{HttpConfig, ModelInfo};
 
@@ -173,7 +174,15 @@ impl ModelListingCapability for OpenAiModels {
         let headers = self.build_request_headers()?;
         let url = self.models_endpoint();
 
-        let response = self.http_client.get(&url).headers(headers).send().await?;
+        let response = send_request(
+            self.http_client.get(&url).headers(headers),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "openai",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -202,7 +211,15 @@ impl ModelListingCapability for OpenAiModels {
         let headers = self.build_request_headers()?;
         let url = self.model_endpoint(&model_id);
 
-        let response = self.http_client.get(&url).headers(headers).send().await?;
+        let response = send_request(
+            self.http_client.get(&url).headers(headers),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "openai",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -324,7 +341,9 @@ fn determine_model_capabilities(model_id: &str) -> Vec<String> {
 }
 
 /// Estimate model specifications based on model ID
-fn estimate_model_specs(model_id: &str) -> (Option<u32>, Option<u32>, Option<f64>, Option<f64>) {
+pub(crate) fn estimate_model_specs(
+    model_id: &str,
+) -> (Option<u32>, Option<u32>, Option<f64>, Option<f64>) {
     match model_id {
         // GPT-4o models
         "gpt-4o" => (
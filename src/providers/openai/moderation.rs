@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::error::LlmError;
+use crate::rate_limiter::send_request;
 use crate::traits::ModerationCapability;
 use crate::types::{ModerationRequest, ModerationResponse, ModerationResult};
 
@@ -302,11 +303,15 @@ impl ModerationCapability for OpenAiModeration {
 
         // Make API request
         let request_builder = self.make_request().await?;
-        let response = request_builder
-            .json(&openai_request)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(format!("Request failed: {e}")))?;
+        let response = send_request(
+            request_builder.json(&openai_request),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             return Err(self.handle_response_error(response).await);
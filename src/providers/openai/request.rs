@@ -288,6 +288,8 @@ impl RequestBuilder for OpenAiRequestBuilder {
             http_config: None,
             web_search: None,
             stream,
+            prompt_cache: None,
+            output_schema: None,
         };
 
         // Validate the request (basic validation always enabled)
@@ -16,14 +16,18 @@ use async_trait::async_trait;
 use secrecy::ExposeSecret;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 use crate::error::LlmError;
+use crate::rate_limiter::{estimate_request_tokens, send_request};
 use crate::stream::ChatStream;
+use crate::stream_handle::{ChatStreamHandle, ResumeContext, StreamResumer};
 use crate::traits::ChatCapability;
 use crate::types::{ChatMessage, ChatResponse, OpenAiBuiltInTool, Tool};
 use crate::web_search::{WebSearchCapability, WebSearchProvider};
 
 use super::config::OpenAiConfig;
+use super::utils::convert_url_citation;
 
 /// Response status for background processing
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -124,23 +128,31 @@ impl OpenAiResponses {
 
     /// Delete a response by ID
     pub async fn delete_response(&self, response_id: &str) -> Result<bool, LlmError> {
-        let response = self
-            .http_client
-            .delete(self.response_endpoint(response_id))
-            .headers({
-                let mut hm = reqwest::header::HeaderMap::new();
-                for (k, v) in self.config.get_headers() {
-                    let name = reqwest::header::HeaderName::from_bytes(k.as_bytes())
-                        .map_err(|e| LlmError::HttpError(format!("Invalid header name: {e}")))?;
-                    let val = reqwest::header::HeaderValue::from_str(&v)
-                        .map_err(|e| LlmError::HttpError(format!("Invalid header value: {e}")))?;
-                    hm.insert(name, val);
-                }
-                hm
-            })
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(e.to_string()))?;
+        let response = send_request(
+            self.http_client
+                .delete(self.response_endpoint(response_id))
+                .headers({
+                    let mut hm = reqwest::header::HeaderMap::new();
+                    for (k, v) in self.config.get_headers() {
+                        let name =
+                            reqwest::header::HeaderName::from_bytes(k.as_bytes()).map_err(|e| {
+                                LlmError::HttpError(format!("Invalid header name: {e}"))
+                            })?;
+                        let val = reqwest::header::HeaderValue::from_str(&v).map_err(|e| {
+                            LlmError::HttpError(format!("Invalid header value: {e}"))
+                        })?;
+                        hm.insert(name, val);
+                    }
+                    hm
+                }),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            "",
+            0,
+        )
+        .await
+        .map_err(|e| LlmError::HttpError(e.to_string()))?;
 
         if !response.status().is_success() {
             let status_code = response.status().as_u16();
@@ -160,10 +172,8 @@ impl OpenAiResponses {
         response_id: &str,
     ) -> Result<serde_json::Value, LlmError> {
         let url = format!("{}/responses/{}/items", self.config.base_url, response_id);
-        let response = self
-            .http_client
-            .get(url)
-            .headers({
+        let response = send_request(
+            self.http_client.get(url).headers({
                 let mut hm = reqwest::header::HeaderMap::new();
                 for (k, v) in self.config.get_headers() {
                     let name = reqwest::header::HeaderName::from_bytes(k.as_bytes())
@@ -173,10 +183,15 @@ impl OpenAiResponses {
                     hm.insert(name, val);
                 }
                 hm
-            })
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(e.to_string()))?;
+            }),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            "",
+            0,
+        )
+        .await
+        .map_err(|e| LlmError::HttpError(e.to_string()))?;
 
         if !response.status().is_success() {
             let status_code = response.status().as_u16();
@@ -210,7 +225,7 @@ impl OpenAiResponses {
             previous_response_id,
         )?;
 
-        let response = self
+        let request = self
             .http_client
             .post(self.responses_endpoint())
             .header(
@@ -218,10 +233,16 @@ impl OpenAiResponses {
                 format!("Bearer {}", self.config.api_key.expose_secret()),
             )
             .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(e.to_string()))?;
+            .json(&request_body);
+        let response = send_request(
+            request,
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            request_body["model"].as_str().unwrap_or_default(),
+            estimate_request_tokens(&request_body),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status_code = response.status().as_u16();
@@ -242,23 +263,31 @@ impl OpenAiResponses {
 
     /// Get a response by ID
     pub async fn get_response(&self, response_id: &str) -> Result<ChatResponse, LlmError> {
-        let response = self
-            .http_client
-            .get(self.response_endpoint(response_id))
-            .headers({
-                let mut hm = reqwest::header::HeaderMap::new();
-                for (k, v) in self.config.get_headers() {
-                    let name = reqwest::header::HeaderName::from_bytes(k.as_bytes())
-                        .map_err(|e| LlmError::HttpError(format!("Invalid header name: {e}")))?;
-                    let val = reqwest::header::HeaderValue::from_str(&v)
-                        .map_err(|e| LlmError::HttpError(format!("Invalid header value: {e}")))?;
-                    hm.insert(name, val);
-                }
-                hm
-            })
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(e.to_string()))?;
+        let response = send_request(
+            self.http_client
+                .get(self.response_endpoint(response_id))
+                .headers({
+                    let mut hm = reqwest::header::HeaderMap::new();
+                    for (k, v) in self.config.get_headers() {
+                        let name =
+                            reqwest::header::HeaderName::from_bytes(k.as_bytes()).map_err(|e| {
+                                LlmError::HttpError(format!("Invalid header name: {e}"))
+                            })?;
+                        let val = reqwest::header::HeaderValue::from_str(&v).map_err(|e| {
+                            LlmError::HttpError(format!("Invalid header value: {e}"))
+                        })?;
+                        hm.insert(name, val);
+                    }
+                    hm
+                }),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            "",
+            0,
+        )
+        .await
+        .map_err(|e| LlmError::HttpError(e.to_string()))?;
 
         if !response.status().is_success() {
             let status_code = response.status().as_u16();
@@ -279,23 +308,31 @@ impl OpenAiResponses {
 
     /// Cancel a background response
     pub async fn cancel_response(&self, response_id: &str) -> Result<ResponseMetadata, LlmError> {
-        let response = self
-            .http_client
-            .post(self.response_cancel_endpoint(response_id))
-            .headers({
-                let mut hm = reqwest::header::HeaderMap::new();
-                for (k, v) in self.config.get_headers() {
-                    let name = reqwest::header::HeaderName::from_bytes(k.as_bytes())
-                        .map_err(|e| LlmError::HttpError(format!("Invalid header name: {e}")))?;
-                    let val = reqwest::header::HeaderValue::from_str(&v)
-                        .map_err(|e| LlmError::HttpError(format!("Invalid header value: {e}")))?;
-                    hm.insert(name, val);
-                }
-                hm
-            })
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(e.to_string()))?;
+        let response = send_request(
+            self.http_client
+                .post(self.response_cancel_endpoint(response_id))
+                .headers({
+                    let mut hm = reqwest::header::HeaderMap::new();
+                    for (k, v) in self.config.get_headers() {
+                        let name =
+                            reqwest::header::HeaderName::from_bytes(k.as_bytes()).map_err(|e| {
+                                LlmError::HttpError(format!("Invalid header name: {e}"))
+                            })?;
+                        let val = reqwest::header::HeaderValue::from_str(&v).map_err(|e| {
+                            LlmError::HttpError(format!("Invalid header value: {e}"))
+                        })?;
+                        hm.insert(name, val);
+                    }
+                    hm
+                }),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            "",
+            0,
+        )
+        .await
+        .map_err(|e| LlmError::HttpError(e.to_string()))?;
 
         if !response.status().is_success() {
             let status_code = response.status().as_u16();
@@ -355,10 +392,8 @@ impl OpenAiResponses {
             }
         }
 
-        let response = self
-            .http_client
-            .get(&url)
-            .headers({
+        let response = send_request(
+            self.http_client.get(&url).headers({
                 let mut hm = reqwest::header::HeaderMap::new();
                 for (k, v) in self.config.get_headers() {
                     let name = reqwest::header::HeaderName::from_bytes(k.as_bytes())
@@ -368,10 +403,15 @@ impl OpenAiResponses {
                     hm.insert(name, val);
                 }
                 hm
-            })
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(e.to_string()))?;
+            }),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            "",
+            0,
+        )
+        .await
+        .map_err(|e| LlmError::HttpError(e.to_string()))?;
 
         if !response.status().is_success() {
             let status_code = response.status().as_u16();
@@ -848,6 +888,38 @@ impl OpenAiResponses {
                                 "format": format
                             }));
                         }
+                        crate::types::ContentPart::Document {
+                            source,
+                            media_type,
+                            title,
+                            citations: _,
+                        } => {
+                            if let Some(text) = source.decoded_text(media_type) {
+                                content_parts.push(serde_json::json!({
+                                    "type": "input_text",
+                                    "text": text
+                                }));
+                                continue;
+                            }
+                            let mut file_part = serde_json::json!({ "type": "input_file" });
+                            match source {
+                                crate::types::DocumentSource::FileId(file_id) => {
+                                    file_part["file_id"] = serde_json::json!(file_id);
+                                }
+                                crate::types::DocumentSource::Url(url) => {
+                                    file_part["file_url"] = serde_json::json!(url);
+                                }
+                                crate::types::DocumentSource::Base64(data) => {
+                                    file_part["filename"] = serde_json::json!(
+                                        title.as_deref().unwrap_or("document.pdf")
+                                    );
+                                    file_part["file_data"] = serde_json::json!(format!(
+                                        "data:{media_type};base64,{data}"
+                                    ));
+                                }
+                            }
+                            content_parts.push(file_part);
+                        }
                     }
                 }
                 api_message["content"] = serde_json::Value::Array(content_parts);
@@ -896,6 +968,7 @@ impl OpenAiResponses {
 
         // Extract full text content: traverse output[*].content[*].text or string fallbacks
         let mut text_content = String::new();
+        let mut citations = Vec::new();
         let mut tool_calls_acc: Vec<crate::types::ToolCall> = Vec::new();
 
         if let Some(output_items) = root.get("output").and_then(|o| o.as_array()) {
@@ -991,6 +1064,11 @@ impl OpenAiResponses {
                                 if !text_content.is_empty() {
                                     text_content.push('\n');
                                 }
+                                citations.extend(output_text_citations(
+                                    part,
+                                    txt,
+                                    text_content.len(),
+                                ));
                                 text_content.push_str(txt);
                             } else if let Some(s) = part.as_str() {
                                 if !text_content.is_empty() {
@@ -1081,7 +1159,8 @@ impl OpenAiResponses {
                 .or_else(|| usage_data.get("reasoningTokens"))
                 .and_then(serde_json::Value::as_u64)
                 .map(|v| v as u32),
-            cached_tokens: None,
+            cached_tokens: cached_input_tokens(usage_data),
+            cache_write_tokens: None,
         });
 
         // Provider-specific data: include reasoning/thinking if present
@@ -1118,6 +1197,8 @@ impl OpenAiResponses {
                 .get("reasoning")
                 .and_then(|v| v.as_str())
                 .map(std::string::ToString::to_string),
+            sources: crate::types::SearchSource::from_citations(&citations),
+            citations,
             metadata: provider_data,
         })
     }
@@ -1138,7 +1219,7 @@ impl ChatCapability for OpenAiResponses {
             false,
         )?;
 
-        let response = self
+        let request = self
             .http_client
             .post(self.responses_endpoint())
             .headers({
@@ -1152,10 +1233,16 @@ impl ChatCapability for OpenAiResponses {
                 }
                 hm
             })
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(e.to_string()))?;
+            .json(&request_body);
+        let response = send_request(
+            request,
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            request_body["model"].as_str().unwrap_or_default(),
+            estimate_request_tokens(&request_body),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status_code = response.status().as_u16();
@@ -1187,46 +1274,198 @@ impl ChatCapability for OpenAiResponses {
             false,
         )?;
 
-        // Build request
-        let request_builder = self
-            .http_client
-            .post(self.responses_endpoint())
-            .headers({
-                let mut hm = reqwest::header::HeaderMap::new();
-                for (k, v) in self.config.get_headers() {
-                    let name = reqwest::header::HeaderName::from_bytes(k.as_bytes())
-                        .map_err(|e| LlmError::HttpError(format!("Invalid header name: {e}")))?;
-                    let val = reqwest::header::HeaderValue::from_str(&v)
-                        .map_err(|e| LlmError::HttpError(format!("Invalid header value: {e}")))?;
-                    hm.insert(name, val);
-                }
-                hm.insert(
-                    reqwest::header::ACCEPT,
-                    reqwest::header::HeaderValue::from_static("text/event-stream"),
-                );
-                hm
-            })
-            .json(&request_body);
-
-        // Use unified EventSource-based stream processor for reliability
         let converter = OpenAiResponsesEventConverter::new(self.config.common_params.model.clone());
-        crate::utils::streaming::StreamFactory::create_eventsource_stream(
-            request_builder,
+        self.send_stream_request(
+            self.http_client
+                .post(self.responses_endpoint())
+                .json(&request_body),
+            estimate_request_tokens(&request_body),
             converter,
         )
         .await
     }
 }
 
+impl OpenAiResponses {
+    /// Stream a response that survives dropped connections.
+    ///
+    /// The response runs in background mode and is stored, so when the
+    /// connection drops the returned handle reconnects and continues after the
+    /// last received event instead of failing.
+    pub async fn chat_stream_resumable(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+    ) -> Result<ChatStreamHandle, LlmError> {
+        let mut request_body = self.build_request_body(
+            &messages,
+            tools.as_deref(),
+            Some(&self.config.built_in_tools),
+            true, // stream = true
+            true, // background = true
+        )?;
+        // Only stored background responses can be streamed again
+        request_body["background"] = serde_json::Value::Bool(true);
+        request_body["store"] = serde_json::Value::Bool(true);
+
+        let cursor = Arc::new(Mutex::new(ResumeCursor::default()));
+        let converter = OpenAiResponsesEventConverter::new(self.config.common_params.model.clone())
+            .with_cursor(cursor.clone());
+        let stream = self
+            .send_stream_request(
+                self.http_client
+                    .post(self.responses_endpoint())
+                    .json(&request_body),
+                estimate_request_tokens(&request_body),
+                converter,
+            )
+            .await?;
+
+        let resumer = ResponsesStreamResumer {
+            responses: OpenAiResponses::new(self.http_client.clone(), self.config.clone()),
+            cursor,
+        };
+        Ok(ChatStreamHandle::new(stream).with_resumer(Arc::new(resumer)))
+    }
+
+    /// Send a streaming request and convert its SSE events
+    async fn send_stream_request(
+        &self,
+        request_builder: reqwest::RequestBuilder,
+        estimated_tokens: u32,
+        converter: OpenAiResponsesEventConverter,
+    ) -> Result<ChatStream, LlmError> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (k, v) in self.config.get_headers() {
+            let name = reqwest::header::HeaderName::from_bytes(k.as_bytes())
+                .map_err(|e| LlmError::HttpError(format!("Invalid header name: {e}")))?;
+            let val = reqwest::header::HeaderValue::from_str(&v)
+                .map_err(|e| LlmError::HttpError(format!("Invalid header value: {e}")))?;
+            headers.insert(name, val);
+        }
+        headers.insert(
+            reqwest::header::ACCEPT,
+            reqwest::header::HeaderValue::from_static("text/event-stream"),
+        );
+
+        let response = send_request(
+            request_builder.headers(headers),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            &self.config.common_params.model,
+            estimated_tokens,
+        )
+        .await?;
+
+        // Use unified EventSource-based stream processor for reliability
+        crate::utils::streaming::StreamFactory::create_eventsource_stream_from_response(
+            response, converter,
+        )
+        .await
+    }
+}
+
+/// Position of a background response stream, used to reconnect after the last received event
+#[derive(Debug, Default)]
+struct ResumeCursor {
+    response_id: Option<String>,
+    last_event_id: Option<String>,
+    sequence_number: Option<u64>,
+}
+
+impl ResumeCursor {
+    fn record(&mut self, event: &eventsource_stream::Event) {
+        if !event.id.is_empty() {
+            self.last_event_id = Some(event.id.clone());
+        }
+
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(&event.data) else {
+            return;
+        };
+        if let Some(sequence_number) = json
+            .get("sequence_number")
+            .and_then(serde_json::Value::as_u64)
+        {
+            self.sequence_number = Some(sequence_number);
+        }
+        if self.response_id.is_none()
+            && let Some(id) = json
+                .get("response")
+                .and_then(|response| response.get("id"))
+                .and_then(|id| id.as_str())
+        {
+            self.response_id = Some(id.to_string());
+        }
+    }
+}
+
+/// Reconnects to a background response stream after the last received event
+struct ResponsesStreamResumer {
+    responses: OpenAiResponses,
+    cursor: Arc<Mutex<ResumeCursor>>,
+}
+
+#[async_trait]
+impl StreamResumer for ResponsesStreamResumer {
+    async fn resume(&self, _context: &ResumeContext) -> Result<ChatStream, LlmError> {
+        let (response_id, last_event_id, sequence_number) = {
+            let cursor = self
+                .cursor
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            (
+                cursor.response_id.clone(),
+                cursor.last_event_id.clone(),
+                cursor.sequence_number,
+            )
+        };
+        let response_id = response_id.ok_or_else(|| {
+            LlmError::StreamError(
+                "Stream interrupted before the response ID was received".to_string(),
+            )
+        })?;
+
+        let mut request_builder = self
+            .responses
+            .http_client
+            .get(self.responses.response_endpoint(&response_id))
+            .query(&[("stream", "true")]);
+        if let Some(sequence_number) = sequence_number {
+            request_builder = request_builder.query(&[("starting_after", sequence_number)]);
+        }
+        if let Some(last_event_id) = last_event_id {
+            request_builder = request_builder.header("Last-Event-ID", last_event_id);
+        }
+
+        let converter =
+            OpenAiResponsesEventConverter::new(self.responses.config.common_params.model.clone())
+                .with_cursor(self.cursor.clone());
+        self.responses
+            .send_stream_request(request_builder, 0, converter)
+            .await
+    }
+}
+
 /// OpenAI Responses SSE event converter using unified streaming utilities
 #[derive(Clone)]
 pub struct OpenAiResponsesEventConverter {
     model: String,
+    cursor: Option<Arc<Mutex<ResumeCursor>>>,
 }
 
 impl OpenAiResponsesEventConverter {
     pub fn new(model: String) -> Self {
-        Self { model }
+        Self {
+            model,
+            cursor: None,
+        }
+    }
+
+    /// Track the stream position for reconnecting
+    fn with_cursor(mut self, cursor: Arc<Mutex<ResumeCursor>>) -> Self {
+        self.cursor = Some(cursor);
+        self
     }
 
     fn convert_responses_event(
@@ -1309,7 +1548,8 @@ impl OpenAiResponsesEventConverter {
                 completion_tokens,
                 total_tokens,
                 reasoning_tokens,
-                cached_tokens: None,
+                cached_tokens: cached_input_tokens(usage),
+                cache_write_tokens: None,
             };
             return Some(crate::stream::ChatStreamEvent::UsageUpdate { usage: usage_info });
         }
@@ -1377,6 +1617,13 @@ impl crate::utils::streaming::SseEventConverter for OpenAiResponsesEventConverte
         >,
     > {
         Box::pin(async move {
+            if let Some(cursor) = &self.cursor {
+                cursor
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .record(&event);
+            }
+
             let data_raw = event.data.trim();
             if data_raw.is_empty() {
                 return None;
@@ -1397,6 +1644,8 @@ impl crate::utils::streaming::SseEventConverter for OpenAiResponsesEventConverte
                         finish_reason: Some(crate::types::FinishReason::Stop),
                         tool_calls: None,
                         thinking: None,
+                        citations: Vec::new(),
+                        sources: Vec::new(),
                         metadata: std::collections::HashMap::new(),
                     },
                 }));
@@ -1416,6 +1665,7 @@ impl crate::utils::streaming::SseEventConverter for OpenAiResponsesEventConverte
 
                 // Aggregate text content
                 let mut text_content = String::new();
+                let mut citations = Vec::new();
                 if let Some(output_items) = root.get("output").and_then(|o| o.as_array()) {
                     for item in output_items {
                         match item.get("content") {
@@ -1431,6 +1681,11 @@ impl crate::utils::streaming::SseEventConverter for OpenAiResponsesEventConverte
                                         if !text_content.is_empty() {
                                             text_content.push('\n');
                                         }
+                                        citations.extend(output_text_citations(
+                                            part,
+                                            txt,
+                                            text_content.len(),
+                                        ));
                                         text_content.push_str(txt);
                                     } else if let Some(s) = part.as_str() {
                                         if !text_content.is_empty() {
@@ -1584,6 +1839,8 @@ impl crate::utils::streaming::SseEventConverter for OpenAiResponsesEventConverte
                         Some(tool_calls)
                     },
                     thinking: None,
+                    sources: crate::types::SearchSource::from_citations(&citations),
+                    citations,
                     metadata: std::collections::HashMap::new(),
                 };
                 return Some(Ok(crate::stream::ChatStreamEvent::StreamEnd { response }));
@@ -1615,6 +1872,17 @@ impl crate::utils::streaming::SseEventConverter for OpenAiResponsesEventConverte
                         return Some(Ok(evt));
                     }
                 }
+                "response.output_text.annotation.added" => {
+                    // Streamed annotation offsets refer to the full text, which is not tracked here
+                    if let Some(citation) = json
+                        .get("annotation")
+                        .and_then(|annotation| convert_url_citation(annotation, None, 0))
+                    {
+                        return Some(Ok(crate::stream::ChatStreamEvent::CitationDelta {
+                            citation,
+                        }));
+                    }
+                }
                 "response.output_item.added" => {
                     // Handle function call item added
                     if let Some(evt) = self.convert_output_item_added(json) {
@@ -1645,6 +1913,8 @@ impl crate::utils::streaming::SseEventConverter for OpenAiResponsesEventConverte
                 finish_reason: Some(crate::types::FinishReason::Stop),
                 tool_calls: None,
                 thinking: None,
+                citations: Vec::new(),
+                sources: Vec::new(),
                 metadata: std::collections::HashMap::new(),
             },
         }))
@@ -1674,6 +1944,8 @@ impl OpenAiResponses {
                             finish_reason: Some(crate::types::FinishReason::Stop),
                             tool_calls: None,
                             thinking: None,
+                            citations: Vec::new(),
+                            sources: Vec::new(),
                             metadata: std::collections::HashMap::new(),
                         },
                     });
@@ -1744,7 +2016,8 @@ impl OpenAiResponses {
                                 .get("reasoning_tokens")
                                 .and_then(serde_json::Value::as_u64)
                                 .map(|v| v as u32),
-                            cached_tokens: None,
+                            cached_tokens: cached_input_tokens(usage),
+                            cache_write_tokens: None,
                         };
                         events.push(crate::stream::ChatStreamEvent::UsageUpdate {
                             usage: usage_info,
@@ -1884,7 +2157,7 @@ impl ResponsesApiCapability for OpenAiResponses {
             Some(previous_response_id),
         )?;
 
-        let response = self
+        let request = self
             .http_client
             .post(self.responses_endpoint())
             .headers({
@@ -1898,10 +2171,16 @@ impl ResponsesApiCapability for OpenAiResponses {
                 }
                 hm
             })
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(e.to_string()))?;
+            .json(&request_body);
+        let response = send_request(
+            request,
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            request_body["model"].as_str().unwrap_or_default(),
+            estimate_request_tokens(&request_body),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status_code = response.status().as_u16();
@@ -1938,16 +2217,21 @@ impl OpenAiResponses {
         &self,
         response_id: &str,
     ) -> Result<ResponseMetadata, LlmError> {
-        let response = self
-            .http_client
-            .get(self.response_endpoint(response_id))
-            .header(
-                "Authorization",
-                format!("Bearer {}", self.config.api_key.expose_secret()),
-            )
-            .send()
-            .await
-            .map_err(|e| LlmError::HttpError(e.to_string()))?;
+        let response = send_request(
+            self.http_client
+                .get(self.response_endpoint(response_id))
+                .header(
+                    "Authorization",
+                    format!("Bearer {}", self.config.api_key.expose_secret()),
+                ),
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            "",
+            0,
+        )
+        .await
+        .map_err(|e| LlmError::HttpError(e.to_string()))?;
 
         if !response.status().is_success() {
             let status_code = response.status().as_u16();
@@ -1967,6 +2251,31 @@ impl OpenAiResponses {
     }
 }
 
+/// Prompt cache hits, reported as `input_tokens_details` (Responses) or
+/// `prompt_tokens_details` (Chat Completions)
+fn cached_input_tokens(usage: &serde_json::Value) -> Option<u32> {
+    usage
+        .get("input_tokens_details")
+        .or_else(|| usage.get("prompt_tokens_details"))
+        .and_then(|details| details.get("cached_tokens"))
+        .and_then(serde_json::Value::as_u64)
+        .map(|v| v as u32)
+}
+
+/// `url_citation` annotations of an output text part, offset into the joined response text
+fn output_text_citations(
+    part: &serde_json::Value,
+    text: &str,
+    offset: usize,
+) -> Vec<crate::types::Citation> {
+    part.get("annotations")
+        .and_then(|annotations| annotations.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|annotation| convert_url_citation(annotation, Some(text), offset))
+        .collect()
+}
+
 #[cfg(test)]
 use crate::utils::streaming::SseEventConverter;
 
@@ -1987,6 +2296,35 @@ fn test_responses_event_converter_content_delta() {
     }
 }
 
+#[test]
+fn test_responses_event_converter_tracks_resume_cursor() {
+    let cursor = Arc::new(Mutex::new(ResumeCursor::default()));
+    let conv = OpenAiResponsesEventConverter::new("gpt-5".to_string()).with_cursor(cursor.clone());
+    for (id, data) in [
+        (
+            "",
+            r#"{"type":"response.created","sequence_number":0,"response":{"id":"resp_1"}}"#,
+        ),
+        (
+            "evt_2",
+            r#"{"type":"response.output_text.delta","sequence_number":2,"delta":"hi"}"#,
+        ),
+    ] {
+        let event = eventsource_stream::Event {
+            event: "message".to_string(),
+            data: data.to_string(),
+            id: id.to_string(),
+            retry: None,
+        };
+        futures::executor::block_on(conv.convert_event(event));
+    }
+
+    let cursor = cursor.lock().unwrap();
+    assert_eq!(cursor.response_id.as_deref(), Some("resp_1"));
+    assert_eq!(cursor.last_event_id.as_deref(), Some("evt_2"));
+    assert_eq!(cursor.sequence_number, Some(2));
+}
+
 #[test]
 fn test_responses_event_converter_tool_call_delta() {
     let conv = OpenAiResponsesEventConverter::new("gpt-5".to_string());
@@ -2034,6 +2372,19 @@ fn test_responses_event_converter_usage_update() {
     }
 }
 
+#[test]
+fn test_cached_input_tokens_from_usage_details() {
+    let usage = serde_json::json!({
+        "input_tokens": 2048,
+        "input_tokens_details": {"cached_tokens": 1920}
+    });
+    assert_eq!(cached_input_tokens(&usage), Some(1920));
+    assert_eq!(
+        cached_input_tokens(&serde_json::json!({"input_tokens": 3})),
+        None
+    );
+}
+
 #[test]
 fn test_responses_event_converter_done() {
     let conv = OpenAiResponsesEventConverter::new("gpt-5".to_string());
@@ -2345,6 +2696,43 @@ fn test_parse_response_text_tool_calls_finish_reason_stop() {
     assert_eq!(usage.total_tokens, 5);
 }
 
+#[test]
+fn test_parse_response_url_citations() {
+    let config = create_test_config();
+    let client = super::OpenAiResponses::new(reqwest::Client::new(), config);
+
+    let response_data = serde_json::json!({
+        "id": "resp_search",
+        "output": [
+            {"type": "web_search_call", "id": "ws_1", "status": "completed"},
+            {
+                "type": "message",
+                "content": [{
+                    "type": "output_text",
+                    "text": "Rust 1.0 was released in May 2015.",
+                    "annotations": [{
+                        "type": "url_citation",
+                        "start_index": 0,
+                        "end_index": 34,
+                        "url": "https://blog.rust-lang.org/2015/05/15/Rust-1.0.html",
+                        "title": "Announcing Rust 1.0"
+                    }]
+                }]
+            }
+        ]
+    });
+
+    let resp = client.parse_response(response_data).unwrap();
+    assert_eq!(resp.citations.len(), 1);
+    assert_eq!(resp.citations[0].start_index, Some(0));
+    assert_eq!(resp.citations[0].end_index, Some(34));
+    assert_eq!(resp.sources.len(), 1);
+    assert_eq!(
+        resp.sources[0].title.as_deref(),
+        Some("Announcing Rust 1.0")
+    );
+}
+
 #[test]
 fn test_parse_response_finish_reason_length_via_stop_reason() {
     let config = create_test_config();
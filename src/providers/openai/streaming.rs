@@ -5,9 +5,11 @@
 
 use crate::error::LlmError;
 use crate::providers::openai::config::OpenAiConfig;
+use crate::providers::openai::utils::convert_url_citation;
+use crate::rate_limiter::{estimate_request_tokens, send_request};
 use crate::stream::{ChatStream, ChatStreamEvent};
-use crate::types::{ChatResponse, FinishReason, MessageContent, Usage};
-use crate::utils::streaming::{SseEventConverter, StreamFactory};
+use crate::types::{ChatResponse, FinishReason, MessageContent, SearchSource, Usage};
+use crate::utils::streaming::{SseEventConverter, StreamFactory, with_grounding_events};
 use eventsource_stream::Event;
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -39,6 +41,7 @@ struct OpenAiStreamDelta {
     content: Option<String>,
     tool_calls: Option<Vec<OpenAiToolCallDelta>>,
     thinking: Option<String>,
+    annotations: Option<Vec<serde_json::Value>>,
 }
 
 /// OpenAI tool call delta
@@ -101,6 +104,7 @@ impl OpenAiEventConverter {
                 cached_tokens: usage
                     .prompt_tokens_details
                     .and_then(|details| details.cached_tokens),
+                cache_write_tokens: None,
                 reasoning_tokens: usage
                     .completion_tokens_details
                     .and_then(|details| details.reasoning_tokens),
@@ -168,6 +172,8 @@ impl OpenAiEventConverter {
                         finish_reason: Some(reason),
                         tool_calls: None,
                         thinking: None,
+                        citations: Vec::new(),
+                        sources: Vec::new(),
                         metadata: HashMap::new(),
                     };
 
@@ -184,6 +190,35 @@ impl OpenAiEventConverter {
     }
 }
 
+impl OpenAiEventConverter {
+    /// Convert an OpenAI stream event, including `url_citation` annotations
+    fn convert_openai_events(&self, event: OpenAiStreamEvent) -> Vec<ChatStreamEvent> {
+        // Streamed annotation offsets refer to the full text, which is not tracked here
+        let citations: Vec<_> = event
+            .choices
+            .iter()
+            .flatten()
+            .filter_map(|choice| choice.delta.as_ref()?.annotations.as_ref())
+            .flatten()
+            .filter_map(|annotation| convert_url_citation(annotation, None, 0))
+            .collect();
+        let sources = SearchSource::from_citations(&citations);
+
+        let has_payload = event.usage.is_some()
+            || event.choices.iter().flatten().any(|choice| {
+                choice.finish_reason.is_some()
+                    || choice.delta.as_ref().is_some_and(|delta| {
+                        delta.content.is_some()
+                            || delta.thinking.is_some()
+                            || delta.tool_calls.is_some()
+                    })
+            });
+        let event = (has_payload || citations.is_empty()).then(|| self.convert_openai_event(event));
+
+        with_grounding_events(event, citations, sources)
+    }
+}
+
 impl SseEventConverter for OpenAiEventConverter {
     fn convert_event(
         &self,
@@ -200,6 +235,25 @@ impl SseEventConverter for OpenAiEventConverter {
         })
     }
 
+    fn convert_events(
+        &self,
+        event: Event,
+    ) -> Pin<Box<dyn Future<Output = Vec<Result<ChatStreamEvent, LlmError>>> + Send + Sync + '_>>
+    {
+        Box::pin(async move {
+            match serde_json::from_str::<OpenAiStreamEvent>(&event.data) {
+                Ok(openai_event) => self
+                    .convert_openai_events(openai_event)
+                    .into_iter()
+                    .map(Ok)
+                    .collect(),
+                Err(e) => vec![Err(LlmError::ParseError(format!(
+                    "Failed to parse OpenAI event: {e}"
+                )))],
+            }
+        })
+    }
+
     fn handle_stream_end(&self) -> Option<Result<ChatStreamEvent, LlmError>> {
         let response = ChatResponse {
             id: None,
@@ -209,6 +263,8 @@ impl SseEventConverter for OpenAiEventConverter {
             finish_reason: Some(FinishReason::Stop),
             tool_calls: None,
             thinking: None,
+            citations: Vec::new(),
+            sources: Vec::new(),
             metadata: HashMap::new(),
         };
 
@@ -248,7 +304,8 @@ impl OpenAiStreaming {
             self.config.project.clone(),
             self.config.http_config.clone(),
             self.config.common_params.clone(),
-        );
+        )
+        .with_provider_id(self.config.provider_id.clone());
 
         let mut request_body = chat_capability.build_chat_request_body(&request)?;
 
@@ -275,7 +332,17 @@ impl OpenAiStreaming {
             .headers(headers)
             .json(&request_body);
 
+        let response = send_request(
+            request_builder,
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "openai",
+            request_body["model"].as_str().unwrap_or_default(),
+            estimate_request_tokens(&request_body),
+        )
+        .await?;
+
         let converter = OpenAiEventConverter::new(self.config);
-        StreamFactory::create_eventsource_stream(request_builder, converter).await
+        StreamFactory::create_eventsource_stream_from_response(response, converter).await
     }
 }
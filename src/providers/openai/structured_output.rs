@@ -3,13 +3,16 @@
 //! This module implements `OpenAI`'s structured output feature which ensures
 //! the model's output conforms to a specified JSON schema.
 //!
+//! Schema conversion and validation are shared with the provider-agnostic
+//! [`OutputSchema`], which `chat_with_schema` and `generate_object` use.
+//!
 //! API Reference: <https://platform.openai.com/docs/guides/structured-outputs>
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::error::LlmError;
-use crate::types::ChatResponse;
+use crate::types::{ChatResponse, OutputSchema, extract_json};
 
 /// Structured output configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -150,14 +153,9 @@ impl ResponseFormat {
                 name,
                 schema,
                 strict,
-            } => serde_json::json!({
-                "type": "json_schema",
-                "json_schema": {
-                    "name": name,
-                    "schema": schema,
-                    "strict": strict
-                }
-            }),
+            } => OutputSchema::new(name.clone(), schema.clone())
+                .with_strict(*strict)
+                .to_openai_response_format(),
         }
     }
 }
@@ -167,96 +165,25 @@ pub struct StructuredOutputValidator;
 
 impl StructuredOutputValidator {
     /// Validate response against schema
+    ///
+    /// Uses the same validation as [`OutputSchema::validate`].
     pub fn validate_response(
         response: &ChatResponse,
         schema: &serde_json::Value,
     ) -> Result<serde_json::Value, LlmError> {
-        // Extract JSON content from response
-        let content = match &response.content {
-            crate::types::MessageContent::Text(text) => text,
-            crate::types::MessageContent::MultiModal(parts) => {
-                // Find the first text part
-                for part in parts {
-                    if let crate::types::ContentPart::Text { text } = part {
-                        return Self::validate_json_string(text, schema);
-                    }
-                }
-                return Err(LlmError::ParseError(
-                    "No text content found in multimodal response".to_string(),
-                ));
-            }
-        };
-
-        Self::validate_json_string(content, schema)
-    }
-
-    /// Validate JSON string against schema
-    fn validate_json_string(
-        json_str: &str,
-        schema: &serde_json::Value,
-    ) -> Result<serde_json::Value, LlmError> {
-        // Parse JSON
-        let parsed_json: serde_json::Value = serde_json::from_str(json_str)
-            .map_err(|e| LlmError::ParseError(format!("Invalid JSON: {e}")))?;
-
-        // Basic schema validation (in a real implementation, you'd use a proper JSON schema validator)
-        Self::basic_schema_validation(&parsed_json, schema)?;
-
-        Ok(parsed_json)
-    }
-
-    /// Basic schema validation
-    fn basic_schema_validation(
-        value: &serde_json::Value,
-        schema: &serde_json::Value,
-    ) -> Result<(), LlmError> {
-        // This is a simplified validation - in production, use a proper JSON schema library
-        if let Some(schema_type) = schema.get("type").and_then(|t| t.as_str()) {
-            match schema_type {
-                "object" => {
-                    if !value.is_object() {
-                        return Err(LlmError::ParseError("Expected object type".to_string()));
-                    }
-
-                    // Check required properties
-                    if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
-                        let obj = value.as_object().unwrap();
-                        for req_prop in required {
-                            if let Some(prop_name) = req_prop.as_str()
-                                && !obj.contains_key(prop_name)
-                            {
-                                return Err(LlmError::ParseError(format!(
-                                    "Missing required property: {prop_name}"
-                                )));
-                            }
-                        }
-                    }
-                }
-                "array" => {
-                    if !value.is_array() {
-                        return Err(LlmError::ParseError("Expected array type".to_string()));
-                    }
-                }
-                "string" => {
-                    if !value.is_string() {
-                        return Err(LlmError::ParseError("Expected string type".to_string()));
-                    }
-                }
-                "number" => {
-                    if !value.is_number() {
-                        return Err(LlmError::ParseError("Expected number type".to_string()));
-                    }
-                }
-                "boolean" => {
-                    if !value.is_boolean() {
-                        return Err(LlmError::ParseError("Expected boolean type".to_string()));
-                    }
-                }
-                _ => {}
-            }
+        let text = response.content.all_text();
+        if text.is_empty() {
+            return Err(LlmError::ParseError(
+                "No text content found in response".to_string(),
+            ));
         }
+        let json = extract_json(&text)
+            .ok_or_else(|| LlmError::ParseError("No JSON found in response".to_string()))?;
+        let value: serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| LlmError::ParseError(format!("Invalid JSON: {e}")))?;
 
-        Ok(())
+        OutputSchema::new("output", schema.clone()).validate(&value)?;
+        Ok(value)
     }
 }
 
@@ -428,6 +355,32 @@ mod tests {
         assert_eq!(json["json_schema"]["strict"], true);
     }
 
+    #[test]
+    fn test_json_schema_format_sends_schema_unchanged() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {"result": {"type": "string"}}
+        });
+
+        let json = ResponseFormat::json_schema("answer", schema.clone()).to_json();
+        let expected = OutputSchema::new("answer", schema.clone()).to_openai_response_format();
+        assert_eq!(json, expected);
+        assert_eq!(json["json_schema"]["schema"], schema);
+        assert_eq!(json["json_schema"]["strict"], true);
+
+        let mut response = ChatResponse::new(crate::types::MessageContent::Text(
+            r#"{"result": 1}"#.into(),
+        ));
+        let validation =
+            StructuredOutputValidator::validate_response(&response, &json["json_schema"]["schema"]);
+        assert!(validation.is_err());
+        response.content = crate::types::MessageContent::Text(r#"{"result": "ok"}"#.into());
+        assert!(
+            StructuredOutputValidator::validate_response(&response, &json["json_schema"]["schema"])
+                .is_ok()
+        );
+    }
+
     #[test]
     fn test_schema_builder() {
         let mut properties = HashMap::new();
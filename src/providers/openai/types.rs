@@ -11,6 +11,9 @@ pub struct OpenAiMessage {
     pub content: Option<serde_json::Value>,
     pub tool_calls: Option<Vec<OpenAiToolCall>>,
     pub tool_call_id: Option<String>,
+    /// Response annotations such as `url_citation` (search models)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Vec<serde_json::Value>>,
 }
 
 /// `OpenAI` Tool Call
@@ -53,6 +56,13 @@ pub struct OpenAiUsage {
     pub prompt_tokens: Option<u32>,
     pub completion_tokens: Option<u32>,
     pub total_tokens: Option<u32>,
+    pub prompt_tokens_details: Option<OpenAiPromptTokensDetails>,
+}
+
+/// `OpenAI` prompt tokens details
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenAiPromptTokensDetails {
+    pub cached_tokens: Option<u32>,
 }
 
 /// `OpenAI` Model information
@@ -59,6 +59,41 @@ pub fn convert_message_content(content: &MessageContent) -> Result<serde_json::V
                             "text": format!("[Audio: {}]", audio_url)
                         }));
                     }
+                    ContentPart::Document {
+                        source,
+                        media_type,
+                        title,
+                        citations: _,
+                    } => {
+                        // Plain text is inlined; PDFs go through file inputs
+                        if let Some(text) = source.decoded_text(media_type) {
+                            content_parts.push(serde_json::json!({
+                                "type": "text",
+                                "text": text
+                            }));
+                            continue;
+                        }
+                        let file = match source {
+                            DocumentSource::FileId(file_id) => serde_json::json!({
+                                "file_id": file_id
+                            }),
+                            DocumentSource::Base64(data) => serde_json::json!({
+                                "filename": title.as_deref().unwrap_or("document.pdf"),
+                                "file_data": format!("data:{media_type};base64,{data}")
+                            }),
+                            DocumentSource::Url(_) => {
+                                return Err(LlmError::UnsupportedOperation(
+                                    "OpenAI Chat Completions cannot fetch documents by URL; \
+                                     upload the file or use the Responses API"
+                                        .to_string(),
+                                ));
+                            }
+                        };
+                        content_parts.push(serde_json::json!({
+                            "type": "file",
+                            "file": file
+                        }));
+                    }
                 }
             }
 
@@ -67,6 +102,45 @@ pub fn convert_message_content(content: &MessageContent) -> Result<serde_json::V
     }
 }
 
+/// Convert a `url_citation` annotation to a citation.
+///
+/// Accepts both the Chat Completions shape (`{"url_citation": {...}}`) and the flat
+/// Responses API shape. Annotation indices count characters of `text`; they are
+/// converted to byte offsets and shifted by `offset` into the joined response text.
+/// Without `text` (streaming) the offsets are left unset.
+pub fn convert_url_citation(
+    annotation: &serde_json::Value,
+    text: Option<&str>,
+    offset: usize,
+) -> Option<Citation> {
+    if annotation.get("type").and_then(|t| t.as_str()) != Some("url_citation") {
+        return None;
+    }
+    let data = annotation.get("url_citation").unwrap_or(annotation);
+    let byte_index = |key: &str| {
+        let text = text?;
+        data.get(key)
+            .and_then(serde_json::Value::as_u64)
+            .map(|chars| {
+                text.char_indices()
+                    .nth(chars as usize)
+                    .map_or(text.len(), |(byte, _)| byte)
+                    + offset
+            })
+    };
+
+    Some(Citation {
+        url: data.get("url").and_then(|u| u.as_str()).map(str::to_string),
+        title: data
+            .get("title")
+            .and_then(|t| t.as_str())
+            .map(str::to_string),
+        start_index: byte_index("start_index"),
+        end_index: byte_index("end_index"),
+        ..Default::default()
+    })
+}
+
 /// Convert messages to `OpenAI` format
 pub fn convert_messages(messages: &[ChatMessage]) -> Result<Vec<OpenAiMessage>, LlmError> {
     let mut openai_messages = Vec::new();
@@ -78,12 +152,14 @@ pub fn convert_messages(messages: &[ChatMessage]) -> Result<Vec<OpenAiMessage>,
                 content: Some(convert_message_content(&message.content)?),
                 tool_calls: None,
                 tool_call_id: None,
+                annotations: None,
             },
             MessageRole::User => OpenAiMessage {
                 role: "user".to_string(),
                 content: Some(convert_message_content(&message.content)?),
                 tool_calls: None,
                 tool_call_id: None,
+                annotations: None,
             },
             MessageRole::Assistant => OpenAiMessage {
                 role: "assistant".to_string(),
@@ -102,18 +178,21 @@ pub fn convert_messages(messages: &[ChatMessage]) -> Result<Vec<OpenAiMessage>,
                         .collect()
                 }),
                 tool_call_id: None,
+                annotations: None,
             },
             MessageRole::Developer => OpenAiMessage {
                 role: "developer".to_string(),
                 content: Some(convert_message_content(&message.content)?),
                 tool_calls: None,
                 tool_call_id: None,
+                annotations: None,
             },
             MessageRole::Tool => OpenAiMessage {
                 role: "tool".to_string(),
                 content: Some(convert_message_content(&message.content)?),
                 tool_calls: None,
                 tool_call_id: message.tool_call_id.clone(),
+                annotations: None,
             },
         };
 
@@ -240,4 +319,74 @@ mod tests {
             .with_responses_api(true);
         assert!(should_route_responses(&cfg));
     }
+
+    #[test]
+    fn test_convert_document_parts() {
+        let content = MessageContent::MultiModal(vec![
+            ContentPart::Document {
+                source: DocumentSource::Base64("JVBERi0=".to_string()),
+                media_type: "application/pdf".to_string(),
+                title: Some("report.pdf".to_string()),
+                citations: false,
+            },
+            ContentPart::Document {
+                source: DocumentSource::FileId("file-abc".to_string()),
+                media_type: "application/pdf".to_string(),
+                title: None,
+                citations: false,
+            },
+        ]);
+        let json = convert_message_content(&content).unwrap();
+        assert_eq!(json[0]["type"], "file");
+        assert_eq!(json[0]["file"]["filename"], "report.pdf");
+        assert_eq!(
+            json[0]["file"]["file_data"],
+            "data:application/pdf;base64,JVBERi0="
+        );
+        assert_eq!(json[1]["file"]["file_id"], "file-abc");
+
+        let url_doc = MessageContent::MultiModal(vec![ContentPart::Document {
+            source: DocumentSource::Url("https://example.com/a.pdf".to_string()),
+            media_type: "application/pdf".to_string(),
+            title: None,
+            citations: false,
+        }]);
+        assert!(convert_message_content(&url_doc).is_err());
+    }
+
+    #[test]
+    fn test_convert_url_citation_shapes() {
+        let text = "Café news: prices rose.";
+        let chat = serde_json::json!({
+            "type": "url_citation",
+            "url_citation": {
+                "start_index": 11,
+                "end_index": 23,
+                "url": "https://news.example/cafe",
+                "title": "Cafe news"
+            }
+        });
+        let citation = convert_url_citation(&chat, Some(text), 0).unwrap();
+        assert_eq!(
+            &text[citation.start_index.unwrap()..citation.end_index.unwrap()],
+            "prices rose."
+        );
+        assert_eq!(citation.title.as_deref(), Some("Cafe news"));
+
+        let responses = serde_json::json!({
+            "type": "url_citation",
+            "start_index": 0,
+            "end_index": 4,
+            "url": "https://news.example/cafe"
+        });
+        let citation = convert_url_citation(&responses, Some(text), 10).unwrap();
+        assert_eq!(citation.start_index, Some(10));
+        assert_eq!(citation.end_index, Some(15));
+
+        let streamed = convert_url_citation(&responses, None, 0).unwrap();
+        assert_eq!(streamed.start_index, None);
+
+        let file = serde_json::json!({"type": "file_citation", "file_id": "file-1"});
+        assert!(convert_url_citation(&file, Some(text), 0).is_none());
+    }
 }
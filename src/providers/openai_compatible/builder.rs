@@ -2,9 +2,13 @@
 //!
 //! This module provides the builder pattern for OpenAI-compatible providers.
 
+use async_trait::async_trait;
+
 use crate::builder::LlmBuilder;
 use crate::error::LlmError;
 use crate::providers::openai::OpenAiClient;
+use crate::types::{WebSearchConfig, WebSearchResult, WebSearchStrategy};
+use crate::web_search::{WebSearchCapability, WebSearchProvider};
 use std::marker::PhantomData;
 
 use super::config::OpenAiCompatibleConfig;
@@ -102,9 +106,12 @@ impl<P: OpenAiCompatibleProvider> OpenAiCompatibleBuilder<P> {
         P::transform_params(&mut provider_params)?;
 
         // Convert to OpenAI configuration
-        let openai_config = self
+        let mut openai_config = self
             .config
             .to_openai_config(P::DEFAULT_BASE_URL, P::DEFAULT_MODEL)?;
+        if openai_config.http_config.cassette.is_none() {
+            openai_config.http_config.cassette = self.base.cassette.clone();
+        }
 
         // Build HTTP client
         let http_client = self.base.build_http_client()?;
@@ -205,3 +212,26 @@ impl OpenAiCompatibleBuilder<OpenRouterProvider> {
         self.with_provider_param("fallback_models".to_string(), models)
     }
 }
+
+#[async_trait]
+impl WebSearchCapability for OpenAiCompatibleClient<OpenRouterProvider> {
+    /// Answer the query with the `web` plugin and return the cited pages
+    async fn web_search(
+        &self,
+        query: String,
+        config: Option<WebSearchConfig>,
+    ) -> Result<Vec<WebSearchResult>, LlmError> {
+        let provider = WebSearchProvider::for_query("openrouter", config);
+        let request = provider.search_request(query, self.client.common_params().clone());
+        let response = self.client.chat_capability().chat(request).await?;
+        Ok(provider.results_from_response(&response))
+    }
+
+    fn supports_web_search(&self) -> bool {
+        true
+    }
+
+    fn web_search_strategy(&self) -> WebSearchStrategy {
+        WebSearchStrategy::BuiltIn
+    }
+}
@@ -98,6 +98,7 @@ impl OpenAiCompatibleConfig {
 
         // Create OpenAI configuration
         let mut openai_config = crate::providers::openai::config::OpenAiConfig::new(self.api_key)
+            .with_provider_id(self.provider_id.clone())
             .with_base_url(base_url)
             .with_model(model);
 
@@ -201,6 +201,7 @@ impl OpenAiCompatibleProvider for OpenRouterProvider {
             .with_vision()
             .with_custom_feature("model_routing", true)
             .with_custom_feature("fallback_models", true)
+            .with_custom_feature("web_search", true)
     }
 }
 
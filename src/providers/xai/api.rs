@@ -3,6 +3,7 @@
 //! Implements model listing and information capabilities for xAI.
 
 use crate::error::LlmError;
+use crate::rate_limiter::send_request;
 use crate::traits::ModelListingCapability;
 use crate::types::{HttpConfig, ModelInfo};
 use async_trait::async_trait;
@@ -89,7 +90,15 @@ impl XaiModels {
         let url = format!("{}/models", self.base_url);
         let headers = build_headers(&self.api_key, &self.http_config.headers)?;
 
-        let response = self.http_client.get(&url).headers(headers).send().await?;
+        let response = send_request(
+            self.http_client.get(&url).headers(headers),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "xai",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -118,7 +127,15 @@ impl XaiModels {
         let url = format!("{}/models/{}", self.base_url, model_id);
         let headers = build_headers(&self.api_key, &self.http_config.headers)?;
 
-        let response = self.http_client.get(&url).headers(headers).send().await?;
+        let response = send_request(
+            self.http_client.get(&url).headers(headers),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "xai",
+            "",
+            0,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
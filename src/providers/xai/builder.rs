@@ -102,6 +102,21 @@ impl XaiBuilder {
         self
     }
 
+    /// Set the client-side rate limiter shared by clients built with it
+    pub fn rate_limiter(
+        mut self,
+        limiter: std::sync::Arc<crate::rate_limiter::RateLimiter>,
+    ) -> Self {
+        self.config.http_config.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Record or replay requests with a cassette
+    pub fn cassette(mut self, cassette: std::sync::Arc<crate::cassette::Cassette>) -> Self {
+        self.config.http_config.cassette = Some(cassette);
+        self
+    }
+
     // === Tracing Configuration ===
 
     /// Set custom tracing configuration
@@ -8,6 +8,7 @@ use std::time::Instant;
 
 use crate::error::LlmError;
 use crate::params::{OpenAiParameterMapper, ParameterMapper};
+use crate::rate_limiter::{estimate_request_tokens, send_request};
 use crate::stream::ChatStream;
 use crate::tracing::ProviderTracer;
 use crate::traits::ChatCapability;
@@ -63,6 +64,11 @@ impl XaiChatCapability {
                 .merge_provider_params(body, provider_params);
         }
 
+        // Constrain the output to the requested schema
+        if let Some(ref schema) = request.output_schema {
+            body["response_format"] = schema.to_openai_response_format();
+        }
+
         // Validate parameters
         self.parameter_mapper.validate_params(&body)?;
 
@@ -77,6 +83,15 @@ impl XaiChatCapability {
             body["tools"] = serde_json::to_value(tools)?;
         }
 
+        // Enable Live Search unless the caller set search_parameters directly
+        if let Some(config) = request.web_search.as_ref().filter(|config| config.enabled)
+            && body.get("search_parameters").is_none()
+        {
+            body["search_parameters"] =
+                crate::web_search::WebSearchProvider::new("xai".to_string(), config.clone())
+                    .build_xai_search_parameters();
+        }
+
         // Add stream parameter if needed
         if request.stream {
             body["stream"] = serde_json::Value::Bool(true);
@@ -168,9 +183,19 @@ impl XaiChatCapability {
             completion_tokens: u.completion_tokens.unwrap_or(0),
             total_tokens: u.total_tokens.unwrap_or(0),
             reasoning_tokens: u.reasoning_tokens, // xAI specific
-            cached_tokens: None,
+            cached_tokens: u
+                .prompt_tokens_details
+                .and_then(|details| details.cached_tokens),
+            cache_write_tokens: None,
         });
 
+        let sources = response
+            .citations
+            .unwrap_or_default()
+            .into_iter()
+            .map(SearchSource::new)
+            .collect();
+
         let _metadata = ResponseMetadata {
             id: Some(response.id.clone()),
             model: Some(response.model.clone()),
@@ -190,6 +215,8 @@ impl XaiChatCapability {
             finish_reason,
             tool_calls,
             thinking: thinking_content, // Now includes extracted thinking content
+            citations: Vec::new(),
+            sources,
             metadata: HashMap::new(),
         })
     }
@@ -213,6 +240,8 @@ impl ChatCapability for XaiChatCapability {
             http_config: None,
             web_search: None,
             stream: false,
+            prompt_cache: None,
+            output_schema: None,
         };
 
         // Extract model name for tracing
@@ -226,13 +255,15 @@ impl ChatCapability for XaiChatCapability {
         tracer.trace_request_start("POST", &url);
         tracer.trace_request_details(&headers, &body);
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "xai",
+            body["model"].as_str().unwrap_or_default(),
+            estimate_request_tokens(&body),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -275,6 +306,8 @@ impl ChatCapability for XaiChatCapability {
             http_config: None,
             web_search: None,
             stream: true,
+            prompt_cache: None,
+            output_schema: None,
         };
 
         // Create streaming client
@@ -300,13 +333,15 @@ impl XaiChatCapability {
         let body = self.build_chat_request_body(&request)?;
         let url = format!("{}/chat/completions", self.base_url);
 
-        let response = self
-            .http_client
-            .post(&url)
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await?;
+        let response = send_request(
+            self.http_client.post(&url).headers(headers).json(&body),
+            self.http_config.rate_limiter.as_deref(),
+            self.http_config.cassette.as_deref(),
+            "xai",
+            body["model"].as_str().unwrap_or_default(),
+            estimate_request_tokens(&body),
+        )
+        .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -7,9 +7,11 @@ use std::time::Duration;
 
 use crate::client::LlmClient;
 use crate::error::LlmError;
+use crate::rate_limiter::send_request;
 use crate::stream::ChatStream;
 use crate::traits::{ChatCapability, ModelListingCapability, ProviderCapabilities};
 use crate::types::*;
+use crate::web_search::{WebSearchCapability, WebSearchProvider};
 
 use super::api::XaiModels;
 use super::chat::XaiChatCapability;
@@ -30,6 +32,8 @@ pub struct XaiClient {
     pub common_params: CommonParams,
     /// HTTP client
     pub http_client: reqwest::Client,
+    /// Live Search configuration applied to chat requests
+    pub web_search_config: WebSearchConfig,
     /// Tracing configuration
     tracing_config: Option<crate::tracing::TracingConfig>,
     /// Tracing guard to keep tracing system active
@@ -43,6 +47,7 @@ impl Clone for XaiClient {
             models_capability: self.models_capability.clone(),
             common_params: self.common_params.clone(),
             http_client: self.http_client.clone(),
+            web_search_config: self.web_search_config.clone(),
             tracing_config: self.tracing_config.clone(),
             _tracing_guard: None, // Don't clone the tracing guard
         }
@@ -103,6 +108,7 @@ impl XaiClient {
             models_capability,
             common_params: config.common_params,
             http_client,
+            web_search_config: config.web_search_config,
             tracing_config: None,
             _tracing_guard: None,
         })
@@ -115,10 +121,17 @@ impl XaiClient {
             base_url: self.chat_capability.base_url.clone(),
             common_params: self.common_params.clone(),
             http_config: self.chat_capability.http_config.clone(),
-            web_search_config: WebSearchConfig::default(),
+            web_search_config: self.web_search_config.clone(),
         }
     }
 
+    /// Live Search configuration for chat requests, if enabled
+    fn live_search(&self) -> Option<WebSearchConfig> {
+        self.web_search_config
+            .enabled
+            .then(|| self.web_search_config.clone())
+    }
+
     /// Update common parameters
     pub fn with_common_params(mut self, params: CommonParams) -> Self {
         self.common_params = params;
@@ -166,6 +179,7 @@ impl LlmClient for XaiClient {
             .with_custom_feature("reasoning", true)
             .with_custom_feature("deferred_completion", true)
             .with_custom_feature("structured_outputs", true)
+            .with_custom_feature("web_search", true)
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -179,6 +193,10 @@ impl LlmClient for XaiClient {
 
 #[async_trait]
 impl ChatCapability for XaiClient {
+    fn default_model(&self) -> Option<String> {
+        Some(self.common_params.model.clone()).filter(|model| !model.is_empty())
+    }
+
     /// Chat with tools implementation
     async fn chat_with_tools(
         &self,
@@ -192,8 +210,10 @@ impl ChatCapability for XaiClient {
             common_params: self.common_params.clone(),
             provider_params: None,
             http_config: None,
-            web_search: None,
+            web_search: self.live_search(),
             stream: false,
+            prompt_cache: None,
+            output_schema: None,
         };
         self.chat_capability.chat(request).await
     }
@@ -204,8 +224,90 @@ impl ChatCapability for XaiClient {
         messages: Vec<ChatMessage>,
         tools: Option<Vec<Tool>>,
     ) -> Result<ChatStream, LlmError> {
-        // Now that XaiChatCapability has the correct common_params, we can use the trait method directly
-        self.chat_capability.chat_stream(messages, tools).await
+        let request = ChatRequest {
+            messages,
+            tools,
+            common_params: self.common_params.clone(),
+            provider_params: None,
+            http_config: None,
+            web_search: self.live_search(),
+            stream: true,
+            prompt_cache: None,
+            output_schema: None,
+        };
+        self.chat_capability.chat_stream_request(request).await
+    }
+
+    /// Chat with structured output via the OpenAI-compatible `response_format`
+    async fn chat_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatResponse, LlmError> {
+        self.chat_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    /// Stream structured output via the OpenAI-compatible `response_format`
+    async fn chat_stream_with_schema(
+        &self,
+        messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatStream, LlmError> {
+        self.chat_stream_with_request(ChatRequest::new(messages).with_output_schema(schema))
+            .await
+    }
+
+    async fn chat_with_request(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        let request = ChatRequest {
+            common_params: self.common_params.merged_with(&request.common_params),
+            http_config: None,
+            web_search: request.web_search.or_else(|| self.live_search()),
+            stream: false,
+            ..request
+        };
+        self.chat_capability.chat(request).await
+    }
+
+    async fn chat_stream_with_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
+        let request = ChatRequest {
+            common_params: self.common_params.merged_with(&request.common_params),
+            http_config: None,
+            web_search: request.web_search.or_else(|| self.live_search()),
+            stream: true,
+            ..request
+        };
+        self.chat_capability.chat_stream_request(request).await
+    }
+}
+
+#[async_trait]
+impl WebSearchCapability for XaiClient {
+    /// Answer the query with Live Search and return the cited sources
+    async fn web_search(
+        &self,
+        query: String,
+        config: Option<WebSearchConfig>,
+    ) -> Result<Vec<WebSearchResult>, LlmError> {
+        let mut provider = WebSearchProvider::for_query(
+            "xai",
+            config.or_else(|| Some(self.web_search_config.clone())),
+        );
+        // An explicit search must not leave the decision to the model
+        if matches!(provider.config.strategy, WebSearchStrategy::Auto) {
+            provider.config.strategy = WebSearchStrategy::BuiltIn;
+        }
+        let request = provider.search_request(query, self.common_params.clone());
+        let response = self.chat_capability.chat(request).await?;
+        Ok(provider.results_from_response(&response))
+    }
+
+    fn supports_web_search(&self) -> bool {
+        true
+    }
+
+    fn web_search_strategy(&self) -> WebSearchStrategy {
+        WebSearchStrategy::BuiltIn
     }
 }
 
@@ -233,6 +335,8 @@ impl XaiClient {
             http_config: None,
             web_search: None,
             stream: false,
+            prompt_cache: None,
+            output_schema: None,
         };
 
         self.chat_capability.chat(request).await
@@ -256,6 +360,8 @@ impl XaiClient {
             http_config: None,
             web_search: None,
             stream: false,
+            prompt_cache: None,
+            output_schema: None,
         };
 
         // This would return a request_id instead of a full response
@@ -283,7 +389,15 @@ impl XaiClient {
             &self.chat_capability.http_config.headers,
         )?;
 
-        let response = self.http_client.get(&url).headers(headers).send().await?;
+        let response = send_request(
+            self.http_client.get(&url).headers(headers),
+            self.chat_capability.http_config.rate_limiter.as_deref(),
+            self.chat_capability.http_config.cassette.as_deref(),
+            "xai",
+            "",
+            0,
+        )
+        .await?;
 
         match response.status().as_u16() {
             200 => {
@@ -3,9 +3,10 @@
 //! Implements streaming chat completions for the `xAI` provider using eventsource-stream.
 
 use crate::error::LlmError;
+use crate::rate_limiter::{estimate_request_tokens, send_request};
 use crate::stream::{ChatStream, ChatStreamEvent};
-use crate::types::{ChatRequest, ChatResponse, FinishReason, MessageContent, Usage};
-use crate::utils::streaming::{SseEventConverter, StreamFactory};
+use crate::types::{ChatRequest, ChatResponse, FinishReason, MessageContent, SearchSource, Usage};
+use crate::utils::streaming::{SseEventConverter, StreamFactory, with_grounding_events};
 use eventsource_stream::Event;
 use std::collections::HashMap;
 use std::future::Future;
@@ -35,7 +36,10 @@ impl XaiEventConverter {
                 prompt_tokens: usage.prompt_tokens.unwrap_or(0),
                 completion_tokens: usage.completion_tokens.unwrap_or(0),
                 total_tokens: usage.total_tokens.unwrap_or(0),
-                cached_tokens: None, // xAI doesn't provide cached tokens info
+                cached_tokens: usage
+                    .prompt_tokens_details
+                    .and_then(|details| details.cached_tokens),
+                cache_write_tokens: None,
                 reasoning_tokens: usage.reasoning_tokens,
             };
             return ChatStreamEvent::UsageUpdate { usage: usage_info };
@@ -94,6 +98,8 @@ impl XaiEventConverter {
                     finish_reason: Some(reason),
                     tool_calls: None,
                     thinking: None,
+                    citations: Vec::new(),
+                    sources: Vec::new(),
                     metadata: HashMap::new(),
                 };
 
@@ -109,6 +115,25 @@ impl XaiEventConverter {
     }
 }
 
+impl XaiEventConverter {
+    /// Convert an xAI stream chunk, including Live Search sources
+    fn convert_xai_events(&self, mut event: XaiStreamChunk) -> Vec<ChatStreamEvent> {
+        let sources: Vec<SearchSource> = event
+            .citations
+            .take()
+            .unwrap_or_default()
+            .into_iter()
+            .map(SearchSource::new)
+            .collect();
+
+        // A chunk carrying only sources has nothing else to convert
+        let sources_only = !sources.is_empty() && event.usage.is_none() && event.choices.is_empty();
+        let event = (!sources_only).then(|| self.convert_xai_event(event));
+
+        with_grounding_events(event, Vec::new(), sources)
+    }
+}
+
 impl SseEventConverter for XaiEventConverter {
     fn convert_event(
         &self,
@@ -125,6 +150,25 @@ impl SseEventConverter for XaiEventConverter {
         })
     }
 
+    fn convert_events(
+        &self,
+        event: Event,
+    ) -> Pin<Box<dyn Future<Output = Vec<Result<ChatStreamEvent, LlmError>>> + Send + Sync + '_>>
+    {
+        Box::pin(async move {
+            match serde_json::from_str::<XaiStreamChunk>(&event.data) {
+                Ok(xai_event) => self
+                    .convert_xai_events(xai_event)
+                    .into_iter()
+                    .map(Ok)
+                    .collect(),
+                Err(e) => vec![Err(LlmError::ParseError(format!(
+                    "Failed to parse xAI event: {e}"
+                )))],
+            }
+        })
+    }
+
     fn handle_stream_end(&self) -> Option<Result<ChatStreamEvent, LlmError>> {
         let response = ChatResponse {
             id: None,
@@ -134,6 +178,8 @@ impl SseEventConverter for XaiEventConverter {
             finish_reason: Some(FinishReason::Stop),
             tool_calls: None,
             thinking: None,
+            citations: Vec::new(),
+            sources: Vec::new(),
             metadata: HashMap::new(),
         };
 
@@ -185,7 +231,53 @@ impl XaiStreaming {
             .headers(headers)
             .json(&request_body);
 
+        let response = send_request(
+            request_builder,
+            self.config.http_config.rate_limiter.as_deref(),
+            self.config.http_config.cassette.as_deref(),
+            "xai",
+            request_body["model"].as_str().unwrap_or_default(),
+            estimate_request_tokens(&request_body),
+        )
+        .await?;
+
         let converter = XaiEventConverter::new(self.config);
-        StreamFactory::create_eventsource_stream(request_builder, converter).await
+        StreamFactory::create_eventsource_stream_from_response(response, converter).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_live_search_sources_in_final_chunk() {
+        let converter = XaiEventConverter::new(XaiConfig::new("test-key"));
+        let event = Event {
+            event: "".to_string(),
+            data: r#"{"id":"c1","object":"chat.completion.chunk","created":0,"model":"grok-3",
+                "choices":[],
+                "usage":{"prompt_tokens":10,"completion_tokens":5,"total_tokens":15},
+                "citations":["https://x.com/a","https://news.example/b"]}"#
+                .to_string(),
+            id: "".to_string(),
+            retry: None,
+        };
+
+        let events: Vec<ChatStreamEvent> = converter
+            .convert_events(event)
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], ChatStreamEvent::UsageUpdate { .. }));
+        match &events[2] {
+            ChatStreamEvent::SourceDelta { source } => {
+                assert_eq!(source.url, "https://news.example/b")
+            }
+            other => panic!("Expected SourceDelta, got {other:?}"),
+        }
     }
 }
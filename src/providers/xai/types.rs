@@ -21,6 +21,9 @@ pub struct XaiChatResponse {
     pub usage: Option<XaiUsage>,
     /// System fingerprint
     pub system_fingerprint: Option<String>,
+    /// URLs of the sources used by Live Search
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub citations: Option<Vec<String>>,
 }
 
 /// `xAI` Choice
@@ -121,6 +124,9 @@ pub struct XaiStreamChunk {
     pub usage: Option<XaiUsage>,
     /// System fingerprint
     pub system_fingerprint: Option<String>,
+    /// URLs of the sources used by Live Search (only in final chunk)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub citations: Option<Vec<String>>,
 }
 
 /// `xAI` Stream Choice
@@ -0,0 +1,557 @@
+//! Client-side Rate Limiting
+//!
+//! [`RateLimiter`] throttles requests before they are sent instead of reacting
+//! to `429` responses afterwards. Each provider (or provider and model) gets a
+//! token bucket for requests per minute and one for tokens per minute.
+//!
+//! The limiter also reads `retry-after` and `x-ratelimit-*` response headers
+//! (and Anthropic's `anthropic-ratelimit-*` equivalents), so its budgets follow
+//! what the provider reports even when several processes share an API key.
+//!
+//! A limiter is attached through [`HttpConfig::rate_limiter`] and shared
+//! through an `Arc`, so every client cloned from the same configuration draws
+//! from the same buckets. Requests waiting for capacity can be cancelled by
+//! dropping their future, or all at once with [`RateLimiter::cancel_pending`].
+//!
+//! [`HttpConfig::rate_limiter`]: crate::types::HttpConfig::rate_limiter
+//!
+//! # Example
+//! ```rust,no_run
+//! use siumai::prelude::*;
+//! use siumai::rate_limiter::{RateLimit, RateLimiter};
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> Result<(), LlmError> {
+//! let limiter = Arc::new(
+//!     RateLimiter::new()
+//!         .with_limit("openai", RateLimit::per_minute(500, 200_000))
+//!         .with_model_limit("openai", "o3", RateLimit::per_minute(50, 30_000)),
+//! );
+//!
+//! let client = Siumai::builder()
+//!     .openai()
+//!     .api_key("your-api-key")
+//!     .model("gpt-4o-mini")
+//!     .rate_limiter(limiter.clone())
+//!     .build()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+use crate::cassette::Cassette;
+use crate::error::LlmError;
+
+/// Requests-per-minute and tokens-per-minute limits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Maximum requests per minute
+    pub requests_per_minute: Option<u32>,
+    /// Maximum tokens per minute
+    pub tokens_per_minute: Option<u32>,
+}
+
+impl RateLimit {
+    /// Create a limit without any bounds
+    pub const fn new() -> Self {
+        Self {
+            requests_per_minute: None,
+            tokens_per_minute: None,
+        }
+    }
+
+    /// Create a limit on both requests and tokens per minute
+    pub const fn per_minute(requests: u32, tokens: u32) -> Self {
+        Self {
+            requests_per_minute: Some(requests),
+            tokens_per_minute: Some(tokens),
+        }
+    }
+
+    /// Set the maximum requests per minute
+    pub const fn with_requests_per_minute(mut self, requests: u32) -> Self {
+        self.requests_per_minute = Some(requests);
+        self
+    }
+
+    /// Set the maximum tokens per minute
+    pub const fn with_tokens_per_minute(mut self, tokens: u32) -> Self {
+        self.tokens_per_minute = Some(tokens);
+        self
+    }
+}
+
+/// One dimension (requests or tokens) of a bucket
+#[derive(Debug)]
+struct Allowance {
+    /// Capacity per minute, or `None` if only provider headers apply
+    capacity: Option<f64>,
+    available: f64,
+    /// Set when the provider reported the allowance as exhausted
+    blocked_until: Option<Instant>,
+}
+
+impl Allowance {
+    fn new(capacity: Option<u32>) -> Self {
+        let capacity = capacity.map(f64::from);
+        Self {
+            capacity,
+            available: capacity.unwrap_or(0.0),
+            blocked_until: None,
+        }
+    }
+
+    fn refill(&mut self, elapsed: Duration) {
+        if let Some(capacity) = self.capacity {
+            self.available =
+                (self.available + elapsed.as_secs_f64() * capacity / 60.0).min(capacity);
+        }
+    }
+
+    /// Time until `cost` can be taken
+    fn wait_time(&self, cost: f64, now: Instant) -> Duration {
+        if let Some(until) = self.blocked_until
+            && until > now
+        {
+            return until - now;
+        }
+        let Some(capacity) = self.capacity else {
+            return Duration::ZERO;
+        };
+        // A single request larger than the whole budget waits for a full bucket
+        let cost = cost.min(capacity);
+        if self.available >= cost {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((cost - self.available) * 60.0 / capacity)
+        }
+    }
+
+    fn take(&mut self, cost: f64) {
+        if let Some(capacity) = self.capacity {
+            self.available -= cost.min(capacity);
+        }
+    }
+
+    /// Apply a `remaining`/`reset` pair reported by the provider
+    fn observe(&mut self, remaining: Option<f64>, reset: Option<Duration>, now: Instant) {
+        let Some(remaining) = remaining else {
+            return;
+        };
+        if self.capacity.is_some() {
+            self.available = self.available.min(remaining);
+        }
+        if remaining < 1.0 {
+            let until = now + reset.unwrap_or(Duration::from_secs(1));
+            self.blocked_until = Some(self.blocked_until.map_or(until, |u| u.max(until)));
+        }
+    }
+}
+
+/// Token bucket for one provider or provider and model
+#[derive(Debug)]
+struct Bucket {
+    requests: Allowance,
+    tokens: Allowance,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            requests: Allowance::new(limit.requests_per_minute),
+            tokens: Allowance::new(limit.tokens_per_minute),
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.requests.refill(elapsed);
+        self.tokens.refill(elapsed);
+        self.last_refill = now;
+    }
+
+    /// Take capacity for a request, or return how long to wait for it
+    fn try_take(&mut self, tokens: u32, now: Instant) -> Result<(), Duration> {
+        self.refill(now);
+        let wait = self
+            .requests
+            .wait_time(1.0, now)
+            .max(self.tokens.wait_time(f64::from(tokens), now));
+        if !wait.is_zero() {
+            return Err(wait);
+        }
+        self.requests.take(1.0);
+        self.tokens.take(f64::from(tokens));
+        Ok(())
+    }
+}
+
+/// Proactive token-bucket rate limiter.
+///
+/// Limits are configured per provider, optionally overridden per model. A
+/// model with its own limit gets its own bucket; other models share the
+/// provider's bucket. Providers without a configured limit are only throttled
+/// by what their response headers report.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    provider_limits: HashMap<String, RateLimit>,
+    model_limits: HashMap<(String, String), RateLimit>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+    /// Incremented by `cancel_pending` so waiting requests can notice
+    generation: AtomicU64,
+    cancelled: Notify,
+}
+
+impl RateLimiter {
+    /// Create a limiter without any configured limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the limit of a provider
+    pub fn with_limit(mut self, provider: impl Into<String>, limit: RateLimit) -> Self {
+        self.provider_limits.insert(provider.into(), limit);
+        self
+    }
+
+    /// Set the limit of a model, separate from its provider's limit
+    pub fn with_model_limit(
+        mut self,
+        provider: impl Into<String>,
+        model: impl Into<String>,
+        limit: RateLimit,
+    ) -> Self {
+        self.model_limits
+            .insert((provider.into(), model.into()), limit);
+        self
+    }
+
+    /// Bucket key and limit for a request
+    fn resolve(&self, provider: &str, model: &str) -> (String, RateLimit) {
+        match self
+            .model_limits
+            .get(&(provider.to_string(), model.to_string()))
+        {
+            Some(limit) => (format!("{provider}/{model}"), *limit),
+            None => (
+                provider.to_string(),
+                self.provider_limits
+                    .get(provider)
+                    .copied()
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+
+    /// Wait until a request to `provider`/`model` using about
+    /// `estimated_tokens` tokens may be sent.
+    ///
+    /// Dropping the returned future removes the request from the queue.
+    /// Returns [`LlmError::ProcessingError`] if [`cancel_pending`] is called
+    /// while waiting.
+    ///
+    /// [`cancel_pending`]: RateLimiter::cancel_pending
+    pub async fn acquire(
+        &self,
+        provider: &str,
+        model: &str,
+        estimated_tokens: u32,
+    ) -> Result<(), LlmError> {
+        let (key, limit) = self.resolve(provider, model);
+        let generation = self.generation.load(Ordering::SeqCst);
+
+        loop {
+            let cancelled = self.cancelled.notified();
+            tokio::pin!(cancelled);
+            cancelled.as_mut().enable();
+
+            if self.generation.load(Ordering::SeqCst) != generation {
+                return Err(LlmError::ProcessingError(format!(
+                    "Request to {provider} was cancelled while waiting for rate limit capacity"
+                )));
+            }
+
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap();
+                let bucket = buckets
+                    .entry(key.clone())
+                    .or_insert_with(|| Bucket::new(limit));
+                match bucket.try_take(estimated_tokens, Instant::now()) {
+                    Ok(()) => return Ok(()),
+                    Err(wait) => wait,
+                }
+            };
+
+            tracing::debug!("Rate limit for {key} reached, waiting {wait:?}");
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {}
+                _ = &mut cancelled => {}
+            }
+        }
+    }
+
+    /// Cancel every request currently waiting in [`acquire`](RateLimiter::acquire)
+    pub fn cancel_pending(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.cancelled.notify_waiters();
+    }
+
+    /// Adjust the budget of `provider`/`model` from response headers.
+    ///
+    /// Understands `retry-after`, `retry-after-ms`, the `x-ratelimit-*` headers
+    /// used by OpenAI-compatible providers and Anthropic's
+    /// `anthropic-ratelimit-*` headers.
+    pub fn update_from_headers(&self, provider: &str, model: &str, headers: &HeaderMap) {
+        let (key, limit) = self.resolve(provider, model);
+        let now = Instant::now();
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(key).or_insert_with(|| Bucket::new(limit));
+        bucket.refill(now);
+
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        let number = |name: &str| header(name).and_then(|v| v.trim().parse::<f64>().ok());
+
+        bucket.requests.observe(
+            number("x-ratelimit-remaining-requests")
+                .or_else(|| number("anthropic-ratelimit-requests-remaining")),
+            header("x-ratelimit-reset-requests")
+                .and_then(parse_reset_duration)
+                .or_else(|| {
+                    header("anthropic-ratelimit-requests-reset").and_then(parse_reset_time)
+                }),
+            now,
+        );
+        bucket.tokens.observe(
+            number("x-ratelimit-remaining-tokens")
+                .or_else(|| number("anthropic-ratelimit-tokens-remaining")),
+            header("x-ratelimit-reset-tokens")
+                .and_then(parse_reset_duration)
+                .or_else(|| header("anthropic-ratelimit-tokens-reset").and_then(parse_reset_time)),
+            now,
+        );
+
+        let retry_after = number("retry-after-ms")
+            .map(|ms| Duration::from_secs_f64(ms.max(0.0) / 1000.0))
+            .or_else(|| header("retry-after").and_then(parse_retry_after));
+        if let Some(retry_after) = retry_after {
+            let until = now + retry_after;
+            for allowance in [&mut bucket.requests, &mut bucket.tokens] {
+                allowance.blocked_until =
+                    Some(allowance.blocked_until.map_or(until, |u| u.max(until)));
+            }
+        }
+    }
+}
+
+/// Parse a `retry-after` value, given in seconds or as an HTTP date
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    if let Ok(seconds) = value.trim().parse::<f64>() {
+        return Some(Duration::from_secs_f64(seconds.max(0.0)));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value.trim()).ok()?;
+    (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+        .to_std()
+        .ok()
+}
+
+/// Parse an OpenAI-style reset duration such as `1s`, `6m0s` or `20ms`
+fn parse_reset_duration(value: &str) -> Option<Duration> {
+    let mut total = 0.0;
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let split = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let (number, tail) = rest.split_at(split);
+        let number: f64 = number.parse().ok()?;
+        let unit_len = tail
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        total += number
+            * match unit {
+                "ms" => 0.001,
+                "s" | "" => 1.0,
+                "m" => 60.0,
+                "h" => 3600.0,
+                _ => return None,
+            };
+        rest = tail;
+    }
+    Some(Duration::from_secs_f64(total))
+}
+
+/// Parse an Anthropic-style RFC 3339 reset time
+fn parse_reset_time(value: &str) -> Option<Duration> {
+    let reset = chrono::DateTime::parse_from_rfc3339(value.trim()).ok()?;
+    (reset.with_timezone(&chrono::Utc) - chrono::Utc::now())
+        .to_std()
+        .ok()
+        .or(Some(Duration::ZERO))
+}
+
+/// Roughly estimate the tokens a request body counts against a
+/// tokens-per-minute limit: the prompt size plus the requested output tokens.
+pub(crate) fn estimate_request_tokens(body: &impl serde::Serialize) -> u32 {
+    let body = serde_json::to_value(body).unwrap_or_default();
+    let prompt = body.to_string().len() / 4;
+    let output = [
+        &body["max_tokens"],
+        &body["max_completion_tokens"],
+        &body["max_output_tokens"],
+        &body["generationConfig"]["maxOutputTokens"],
+        &body["options"]["num_predict"],
+    ]
+    .into_iter()
+    .find_map(serde_json::Value::as_u64)
+    .unwrap_or(0);
+
+    u32::try_from(prompt as u64 + output).unwrap_or(u32::MAX)
+}
+
+/// Send a request through an optional rate limiter and cassette.
+///
+/// Waits for capacity before sending and updates the limiter from the
+/// response headers afterwards. With a cassette, the request is recorded or
+/// replayed instead of being sent directly.
+pub(crate) async fn send_request(
+    request: reqwest::RequestBuilder,
+    rate_limiter: Option<&RateLimiter>,
+    cassette: Option<&Cassette>,
+    provider: &str,
+    model: &str,
+    estimated_tokens: u32,
+) -> Result<reqwest::Response, LlmError> {
+    let send = async move {
+        match cassette {
+            Some(cassette) => cassette.send(request).await,
+            None => Ok(request.send().await?),
+        }
+    };
+
+    let Some(limiter) = rate_limiter else {
+        return send.await;
+    };
+
+    limiter.acquire(provider, model, estimated_tokens).await?;
+    let response = send.await?;
+    limiter.update_from_headers(provider, model, response.headers());
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use std::sync::Arc;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_requests_per_minute() {
+        let limiter =
+            RateLimiter::new().with_limit("openai", RateLimit::new().with_requests_per_minute(60));
+        let start = Instant::now();
+
+        for _ in 0..60 {
+            limiter.acquire("openai", "gpt-4o", 0).await.unwrap();
+        }
+        assert!(start.elapsed() < Duration::from_millis(1));
+
+        // The bucket refills at one request per second
+        limiter.acquire("openai", "gpt-4o", 0).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(999));
+
+        // Other providers are not limited
+        limiter.acquire("anthropic", "claude", 0).await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_tokens_per_minute_and_model_buckets() {
+        let limiter = RateLimiter::new()
+            .with_limit("openai", RateLimit::new().with_tokens_per_minute(6_000))
+            .with_model_limit("openai", "o3", RateLimit::new().with_tokens_per_minute(600));
+        let start = Instant::now();
+
+        limiter.acquire("openai", "o3", 600).await.unwrap();
+        limiter.acquire("openai", "gpt-4o", 6_000).await.unwrap();
+        assert!(start.elapsed() < Duration::from_millis(1));
+
+        limiter.acquire("openai", "o3", 300).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_secs(29));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_headers_adjust_budget() {
+        let limiter = RateLimiter::new();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-remaining-requests",
+            HeaderValue::from_static("0"),
+        );
+        headers.insert(
+            "x-ratelimit-reset-requests",
+            HeaderValue::from_static("1m30s"),
+        );
+        limiter.update_from_headers("groq", "llama", &headers);
+
+        let start = Instant::now();
+        limiter.acquire("groq", "llama", 0).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_secs(90));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("5"));
+        limiter.update_from_headers("groq", "llama", &headers);
+
+        let start = Instant::now();
+        limiter.acquire("groq", "llama", 0).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_secs(5));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cancel_pending() {
+        let limiter = Arc::new(
+            RateLimiter::new().with_limit("openai", RateLimit::new().with_requests_per_minute(1)),
+        );
+        limiter.acquire("openai", "gpt-4o", 0).await.unwrap();
+
+        let waiting = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire("openai", "gpt-4o", 0).await }
+        });
+        tokio::task::yield_now().await;
+        limiter.cancel_pending();
+
+        assert!(matches!(
+            waiting.await.unwrap(),
+            Err(LlmError::ProcessingError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_reset_duration() {
+        assert_eq!(parse_reset_duration("6m0s"), Some(Duration::from_secs(360)));
+        assert_eq!(
+            parse_reset_duration("20ms"),
+            Some(Duration::from_millis(20))
+        );
+        assert_eq!(
+            parse_reset_duration("7.5s"),
+            Some(Duration::from_millis(7500))
+        );
+        assert_eq!(parse_reset_duration("soon"), None);
+    }
+}
@@ -257,6 +257,8 @@ impl StandardRequestBuilder {
             http_config: None,
             web_search: None,
             stream,
+            prompt_cache: None,
+            output_schema: None,
         };
 
         // Validate the request
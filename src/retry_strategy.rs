@@ -475,8 +475,17 @@ impl FailoverManager {
 
     /// Get the next available provider
     pub fn get_next_provider(&mut self, providers: &[String]) -> Option<String> {
+        self.available_providers(providers).into_iter().next()
+    }
+
+    /// Get all providers that may currently be tried, in the order they should be tried.
+    ///
+    /// Healthy providers come first, then providers whose cooldown has elapsed.
+    /// Within each group providers are ordered by priority (descending), keeping
+    /// the given order for equal priorities. Providers still cooling down are omitted.
+    pub fn available_providers(&mut self, providers: &[String]) -> Vec<String> {
         if !self.config.auto_failover {
-            return providers.first().cloned();
+            return providers.first().cloned().into_iter().collect();
         }
 
         // Sort providers by priority and health
@@ -508,7 +517,10 @@ impl FailoverManager {
                 .then_with(|| b.1.cmp(&a.1)) // Then by priority
         });
 
-        available_providers.first().map(|(name, _, _)| name.clone())
+        available_providers
+            .into_iter()
+            .map(|(name, _, _)| name)
+            .collect()
     }
 
     /// Record a provider failure
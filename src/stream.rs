@@ -67,6 +67,8 @@ pub struct StreamProcessor {
     tool_call_order: Vec<String>, // Track order of tool calls for consistent output
     thinking_buffer: String,
     current_usage: Option<Usage>,
+    citations: Vec<Citation>,
+    sources: Vec<SearchSource>,
     config: StreamProcessorConfig,
 }
 
@@ -83,6 +85,8 @@ impl StreamProcessor {
             tool_call_order: Vec::new(),
             thinking_buffer: String::new(),
             current_usage: None,
+            citations: Vec::new(),
+            sources: Vec::new(),
             config,
         }
     }
@@ -239,6 +243,24 @@ impl StreamProcessor {
                     usage: self.current_usage.clone().unwrap(),
                 }
             }
+            ChatStreamEvent::CitationDelta { citation } => {
+                if let Some(url) = &citation.url {
+                    SearchSource::merge(
+                        &mut self.sources,
+                        [SearchSource {
+                            url: url.clone(),
+                            title: citation.title.clone(),
+                            snippet: None,
+                        }],
+                    );
+                }
+                self.citations.push(citation.clone());
+                ProcessedEvent::CitationUpdate { citation }
+            }
+            ChatStreamEvent::SourceDelta { source } => {
+                SearchSource::merge(&mut self.sources, [source.clone()]);
+                ProcessedEvent::SourceUpdate { source }
+            }
             ChatStreamEvent::StreamStart { metadata } => ProcessedEvent::StreamStart { metadata },
             ChatStreamEvent::StreamEnd { response } => ProcessedEvent::StreamEnd { response },
 
@@ -253,6 +275,43 @@ impl StreamProcessor {
         self.build_final_response_with_finish_reason(None)
     }
 
+    /// Build the final response for a stream that ended with `end`
+    ///
+    /// Providers' `StreamEnd` responses usually carry no content, tool calls or
+    /// usage, so the response is built from the accumulated deltas. Only the id,
+    /// model and finish reason are taken from `end`, plus its usage when no
+    /// `UsageUpdate` was received.
+    pub fn build_final_response_with_end(&self, end: Option<&ChatResponse>) -> ChatResponse {
+        let Some(end) = end else {
+            return self.build_final_response();
+        };
+
+        let mut response = self.build_final_response_with_finish_reason(end.finish_reason.clone());
+        response.id = end.id.clone();
+        response.model = end.model.clone();
+        if response.usage.is_none() {
+            response.usage = end.usage.clone();
+        }
+        response
+    }
+
+    /// The complete response of a stream that ended with `end`
+    ///
+    /// Aggregating streams (such as the tool runner) end with the complete
+    /// response, which is kept as is. Provider streams end with an empty one,
+    /// so the response is built with [`build_final_response_with_end`](Self::build_final_response_with_end).
+    pub fn complete_response(&self, end: ChatResponse) -> ChatResponse {
+        if end.has_tool_calls() || end.content_text().is_some_and(|text| !text.is_empty()) {
+            return end;
+        }
+        self.build_final_response_with_end(Some(&end))
+    }
+
+    /// Usage accumulated from `UsageUpdate` events
+    pub const fn usage(&self) -> Option<&Usage> {
+        self.current_usage.as_ref()
+    }
+
     /// Build the final response with finish reason
     pub fn build_final_response_with_finish_reason(
         &self,
@@ -294,6 +353,8 @@ impl StreamProcessor {
             finish_reason,
             tool_calls,
             thinking,
+            citations: self.citations.clone(),
+            sources: self.sources.clone(),
             metadata,
         }
     }
@@ -319,6 +380,12 @@ pub enum ProcessedEvent {
     UsageUpdate {
         usage: Usage,
     },
+    CitationUpdate {
+        citation: Citation,
+    },
+    SourceUpdate {
+        source: SearchSource,
+    },
     StreamStart {
         metadata: ResponseMetadata,
     },
@@ -395,7 +462,7 @@ pub async fn collect_stream_response(mut stream: ChatStream) -> Result<ChatRespo
                 _metadata = Some(meta);
             }
             ChatStreamEvent::StreamEnd { response } => {
-                return Ok(response);
+                return Ok(processor.complete_response(response));
             }
             ChatStreamEvent::Error { error } => {
                 return Err(LlmError::InternalError(error));
@@ -435,6 +502,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_stream_processor_collects_citations_and_sources() {
+        let mut processor = StreamProcessor::new();
+        processor.process_event(ChatStreamEvent::ContentDelta {
+            delta: "Rust 1.0 shipped in 2015.".to_string(),
+            index: None,
+        });
+        processor.process_event(ChatStreamEvent::SourceDelta {
+            source: SearchSource::new("https://blog.rust-lang.org"),
+        });
+        processor.process_event(ChatStreamEvent::CitationDelta {
+            citation: Citation {
+                url: Some("https://blog.rust-lang.org".to_string()),
+                start_index: Some(0),
+                end_index: Some(24),
+                ..Default::default()
+            },
+        });
+
+        let response = processor.build_final_response();
+        assert_eq!(response.citations.len(), 1);
+        assert_eq!(response.sources.len(), 1);
+    }
+
     // Test that stream types are Send + Sync for multi-threading
     #[test]
     fn test_stream_types_are_send_sync() {
@@ -474,6 +565,8 @@ mod tests {
                     finish_reason: Some(crate::types::FinishReason::Stop),
                     tool_calls: None,
                     thinking: None,
+                    citations: Vec::new(),
+                    sources: Vec::new(),
                     metadata: std::collections::HashMap::new(),
                 },
             }),
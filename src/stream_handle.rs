@@ -0,0 +1,538 @@
+//! Cancellable and Resumable Chat Streams
+//!
+//! [`ChatStreamHandle`] wraps a [`ChatStream`] and accumulates its events as
+//! they are consumed. Cancelling the handle stops the stream and returns the
+//! partial response and usage received so far, instead of losing them as when
+//! the stream is dropped.
+//!
+//! With a [`StreamResumer`] attached, a stream interrupted by a transport error
+//! is reopened and continues transparently:
+//! - OpenAI Responses streams run in background mode and reconnect after the
+//!   last received event (`starting_after`, `Last-Event-ID`)
+//! - Other providers are asked to continue the partial answer with a
+//!   continuation prompt ([`ContinuationResumer`])
+//!
+//! # Example
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use siumai::prelude::*;
+//! use siumai::stream_handle::ChatStreamHandle;
+//! use std::sync::Arc;
+//!
+//! # async fn example(client: Siumai) -> Result<(), LlmError> {
+//! let client: Arc<dyn ChatCapability> = Arc::new(client);
+//! let mut handle =
+//!     ChatStreamHandle::resumable(client, vec![user!("Write a long story")], None).await?;
+//!
+//! let canceller = handle.canceller();
+//! tokio::spawn(async move {
+//!     tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+//!     canceller.cancel();
+//! });
+//!
+//! while let Some(event) = handle.next().await {
+//!     if let ChatStreamEvent::ContentDelta { delta, .. } = event? {
+//!         print!("{delta}");
+//!     }
+//! }
+//!
+//! let response = handle.cancel();
+//! println!("\nUsage: {:?}", response.usage);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, ready};
+
+use async_trait::async_trait;
+use futures::Stream;
+use futures::task::AtomicWaker;
+
+use crate::error::LlmError;
+use crate::stream::{ChatStream, StreamProcessor};
+use crate::traits::ChatCapability;
+use crate::types::*;
+
+/// Default number of times an interrupted stream is resumed
+const DEFAULT_MAX_RESUMES: u32 = 3;
+
+/// Default prompt asking the model to continue an interrupted answer
+const DEFAULT_CONTINUATION_PROMPT: &str = "Your previous answer was cut off. Continue exactly where it stopped, without repeating any of it.";
+
+/// Finish reason of a response cut short by [`ChatStreamHandle::cancel`]
+pub const CANCELLED: &str = "cancelled";
+
+type ResumeFuture = Pin<Box<dyn Future<Output = Result<ChatStream, LlmError>> + Send>>;
+
+/// State of an interrupted stream, passed to a [`StreamResumer`]
+#[derive(Debug, Clone)]
+pub struct ResumeContext {
+    /// Response accumulated before the interruption
+    pub partial: ChatResponse,
+    /// Error that interrupted the stream
+    pub error: LlmError,
+    /// Resume attempt, starting at 1
+    pub attempt: u32,
+}
+
+/// Reopens an interrupted chat stream.
+///
+/// The returned stream should only yield the events that follow the
+/// interruption.
+#[async_trait]
+pub trait StreamResumer: Send + Sync {
+    /// Open a stream continuing the interrupted one
+    async fn resume(&self, context: &ResumeContext) -> Result<ChatStream, LlmError>;
+}
+
+/// Resumes any chat stream by asking the model to continue its partial answer.
+///
+/// A stream interrupted before any content is simply retried; one interrupted
+/// during a tool call cannot be continued.
+pub struct ContinuationResumer {
+    client: Arc<dyn ChatCapability>,
+    messages: Vec<ChatMessage>,
+    tools: Option<Vec<Tool>>,
+    prompt: String,
+}
+
+impl ContinuationResumer {
+    /// Create a resumer for a stream started with these messages and tools
+    pub fn new(
+        client: Arc<dyn ChatCapability>,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+    ) -> Self {
+        Self {
+            client,
+            messages,
+            tools,
+            prompt: DEFAULT_CONTINUATION_PROMPT.to_string(),
+        }
+    }
+
+    /// Set the prompt asking the model to continue
+    pub fn with_prompt<S: Into<String>>(mut self, prompt: S) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+}
+
+#[async_trait]
+impl StreamResumer for ContinuationResumer {
+    async fn resume(&self, context: &ResumeContext) -> Result<ChatStream, LlmError> {
+        if context.partial.has_tool_calls() {
+            return Err(LlmError::StreamError(
+                "Cannot continue a response interrupted during a tool call".to_string(),
+            ));
+        }
+
+        let mut messages = self.messages.clone();
+        let partial = context.partial.content.all_text();
+        if !partial.is_empty() {
+            messages.push(ChatMessage::assistant(partial).build());
+            messages.push(ChatMessage::user(self.prompt.clone()).build());
+        }
+        self.client.chat_stream(messages, self.tools.clone()).await
+    }
+}
+
+#[derive(Debug, Default)]
+struct CancelState {
+    cancelled: AtomicBool,
+    waker: AtomicWaker,
+}
+
+/// Cancels a [`ChatStreamHandle`] from another task
+#[derive(Debug, Clone, Default)]
+pub struct StreamCanceller {
+    state: Arc<CancelState>,
+}
+
+impl StreamCanceller {
+    /// Stop the stream; it ends at its next poll
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::SeqCst);
+        self.state.waker.wake();
+    }
+
+    /// Whether the stream was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// A chat stream that can be cancelled and resumed.
+///
+/// Yields the same events as the wrapped stream. After a resume, duplicate
+/// `StreamStart` events are skipped and the final `StreamEnd` response carries
+/// the content accumulated across all connections.
+pub struct ChatStreamHandle {
+    stream: Option<ChatStream>,
+    // Wrapped in a mutex only so the handle stays `Sync` like `ChatStream`
+    pending: Option<Mutex<ResumeFuture>>,
+    interruption: Option<LlmError>,
+    resumer: Option<Arc<dyn StreamResumer>>,
+    max_resumes: u32,
+    resumes: u32,
+    processor: StreamProcessor,
+    metadata: Option<ResponseMetadata>,
+    completed: Option<ChatResponse>,
+    canceller: StreamCanceller,
+}
+
+impl ChatStreamHandle {
+    /// Wrap a chat stream
+    pub fn new(stream: ChatStream) -> Self {
+        Self {
+            stream: Some(stream),
+            pending: None,
+            interruption: None,
+            resumer: None,
+            max_resumes: DEFAULT_MAX_RESUMES,
+            resumes: 0,
+            processor: StreamProcessor::new(),
+            metadata: None,
+            completed: None,
+            canceller: StreamCanceller::default(),
+        }
+    }
+
+    /// Start a chat stream that resumes with a continuation prompt when interrupted
+    pub async fn resumable(
+        client: Arc<dyn ChatCapability>,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+    ) -> Result<Self, LlmError> {
+        let stream = client.chat_stream(messages.clone(), tools.clone()).await?;
+        let resumer = ContinuationResumer::new(client, messages, tools);
+        Ok(Self::new(stream).with_resumer(Arc::new(resumer)))
+    }
+
+    /// Resume the stream with this resumer when it is interrupted
+    pub fn with_resumer(mut self, resumer: Arc<dyn StreamResumer>) -> Self {
+        self.resumer = Some(resumer);
+        self
+    }
+
+    /// Set how many times the stream may be resumed (default: 3)
+    pub const fn with_max_resumes(mut self, max_resumes: u32) -> Self {
+        self.max_resumes = max_resumes;
+        self
+    }
+
+    /// Canceller usable from another task
+    pub fn canceller(&self) -> StreamCanceller {
+        self.canceller.clone()
+    }
+
+    /// Whether the stream was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.canceller.is_cancelled()
+    }
+
+    /// Number of times the stream was resumed
+    pub const fn resumes(&self) -> u32 {
+        self.resumes
+    }
+
+    /// Response accumulated so far, or the final response once the stream ended
+    pub fn partial_response(&self) -> ChatResponse {
+        if let Some(response) = &self.completed {
+            return response.clone();
+        }
+
+        let finish_reason = self
+            .is_cancelled()
+            .then(|| FinishReason::Other(CANCELLED.to_string()));
+        let mut response = self
+            .processor
+            .build_final_response_with_finish_reason(finish_reason);
+        if let Some(metadata) = &self.metadata {
+            response.id = metadata.id.clone();
+            response.model = metadata.model.clone();
+        }
+        response
+    }
+
+    /// Stop the stream, closing the connection, and return the response
+    /// accumulated so far with its usage.
+    ///
+    /// Returns the final response if the stream had already ended.
+    pub fn cancel(mut self) -> ChatResponse {
+        self.canceller.cancel();
+        self.stream = None;
+        self.pending = None;
+        self.partial_response()
+    }
+
+    /// Consume the stream and return the final response
+    pub async fn into_response(mut self) -> Result<ChatResponse, LlmError> {
+        use futures::StreamExt;
+
+        while let Some(event) = self.next().await {
+            if let ChatStreamEvent::Error { error } = event? {
+                return Err(LlmError::StreamError(error));
+            }
+        }
+        Ok(self.partial_response())
+    }
+
+    /// Box the handle as a plain chat stream
+    pub fn into_stream(self) -> ChatStream {
+        Box::pin(self)
+    }
+
+    fn can_resume(&self, error: &LlmError) -> bool {
+        let interrupted = matches!(
+            error,
+            LlmError::StreamError(_)
+                | LlmError::HttpError(_)
+                | LlmError::ConnectionError(_)
+                | LlmError::TimeoutError(_)
+        ) || error.is_retryable();
+
+        interrupted && self.resumer.is_some() && self.resumes < self.max_resumes
+    }
+
+    fn start_resume(&mut self, error: LlmError) {
+        let Some(resumer) = self.resumer.clone() else {
+            return;
+        };
+        self.resumes += 1;
+        self.stream = None;
+
+        let context = ResumeContext {
+            partial: self.partial_response(),
+            error: error.clone(),
+            attempt: self.resumes,
+        };
+        tracing::debug!(
+            "Resuming interrupted chat stream (attempt {}): {}",
+            context.attempt,
+            error
+        );
+        self.interruption = Some(error);
+        self.pending = Some(Mutex::new(Box::pin(async move {
+            resumer.resume(&context).await
+        })));
+    }
+
+    /// Record an event, returning it unless it should be hidden from the caller
+    fn observe(&mut self, event: ChatStreamEvent) -> Option<ChatStreamEvent> {
+        match event {
+            ChatStreamEvent::StreamStart { .. } if self.resumes > 0 && self.metadata.is_some() => {
+                None
+            }
+            ChatStreamEvent::StreamStart { metadata } => {
+                self.metadata = Some(metadata.clone());
+                Some(ChatStreamEvent::StreamStart { metadata })
+            }
+            ChatStreamEvent::StreamEnd { response } => {
+                // Provider end responses are empty, so use the accumulated deltas.
+                // Some providers end twice; the first end has the real id and reason.
+                let mut response = self
+                    .processor
+                    .build_final_response_with_end(Some(&response));
+                if let Some(previous) = self.completed.take() {
+                    response.id = previous.id.or(response.id);
+                    response.model = previous.model.or(response.model);
+                    response.finish_reason = previous.finish_reason.or(response.finish_reason);
+                }
+                if let Some(metadata) = &self.metadata {
+                    response.id = response.id.or_else(|| metadata.id.clone());
+                    response.model = response.model.or_else(|| metadata.model.clone());
+                }
+                self.completed = Some(response.clone());
+                Some(ChatStreamEvent::StreamEnd { response })
+            }
+            event => {
+                self.processor.process_event(event.clone());
+                Some(event)
+            }
+        }
+    }
+}
+
+impl Stream for ChatStreamHandle {
+    type Item = Result<ChatStreamEvent, LlmError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        this.canceller.state.waker.register(cx.waker());
+
+        loop {
+            if this.canceller.is_cancelled() {
+                this.stream = None;
+                this.pending = None;
+                return Poll::Ready(None);
+            }
+
+            if let Some(pending) = this.pending.as_mut() {
+                let future = pending
+                    .get_mut()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner);
+                let result = ready!(future.as_mut().poll(cx));
+                this.pending = None;
+                let interruption = this.interruption.take();
+                match result {
+                    Ok(stream) => this.stream = Some(stream),
+                    Err(e) => {
+                        tracing::warn!("Failed to resume chat stream: {e}");
+                        return Poll::Ready(Some(Err(interruption.unwrap_or(e))));
+                    }
+                }
+            }
+
+            let Some(stream) = this.stream.as_mut() else {
+                return Poll::Ready(None);
+            };
+
+            match ready!(stream.as_mut().poll_next(cx)) {
+                Some(Ok(event)) => {
+                    if let Some(event) = this.observe(event) {
+                        return Poll::Ready(Some(Ok(event)));
+                    }
+                }
+                Some(Err(error)) if this.can_resume(&error) => this.start_resume(error),
+                Some(Err(error)) => return Poll::Ready(Some(Err(error))),
+                None => {
+                    this.stream = None;
+                    return Poll::Ready(None);
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for ChatStreamHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChatStreamHandle")
+            .field("active", &(self.stream.is_some() || self.pending.is_some()))
+            .field("resumable", &self.resumer.is_some())
+            .field("resumes", &self.resumes)
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{MockClient, MockReply};
+    use futures::StreamExt;
+
+    fn delta(text: &str) -> Result<ChatStreamEvent, LlmError> {
+        Ok(ChatStreamEvent::ContentDelta {
+            delta: text.to_string(),
+            index: None,
+        })
+    }
+
+    fn usage() -> Result<ChatStreamEvent, LlmError> {
+        Ok(ChatStreamEvent::UsageUpdate {
+            usage: Usage::new(10, 2),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_cancel_returns_partial_response_and_usage() {
+        let events = vec![delta("Hello"), usage(), delta(" wor"), delta("ld")];
+        let mut handle = ChatStreamHandle::new(Box::pin(futures::stream::iter(events)));
+
+        handle.next().await.unwrap().unwrap();
+        handle.next().await.unwrap().unwrap();
+        handle.next().await.unwrap().unwrap();
+
+        let response = handle.cancel();
+        assert_eq!(response.content_text(), Some("Hello wor"));
+        assert_eq!(response.usage.unwrap().total_tokens, 12);
+        assert_eq!(
+            response.finish_reason,
+            Some(FinishReason::Other(CANCELLED.to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_completed_response_built_from_deltas() {
+        let tool_call = Ok(ChatStreamEvent::ToolCallDelta {
+            id: "call_1".to_string(),
+            function_name: Some("lookup".to_string()),
+            arguments_delta: Some("{}".to_string()),
+            index: Some(0),
+        });
+        let end = |reason: FinishReason| {
+            let mut response = ChatResponse::new(MessageContent::Text(String::new()));
+            response.finish_reason = Some(reason);
+            Ok(ChatStreamEvent::StreamEnd { response })
+        };
+        let events = vec![
+            delta("Checking"),
+            tool_call,
+            usage(),
+            end(FinishReason::ToolCalls),
+            end(FinishReason::Stop),
+        ];
+        let mut handle = ChatStreamHandle::new(Box::pin(futures::stream::iter(events)));
+        while handle.next().await.is_some() {}
+
+        let response = handle.partial_response();
+        assert_eq!(response.content_text(), Some("Checking"));
+        assert_eq!(response.tool_calls.unwrap()[0].id, "call_1");
+        assert_eq!(response.usage.unwrap().total_tokens, 12);
+        assert_eq!(response.finish_reason, Some(FinishReason::ToolCalls));
+    }
+
+    #[tokio::test]
+    async fn test_canceller_ends_stream() {
+        let events = vec![delta("a"), delta("b")];
+        let mut handle = ChatStreamHandle::new(Box::pin(futures::stream::iter(events)));
+        let canceller = handle.canceller();
+
+        handle.next().await.unwrap().unwrap();
+        canceller.cancel();
+        assert!(handle.next().await.is_none());
+        assert_eq!(handle.partial_response().content_text(), Some("a"));
+    }
+
+    #[tokio::test]
+    async fn test_resume_with_continuation_prompt() {
+        let interrupted: ChatStream = Box::pin(futures::stream::iter(vec![
+            delta("The answer "),
+            Err(LlmError::StreamError("connection reset".to_string())),
+        ]));
+        let mock = MockClient::new().with_reply(MockReply::text_stream(["is 42."]));
+        let resumer = ContinuationResumer::new(
+            Arc::new(mock.clone()),
+            vec![ChatMessage::user("What is the answer?").build()],
+            None,
+        );
+        let handle = ChatStreamHandle::new(interrupted).with_resumer(Arc::new(resumer));
+
+        let response = handle.into_response().await.unwrap();
+        assert_eq!(response.content_text(), Some("The answer is 42."));
+
+        // The continuation request carries the partial answer
+        let request = mock.last_request().unwrap();
+        assert_eq!(request.messages.len(), 3);
+        assert_eq!(request.messages[1].content.all_text(), "The answer ");
+    }
+
+    #[tokio::test]
+    async fn test_interruption_without_resumer_is_returned() {
+        let events = vec![
+            delta("partial"),
+            Err(LlmError::StreamError("connection reset".to_string())),
+        ];
+        let handle = ChatStreamHandle::new(Box::pin(futures::stream::iter(events)));
+
+        assert!(matches!(
+            handle.into_response().await,
+            Err(LlmError::StreamError(_))
+        ));
+    }
+}
@@ -0,0 +1,782 @@
+//! Testing Utilities
+//!
+//! [`MockClient`] is an in-memory [`LlmClient`] for unit-testing code built on
+//! this library without HTTP. Chat calls are answered from a script of
+//! [`MockReply`] values (responses, tool calls, stream event sequences and
+//! errors, each with an optional latency), and every call is recorded as a
+//! [`ChatRequest`] for assertions.
+//!
+//! Enabled with the `testing` cargo feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! siumai = { version = "*", features = ["testing"] }
+//! ```
+//!
+//! # Example
+//! ```rust
+//! use siumai::prelude::*;
+//! use siumai::testing::{MockClient, MockReply};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), LlmError> {
+//! let mock = MockClient::new()
+//!     .with_reply(MockReply::tool_call("get_weather", r#"{"city":"Paris"}"#))
+//!     .with_reply(MockReply::text("It is sunny in Paris"));
+//!
+//! let client = Siumai::new(Box::new(mock.clone()));
+//! let first = client.chat(vec![user!("Weather in Paris?")]).await?;
+//! assert!(first.has_tool_calls());
+//!
+//! let second = client.chat(vec![user!("Thanks")]).await?;
+//! assert_eq!(second.content_text(), Some("It is sunny in Paris"));
+//! assert_eq!(mock.requests().len(), 2);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::client::LlmClient;
+use crate::error::LlmError;
+use crate::stream::{ChatStream, StreamProcessor, collect_stream_response};
+use crate::traits::*;
+use crate::types::*;
+
+/// Default model reported by the mock
+const DEFAULT_MODEL: &str = "mock-model";
+
+/// What a scripted reply produces
+#[derive(Debug, Clone)]
+enum MockOutput {
+    Response(ChatResponse),
+    Stream(Vec<ChatStreamEvent>),
+    Error(LlmError),
+}
+
+/// A scripted answer to one chat call.
+///
+/// Responses are streamed as content, thinking, tool call and usage events
+/// followed by `StreamEnd`; event sequences are collected into a response when
+/// requested through `chat`.
+///
+/// Like real providers, streams end with an empty response carrying only the
+/// id, model and finish reason, and report usage through `UsageUpdate`. Use
+/// [`with_full_stream_end`](Self::with_full_stream_end) to end with the
+/// complete response instead.
+#[derive(Debug, Clone)]
+pub struct MockReply {
+    output: MockOutput,
+    latency: Option<Duration>,
+    full_stream_end: bool,
+}
+
+impl MockReply {
+    /// Reply with a full response
+    pub const fn response(response: ChatResponse) -> Self {
+        Self {
+            output: MockOutput::Response(response),
+            latency: None,
+            full_stream_end: false,
+        }
+    }
+
+    /// Reply with a text response
+    pub fn text<S: Into<String>>(text: S) -> Self {
+        let mut response = ChatResponse::new(MessageContent::Text(text.into()));
+        response.finish_reason = Some(FinishReason::Stop);
+        Self::response(response)
+    }
+
+    /// Reply with a single tool call
+    pub fn tool_call<N: Into<String>, A: Into<String>>(name: N, arguments: A) -> Self {
+        Self::tool_calls(vec![(name.into(), arguments.into())])
+    }
+
+    /// Reply with several tool calls, given as (function name, JSON arguments)
+    pub fn tool_calls(calls: Vec<(String, String)>) -> Self {
+        let mut response = ChatResponse::new(MessageContent::Text(String::new()));
+        response.finish_reason = Some(FinishReason::ToolCalls);
+        response.tool_calls = Some(
+            calls
+                .into_iter()
+                .enumerate()
+                .map(|(i, (name, arguments))| ToolCall {
+                    id: format!("call_mock_{i}"),
+                    r#type: "function".to_string(),
+                    function: Some(FunctionCall { name, arguments }),
+                })
+                .collect(),
+        );
+        Self::response(response)
+    }
+
+    /// Reply with a stream of events, sent as-is unless a full stream end is requested
+    pub const fn stream(events: Vec<ChatStreamEvent>) -> Self {
+        Self {
+            output: MockOutput::Stream(events),
+            latency: None,
+            full_stream_end: false,
+        }
+    }
+
+    /// Reply with a text stream made of the given deltas
+    pub fn text_stream<S: Into<String>>(deltas: impl IntoIterator<Item = S>) -> Self {
+        let mut events: Vec<ChatStreamEvent> = deltas
+            .into_iter()
+            .map(|delta| ChatStreamEvent::ContentDelta {
+                delta: delta.into(),
+                index: None,
+            })
+            .collect();
+
+        let mut response = ChatResponse::new(MessageContent::Text(String::new()));
+        response.finish_reason = Some(FinishReason::Stop);
+        events.push(ChatStreamEvent::StreamEnd { response });
+        Self::stream(events)
+    }
+
+    /// Fail the call with an error
+    pub const fn error(error: LlmError) -> Self {
+        Self {
+            output: MockOutput::Error(error),
+            latency: None,
+            full_stream_end: false,
+        }
+    }
+
+    /// Wait before answering
+    pub const fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// End streams with the complete response, as aggregating streams do
+    ///
+    /// The `StreamEnd` response then holds the accumulated content, tool calls
+    /// and usage, for testing consumers that must handle both kinds of end.
+    pub const fn with_full_stream_end(mut self) -> Self {
+        self.full_stream_end = true;
+        self
+    }
+}
+
+/// Shared state of a mock and its clones
+#[derive(Debug, Default)]
+struct MockState {
+    replies: VecDeque<MockReply>,
+    requests: Vec<ChatRequest>,
+}
+
+/// In-memory LLM client for unit tests.
+///
+/// Clones share the reply script and the recorded requests, so a clone can be
+/// handed to the code under test (for example boxed into [`Siumai`]) while the
+/// original is kept for assertions.
+///
+/// Besides chat, the mock implements embeddings (deterministic vectors derived
+/// from the input text), audio, image generation and model listing with fixed
+/// results configurable through its builder methods.
+///
+/// [`Siumai`]: crate::provider::Siumai
+#[derive(Debug, Clone)]
+pub struct MockClient {
+    model: String,
+    latency: Option<Duration>,
+    embedding_dimension: usize,
+    speech: Vec<u8>,
+    transcription: String,
+    images: Vec<GeneratedImage>,
+    models: Vec<ModelInfo>,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockClient {
+    /// Create a mock with an empty reply script
+    pub fn new() -> Self {
+        Self {
+            model: DEFAULT_MODEL.to_string(),
+            latency: None,
+            embedding_dimension: 8,
+            speech: Vec::new(),
+            transcription: String::new(),
+            images: Vec::new(),
+            models: Vec::new(),
+            state: Arc::new(Mutex::new(MockState::default())),
+        }
+    }
+
+    /// Set the model reported in requests and responses
+    pub fn with_model<S: Into<String>>(mut self, model: S) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Queue a reply; replies are consumed in order, one per chat call
+    pub fn with_reply(self, reply: MockReply) -> Self {
+        self.push_reply(reply);
+        self
+    }
+
+    /// Queue several replies
+    pub fn with_replies(self, replies: impl IntoIterator<Item = MockReply>) -> Self {
+        for reply in replies {
+            self.push_reply(reply);
+        }
+        self
+    }
+
+    /// Queue a text reply
+    pub fn with_text<S: Into<String>>(self, text: S) -> Self {
+        self.with_reply(MockReply::text(text))
+    }
+
+    /// Queue an error reply
+    pub fn with_error(self, error: LlmError) -> Self {
+        self.with_reply(MockReply::error(error))
+    }
+
+    /// Wait before answering every call
+    pub const fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Set the dimension of the generated embeddings
+    pub const fn with_embedding_dimension(mut self, dimension: usize) -> Self {
+        self.embedding_dimension = dimension;
+        self
+    }
+
+    /// Set the audio returned by text-to-speech
+    pub fn with_speech(mut self, audio: Vec<u8>) -> Self {
+        self.speech = audio;
+        self
+    }
+
+    /// Set the text returned by speech-to-text
+    pub fn with_transcription<S: Into<String>>(mut self, text: S) -> Self {
+        self.transcription = text.into();
+        self
+    }
+
+    /// Set the images returned by image generation
+    pub fn with_images(mut self, images: Vec<GeneratedImage>) -> Self {
+        self.images = images;
+        self
+    }
+
+    /// Set the models returned by model listing
+    pub fn with_models(mut self, models: Vec<ModelInfo>) -> Self {
+        self.models = models;
+        self
+    }
+
+    /// Queue a reply after construction
+    pub fn push_reply(&self, reply: MockReply) {
+        self.lock().replies.push_back(reply);
+    }
+
+    /// Chat requests received so far, in order
+    pub fn requests(&self) -> Vec<ChatRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// The most recent chat request
+    pub fn last_request(&self) -> Option<ChatRequest> {
+        self.lock().requests.last().cloned()
+    }
+
+    /// Number of scripted replies not consumed yet
+    pub fn remaining_replies(&self) -> usize {
+        self.lock().replies.len()
+    }
+
+    /// Forget the recorded requests
+    pub fn clear_requests(&self) {
+        self.lock().requests.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Record a request and take the next reply, waiting for its latency
+    ///
+    /// Returns the reply's output and whether streams end with the full response.
+    async fn next_reply(&self, request: ChatRequest) -> Result<(MockOutput, bool), LlmError> {
+        let reply = {
+            let mut state = self.lock();
+            state.requests.push(request);
+            state.replies.pop_front()
+        };
+
+        let reply = reply.ok_or_else(|| {
+            LlmError::InternalError("MockClient has no scripted reply left".to_string())
+        })?;
+
+        if let Some(latency) = reply.latency.or(self.latency) {
+            tokio::time::sleep(latency).await;
+        }
+
+        match reply.output {
+            MockOutput::Error(error) => Err(error),
+            output => Ok((output, reply.full_stream_end)),
+        }
+    }
+
+    /// Resolve the model and streaming flag of a received request
+    fn resolve_request(&self, mut request: ChatRequest, stream: bool) -> ChatRequest {
+        if request.common_params.model.is_empty() {
+            request.common_params.model = self.model.clone();
+        }
+        request.stream = stream;
+        request
+    }
+
+    /// Fill in the requested model on a scripted response
+    fn finish_response(mut response: ChatResponse, model: &str) -> ChatResponse {
+        if response.model.is_none() {
+            response.model = Some(model.to_string());
+        }
+        response
+    }
+
+    /// Deterministic embedding of a text
+    fn embedding_for(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0_f32; self.embedding_dimension];
+        if vector.is_empty() {
+            return vector;
+        }
+        for (i, byte) in text.bytes().enumerate() {
+            vector[i % self.embedding_dimension] += f32::from(byte) / 255.0;
+        }
+        let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|v| *v /= norm);
+        }
+        vector
+    }
+}
+
+impl Default for MockClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Stream events equivalent to a complete response
+fn response_events(response: ChatResponse, full_end: bool) -> Vec<ChatStreamEvent> {
+    let mut events = Vec::new();
+    if let Some(thinking) = &response.thinking {
+        events.push(ChatStreamEvent::ThinkingDelta {
+            delta: thinking.clone(),
+        });
+    }
+    if let Some(text) = response.content_text()
+        && !text.is_empty()
+    {
+        events.push(ChatStreamEvent::ContentDelta {
+            delta: text.to_string(),
+            index: None,
+        });
+    }
+    for call in response.tool_calls.iter().flatten() {
+        events.push(ChatStreamEvent::ToolCallDelta {
+            id: call.id.clone(),
+            function_name: call.function.as_ref().map(|f| f.name.clone()),
+            arguments_delta: call.function.as_ref().map(|f| f.arguments.clone()),
+            index: None,
+        });
+    }
+    if let Some(usage) = &response.usage {
+        events.push(ChatStreamEvent::UsageUpdate {
+            usage: usage.clone(),
+        });
+    }
+    let response = if full_end {
+        response
+    } else {
+        let mut end = ChatResponse::new(MessageContent::Text(String::new()));
+        end.id = response.id;
+        end.model = response.model;
+        end.finish_reason = response.finish_reason;
+        end
+    };
+    events.push(ChatStreamEvent::StreamEnd { response });
+    events
+}
+
+/// Replace the responses of `StreamEnd` events with the accumulated response
+fn with_full_end(events: Vec<ChatStreamEvent>) -> Vec<ChatStreamEvent> {
+    let mut processor = StreamProcessor::new();
+    events
+        .into_iter()
+        .map(|event| match event {
+            ChatStreamEvent::StreamEnd { response } => ChatStreamEvent::StreamEnd {
+                response: processor.complete_response(response),
+            },
+            event => {
+                processor.process_event(event.clone());
+                event
+            }
+        })
+        .collect()
+}
+
+#[async_trait]
+impl ChatCapability for MockClient {
+    fn default_model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+    ) -> Result<ChatResponse, LlmError> {
+        self.chat_with_request(ChatRequest {
+            messages,
+            tools,
+            ..Default::default()
+        })
+        .await
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+    ) -> Result<ChatStream, LlmError> {
+        self.chat_stream_with_request(ChatRequest {
+            messages,
+            tools,
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Record the request with its parameters and reply with the next script entry
+    ///
+    /// The requested model is reported on scripted responses that name none.
+    async fn chat_with_request(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        let request = self.resolve_request(request, false);
+        let model = request.common_params.model.clone();
+        let response = match self.next_reply(request).await?.0 {
+            MockOutput::Response(response) => response,
+            MockOutput::Stream(events) => {
+                let stream: ChatStream =
+                    Box::pin(futures::stream::iter(events.into_iter().map(Ok)));
+                collect_stream_response(stream).await?
+            }
+            MockOutput::Error(error) => return Err(error),
+        };
+        Ok(Self::finish_response(response, &model))
+    }
+
+    async fn chat_stream_with_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
+        let request = self.resolve_request(request, true);
+        let model = request.common_params.model.clone();
+        let (output, full_end) = self.next_reply(request).await?;
+        let events = match output {
+            MockOutput::Response(response) => {
+                response_events(Self::finish_response(response, &model), full_end)
+            }
+            MockOutput::Stream(events) if full_end => with_full_end(events),
+            MockOutput::Stream(events) => events,
+            MockOutput::Error(error) => return Err(error),
+        };
+        Ok(Box::pin(futures::stream::iter(events.into_iter().map(Ok))))
+    }
+}
+
+#[async_trait]
+impl EmbeddingCapability for MockClient {
+    async fn embed(&self, input: Vec<String>) -> Result<EmbeddingResponse, LlmError> {
+        if let Some(latency) = self.latency {
+            tokio::time::sleep(latency).await;
+        }
+        let embeddings = input.iter().map(|text| self.embedding_for(text)).collect();
+        Ok(EmbeddingResponse::new(embeddings, self.model.clone()))
+    }
+
+    fn embedding_dimension(&self) -> usize {
+        self.embedding_dimension
+    }
+}
+
+#[async_trait]
+impl AudioCapability for MockClient {
+    fn supported_features(&self) -> &[AudioFeature] {
+        &[AudioFeature::TextToSpeech, AudioFeature::SpeechToText]
+    }
+
+    async fn text_to_speech(&self, request: TtsRequest) -> Result<TtsResponse, LlmError> {
+        Ok(TtsResponse {
+            audio_data: self.speech.clone(),
+            format: request.format.unwrap_or_else(|| "mp3".to_string()),
+            duration: None,
+            sample_rate: None,
+            metadata: HashMap::new(),
+        })
+    }
+
+    async fn speech_to_text(&self, request: SttRequest) -> Result<SttResponse, LlmError> {
+        Ok(SttResponse {
+            text: self.transcription.clone(),
+            language: request.language,
+            confidence: None,
+            words: None,
+            duration: None,
+            metadata: HashMap::new(),
+        })
+    }
+}
+
+#[async_trait]
+impl ImageGenerationCapability for MockClient {
+    async fn generate_images(
+        &self,
+        request: ImageGenerationRequest,
+    ) -> Result<ImageGenerationResponse, LlmError> {
+        let images = if self.images.is_empty() {
+            (0..request.count.max(1))
+                .map(|i| GeneratedImage {
+                    url: Some(format!("https://mock.invalid/images/{i}.png")),
+                    b64_json: None,
+                    format: Some("png".to_string()),
+                    width: None,
+                    height: None,
+                    revised_prompt: None,
+                    metadata: HashMap::new(),
+                })
+                .collect()
+        } else {
+            self.images.clone()
+        };
+
+        Ok(ImageGenerationResponse {
+            images,
+            metadata: HashMap::new(),
+        })
+    }
+
+    fn get_supported_sizes(&self) -> Vec<String> {
+        vec!["1024x1024".to_string()]
+    }
+
+    fn get_supported_formats(&self) -> Vec<String> {
+        vec!["url".to_string(), "b64_json".to_string()]
+    }
+}
+
+#[async_trait]
+impl ModelListingCapability for MockClient {
+    async fn list_models(&self) -> Result<Vec<ModelInfo>, LlmError> {
+        if !self.models.is_empty() {
+            return Ok(self.models.clone());
+        }
+        Ok(vec![ModelInfo {
+            id: self.model.clone(),
+            name: None,
+            description: None,
+            owned_by: "mock".to_string(),
+            created: None,
+            capabilities: vec!["chat".to_string()],
+            context_window: None,
+            max_output_tokens: None,
+            input_cost_per_token: None,
+            output_cost_per_token: None,
+        }])
+    }
+
+    async fn get_model(&self, model_id: String) -> Result<ModelInfo, LlmError> {
+        self.list_models()
+            .await?
+            .into_iter()
+            .find(|model| model.id == model_id)
+            .ok_or_else(|| LlmError::NotFound(format!("Model {model_id} not found")))
+    }
+}
+
+impl LlmClient for MockClient {
+    fn provider_name(&self) -> &'static str {
+        "mock"
+    }
+
+    fn supported_models(&self) -> Vec<String> {
+        if self.models.is_empty() {
+            vec![self.model.clone()]
+        } else {
+            self.models.iter().map(|model| model.id.clone()).collect()
+        }
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::new()
+            .with_chat()
+            .with_streaming()
+            .with_tools()
+            .with_embedding()
+            .with_audio()
+            .with_custom_feature("image_generation", true)
+            .with_custom_feature("model_listing", true)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn LlmClient> {
+        Box::new(self.clone())
+    }
+
+    fn as_embedding_capability(&self) -> Option<&dyn EmbeddingCapability> {
+        Some(self)
+    }
+
+    fn as_audio_capability(&self) -> Option<&dyn AudioCapability> {
+        Some(self)
+    }
+
+    fn as_image_generation_capability(&self) -> Option<&dyn ImageGenerationCapability> {
+        Some(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    #[tokio::test]
+    async fn test_scripted_replies_and_recorded_requests() {
+        let mock = MockClient::new()
+            .with_reply(MockReply::tool_call("lookup", r#"{"id":1}"#))
+            .with_reply(MockReply::text_stream(["Hel", "lo"]))
+            .with_error(LlmError::RateLimitError("slow down".to_string()));
+
+        let tools = vec![Tool::function(
+            "lookup".to_string(),
+            "Look up a record".to_string(),
+            serde_json::json!({"type": "object"}),
+        )];
+        let response = mock
+            .chat_with_tools(vec![ChatMessage::user("find 1").build()], Some(tools))
+            .await
+            .unwrap();
+        let call = &response.tool_calls.unwrap()[0];
+        assert_eq!(call.function.as_ref().unwrap().name, "lookup");
+
+        // A stream reply requested through `chat` is collected
+        let response = mock
+            .chat(vec![ChatMessage::user("hi").build()])
+            .await
+            .unwrap();
+        assert_eq!(response.content_text(), Some("Hello"));
+
+        assert!(matches!(
+            mock.chat(vec![]).await,
+            Err(LlmError::RateLimitError(_))
+        ));
+        assert!(mock.chat(vec![]).await.is_err());
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[0].tools.as_ref().unwrap().len(), 1);
+        assert_eq!(requests[1].messages[0].content_text(), Some("hi"));
+        assert_eq!(requests[1].common_params.model, DEFAULT_MODEL);
+    }
+
+    #[tokio::test]
+    async fn test_response_reply_is_streamed() {
+        let mock = MockClient::new().with_text("streamed");
+        let events: Vec<_> = mock
+            .chat_stream(vec![ChatMessage::user("hi").build()], None)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert!(matches!(
+            &events[0],
+            Ok(ChatStreamEvent::ContentDelta { delta, .. }) if delta == "streamed"
+        ));
+        assert!(matches!(
+            events.last(),
+            Some(Ok(ChatStreamEvent::StreamEnd { .. }))
+        ));
+        assert!(mock.last_request().unwrap().stream);
+    }
+
+    #[tokio::test]
+    async fn test_stream_end_is_provider_shaped() {
+        let mut response = ChatResponse::new(MessageContent::Text("done".to_string()));
+        response.finish_reason = Some(FinishReason::Stop);
+        response.usage = Some(Usage::new(3, 1));
+        let mock = MockClient::new()
+            .with_reply(MockReply::response(response.clone()))
+            .with_reply(MockReply::response(response).with_full_stream_end())
+            .with_reply(MockReply::text_stream(["a", "b"]).with_full_stream_end());
+
+        let end = |events: Vec<Result<ChatStreamEvent, LlmError>>| match events.last() {
+            Some(Ok(ChatStreamEvent::StreamEnd { response })) => response.clone(),
+            other => panic!("expected StreamEnd, got {other:?}"),
+        };
+        let stream = || mock.chat_stream(vec![ChatMessage::user("hi").build()], None);
+
+        let events: Vec<_> = stream().await.unwrap().collect().await;
+        assert!(events.iter().any(|event| matches!(
+            event,
+            Ok(ChatStreamEvent::UsageUpdate { usage }) if usage.total_tokens == 4
+        )));
+        let response = end(events);
+        assert_eq!(response.content_text(), Some(""));
+        assert!(response.usage.is_none());
+        assert_eq!(response.finish_reason, Some(FinishReason::Stop));
+        assert_eq!(response.model.as_deref(), Some(DEFAULT_MODEL));
+
+        let response = end(stream().await.unwrap().collect().await);
+        assert_eq!(response.content_text(), Some("done"));
+        assert_eq!(response.usage.unwrap().total_tokens, 4);
+
+        let response = end(stream().await.unwrap().collect().await);
+        assert_eq!(response.content_text(), Some("ab"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reply_latency() {
+        let mock = MockClient::new()
+            .with_reply(MockReply::text("late").with_latency(Duration::from_secs(5)));
+        let start = tokio::time::Instant::now();
+        mock.chat(vec![]).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_other_capabilities() {
+        let mock = MockClient::new().with_embedding_dimension(4);
+        let client: Box<dyn LlmClient> = Box::new(mock);
+
+        let embeddings = client
+            .as_embedding_capability()
+            .unwrap()
+            .embed(vec!["a".to_string(), "a".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(embeddings.embeddings[0].len(), 4);
+        assert_eq!(embeddings.embeddings[0], embeddings.embeddings[1]);
+
+        let images = client
+            .as_image_generation_capability()
+            .unwrap()
+            .generate_image("a cat".to_string(), None, Some(2))
+            .await
+            .unwrap();
+        assert_eq!(images.len(), 2);
+    }
+}
@@ -0,0 +1,337 @@
+//! Token Counting
+//!
+//! This module provides the [`TokenCounter`] abstraction for counting prompt
+//! tokens before a request is sent. Three kinds of counters are available:
+//!
+//! - [`TiktokenCounter`] - exact local BPE counts for OpenAI-family models
+//!   (requires the `tokenizer` feature)
+//! - [`HeuristicTokenCounter`] - fast character-based estimates for any model
+//! - Provider-native counters - `AnthropicClient` and `GeminiClient` implement
+//!   `TokenCounter` through their count-tokens endpoints
+//!
+//! [`token_counter_for_model`] picks the best local counter for a model, and
+//! [`context_window_for_model`] looks up the model's context window.
+//!
+//! # Example
+//! ```rust,no_run
+//! use siumai::prelude::*;
+//! use siumai::token_counter::{context_window_for_model, token_counter_for_model};
+//!
+//! # async fn example() -> Result<(), LlmError> {
+//! let counter = token_counter_for_model("gpt-4o");
+//! let messages = vec![system!("You are helpful."), user!("Hello!")];
+//! let tokens = counter.count_messages(&messages, None).await?;
+//! println!("{tokens} of {:?} tokens", context_window_for_model("gpt-4o"));
+//! # Ok(())
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::error::LlmError;
+use crate::types::*;
+
+/// Estimated tokens for an image part (a high-detail 1024x1024 image on OpenAI)
+pub const IMAGE_TOKEN_ESTIMATE: u32 = 765;
+
+/// Estimated tokens for a document whose text is not available locally (about one PDF page)
+pub const DOCUMENT_TOKEN_ESTIMATE: u32 = 1_500;
+
+/// Tokens added to every chat prompt to prime the assistant reply
+const REPLY_PRIMING_TOKENS: u32 = 3;
+
+/// Counts prompt tokens for text and conversations.
+#[async_trait]
+pub trait TokenCounter: Send + Sync {
+    /// Count the tokens of a plain text
+    async fn count_text(&self, text: &str) -> Result<u32, LlmError>;
+
+    /// Count the prompt tokens of a conversation, including per-message
+    /// formatting overhead and tool definitions
+    async fn count_messages(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+    ) -> Result<u32, LlmError>;
+}
+
+/// Count a conversation with a local text counter.
+///
+/// Follows the OpenAI chat format accounting: a fixed overhead per message,
+/// the content and tool calls of each message, tool definitions and the reply
+/// priming tokens.
+fn count_conversation(
+    messages: &[ChatMessage],
+    tools: Option<&[Tool]>,
+    tokens_per_message: u32,
+    count: impl Fn(&str) -> u32,
+) -> u32 {
+    let mut total = REPLY_PRIMING_TOKENS;
+
+    for message in messages {
+        total += tokens_per_message;
+        match &message.content {
+            MessageContent::Text(text) => total += count(text),
+            MessageContent::MultiModal(parts) => {
+                for part in parts {
+                    total += match part {
+                        ContentPart::Text { text } => count(text),
+                        ContentPart::Image { .. } => IMAGE_TOKEN_ESTIMATE,
+                        ContentPart::Audio { .. } => 0,
+                        ContentPart::Document {
+                            source, media_type, ..
+                        } => source
+                            .decoded_text(media_type)
+                            .map_or(DOCUMENT_TOKEN_ESTIMATE, |text| count(&text)),
+                    };
+                }
+            }
+        }
+
+        for call in message.tool_calls.iter().flatten() {
+            total += count(&call.id);
+            if let Some(function) = &call.function {
+                total += count(&function.name) + count(&function.arguments);
+            }
+        }
+        if let Some(id) = &message.tool_call_id {
+            total += count(id);
+        }
+    }
+
+    for tool in tools.unwrap_or_default() {
+        let definition = serde_json::to_string(&tool.function).unwrap_or_default();
+        total += count(&definition);
+    }
+
+    total
+}
+
+/// Character-based token estimates.
+///
+/// ASCII text is counted at a fixed number of characters per token, and every
+/// other character as one token. This overestimates accented Latin text,
+/// which is the safe direction when fitting a context window.
+#[derive(Debug, Clone)]
+pub struct HeuristicTokenCounter {
+    chars_per_token: f32,
+    tokens_per_message: u32,
+}
+
+impl HeuristicTokenCounter {
+    /// Create a counter with the given average number of ASCII characters per token
+    pub fn new(chars_per_token: f32) -> Self {
+        Self {
+            chars_per_token: chars_per_token.max(1.0),
+            tokens_per_message: 4,
+        }
+    }
+
+    /// Create a counter tuned for a model family
+    pub fn for_model(model: &str) -> Self {
+        if model.contains("claude") {
+            Self::new(3.5)
+        } else {
+            Self::new(4.0)
+        }
+    }
+
+    /// Set the formatting overhead counted for every message
+    pub const fn with_tokens_per_message(mut self, tokens: u32) -> Self {
+        self.tokens_per_message = tokens;
+        self
+    }
+
+    /// Estimate the tokens of a text
+    pub fn count(&self, text: &str) -> u32 {
+        let (ascii, other) = text.chars().fold((0u32, 0u32), |(ascii, other), c| {
+            if c.is_ascii() {
+                (ascii + 1, other)
+            } else {
+                (ascii, other + 1)
+            }
+        });
+        (ascii as f32 / self.chars_per_token).ceil() as u32 + other
+    }
+}
+
+impl Default for HeuristicTokenCounter {
+    fn default() -> Self {
+        Self::new(4.0)
+    }
+}
+
+#[async_trait]
+impl TokenCounter for HeuristicTokenCounter {
+    async fn count_text(&self, text: &str) -> Result<u32, LlmError> {
+        Ok(self.count(text))
+    }
+
+    async fn count_messages(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+    ) -> Result<u32, LlmError> {
+        Ok(count_conversation(
+            messages,
+            tools,
+            self.tokens_per_message,
+            |text| self.count(text),
+        ))
+    }
+}
+
+/// Exact local token counts for OpenAI-family models using their BPE vocabularies.
+#[cfg(feature = "tokenizer")]
+#[derive(Clone)]
+pub struct TiktokenCounter {
+    bpe: &'static tiktoken_rs::CoreBPE,
+    tokens_per_message: u32,
+}
+
+#[cfg(feature = "tokenizer")]
+impl TiktokenCounter {
+    /// Create a counter for an OpenAI model, or `None` if its tokenizer is unknown
+    pub fn for_model(model: &str) -> Option<Self> {
+        use tiktoken_rs::tokenizer::Tokenizer;
+
+        let bpe = match tiktoken_rs::tokenizer::get_tokenizer(model)? {
+            Tokenizer::O200kBase => tiktoken_rs::o200k_base_singleton(),
+            Tokenizer::Cl100kBase => tiktoken_rs::cl100k_base_singleton(),
+            Tokenizer::P50kBase => tiktoken_rs::p50k_base_singleton(),
+            Tokenizer::P50kEdit => tiktoken_rs::p50k_edit_singleton(),
+            Tokenizer::R50kBase | Tokenizer::Gpt2 => tiktoken_rs::r50k_base_singleton(),
+        };
+
+        Some(Self {
+            bpe,
+            tokens_per_message: 3,
+        })
+    }
+
+    /// Count the tokens of a text
+    pub fn count(&self, text: &str) -> u32 {
+        self.bpe.encode_with_special_tokens(text).len() as u32
+    }
+}
+
+#[cfg(feature = "tokenizer")]
+impl std::fmt::Debug for TiktokenCounter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TiktokenCounter")
+            .field("tokens_per_message", &self.tokens_per_message)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "tokenizer")]
+#[async_trait]
+impl TokenCounter for TiktokenCounter {
+    async fn count_text(&self, text: &str) -> Result<u32, LlmError> {
+        Ok(self.count(text))
+    }
+
+    async fn count_messages(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+    ) -> Result<u32, LlmError> {
+        Ok(count_conversation(
+            messages,
+            tools,
+            self.tokens_per_message,
+            |text| self.count(text),
+        ))
+    }
+}
+
+/// Get the most accurate local token counter for a model.
+///
+/// With the `tokenizer` feature, OpenAI-family models get an exact BPE counter;
+/// all other models get a [`HeuristicTokenCounter`].
+pub fn token_counter_for_model(model: &str) -> Arc<dyn TokenCounter> {
+    #[cfg(feature = "tokenizer")]
+    if let Some(counter) = TiktokenCounter::for_model(model) {
+        return Arc::new(counter);
+    }
+
+    Arc::new(HeuristicTokenCounter::for_model(model))
+}
+
+/// Look up the context window (in tokens) of a known model
+pub fn context_window_for_model(model: &str) -> Option<u32> {
+    #[cfg(feature = "anthropic")]
+    if model.contains("claude") {
+        return crate::providers::anthropic::models::estimate_model_specs(model).0;
+    }
+
+    #[cfg(feature = "google")]
+    if model.contains("gemini") {
+        return Some(crate::providers::gemini::models::get_model_context_window(
+            model,
+        ));
+    }
+
+    #[cfg(feature = "openai")]
+    if ["gpt-", "o1", "o3", "o4", "chatgpt-", "text-embedding-"]
+        .iter()
+        .any(|prefix| model.starts_with(prefix))
+    {
+        return crate::providers::openai::models::estimate_model_specs(model).0;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heuristic_count() {
+        let counter = HeuristicTokenCounter::new(4.0);
+        assert_eq!(counter.count(""), 0);
+        assert_eq!(counter.count("abcd"), 1);
+        assert_eq!(counter.count("abcde"), 2);
+        assert_eq!(counter.count("你好"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_conversation_overhead() {
+        let counter = HeuristicTokenCounter::new(4.0);
+        let messages = vec![ChatMessage::user("abcd").build()];
+
+        // 3 priming + 4 per message + 1 content
+        assert_eq!(counter.count_messages(&messages, None).await.unwrap(), 8);
+
+        let tool = Tool::function(
+            "lookup".to_string(),
+            "Look up a value".to_string(),
+            serde_json::json!({"type": "object"}),
+        );
+        let with_tools = counter
+            .count_messages(&messages, Some(&[tool]))
+            .await
+            .unwrap();
+        assert!(with_tools > 8);
+    }
+
+    #[cfg(feature = "tokenizer")]
+    #[test]
+    fn test_tiktoken_counter() {
+        let counter = TiktokenCounter::for_model("gpt-4o").unwrap();
+        assert_eq!(counter.count("hello world"), 2);
+        assert!(TiktokenCounter::for_model("claude-3-5-sonnet").is_none());
+    }
+
+    #[test]
+    fn test_context_window_lookup() {
+        assert_eq!(context_window_for_model("gpt-4o"), Some(128_000));
+        assert_eq!(
+            context_window_for_model("claude-3-5-sonnet-20241022"),
+            Some(200_000)
+        );
+        assert_eq!(context_window_for_model("llama3.2"), None);
+    }
+}
@@ -0,0 +1,1039 @@
+//! Automatic Tool Execution
+//!
+//! This module provides a tool-execution loop on top of `ChatCapability`.
+//! Instead of manually calling tools, appending `ChatMessage::tool` results
+//! and re-asking the model, register handlers in a `ToolRegistry` and let a
+//! `ToolRunner` drive the conversation until the model stops requesting tools
+//! or the step budget is exhausted.
+//!
+//! # Example
+//! ```rust,no_run
+//! use siumai::prelude::*;
+//! use siumai::tool_runner::{ToolRegistry, ToolRunner};
+//!
+//! #[derive(serde::Deserialize)]
+//! struct WeatherArgs {
+//!     city: String,
+//! }
+//!
+//! # async fn example(client: Siumai) -> Result<(), LlmError> {
+//! let weather_tool = Tool::function(
+//!     "get_weather".to_string(),
+//!     "Get the current weather for a city".to_string(),
+//!     serde_json::json!({
+//!         "type": "object",
+//!         "properties": { "city": { "type": "string" } },
+//!         "required": ["city"]
+//!     }),
+//! );
+//!
+//! let mut registry = ToolRegistry::new();
+//! registry.register_fn(weather_tool, |args: WeatherArgs| async move {
+//!     Ok(format!("Sunny in {}", args.city))
+//! });
+//!
+//! let runner = ToolRunner::new(registry).with_max_steps(5);
+//! let result = runner
+//!     .run(&client, vec![user!("What's the weather in Paris?")])
+//!     .await?;
+//! println!("{}", result.response.text().unwrap_or_default());
+//! # Ok(())
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::error::LlmError;
+use crate::stream::{ChatStream, ChatStreamEvent, StreamProcessor};
+use crate::traits::ChatCapability;
+use crate::types::*;
+
+/// Handler invoked when the model calls a registered tool.
+#[async_trait]
+pub trait ToolHandler: Send + Sync {
+    /// Execute the tool with the parsed JSON arguments.
+    async fn call(&self, arguments: serde_json::Value) -> Result<serde_json::Value, LlmError>;
+}
+
+/// Adapter turning a typed async function into a `ToolHandler`.
+struct FnToolHandler<Args, Out, F> {
+    func: F,
+    _marker: PhantomData<fn(Args) -> Out>,
+}
+
+#[async_trait]
+impl<Args, Out, F, Fut> ToolHandler for FnToolHandler<Args, Out, F>
+where
+    Args: DeserializeOwned + Send + 'static,
+    Out: Serialize + 'static,
+    F: Fn(Args) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Out, LlmError>> + Send + 'static,
+{
+    async fn call(&self, arguments: serde_json::Value) -> Result<serde_json::Value, LlmError> {
+        let args: Args = serde_json::from_value(arguments)
+            .map_err(|e| LlmError::ToolValidationError(format!("Invalid arguments: {e}")))?;
+        let output = (self.func)(args).await?;
+        serde_json::to_value(output).map_err(|e| LlmError::JsonError(e.to_string()))
+    }
+}
+
+type ToolFuture<Out> = Pin<Box<dyn Future<Output = Result<Out, LlmError>> + Send>>;
+
+/// A tool whose definition and argument parsing derive from Rust types.
+///
+/// The parameter schema is generated from `Args`. Arguments are validated
+/// against it before being deserialized, so a malformed call is reported back
+/// to the model with the reason instead of reaching the function.
+///
+/// # Example
+/// ```rust,no_run
+/// use siumai::prelude::*;
+///
+/// #[derive(serde::Deserialize, JsonSchema)]
+/// struct WeatherArgs {
+///     /// City name
+///     city: String,
+/// }
+///
+/// let weather = TypedTool::new(
+///     "get_weather",
+///     "Get the current weather for a city",
+///     |args: WeatherArgs| async move { Ok(format!("Sunny in {}", args.city)) },
+/// );
+///
+/// let mut registry = ToolRegistry::new();
+/// registry.register_typed(weather);
+/// ```
+pub struct TypedTool<Args, Out> {
+    tool: Tool,
+    schema: OutputSchema,
+    func: Arc<dyn Fn(Args) -> ToolFuture<Out> + Send + Sync>,
+}
+
+impl<Args, Out> Clone for TypedTool<Args, Out> {
+    fn clone(&self) -> Self {
+        Self {
+            tool: self.tool.clone(),
+            schema: self.schema.clone(),
+            func: self.func.clone(),
+        }
+    }
+}
+
+impl<Args, Out> TypedTool<Args, Out>
+where
+    Args: DeserializeOwned + JsonSchema + Send + 'static,
+    Out: Serialize + 'static,
+{
+    /// Create a tool from an async function taking the typed arguments
+    pub fn new<F, Fut>(name: impl Into<String>, description: impl Into<String>, func: F) -> Self
+    where
+        F: Fn(Args) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Out, LlmError>> + Send + 'static,
+    {
+        Self {
+            tool: Tool::from_type::<Args>(name, description),
+            schema: OutputSchema::from_type::<Args>(),
+            func: Arc::new(move |args| Box::pin(func(args))),
+        }
+    }
+
+    /// Tool definition sent to the model
+    pub const fn tool(&self) -> &Tool {
+        &self.tool
+    }
+
+    /// Tool name
+    pub fn name(&self) -> &str {
+        &self.tool.function.name
+    }
+
+    /// Validate and deserialize JSON arguments
+    pub fn parse_value(&self, arguments: serde_json::Value) -> Result<Args, LlmError> {
+        self.schema.validate(&arguments).map_err(|e| match e {
+            LlmError::ParseError(message) => self.invalid_arguments(message),
+            other => other,
+        })?;
+        serde_json::from_value(arguments).map_err(|e| self.invalid_arguments(e))
+    }
+
+    /// Validate and deserialize the arguments string of a tool call
+    pub fn parse_arguments(&self, arguments: &str) -> Result<Args, LlmError> {
+        let value = if arguments.trim().is_empty() {
+            serde_json::Value::Object(serde_json::Map::new())
+        } else {
+            serde_json::from_str(arguments)
+                .map_err(|e| self.invalid_arguments(format!("Invalid JSON: {e}")))?
+        };
+        self.parse_value(value)
+    }
+
+    /// Parse the arguments of a call to this tool
+    pub fn parse_call(&self, call: &ToolCall) -> Result<Args, LlmError> {
+        match &call.function {
+            Some(function) if function.name == self.name() => {
+                self.parse_arguments(&function.arguments)
+            }
+            _ => Err(LlmError::ToolCallError(format!(
+                "Tool call '{}' is not a call to '{}'",
+                call.id,
+                self.name()
+            ))),
+        }
+    }
+
+    /// Run the function with already parsed arguments
+    pub async fn invoke(&self, args: Args) -> Result<Out, LlmError> {
+        (self.func)(args).await
+    }
+
+    fn invalid_arguments(&self, error: impl std::fmt::Display) -> LlmError {
+        LlmError::ToolValidationError(format!(
+            "Invalid arguments for tool '{}': {error}",
+            self.name()
+        ))
+    }
+}
+
+#[async_trait]
+impl<Args, Out> ToolHandler for TypedTool<Args, Out>
+where
+    Args: DeserializeOwned + JsonSchema + Send + 'static,
+    Out: Serialize + 'static,
+{
+    async fn call(&self, arguments: serde_json::Value) -> Result<serde_json::Value, LlmError> {
+        let args = self.parse_value(arguments)?;
+        let output = self.invoke(args).await?;
+        serde_json::to_value(output).map_err(|e| LlmError::JsonError(e.to_string()))
+    }
+}
+
+impl<Args, Out> std::fmt::Debug for TypedTool<Args, Out> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TypedTool")
+            .field("tool", &self.tool)
+            .finish()
+    }
+}
+
+/// Registry of tools and their handlers, keyed by `Tool.function.name`.
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    tools: Vec<Tool>,
+    handlers: HashMap<String, Arc<dyn ToolHandler>>,
+}
+
+impl ToolRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a tool with a handler
+    ///
+    /// Registering a tool with an existing name replaces the previous definition.
+    pub fn register<H: ToolHandler + 'static>(&mut self, tool: Tool, handler: H) {
+        let name = tool.function.name.clone();
+        self.tools.retain(|t| t.function.name != name);
+        self.tools.push(tool);
+        self.handlers.insert(name, Arc::new(handler));
+    }
+
+    /// Register a tool with a typed async function
+    ///
+    /// Arguments are deserialized into `Args` and the output is serialized back
+    /// to JSON before being sent to the model.
+    pub fn register_fn<Args, Out, F, Fut>(&mut self, tool: Tool, func: F)
+    where
+        Args: DeserializeOwned + Send + 'static,
+        Out: Serialize + 'static,
+        F: Fn(Args) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Out, LlmError>> + Send + 'static,
+    {
+        self.register(
+            tool,
+            FnToolHandler {
+                func,
+                _marker: PhantomData,
+            },
+        );
+    }
+
+    /// Register a typed tool, using its generated definition
+    pub fn register_typed<Args, Out>(&mut self, tool: TypedTool<Args, Out>)
+    where
+        Args: DeserializeOwned + JsonSchema + Send + 'static,
+        Out: Serialize + 'static,
+    {
+        self.register(tool.tool().clone(), tool);
+    }
+
+    /// Get the registered tool definitions
+    pub fn tools(&self) -> &[Tool] {
+        &self.tools
+    }
+
+    /// Get the handler for a tool
+    pub fn handler(&self, name: &str) -> Option<Arc<dyn ToolHandler>> {
+        self.handlers.get(name).cloned()
+    }
+
+    /// Check if a tool is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.handlers.contains_key(name)
+    }
+
+    /// Number of registered tools
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    /// Check if the registry is empty
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+}
+
+impl std::fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field(
+                "tools",
+                &self
+                    .tools
+                    .iter()
+                    .map(|t| t.function.name.as_str())
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// Tool runner configuration
+#[derive(Debug, Clone)]
+pub struct ToolRunnerConfig {
+    /// Maximum number of model calls before stopping
+    pub max_steps: usize,
+    /// Whether tool calls from a single response run concurrently
+    pub parallel_execution: bool,
+    /// Whether a failing tool aborts the run instead of reporting the error to the model
+    pub fail_on_tool_error: bool,
+}
+
+impl Default for ToolRunnerConfig {
+    fn default() -> Self {
+        Self {
+            max_steps: 10,
+            parallel_execution: true,
+            fail_on_tool_error: false,
+        }
+    }
+}
+
+/// Why a tool run finished
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolRunStopReason {
+    /// The model produced a response without tool calls
+    Completed,
+    /// The model stopped for a non-tool reason (length, content filter, ...)
+    FinishReason(FinishReason),
+    /// The step budget was exhausted while the model still requested tools
+    MaxStepsReached,
+}
+
+/// Record of a single tool execution
+#[derive(Debug, Clone)]
+pub struct ToolExecution {
+    /// The tool call requested by the model
+    pub tool_call: ToolCall,
+    /// Output sent back to the model
+    pub output: String,
+    /// Whether the execution failed
+    pub is_error: bool,
+}
+
+/// Result of a tool run
+#[derive(Debug, Clone)]
+pub struct ToolRunResult {
+    /// The last response returned by the model
+    pub response: ChatResponse,
+    /// Full conversation including assistant tool calls and tool results
+    pub messages: Vec<ChatMessage>,
+    /// All tool executions in call order
+    pub tool_executions: Vec<ToolExecution>,
+    /// Number of model calls made
+    pub steps: usize,
+    /// Usage accumulated across all steps
+    pub usage: Option<Usage>,
+    /// Why the run finished
+    pub stop_reason: ToolRunStopReason,
+}
+
+/// Drives the tool-calling loop for any `ChatCapability` implementation.
+#[derive(Debug, Clone)]
+pub struct ToolRunner {
+    registry: ToolRegistry,
+    config: ToolRunnerConfig,
+}
+
+impl ToolRunner {
+    /// Create a new tool runner with default configuration
+    pub fn new(registry: ToolRegistry) -> Self {
+        Self::with_config(registry, ToolRunnerConfig::default())
+    }
+
+    /// Create a new tool runner with custom configuration
+    pub fn with_config(registry: ToolRegistry, config: ToolRunnerConfig) -> Self {
+        Self { registry, config }
+    }
+
+    /// Set the maximum number of model calls
+    pub const fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.config.max_steps = max_steps;
+        self
+    }
+
+    /// Enable or disable concurrent execution of tool calls
+    pub const fn with_parallel_execution(mut self, parallel: bool) -> Self {
+        self.config.parallel_execution = parallel;
+        self
+    }
+
+    /// Abort the run when a tool fails instead of reporting the error to the model
+    pub const fn with_fail_on_tool_error(mut self, fail: bool) -> Self {
+        self.config.fail_on_tool_error = fail;
+        self
+    }
+
+    /// Get the tool registry
+    pub const fn registry(&self) -> &ToolRegistry {
+        &self.registry
+    }
+
+    /// Get the configuration
+    pub const fn config(&self) -> &ToolRunnerConfig {
+        &self.config
+    }
+
+    /// Run the tool loop until the model produces a final answer.
+    ///
+    /// # Arguments
+    /// * `client` - Any chat-capable client (provider client, `Siumai`, `ClientWrapper`, ...)
+    /// * `messages` - The initial conversation
+    ///
+    /// # Returns
+    /// The final response together with the full conversation history
+    pub async fn run<C: ChatCapability + ?Sized>(
+        &self,
+        client: &C,
+        mut messages: Vec<ChatMessage>,
+    ) -> Result<ToolRunResult, LlmError> {
+        let tools = self.tools_option();
+        let mut tool_executions = Vec::new();
+        let mut usage: Option<Usage> = None;
+        let mut steps = 0;
+
+        loop {
+            let response = client
+                .chat_with_tools(messages.clone(), tools.clone())
+                .await?;
+            steps += 1;
+            merge_usage(&mut usage, response.usage.as_ref());
+
+            let stop_reason = self.stop_reason(&response, steps);
+            if let Some(stop_reason) = stop_reason {
+                messages.push(assistant_message(&response));
+                return Ok(ToolRunResult {
+                    response,
+                    messages,
+                    tool_executions,
+                    steps,
+                    usage,
+                    stop_reason,
+                });
+            }
+
+            let executions = self.execute_tool_calls(&response).await?;
+            messages.push(assistant_message(&response));
+            messages.extend(executions.iter().map(tool_result_message));
+            tool_executions.extend(executions);
+        }
+    }
+
+    /// Run the tool loop in streaming mode.
+    ///
+    /// Content, thinking, tool call and usage deltas from every step are forwarded
+    /// as they arrive. Only the first `StreamStart` and the final `StreamEnd` are
+    /// emitted, so `collect_stream_response` yields the final answer. The final
+    /// `StreamEnd` carries the last step's content and the usage of all steps.
+    ///
+    /// Dropping the stream cancels the loop: no further tools are executed and
+    /// no further steps are requested.
+    ///
+    /// # Arguments
+    /// * `client` - Shared chat-capable client
+    /// * `messages` - The initial conversation
+    pub fn run_stream<C>(&self, client: Arc<C>, mut messages: Vec<ChatMessage>) -> ChatStream
+    where
+        C: ChatCapability + ?Sized + 'static,
+    {
+        let runner = self.clone();
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<Result<ChatStreamEvent, LlmError>>(64);
+
+        let task = tokio::spawn(async move {
+            let tools = runner.tools_option();
+            let mut steps = 0;
+            let mut started = false;
+            let mut usage: Option<Usage> = None;
+
+            loop {
+                if tx.is_closed() {
+                    return;
+                }
+                let mut stream = match client.chat_stream(messages.clone(), tools.clone()).await {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                };
+                steps += 1;
+
+                let mut processor = StreamProcessor::new();
+                let mut final_response = None;
+
+                while let Some(event) = stream.next().await {
+                    let event = match event {
+                        Ok(event) => event,
+                        Err(e) => {
+                            let _ = tx.send(Err(e)).await;
+                            return;
+                        }
+                    };
+
+                    let forward = match &event {
+                        ChatStreamEvent::StreamStart { .. } => {
+                            !std::mem::replace(&mut started, true)
+                        }
+                        ChatStreamEvent::StreamEnd { response } => {
+                            final_response = Some(response.clone());
+                            false
+                        }
+                        _ => true,
+                    };
+
+                    if !matches!(
+                        event,
+                        ChatStreamEvent::StreamStart { .. } | ChatStreamEvent::StreamEnd { .. }
+                    ) {
+                        processor.process_event(event.clone());
+                    }
+
+                    if forward && tx.send(Ok(event)).await.is_err() {
+                        return;
+                    }
+                }
+
+                let response = processor.build_final_response_with_end(final_response.as_ref());
+                merge_usage(&mut usage, response.usage.as_ref());
+
+                if runner.stop_reason(&response, steps).is_some() {
+                    let mut response = response;
+                    response.usage = usage;
+                    let _ = tx.send(Ok(ChatStreamEvent::StreamEnd { response })).await;
+                    return;
+                }
+
+                if tx.is_closed() {
+                    return;
+                }
+                let executions = match runner.execute_tool_calls(&response).await {
+                    Ok(executions) => executions,
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                };
+                messages.push(assistant_message(&response));
+                messages.extend(executions.iter().map(tool_result_message));
+            }
+        });
+
+        let task = AbortOnDrop(task);
+        Box::pin(futures::stream::poll_fn(move |cx| {
+            let _ = &task;
+            rx.poll_recv(cx)
+        }))
+    }
+
+    /// Execute all tool calls of a response
+    pub async fn execute_tool_calls(
+        &self,
+        response: &ChatResponse,
+    ) -> Result<Vec<ToolExecution>, LlmError> {
+        let tool_calls = response.tool_calls.clone().unwrap_or_default();
+
+        let results = if self.config.parallel_execution {
+            futures::future::join_all(tool_calls.iter().map(|call| self.execute_tool_call(call)))
+                .await
+        } else {
+            let mut results = Vec::with_capacity(tool_calls.len());
+            for call in &tool_calls {
+                results.push(self.execute_tool_call(call).await);
+            }
+            results
+        };
+
+        tool_calls
+            .into_iter()
+            .zip(results)
+            .map(|(tool_call, result)| match result {
+                Ok(value) => Ok(ToolExecution {
+                    tool_call,
+                    output: value_to_output(value),
+                    is_error: false,
+                }),
+                Err(e) if self.config.fail_on_tool_error => Err(e),
+                Err(LlmError::ToolValidationError(message)) => Ok(ToolExecution {
+                    output: self.invalid_arguments_output(&tool_call, &message),
+                    tool_call,
+                    is_error: true,
+                }),
+                Err(e) => Ok(ToolExecution {
+                    tool_call,
+                    output: format!("Error: {e}"),
+                    is_error: true,
+                }),
+            })
+            .collect()
+    }
+
+    async fn execute_tool_call(&self, call: &ToolCall) -> Result<serde_json::Value, LlmError> {
+        let function = call.function.as_ref().ok_or_else(|| {
+            LlmError::ToolCallError(format!("Tool call '{}' has no function", call.id))
+        })?;
+
+        let handler = self
+            .registry
+            .handler(&function.name)
+            .ok_or_else(|| LlmError::ToolCallError(format!("Unknown tool: {}", function.name)))?;
+
+        let arguments = if function.arguments.trim().is_empty() {
+            serde_json::Value::Object(serde_json::Map::new())
+        } else {
+            serde_json::from_str(&function.arguments).map_err(|e| {
+                LlmError::ToolValidationError(format!(
+                    "Invalid JSON arguments for tool '{}': {e}",
+                    function.name
+                ))
+            })?
+        };
+
+        handler.call(arguments).await
+    }
+
+    /// Structured report of invalid arguments, with the expected schema so the model can retry
+    fn invalid_arguments_output(&self, call: &ToolCall, message: &str) -> String {
+        let parameters = call.function.as_ref().and_then(|function| {
+            self.registry
+                .tools()
+                .iter()
+                .find(|tool| tool.function.name == function.name)
+                .map(|tool| tool.function.parameters.clone())
+        });
+
+        serde_json::json!({
+            "error": "invalid_arguments",
+            "message": message,
+            "parameters": parameters,
+        })
+        .to_string()
+    }
+
+    fn tools_option(&self) -> Option<Vec<Tool>> {
+        if self.registry.is_empty() {
+            None
+        } else {
+            Some(self.registry.tools().to_vec())
+        }
+    }
+
+    fn stop_reason(&self, response: &ChatResponse, steps: usize) -> Option<ToolRunStopReason> {
+        if !response.has_tool_calls() {
+            return Some(ToolRunStopReason::Completed);
+        }
+
+        // Some providers (Gemini, Ollama) report `Stop` alongside tool calls, so only
+        // abnormal finish reasons end the loop.
+        if let Some(
+            reason @ (FinishReason::Length | FinishReason::ContentFilter | FinishReason::Error),
+        ) = &response.finish_reason
+        {
+            return Some(ToolRunStopReason::FinishReason(reason.clone()));
+        }
+
+        if steps >= self.config.max_steps {
+            return Some(ToolRunStopReason::MaxStepsReached);
+        }
+
+        None
+    }
+}
+
+fn merge_usage(total: &mut Option<Usage>, usage: Option<&Usage>) {
+    if let Some(usage) = usage {
+        match total {
+            Some(total) => total.merge(usage),
+            None => *total = Some(usage.clone()),
+        }
+    }
+}
+
+fn assistant_message(response: &ChatResponse) -> ChatMessage {
+    ChatMessage {
+        role: MessageRole::Assistant,
+        content: response.content.clone(),
+        metadata: MessageMetadata::default(),
+        tool_calls: response.tool_calls.clone(),
+        tool_call_id: None,
+    }
+}
+
+fn tool_result_message(execution: &ToolExecution) -> ChatMessage {
+    ChatMessage::tool(execution.output.clone(), execution.tool_call.id.clone()).build()
+}
+
+/// Aborts the streaming tool loop when its stream is dropped
+struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+fn value_to_output(value: serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Mock client that requests `add` once, then answers with the tool result.
+    struct AddingClient {
+        calls: AtomicUsize,
+    }
+
+    impl AddingClient {
+        fn new() -> Self {
+            Self {
+                calls: AtomicUsize::new(0),
+            }
+        }
+
+        fn respond(&self, messages: &[ChatMessage]) -> ChatResponse {
+            if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                let mut response = ChatResponse::new(MessageContent::Text(String::new()));
+                response.finish_reason = Some(FinishReason::ToolCalls);
+                response.usage = Some(Usage::new(10, 5));
+                response.tool_calls = Some(vec![ToolCall {
+                    id: "call_1".to_string(),
+                    r#type: "function".to_string(),
+                    function: Some(FunctionCall {
+                        name: "add".to_string(),
+                        arguments: r#"{"a": 2, "b": 3}"#.to_string(),
+                    }),
+                }]);
+                response
+            } else {
+                let tool_output = messages
+                    .iter()
+                    .rev()
+                    .find(|m| m.role == MessageRole::Tool)
+                    .and_then(|m| m.content_text())
+                    .unwrap_or_default()
+                    .to_string();
+                let mut response =
+                    ChatResponse::new(MessageContent::Text(format!("The sum is {tool_output}")));
+                response.finish_reason = Some(FinishReason::Stop);
+                response.usage = Some(Usage::new(20, 5));
+                response
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ChatCapability for AddingClient {
+        async fn chat_with_tools(
+            &self,
+            messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+        ) -> Result<ChatResponse, LlmError> {
+            Ok(self.respond(&messages))
+        }
+
+        async fn chat_stream(
+            &self,
+            messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+        ) -> Result<ChatStream, LlmError> {
+            let response = self.respond(&messages);
+            let mut events = Vec::new();
+            if let Some(calls) = &response.tool_calls {
+                for call in calls {
+                    let function = call.function.clone().unwrap();
+                    events.push(Ok(ChatStreamEvent::ToolCallDelta {
+                        id: call.id.clone(),
+                        function_name: Some(function.name),
+                        arguments_delta: Some(function.arguments),
+                        index: None,
+                    }));
+                }
+            } else {
+                events.push(Ok(ChatStreamEvent::ContentDelta {
+                    delta: response.content_text().unwrap_or_default().to_string(),
+                    index: None,
+                }));
+            }
+            if let Some(usage) = response.usage.clone() {
+                events.push(Ok(ChatStreamEvent::UsageUpdate { usage }));
+            }
+            // Providers end streams with an empty response
+            let mut end = ChatResponse::new(MessageContent::Text(String::new()));
+            end.finish_reason = response.finish_reason;
+            events.push(Ok(ChatStreamEvent::StreamEnd { response: end }));
+            Ok(Box::pin(futures::stream::iter(events)))
+        }
+    }
+
+    fn add_registry() -> ToolRegistry {
+        #[derive(serde::Deserialize)]
+        struct AddArgs {
+            a: i64,
+            b: i64,
+        }
+
+        let mut registry = ToolRegistry::new();
+        registry.register_fn(
+            Tool::function(
+                "add".to_string(),
+                "Add two numbers".to_string(),
+                serde_json::json!({"type": "object"}),
+            ),
+            |args: AddArgs| async move { Ok(args.a + args.b) },
+        );
+        registry
+    }
+
+    #[tokio::test]
+    async fn test_tool_runner_executes_tools_until_completion() {
+        let client = AddingClient::new();
+        let runner = ToolRunner::new(add_registry());
+
+        let result = runner
+            .run(&client, vec![ChatMessage::user("2 + 3?").build()])
+            .await
+            .unwrap();
+
+        assert_eq!(result.steps, 2);
+        assert_eq!(result.stop_reason, ToolRunStopReason::Completed);
+        assert_eq!(result.response.content_text(), Some("The sum is 5"));
+        assert_eq!(result.tool_executions.len(), 1);
+        assert!(!result.tool_executions[0].is_error);
+        // user, assistant(tool call), tool, assistant(final)
+        assert_eq!(result.messages.len(), 4);
+        assert_eq!(result.usage.unwrap().total_tokens, 40);
+    }
+
+    #[tokio::test]
+    async fn test_tool_runner_respects_max_steps() {
+        let client = AddingClient::new();
+        let runner = ToolRunner::new(add_registry()).with_max_steps(1);
+
+        let result = runner
+            .run(&client, vec![ChatMessage::user("2 + 3?").build()])
+            .await
+            .unwrap();
+
+        assert_eq!(result.steps, 1);
+        assert_eq!(result.stop_reason, ToolRunStopReason::MaxStepsReached);
+        assert!(result.tool_executions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_tool_reported_to_model() {
+        let client = AddingClient::new();
+        let runner = ToolRunner::new(ToolRegistry::new());
+
+        let result = runner
+            .run(&client, vec![ChatMessage::user("2 + 3?").build()])
+            .await
+            .unwrap();
+
+        assert!(result.tool_executions[0].is_error);
+        assert!(result.tool_executions[0].output.contains("Unknown tool"));
+    }
+
+    #[tokio::test]
+    async fn test_tool_runner_streaming() {
+        let client = Arc::new(AddingClient::new());
+        let runner = ToolRunner::new(add_registry());
+
+        let stream = runner.run_stream(client, vec![ChatMessage::user("2 + 3?").build()]);
+        let response = crate::stream::collect_stream_response(stream)
+            .await
+            .unwrap();
+
+        assert_eq!(response.content_text(), Some("The sum is 5"));
+        assert!(!response.has_tool_calls());
+        assert_eq!(response.usage.unwrap().total_tokens, 40);
+    }
+
+    /// Mock client that streams a tool call and holds the stream open until released
+    struct GatedClient {
+        release: Arc<tokio::sync::Notify>,
+    }
+
+    #[async_trait]
+    impl ChatCapability for GatedClient {
+        async fn chat_with_tools(
+            &self,
+            _messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+        ) -> Result<ChatResponse, LlmError> {
+            Err(LlmError::UnsupportedOperation("streaming only".to_string()))
+        }
+
+        async fn chat_stream(
+            &self,
+            _messages: Vec<ChatMessage>,
+            _tools: Option<Vec<Tool>>,
+        ) -> Result<ChatStream, LlmError> {
+            let release = self.release.clone();
+            let call = futures::stream::iter(vec![Ok(ChatStreamEvent::ToolCallDelta {
+                id: "call_1".to_string(),
+                function_name: Some("add".to_string()),
+                arguments_delta: Some(r#"{"a": 2, "b": 3}"#.to_string()),
+                index: None,
+            })]);
+            let end = futures::stream::once(async move {
+                release.notified().await;
+                let mut end = ChatResponse::new(MessageContent::Text(String::new()));
+                end.finish_reason = Some(FinishReason::ToolCalls);
+                Ok(ChatStreamEvent::StreamEnd { response: end })
+            });
+            Ok(Box::pin(call.chain(end)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dropping_stream_stops_tool_execution() {
+        let executed = Arc::new(AtomicUsize::new(0));
+        let mut registry = ToolRegistry::new();
+        let counter = executed.clone();
+        registry.register_fn(
+            Tool::function(
+                "add".to_string(),
+                "Add two numbers".to_string(),
+                serde_json::json!({"type": "object"}),
+            ),
+            move |_: serde_json::Value| {
+                let counter = counter.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Ok(5)
+                }
+            },
+        );
+
+        let release = Arc::new(tokio::sync::Notify::new());
+        let client = Arc::new(GatedClient {
+            release: release.clone(),
+        });
+        let mut stream =
+            ToolRunner::new(registry).run_stream(client, vec![ChatMessage::user("2 + 3?").build()]);
+
+        assert!(matches!(
+            stream.next().await,
+            Some(Ok(ChatStreamEvent::ToolCallDelta { .. }))
+        ));
+        drop(stream);
+
+        release.notify_one();
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(executed.load(Ordering::SeqCst), 0);
+    }
+
+    #[derive(serde::Deserialize, JsonSchema)]
+    struct TypedAddArgs {
+        /// First operand
+        a: i64,
+        /// Second operand
+        b: i64,
+    }
+
+    fn typed_add() -> TypedTool<TypedAddArgs, i64> {
+        TypedTool::new("add", "Add two numbers", |args: TypedAddArgs| async move {
+            Ok(args.a + args.b)
+        })
+    }
+
+    #[test]
+    fn test_typed_tool_schema_and_parsing() {
+        let tool = typed_add();
+        let parameters = &tool.tool().function.parameters;
+        assert_eq!(parameters["type"], "object");
+        assert_eq!(
+            parameters["properties"]["a"]["description"],
+            "First operand"
+        );
+        assert_eq!(parameters["required"], serde_json::json!(["a", "b"]));
+
+        let args = tool.parse_arguments(r#"{"a": 2, "b": 3}"#).unwrap();
+        assert_eq!(args.a + args.b, 5);
+        assert!(matches!(
+            tool.parse_arguments(r#"{"a": "two"}"#),
+            Err(LlmError::ToolValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_typed_tool_validation_error_reported_to_model() {
+        use crate::testing::{MockClient, MockReply};
+
+        let client = MockClient::new().with_replies([
+            MockReply::tool_call("add", r#"{"a": "two", "b": 3}"#),
+            MockReply::text("Let me fix that."),
+        ]);
+        let mut registry = ToolRegistry::new();
+        registry.register_typed(typed_add());
+
+        let result = ToolRunner::new(registry)
+            .run(&client, vec![ChatMessage::user("2 + 3?").build()])
+            .await
+            .unwrap();
+
+        let execution = &result.tool_executions[0];
+        assert!(execution.is_error);
+        let report: serde_json::Value = serde_json::from_str(&execution.output).unwrap();
+        assert_eq!(report["error"], "invalid_arguments");
+        assert!(report["message"].as_str().unwrap().contains("add"));
+        assert_eq!(report["parameters"]["type"], "object");
+    }
+}
@@ -62,6 +62,119 @@ pub trait ChatCapability: Send + Sync {
         messages: Vec<ChatMessage>,
         tools: Option<Vec<Tool>>,
     ) -> Result<ChatStream, LlmError>;
+
+    /// Sends a chat request whose output must conform to a JSON schema.
+    ///
+    /// Providers map the schema to their native mechanism (`response_format`,
+    /// `responseSchema`, forced tool use, `format`). The response content is
+    /// the JSON text of the output.
+    ///
+    /// # Arguments
+    /// * `messages` - The conversation history as a list of chat messages
+    /// * `schema` - The expected output schema
+    ///
+    /// # Returns
+    /// The provider's response or an error
+    ///
+    /// # Default Implementation
+    /// By default, the schema is sent as a system instruction.
+    async fn chat_with_schema(
+        &self,
+        mut messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatResponse, LlmError> {
+        messages.insert(0, ChatMessage::system(schema.instructions()).build());
+        self.chat_with_tools(messages, None).await
+    }
+
+    /// Streams a chat response whose output must conform to a JSON schema.
+    ///
+    /// The JSON arrives as content deltas, or as tool argument deltas for
+    /// providers that enforce schemas through tool use. Wrap the stream in an
+    /// [`ObjectStream`](crate::object_stream::ObjectStream) to parse it.
+    ///
+    /// # Arguments
+    /// * `messages` - The conversation history as a list of chat messages
+    /// * `schema` - The expected output schema
+    ///
+    /// # Returns
+    /// A stream of chat events or an error
+    ///
+    /// # Default Implementation
+    /// By default, the schema is sent as a system instruction.
+    async fn chat_stream_with_schema(
+        &self,
+        mut messages: Vec<ChatMessage>,
+        schema: OutputSchema,
+    ) -> Result<ChatStream, LlmError> {
+        messages.insert(0, ChatMessage::system(schema.instructions()).build());
+        self.chat_stream(messages, None).await
+    }
+
+    /// Sends a chat request whose parameters override the client's configuration.
+    ///
+    /// A non-empty `common_params.model` and every `common_params` field that
+    /// is set replace the client's value, and `provider_params` entries are
+    /// merged over the client's provider parameters. `web_search` and
+    /// `prompt_cache` apply where the provider supports them; the client's
+    /// HTTP configuration is always used. An `output_schema` is applied as in
+    /// [`chat_with_schema`](Self::chat_with_schema). Wrappers such as
+    /// middleware and fallbacks use this to forward parameter changes.
+    ///
+    /// # Arguments
+    /// * `request` - The messages, tools and parameter overrides
+    ///
+    /// # Returns
+    /// The provider's response or an error
+    ///
+    /// # Default Implementation
+    /// Requests without parameters are sent with `chat_with_schema` when they
+    /// carry an output schema and with `chat_with_tools` otherwise; others
+    /// fail with `LlmError::UnsupportedOperation`.
+    async fn chat_with_request(&self, request: ChatRequest) -> Result<ChatResponse, LlmError> {
+        if request.has_params() {
+            return Err(LlmError::UnsupportedOperation(
+                "This client cannot apply per-request parameters".to_string(),
+            ));
+        }
+        match request.output_schema {
+            Some(schema) => self.chat_with_schema(request.messages, schema).await,
+            None => self.chat_with_tools(request.messages, request.tools).await,
+        }
+    }
+
+    /// Streams a chat request whose parameters override the client's configuration.
+    ///
+    /// Parameters are applied as in [`chat_with_request`](Self::chat_with_request).
+    ///
+    /// # Arguments
+    /// * `request` - The messages, tools and parameter overrides
+    ///
+    /// # Returns
+    /// A stream of chat events or an error
+    ///
+    /// # Default Implementation
+    /// Requests without parameters are sent with `chat_stream_with_schema`
+    /// when they carry an output schema and with `chat_stream` otherwise;
+    /// others fail with `LlmError::UnsupportedOperation`.
+    async fn chat_stream_with_request(&self, request: ChatRequest) -> Result<ChatStream, LlmError> {
+        if request.has_params() {
+            return Err(LlmError::UnsupportedOperation(
+                "This client cannot apply per-request parameters".to_string(),
+            ));
+        }
+        match request.output_schema {
+            Some(schema) => self.chat_stream_with_schema(request.messages, schema).await,
+            None => self.chat_stream(request.messages, request.tools).await,
+        }
+    }
+
+    /// The model this client sends requests to, if known.
+    ///
+    /// Wrappers such as caches use it to tell clients with different models apart.
+    fn default_model(&self) -> Option<String> {
+        None
+    }
 }
 
 /// Extended chat capabilities providing convenience methods and advanced features.
@@ -258,6 +371,121 @@ pub trait ChatExtensions: ChatCapability {
         );
         self.ask_with_system(system_prompt, prompt).await
     }
+
+    /// Generate a typed object from the conversation.
+    ///
+    /// The output schema is derived from `T`, enforced through the provider's
+    /// structured output support, validated and deserialized.
+    ///
+    /// # Arguments
+    /// * `messages` - The conversation messages
+    ///
+    /// # Returns
+    /// The deserialized object
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use siumai::prelude::*;
+    /// #[derive(serde::Deserialize, JsonSchema)]
+    /// struct Recipe {
+    ///     name: String,
+    ///     ingredients: Vec<String>,
+    /// }
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = quick_openai().await?;
+    /// let recipe: Recipe = client
+    ///     .generate_object(vec![user!("Give me a pancake recipe")])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn generate_object<T>(&self, messages: Vec<ChatMessage>) -> Result<T, LlmError>
+    where
+        T: serde::de::DeserializeOwned + schemars::JsonSchema + Send,
+    {
+        self.generate_object_with_options(messages, GenerateObjectOptions::default())
+            .await
+            .map(|result| result.object)
+    }
+
+    /// Generate a typed object with custom options.
+    ///
+    /// When the output fails validation, the invalid output and the error are
+    /// sent back to the model for up to `max_repair_attempts` repair rounds.
+    ///
+    /// # Arguments
+    /// * `messages` - The conversation messages
+    /// * `options` - Schema naming, strictness and repair configuration
+    ///
+    /// # Returns
+    /// The deserialized object together with the final response
+    async fn generate_object_with_options<T>(
+        &self,
+        mut messages: Vec<ChatMessage>,
+        options: GenerateObjectOptions,
+    ) -> Result<ObjectResponse<T>, LlmError>
+    where
+        T: serde::de::DeserializeOwned + schemars::JsonSchema + Send,
+    {
+        let schema = options.schema_for::<T>();
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+            let response = self
+                .chat_with_schema(messages.clone(), schema.clone())
+                .await?;
+
+            match schema.parse_response::<T>(&response) {
+                Ok(object) => {
+                    return Ok(ObjectResponse {
+                        object,
+                        response,
+                        attempts,
+                    });
+                }
+                Err(e) if attempts <= options.max_repair_attempts => {
+                    messages.push(ChatMessage::assistant(response.content.all_text()).build());
+                    messages.push(
+                        ChatMessage::user(format!(
+                            "The previous response did not match the required JSON schema: {e}. \
+                             Respond again with only a JSON value that conforms to the schema."
+                        ))
+                        .build(),
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Stream a typed object from the conversation.
+    ///
+    /// Yields JSON snapshots and best-effort partial objects while the output
+    /// is generated, then the object validated against the schema derived
+    /// from `T`. Partial objects are only produced once every required field
+    /// of `T` has arrived, so fields marked `#[serde(default)]` show up sooner.
+    ///
+    /// # Arguments
+    /// * `messages` - The conversation messages
+    ///
+    /// # Returns
+    /// A stream of [`ObjectStreamEvent`](crate::object_stream::ObjectStreamEvent)s
+    async fn stream_object<T>(
+        &self,
+        messages: Vec<ChatMessage>,
+    ) -> Result<crate::object_stream::ObjectStream<T>, LlmError>
+    where
+        T: serde::de::DeserializeOwned + schemars::JsonSchema + Send + 'static,
+    {
+        let schema = OutputSchema::from_type::<T>();
+        let stream = self
+            .chat_stream_with_schema(messages, schema.clone())
+            .await?;
+        Ok(crate::object_stream::ObjectStream::new(stream, schema))
+    }
 }
 
 /// Automatic implementation of `ChatExtensions` for all types that implement `ChatCapability`
@@ -801,6 +1029,117 @@ pub trait FileManagementCapability: Send + Sync {
     async fn get_file_content(&self, file_id: String) -> Result<Vec<u8>, LlmError>;
 }
 
+/// Batch processing capability for asynchronous, discounted chat requests.
+///
+/// Batches are processed within a provider-defined window (usually 24 hours)
+/// at a reduced price. Results are matched to requests by `custom_id`.
+///
+/// # API References
+/// - OpenAI: <https://platform.openai.com/docs/api-reference/batch>
+/// - Anthropic: <https://docs.anthropic.com/en/api/creating-message-batches>
+/// - Groq: <https://console.groq.com/docs/batch>
+#[async_trait]
+pub trait BatchCapability: Send + Sync {
+    /// Create a batch job.
+    ///
+    /// # Arguments
+    /// * `requests` - The chat requests to process
+    ///
+    /// # Returns
+    /// Batch job information
+    async fn create_batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResponse, LlmError>;
+
+    /// Retrieve the current state of a batch job.
+    ///
+    /// # Arguments
+    /// * `batch_id` - The batch identifier
+    ///
+    /// # Returns
+    /// Batch job information
+    async fn retrieve_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError>;
+
+    /// List batch jobs.
+    ///
+    /// # Arguments
+    /// * `query` - Optional pagination parameters
+    ///
+    /// # Returns
+    /// Paginated list of batch jobs
+    async fn list_batches(
+        &self,
+        query: Option<BatchListQuery>,
+    ) -> Result<BatchListResponse, LlmError>;
+
+    /// Cancel a batch job.
+    ///
+    /// # Arguments
+    /// * `batch_id` - The batch identifier
+    ///
+    /// # Returns
+    /// Batch job information after the cancellation request
+    async fn cancel_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError>;
+
+    /// Get the results of a finished batch job.
+    ///
+    /// # Arguments
+    /// * `batch_id` - The batch identifier
+    ///
+    /// # Returns
+    /// One result per processed request, keyed by `custom_id`
+    async fn get_batch_results(&self, batch_id: String) -> Result<Vec<BatchResult>, LlmError>;
+
+    /// Poll a batch job until it reaches a terminal status.
+    ///
+    /// Retryable errors while checking the status (rate limits, server and
+    /// network errors) are logged and the batch is checked again after
+    /// `poll_interval`; other errors end the wait.
+    ///
+    /// # Arguments
+    /// * `batch_id` - The batch identifier
+    /// * `poll_interval` - Time to wait between status checks
+    /// * `max_wait` - Maximum time to wait, or `None` to wait until the batch
+    ///   finishes
+    ///
+    /// # Returns
+    /// Batch job information in its terminal status, or
+    /// `LlmError::TimeoutError` once `max_wait` has elapsed (the last status
+    /// check error, if it failed)
+    async fn wait_for_batch(
+        &self,
+        batch_id: String,
+        poll_interval: std::time::Duration,
+        max_wait: Option<std::time::Duration>,
+    ) -> Result<BatchResponse, LlmError> {
+        let deadline = max_wait.map(|max_wait| tokio::time::Instant::now() + max_wait);
+        loop {
+            let error = match self.retrieve_batch(batch_id.clone()).await {
+                Ok(batch) if batch.status.is_terminal() => return Ok(batch),
+                Ok(_) => None,
+                Err(e) if e.is_retryable() => {
+                    tracing::warn!("Failed to check batch {batch_id}, retrying: {e}");
+                    Some(e)
+                }
+                Err(e) => return Err(e),
+            };
+
+            let mut sleep = poll_interval;
+            if let Some(deadline) = deadline {
+                let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+                if remaining.is_zero() {
+                    return Err(error.unwrap_or_else(|| {
+                        LlmError::TimeoutError(format!(
+                            "Batch {batch_id} did not finish within {:?}",
+                            max_wait.unwrap_or_default()
+                        ))
+                    }));
+                }
+                sleep = sleep.min(remaining);
+            }
+            tokio::time::sleep(sleep).await;
+        }
+    }
+}
+
 /// Content moderation capability.
 ///
 /// This trait provides content moderation functionality to check for
@@ -881,6 +1220,32 @@ pub trait ModelListingCapability: Send + Sync {
     }
 }
 
+/// Server-side conversation state capability.
+///
+/// Providers that store the conversation on their side continue it from the
+/// ID of the previous response, so only the new messages are sent.
+///
+/// # API References
+/// - OpenAI: <https://platform.openai.com/docs/guides/conversation-state>
+#[async_trait]
+pub trait ConversationStateCapability: Send + Sync {
+    /// Send new messages continuing a stored conversation.
+    ///
+    /// # Arguments
+    /// * `messages` - Messages added since the previous response (the full conversation when starting)
+    /// * `tools` - Optional list of tools to use in the chat
+    /// * `previous_response_id` - ID of the response to continue from, `None` to start
+    ///
+    /// # Returns
+    /// The provider's response; its `id` continues the conversation
+    async fn chat_with_previous_response(
+        &self,
+        messages: Vec<ChatMessage>,
+        tools: Option<Vec<Tool>>,
+        previous_response_id: Option<String>,
+    ) -> Result<ChatResponse, LlmError>;
+}
+
 /// Text completion capability (non-chat).
 ///
 /// This trait provides traditional text completion functionality,
@@ -1054,17 +1419,8 @@ pub trait OpenAiCapability: Send + Sync {
     async fn chat_with_structured_output(
         &self,
         messages: Vec<ChatMessage>,
-        schema: JsonSchema,
-    ) -> Result<StructuredResponse, LlmError>;
-
-    /// Create a batch processing job.
-    ///
-    /// # Arguments
-    /// * `requests` - List of requests to process in batch
-    ///
-    /// # Returns
-    /// Batch job information
-    async fn create_batch(&self, requests: Vec<BatchRequest>) -> Result<BatchResponse, LlmError>;
+        schema: OutputSchema,
+    ) -> Result<ChatResponse, LlmError>;
 
     /// Use the Responses API instead of Chat Completions.
     ///
@@ -1353,6 +1709,99 @@ mod tests {
         assert!(!caps.supports("audio"));
     }
 
+    /// Batch provider answering status checks from a script
+    struct ScriptedBatches(std::sync::Mutex<Vec<Result<BatchStatus, LlmError>>>);
+
+    #[async_trait]
+    impl BatchCapability for ScriptedBatches {
+        async fn create_batch(
+            &self,
+            _requests: Vec<BatchRequest>,
+        ) -> Result<BatchResponse, LlmError> {
+            unimplemented!()
+        }
+
+        async fn retrieve_batch(&self, batch_id: String) -> Result<BatchResponse, LlmError> {
+            let mut script = self.0.lock().unwrap();
+            let status = if script.len() > 1 {
+                script.remove(0)
+            } else {
+                script[0].clone()
+            }?;
+            Ok(BatchResponse {
+                id: batch_id,
+                status,
+                request_counts: BatchRequestCounts::default(),
+                created_at: None,
+                ended_at: None,
+                expires_at: None,
+                output_file_id: None,
+                error_file_id: None,
+                results_url: None,
+                metadata: HashMap::new(),
+            })
+        }
+
+        async fn list_batches(
+            &self,
+            _query: Option<BatchListQuery>,
+        ) -> Result<BatchListResponse, LlmError> {
+            unimplemented!()
+        }
+
+        async fn cancel_batch(&self, _batch_id: String) -> Result<BatchResponse, LlmError> {
+            unimplemented!()
+        }
+
+        async fn get_batch_results(&self, _batch_id: String) -> Result<Vec<BatchResult>, LlmError> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wait_for_batch_retries_and_times_out() {
+        let interval = std::time::Duration::from_secs(10);
+        let server_error = LlmError::ApiError {
+            code: 503,
+            message: "unavailable".to_string(),
+            details: None,
+        };
+
+        // Transient errors are polled through
+        let batches = ScriptedBatches(std::sync::Mutex::new(vec![
+            Ok(BatchStatus::InProgress),
+            Err(server_error.clone()),
+            Ok(BatchStatus::Completed),
+        ]));
+        let batch = batches
+            .wait_for_batch("batch_1".to_string(), interval, None)
+            .await
+            .unwrap();
+        assert_eq!(batch.status, BatchStatus::Completed);
+
+        // Other errors end the wait
+        let batches = ScriptedBatches(std::sync::Mutex::new(vec![Err(
+            LlmError::AuthenticationError("bad key".to_string()),
+        )]));
+        let result = batches
+            .wait_for_batch("batch_1".to_string(), interval, None)
+            .await;
+        assert!(matches!(result, Err(LlmError::AuthenticationError(_))));
+
+        // A batch that never finishes times out
+        let batches = ScriptedBatches(std::sync::Mutex::new(vec![Ok(BatchStatus::InProgress)]));
+        let start = tokio::time::Instant::now();
+        let result = batches
+            .wait_for_batch(
+                "batch_1".to_string(),
+                interval,
+                Some(std::time::Duration::from_secs(25)),
+            )
+            .await;
+        assert!(matches!(result, Err(LlmError::TimeoutError(_))));
+        assert_eq!(start.elapsed(), std::time::Duration::from_secs(25));
+    }
+
     // Test that all capability traits are Send + Sync
     #[test]
     fn test_capability_traits_are_send_sync() {
@@ -1402,6 +1851,8 @@ mod tests {
                     finish_reason: Some(crate::types::FinishReason::Stop),
                     tool_calls: None,
                     thinking: None,
+                    citations: Vec::new(),
+                    sources: Vec::new(),
                     metadata: std::collections::HashMap::new(),
                 })
             }
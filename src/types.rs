@@ -3,7 +3,9 @@
 //! Defines all data structures used in the LLM library.
 
 pub mod audio;
+pub mod batch;
 pub mod chat;
+pub mod citation;
 pub mod common;
 pub mod completion;
 pub mod embedding;
@@ -11,13 +13,17 @@ pub mod files;
 pub mod image;
 pub mod models;
 pub mod moderation;
+pub mod prompt_cache;
 pub mod streaming;
+pub mod structured_output;
 pub mod tools;
 pub mod web_search;
 
 // Re-export all types for backward compatibility
 pub use audio::*;
+pub use batch::*;
 pub use chat::*;
+pub use citation::*;
 pub use common::*;
 pub use completion::*;
 pub use embedding::*;
@@ -25,6 +31,8 @@ pub use files::*;
 pub use image::*;
 pub use models::*;
 pub use moderation::*;
+pub use prompt_cache::*;
 pub use streaming::*;
+pub use structured_output::*;
 pub use tools::*;
 pub use web_search::*;
@@ -0,0 +1,188 @@
+//! Batch processing types
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{ChatMessage, ChatRequest, ChatResponse, CommonParams, Tool};
+use crate::error::LlmError;
+
+/// A single chat request in a batch job
+#[derive(Debug, Clone)]
+pub struct BatchRequest {
+    /// Caller-chosen identifier used to match results to requests
+    pub custom_id: String,
+    /// The chat request. Unset parameters fall back to the client's defaults.
+    pub request: ChatRequest,
+}
+
+impl BatchRequest {
+    /// Create a batch request from messages
+    pub fn new(custom_id: impl Into<String>, messages: Vec<ChatMessage>) -> Self {
+        Self {
+            custom_id: custom_id.into(),
+            request: ChatRequest::new(messages),
+        }
+    }
+
+    /// Create a batch request from a full chat request
+    pub fn from_request(custom_id: impl Into<String>, request: ChatRequest) -> Self {
+        Self {
+            custom_id: custom_id.into(),
+            request,
+        }
+    }
+
+    /// Set the tools available to the request
+    pub fn with_tools(mut self, tools: Vec<Tool>) -> Self {
+        self.request.tools = Some(tools);
+        self
+    }
+
+    /// Set the model for this request
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.request.common_params.model = model.into();
+        self
+    }
+
+    /// Get the chat request with unset parameters filled from `defaults`
+    pub fn resolve(&self, defaults: &CommonParams) -> ChatRequest {
+        let mut request = self.request.clone();
+        let params = &mut request.common_params;
+        if params.model.is_empty() {
+            params.model = defaults.model.clone();
+        }
+        params.temperature = params.temperature.or(defaults.temperature);
+        params.max_tokens = params.max_tokens.or(defaults.max_tokens);
+        params.top_p = params.top_p.or(defaults.top_p);
+        if params.stop_sequences.is_none() {
+            params.stop_sequences = defaults.stop_sequences.clone();
+        }
+        params.seed = params.seed.or(defaults.seed);
+        request.stream = false;
+        request
+    }
+}
+
+/// Batch job status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatus {
+    /// Input is being validated
+    Validating,
+    /// Requests are being processed
+    InProgress,
+    /// Results are being prepared
+    Finalizing,
+    /// All requests have been processed
+    Completed,
+    /// The batch failed validation or processing
+    Failed,
+    /// The batch did not finish within its completion window
+    Expired,
+    /// Cancellation has been requested
+    Cancelling,
+    /// The batch was cancelled
+    Cancelled,
+}
+
+impl BatchStatus {
+    /// Whether the batch will not change state anymore
+    pub const fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::Completed | Self::Failed | Self::Expired | Self::Cancelled
+        )
+    }
+}
+
+impl std::str::FromStr for BatchStatus {
+    type Err = LlmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "validating" => Ok(Self::Validating),
+            "in_progress" => Ok(Self::InProgress),
+            "finalizing" => Ok(Self::Finalizing),
+            "completed" | "ended" => Ok(Self::Completed),
+            "failed" => Ok(Self::Failed),
+            "expired" => Ok(Self::Expired),
+            "cancelling" | "canceling" => Ok(Self::Cancelling),
+            "cancelled" | "canceled" => Ok(Self::Cancelled),
+            other => Err(LlmError::ParseError(format!(
+                "Unknown batch status: {other}"
+            ))),
+        }
+    }
+}
+
+/// Request counts of a batch job
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchRequestCounts {
+    /// Total number of requests
+    pub total: u32,
+    /// Requests that completed successfully
+    pub completed: u32,
+    /// Requests that failed, expired or were cancelled
+    pub failed: u32,
+}
+
+/// Batch job information
+#[derive(Debug, Clone)]
+pub struct BatchResponse {
+    /// Batch ID
+    pub id: String,
+    /// Current status
+    pub status: BatchStatus,
+    /// Request counts
+    pub request_counts: BatchRequestCounts,
+    /// Creation timestamp (Unix seconds)
+    pub created_at: Option<u64>,
+    /// Timestamp at which processing ended (Unix seconds)
+    pub ended_at: Option<u64>,
+    /// Timestamp at which the batch expires (Unix seconds)
+    pub expires_at: Option<u64>,
+    /// File containing successful results (OpenAI-compatible providers)
+    pub output_file_id: Option<String>,
+    /// File containing failed requests (OpenAI-compatible providers)
+    pub error_file_id: Option<String>,
+    /// URL of the results file (Anthropic)
+    pub results_url: Option<String>,
+    /// Additional provider-specific fields
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+/// Batch list query parameters
+#[derive(Debug, Clone, Default)]
+pub struct BatchListQuery {
+    /// Maximum number of batches to return
+    pub limit: Option<u32>,
+    /// Cursor for pagination
+    pub after: Option<String>,
+}
+
+/// Batch list response
+#[derive(Debug, Clone)]
+pub struct BatchListResponse {
+    /// List of batches
+    pub batches: Vec<BatchResponse>,
+    /// Whether there are more results
+    pub has_more: bool,
+    /// Next page cursor
+    pub next_cursor: Option<String>,
+}
+
+/// Result of a single request in a batch job
+#[derive(Debug, Clone)]
+pub struct BatchResult {
+    /// The `custom_id` of the originating request
+    pub custom_id: String,
+    /// The response, or the error the request failed with
+    pub result: Result<ChatResponse, LlmError>,
+}
+
+impl BatchResult {
+    /// Whether the request succeeded
+    pub const fn is_success(&self) -> bool {
+        self.result.is_ok()
+    }
+}
@@ -1,6 +1,9 @@
 //! Chat-related types and message handling
 
+use super::citation::{Citation, SearchSource};
 use super::common::{CommonParams, FinishReason, HttpConfig, ProviderParams, Usage};
+use super::prompt_cache::PromptCachePolicy;
+use super::structured_output::OutputSchema;
 use super::tools::{Tool, ToolCall};
 use super::web_search::WebSearchConfig;
 use serde::{Deserialize, Serialize};
@@ -77,6 +80,45 @@ pub enum ContentPart {
         audio_url: String,
         format: String,
     },
+    Document {
+        source: DocumentSource,
+        media_type: String,
+        title: Option<String>,
+        citations: bool,
+    },
+}
+
+/// Where a document's content comes from
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DocumentSource {
+    /// Base64-encoded content
+    Base64(String),
+    /// Publicly reachable URL
+    Url(String),
+    /// File previously uploaded through the provider's Files API
+    FileId(String),
+}
+
+impl DocumentSource {
+    /// Create a source from raw bytes
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        use base64::Engine;
+        Self::Base64(base64::engine::general_purpose::STANDARD.encode(bytes))
+    }
+
+    /// Decode inline plain-text content (`text/*` media types only)
+    pub fn decoded_text(&self, media_type: &str) -> Option<String> {
+        use base64::Engine;
+        match self {
+            Self::Base64(data) if media_type.starts_with("text/") => {
+                let bytes = base64::engine::general_purpose::STANDARD
+                    .decode(data)
+                    .ok()?;
+                String::from_utf8(bytes).ok()
+            }
+            _ => None,
+        }
+    }
 }
 
 /// Cache control
@@ -196,6 +238,11 @@ impl ChatMessage {
                     ContentPart::Text { text } => text.len(),
                     ContentPart::Image { image_url, .. } => image_url.len(),
                     ContentPart::Audio { audio_url, .. } => audio_url.len(),
+                    ContentPart::Document { source, .. } => match source {
+                        DocumentSource::Base64(data) => data.len(),
+                        DocumentSource::Url(url) => url.len(),
+                        DocumentSource::FileId(id) => id.len(),
+                    },
                 })
                 .sum(),
         }
@@ -309,6 +356,38 @@ impl ChatMessageBuilder {
         self
     }
 
+    /// Adds a document (PDF or plain text) with citations enabled
+    pub fn with_document(
+        mut self,
+        source: DocumentSource,
+        media_type: impl Into<String>,
+        title: Option<String>,
+    ) -> Self {
+        let document_part = ContentPart::Document {
+            source,
+            media_type: media_type.into(),
+            title,
+            citations: true,
+        };
+
+        match self.content {
+            Some(MessageContent::Text(text)) => {
+                self.content = Some(MessageContent::MultiModal(vec![
+                    ContentPart::Text { text },
+                    document_part,
+                ]));
+            }
+            Some(MessageContent::MultiModal(ref mut parts)) => {
+                parts.push(document_part);
+            }
+            None => {
+                self.content = Some(MessageContent::MultiModal(vec![document_part]));
+            }
+        }
+
+        self
+    }
+
     /// Adds tool calls
     pub fn with_tool_calls(mut self, tool_calls: Vec<ToolCall>) -> Self {
         self.tool_calls = Some(tool_calls);
@@ -344,6 +423,11 @@ pub struct ChatRequest {
     pub web_search: Option<WebSearchConfig>,
     /// Stream the response
     pub stream: bool,
+    /// Prompt caching policy
+    pub prompt_cache: Option<PromptCachePolicy>,
+    /// Schema the output must conform to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<OutputSchema>,
 }
 
 impl ChatRequest {
@@ -357,9 +441,20 @@ impl ChatRequest {
             http_config: None,
             web_search: None,
             stream: false,
+            prompt_cache: None,
+            output_schema: None,
         }
     }
 
+    /// Whether the request carries anything besides its messages, tools and output schema
+    pub fn has_params(&self) -> bool {
+        !self.common_params.is_minimal()
+            || self.provider_params.is_some()
+            || self.http_config.is_some()
+            || self.web_search.is_some()
+            || self.prompt_cache.is_some()
+    }
+
     /// Create a builder for the chat request
     pub fn builder() -> ChatRequestBuilder {
         ChatRequestBuilder::new()
@@ -418,6 +513,18 @@ impl ChatRequest {
         self.web_search = Some(config);
         self
     }
+
+    /// Set the prompt caching policy
+    pub fn with_prompt_cache(mut self, policy: PromptCachePolicy) -> Self {
+        self.prompt_cache = Some(policy);
+        self
+    }
+
+    /// Require the output to conform to a schema
+    pub fn with_output_schema(mut self, schema: OutputSchema) -> Self {
+        self.output_schema = Some(schema);
+        self
+    }
 }
 
 /// Chat request builder
@@ -430,6 +537,8 @@ pub struct ChatRequestBuilder {
     http_config: Option<HttpConfig>,
     web_search: Option<WebSearchConfig>,
     stream: bool,
+    prompt_cache: Option<PromptCachePolicy>,
+    output_schema: Option<OutputSchema>,
 }
 
 impl ChatRequestBuilder {
@@ -443,6 +552,8 @@ impl ChatRequestBuilder {
             http_config: None,
             web_search: None,
             stream: false,
+            prompt_cache: None,
+            output_schema: None,
         }
     }
 
@@ -500,6 +611,18 @@ impl ChatRequestBuilder {
         self
     }
 
+    /// Set the prompt caching policy
+    pub fn prompt_cache(mut self, policy: PromptCachePolicy) -> Self {
+        self.prompt_cache = Some(policy);
+        self
+    }
+
+    /// Require the output to conform to a schema
+    pub fn output_schema(mut self, schema: OutputSchema) -> Self {
+        self.output_schema = Some(schema);
+        self
+    }
+
     /// Build the chat request
     pub fn build(self) -> ChatRequest {
         ChatRequest {
@@ -510,6 +633,8 @@ impl ChatRequestBuilder {
             http_config: self.http_config,
             web_search: self.web_search,
             stream: self.stream,
+            prompt_cache: self.prompt_cache,
+            output_schema: self.output_schema,
         }
     }
 }
@@ -537,6 +662,12 @@ pub struct ChatResponse {
     pub tool_calls: Option<Vec<ToolCall>>,
     /// Thinking content (if available)
     pub thinking: Option<String>,
+    /// Citations supporting the response text
+    #[serde(default)]
+    pub citations: Vec<Citation>,
+    /// Web pages consulted by search or grounding
+    #[serde(default)]
+    pub sources: Vec<SearchSource>,
     /// Provider-specific metadata
     pub metadata: HashMap<String, serde_json::Value>,
 }
@@ -552,6 +683,8 @@ impl ChatResponse {
             finish_reason: None,
             tool_calls: None,
             thinking: None,
+            citations: Vec::new(),
+            sources: Vec::new(),
             metadata: HashMap::new(),
         }
     }
@@ -592,4 +725,9 @@ impl ChatResponse {
     pub fn thinking_or_empty(&self) -> &str {
         self.thinking.as_deref().unwrap_or("")
     }
+
+    /// Check if the response is grounded in citations or search sources
+    pub fn has_citations(&self) -> bool {
+        !self.citations.is_empty() || !self.sources.is_empty()
+    }
 }
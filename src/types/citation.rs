@@ -0,0 +1,134 @@
+//! Citation and search source types shared by every provider
+
+use serde::{Deserialize, Serialize};
+
+/// A citation attached to a span of the response text
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Citation {
+    /// Text quoted from the source
+    pub cited_text: Option<String>,
+    /// Title of the cited document or page
+    pub title: Option<String>,
+    /// URL of the cited source (web citations)
+    pub url: Option<String>,
+    /// Index of the cited document among the request's documents
+    pub document_index: Option<usize>,
+    /// Location of the cited passage inside the source
+    pub location: Option<CitationLocation>,
+    /// Byte offset in the response text where the supported span starts
+    pub start_index: Option<usize>,
+    /// Byte offset in the response text where the supported span ends
+    pub end_index: Option<usize>,
+}
+
+/// Location of a cited passage inside its source document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CitationLocation {
+    /// Character range in a plain-text document (end exclusive)
+    Chars { start: usize, end: usize },
+    /// Page range in a PDF document, 1-based (end exclusive)
+    Pages { start: usize, end: usize },
+    /// Content block range in a custom-content document (end exclusive)
+    ContentBlocks { start: usize, end: usize },
+}
+
+/// A web page consulted by search or grounding
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchSource {
+    /// URL of the page
+    pub url: String,
+    /// Page title
+    pub title: Option<String>,
+    /// Snippet or excerpt returned by the search
+    pub snippet: Option<String>,
+}
+
+impl SearchSource {
+    /// Create a source from its URL
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        Self {
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the page title
+    pub fn with_title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the snippet
+    pub fn with_snippet<S: Into<String>>(mut self, snippet: S) -> Self {
+        self.snippet = Some(snippet.into());
+        self
+    }
+
+    /// Distinct sources referenced by web citations, in order of first appearance
+    pub fn from_citations(citations: &[Citation]) -> Vec<Self> {
+        let mut sources: Vec<Self> = Vec::new();
+        for citation in citations {
+            let Some(url) = &citation.url else {
+                continue;
+            };
+            if sources.iter().any(|source| &source.url == url) {
+                continue;
+            }
+            sources.push(Self {
+                url: url.clone(),
+                title: citation.title.clone(),
+                snippet: None,
+            });
+        }
+        sources
+    }
+
+    /// Append sources whose URL is not already present
+    pub fn merge(sources: &mut Vec<Self>, new_sources: impl IntoIterator<Item = Self>) {
+        for source in new_sources {
+            if !sources.iter().any(|existing| existing.url == source.url) {
+                sources.push(source);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sources_from_citations_are_deduplicated() {
+        let citations = vec![
+            Citation {
+                url: Some("https://a.example".to_string()),
+                title: Some("A".to_string()),
+                ..Default::default()
+            },
+            Citation {
+                document_index: Some(0),
+                ..Default::default()
+            },
+            Citation {
+                url: Some("https://a.example".to_string()),
+                ..Default::default()
+            },
+        ];
+
+        let mut sources = SearchSource::from_citations(&citations);
+        assert_eq!(
+            sources,
+            vec![SearchSource::new("https://a.example").with_title("A")]
+        );
+
+        SearchSource::merge(
+            &mut sources,
+            vec![
+                SearchSource::new("https://a.example"),
+                SearchSource::new("https://b.example"),
+            ],
+        );
+        assert_eq!(sources.len(), 2);
+    }
+}
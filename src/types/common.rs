@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 use validator::Validate;
 
@@ -32,7 +33,7 @@ impl std::fmt::Display for ProviderType {
 }
 
 /// Common AI parameters
-#[derive(Debug, Clone, Serialize, Deserialize, Default, Validate)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, Validate)]
 pub struct CommonParams {
     /// Model name
     #[validate(length(min = 1, message = "Model name cannot be empty"))]
@@ -79,6 +80,28 @@ impl CommonParams {
             && self.seed.is_none()
     }
 
+    /// Overlay the parameters set in `overrides`
+    ///
+    /// A non-empty model and every `Some` field of `overrides` replace the
+    /// values of `self`.
+    pub fn merged_with(&self, overrides: &Self) -> Self {
+        Self {
+            model: if overrides.model.is_empty() {
+                self.model.clone()
+            } else {
+                overrides.model.clone()
+            },
+            temperature: overrides.temperature.or(self.temperature),
+            max_tokens: overrides.max_tokens.or(self.max_tokens),
+            top_p: overrides.top_p.or(self.top_p),
+            stop_sequences: overrides
+                .stop_sequences
+                .clone()
+                .or_else(|| self.stop_sequences.clone()),
+            seed: overrides.seed.or(self.seed),
+        }
+    }
+
     /// Estimate memory usage for caching decisions
     pub fn memory_footprint(&self) -> usize {
         let mut size = std::mem::size_of::<Self>();
@@ -104,6 +127,8 @@ impl CommonParams {
             .hash(&mut hasher);
         self.max_tokens.hash(&mut hasher);
         self.top_p.map(|t| (t * 1000.0) as u32).hash(&mut hasher);
+        self.stop_sequences.hash(&mut hasher);
+        self.seed.hash(&mut hasher);
         hasher.finish()
     }
 
@@ -201,7 +226,7 @@ impl CommonParamsBuilder {
 }
 
 /// Provider-specific parameters
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProviderParams {
     /// A map for provider-specific parameters
     pub params: HashMap<String, serde_json::Value>,
@@ -229,6 +254,21 @@ impl ProviderParams {
             .and_then(|v| serde_json::from_value(v.clone()).ok())
     }
 
+    /// Overlay the entries of `overrides`, which win on conflicts
+    pub fn merged_with(&self, overrides: &Self) -> Self {
+        let mut params = self.params.clone();
+        params.extend(overrides.params.clone());
+        Self { params }
+    }
+
+    /// Overlay optional overrides on optional parameters
+    pub fn overlay(params: Option<&Self>, overrides: Option<&Self>) -> Option<Self> {
+        match (params, overrides) {
+            (Some(params), Some(overrides)) => Some(params.merged_with(overrides)),
+            (params, overrides) => overrides.or(params).cloned(),
+        }
+    }
+
     /// Creates provider parameters from `OpenAI` parameters
     pub fn from_openai(openai_params: crate::params::OpenAiParams) -> Self {
         let mut params = HashMap::new();
@@ -316,6 +356,12 @@ pub struct HttpConfig {
     pub proxy: Option<String>,
     /// User agent
     pub user_agent: Option<String>,
+    /// Client-side rate limiter shared by every client using this configuration
+    #[serde(skip)]
+    pub rate_limiter: Option<Arc<crate::rate_limiter::RateLimiter>>,
+    /// Record/replay cassette; when set, requests are recorded or answered from it
+    #[serde(skip)]
+    pub cassette: Option<Arc<crate::cassette::Cassette>>,
 }
 
 // Helper module for Duration serialization
@@ -350,6 +396,8 @@ impl Default for HttpConfig {
             headers: HashMap::new(),
             proxy: None,
             user_agent: Some("siumai/0.1.0".to_string()),
+            rate_limiter: None,
+            cassette: None,
         }
     }
 }
@@ -367,6 +415,9 @@ pub struct Usage {
     pub cached_tokens: Option<u32>,
     /// Reasoning tokens (for models like o1)
     pub reasoning_tokens: Option<u32>,
+    /// Tokens written to the prompt cache (if applicable)
+    #[serde(default)]
+    pub cache_write_tokens: Option<u32>,
 }
 
 impl Usage {
@@ -378,6 +429,7 @@ impl Usage {
             total_tokens: prompt_tokens + completion_tokens,
             cached_tokens: None,
             reasoning_tokens: None,
+            cache_write_tokens: None,
         }
     }
 
@@ -392,6 +444,9 @@ impl Usage {
         if let Some(other_reasoning) = other.reasoning_tokens {
             self.reasoning_tokens = Some(self.reasoning_tokens.unwrap_or(0) + other_reasoning);
         }
+        if let Some(other_written) = other.cache_write_tokens {
+            self.cache_write_tokens = Some(self.cache_write_tokens.unwrap_or(0) + other_written);
+        }
     }
 }
 
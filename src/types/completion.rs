@@ -4,10 +4,12 @@ use super::common::Usage;
 use std::collections::HashMap;
 
 /// Text completion request
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct CompletionRequest {
     /// Input prompt
     pub prompt: String,
+    /// Text after the insertion point (fill-in-the-middle)
+    pub suffix: Option<String>,
     /// Model to use
     pub model: Option<String>,
     /// Maximum tokens to generate
@@ -28,6 +30,64 @@ pub struct CompletionRequest {
     pub extra_params: HashMap<String, serde_json::Value>,
 }
 
+impl CompletionRequest {
+    /// Create a new completion request with a prompt
+    pub fn new(prompt: impl Into<String>) -> Self {
+        Self {
+            prompt: prompt.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the suffix for fill-in-the-middle completion
+    pub fn with_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = Some(suffix.into());
+        self
+    }
+
+    /// Set the model to use
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Set maximum tokens to generate
+    pub const fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Set temperature
+    pub const fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Set top-p sampling
+    pub const fn with_top_p(mut self, top_p: f32) -> Self {
+        self.top_p = Some(top_p);
+        self
+    }
+
+    /// Set top-k sampling
+    pub const fn with_top_k(mut self, top_k: u32) -> Self {
+        self.top_k = Some(top_k);
+        self
+    }
+
+    /// Set stop sequences
+    pub fn with_stop(mut self, stop: Vec<String>) -> Self {
+        self.stop = Some(stop);
+        self
+    }
+
+    /// Add a provider-specific parameter
+    pub fn with_param(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.extra_params.insert(key.into(), value);
+        self
+    }
+}
+
 /// Text completion response
 #[derive(Debug, Clone)]
 pub struct CompletionResponse {
@@ -42,10 +102,10 @@ pub struct CompletionResponse {
 }
 
 // Placeholder types for future implementation
+#[deprecated(note = "use `OutputSchema` for structured output")]
 pub type JsonSchema = ();
+#[deprecated(note = "use `ObjectResponse` for structured output")]
 pub type StructuredResponse = ();
-pub type BatchRequest = ();
-pub type BatchResponse = ();
 pub type CacheConfig = ();
 pub type ThinkingResponse = ();
 pub type SearchConfig = ();
@@ -1,6 +1,9 @@
 //! Image generation and processing types
 
 use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::{ChatResponse, CommonParams, FinishReason, Usage};
 
 /// Image generation request
 #[derive(Debug, Clone, Default)]
@@ -95,8 +98,188 @@ pub struct GeneratedImage {
     pub metadata: HashMap<String, serde_json::Value>,
 }
 
+/// Image input for vision requests
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageInput {
+    /// Remote image URL (http, https or gs) or a `data:` URL
+    Url(String),
+    /// Base64 encoded image data
+    Base64 {
+        data: String,
+        media_type: Option<String>,
+    },
+    /// Raw image bytes
+    Bytes {
+        data: Vec<u8>,
+        media_type: Option<String>,
+    },
+    /// Local image file
+    File(PathBuf),
+}
+
+impl ImageInput {
+    /// Create an image input from a URL
+    pub fn url(url: impl Into<String>) -> Self {
+        Self::Url(url.into())
+    }
+
+    /// Create an image input from a local file path
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self::File(path.into())
+    }
+
+    /// Create an image input from raw bytes
+    pub fn bytes(data: impl Into<Vec<u8>>) -> Self {
+        Self::Bytes {
+            data: data.into(),
+            media_type: None,
+        }
+    }
+
+    /// Create an image input from base64 encoded data
+    pub fn base64(data: impl Into<String>, media_type: Option<String>) -> Self {
+        Self::Base64 {
+            data: data.into(),
+            media_type,
+        }
+    }
+}
+
+impl From<&str> for ImageInput {
+    fn from(url: &str) -> Self {
+        Self::Url(url.to_string())
+    }
+}
+
+impl From<String> for ImageInput {
+    fn from(url: String) -> Self {
+        Self::Url(url)
+    }
+}
+
+impl From<PathBuf> for ImageInput {
+    fn from(path: PathBuf) -> Self {
+        Self::File(path)
+    }
+}
+
+impl From<Vec<u8>> for ImageInput {
+    fn from(data: Vec<u8>) -> Self {
+        Self::bytes(data)
+    }
+}
+
+/// Image analysis request
+#[derive(Debug, Clone, Default)]
+pub struct VisionRequest {
+    /// Question or instruction about the images
+    pub prompt: String,
+    /// Images to analyze
+    pub images: Vec<ImageInput>,
+    /// Detail level hint ("low", "high" or "auto")
+    pub detail: Option<String>,
+    /// Optional system prompt
+    pub system_prompt: Option<String>,
+    /// Model override (uses the client's model when `None`)
+    pub model: Option<String>,
+    /// Maximum tokens to generate
+    pub max_tokens: Option<u32>,
+}
+
+impl VisionRequest {
+    /// Create a new vision request with a prompt
+    pub fn new(prompt: impl Into<String>) -> Self {
+        Self {
+            prompt: prompt.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Add an image to analyze
+    pub fn with_image(mut self, image: impl Into<ImageInput>) -> Self {
+        self.images.push(image.into());
+        self
+    }
+
+    /// Add an image from a URL
+    pub fn with_image_url(self, url: impl Into<String>) -> Self {
+        self.with_image(ImageInput::url(url))
+    }
+
+    /// Add an image from a local file
+    pub fn with_image_file(self, path: impl Into<PathBuf>) -> Self {
+        self.with_image(ImageInput::file(path))
+    }
+
+    /// Add an image from raw bytes
+    pub fn with_image_bytes(self, data: impl Into<Vec<u8>>) -> Self {
+        self.with_image(ImageInput::bytes(data))
+    }
+
+    /// Set the detail level
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// Set the system prompt
+    pub fn with_system_prompt(mut self, system_prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(system_prompt.into());
+        self
+    }
+
+    /// Set the model
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Set maximum tokens
+    pub const fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Apply the request's model and token overrides to a client's common parameters
+    pub(crate) fn common_params(&self, base: &CommonParams) -> CommonParams {
+        let mut params = base.clone();
+        if let Some(model) = &self.model {
+            params.model = model.clone();
+        }
+        if self.max_tokens.is_some() {
+            params.max_tokens = self.max_tokens;
+        }
+        params
+    }
+}
+
+/// Image analysis response
+#[derive(Debug, Clone)]
+pub struct VisionResponse {
+    /// Analysis text
+    pub text: String,
+    /// Model used
+    pub model: Option<String>,
+    /// Token usage
+    pub usage: Option<Usage>,
+    /// Finish reason
+    pub finish_reason: Option<FinishReason>,
+    /// Provider-specific metadata
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+impl From<ChatResponse> for VisionResponse {
+    fn from(response: ChatResponse) -> Self {
+        Self {
+            text: response.content.all_text(),
+            model: response.model,
+            usage: response.usage,
+            finish_reason: response.finish_reason,
+            metadata: response.metadata,
+        }
+    }
+}
+
 // Keep for backward compatibility
-pub type ImageGenRequest = ();
-pub type ImageResponse = ();
-pub type VisionRequest = ();
-pub type VisionResponse = ();
+pub type ImageGenRequest = ImageGenerationRequest;
+pub type ImageResponse = ImageGenerationResponse;
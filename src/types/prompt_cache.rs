@@ -0,0 +1,171 @@
+//! Provider-agnostic prompt caching policy
+//!
+//! A [`PromptCachePolicy`] describes *what* part of a request should be cached;
+//! each provider translates it into its native mechanism:
+//!
+//! - Anthropic: `cache_control` breakpoints on the system prompt, tools and messages
+//! - OpenAI: `prompt_cache_key` routing hint (prefix caching itself is automatic)
+//! - Gemini, Groq, xAI: implicit prefix caching, hits are reported in [`Usage`](super::Usage)
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::{CacheControl, ChatMessage, MessageRole};
+
+/// Prompt caching policy attached to a [`ChatRequest`](super::ChatRequest)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptCachePolicy {
+    /// Cache the system prompt
+    pub cache_system: bool,
+    /// Cache the tool definitions
+    pub cache_tools: bool,
+    /// Cache the first N non-system messages of the conversation
+    pub prefix_messages: Option<usize>,
+    /// Explicit breakpoints, as indices into the request messages
+    pub breakpoints: Vec<usize>,
+    /// Cache key used by providers that route on it (OpenAI `prompt_cache_key`)
+    pub cache_key: Option<String>,
+    /// Requested cache lifetime; providers fall back to their default when unsupported
+    pub ttl: Option<Duration>,
+}
+
+impl PromptCachePolicy {
+    /// Cache the system prompt and tool definitions
+    pub fn system_prompt() -> Self {
+        Self {
+            cache_system: true,
+            cache_tools: true,
+            ..Default::default()
+        }
+    }
+
+    /// Cache the system prompt, tool definitions and the first `n` messages
+    pub fn prefix(n: usize) -> Self {
+        Self {
+            prefix_messages: Some(n),
+            ..Self::system_prompt()
+        }
+    }
+
+    /// Place cache breakpoints after the given message indices
+    pub fn breakpoints(indices: Vec<usize>) -> Self {
+        Self {
+            breakpoints: indices,
+            ..Default::default()
+        }
+    }
+
+    /// Enable or disable caching of tool definitions
+    pub const fn with_tools(mut self, cache_tools: bool) -> Self {
+        self.cache_tools = cache_tools;
+        self
+    }
+
+    /// Set the cache key
+    pub fn with_cache_key<S: Into<String>>(mut self, key: S) -> Self {
+        self.cache_key = Some(key.into());
+        self
+    }
+
+    /// Set the requested cache lifetime
+    pub const fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Cache control marker used for breakpoints produced by this policy
+    pub const fn cache_control(&self) -> CacheControl {
+        match self.ttl {
+            Some(ttl) => CacheControl::Persistent { ttl: Some(ttl) },
+            None => CacheControl::Ephemeral,
+        }
+    }
+
+    /// Indices of the messages that end a cached prefix, sorted and deduplicated
+    pub fn breakpoint_indices(&self, messages: &[ChatMessage]) -> Vec<usize> {
+        let mut indices: Vec<usize> = self
+            .breakpoints
+            .iter()
+            .copied()
+            .filter(|&i| i < messages.len())
+            .collect();
+
+        if self.cache_system
+            && let Some(last_system) = messages
+                .iter()
+                .rposition(|m| matches!(m.role, MessageRole::System | MessageRole::Developer))
+        {
+            indices.push(last_system);
+        }
+
+        if let Some(n) = self.prefix_messages
+            && n > 0
+            && let Some((index, _)) = messages
+                .iter()
+                .enumerate()
+                .filter(|(_, m)| !matches!(m.role, MessageRole::System | MessageRole::Developer))
+                .take(n)
+                .last()
+        {
+            indices.push(index);
+        }
+
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+    }
+
+    /// Mark breakpoint messages with a cache control, keeping markers set by the caller
+    pub fn apply(&self, messages: &mut [ChatMessage]) {
+        let control = self.cache_control();
+        for index in self.breakpoint_indices(messages) {
+            let metadata = &mut messages[index].metadata;
+            if metadata.cache_control.is_none() {
+                metadata.cache_control = Some(control.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conversation() -> Vec<ChatMessage> {
+        vec![
+            ChatMessage::system("You are helpful").build(),
+            ChatMessage::user("first").build(),
+            ChatMessage::assistant("second").build(),
+            ChatMessage::user("third").build(),
+        ]
+    }
+
+    #[test]
+    fn test_prefix_policy_marks_system_and_nth_message() {
+        let policy = PromptCachePolicy::prefix(2);
+        assert_eq!(policy.breakpoint_indices(&conversation()), vec![0, 2]);
+
+        let policy = PromptCachePolicy::prefix(10);
+        assert_eq!(policy.breakpoint_indices(&conversation()), vec![0, 3]);
+    }
+
+    #[test]
+    fn test_apply_keeps_existing_markers() {
+        let mut messages = conversation();
+        messages[3].metadata.cache_control = Some(CacheControl::Persistent { ttl: None });
+
+        PromptCachePolicy::breakpoints(vec![1, 3, 7])
+            .with_ttl(Duration::from_secs(3600))
+            .apply(&mut messages);
+
+        assert!(messages[0].metadata.cache_control.is_none());
+        assert!(matches!(
+            messages[1].metadata.cache_control,
+            Some(CacheControl::Persistent { ttl: Some(_) })
+        ));
+        assert!(matches!(
+            messages[3].metadata.cache_control,
+            Some(CacheControl::Persistent { ttl: None })
+        ));
+    }
+}
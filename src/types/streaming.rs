@@ -1,6 +1,7 @@
 //! Streaming event types for real-time responses
 
 use super::chat::ChatResponse;
+use super::citation::{Citation, SearchSource};
 use super::common::FinishReason;
 use super::common::{ResponseMetadata, Usage};
 use crate::error::LlmError;
@@ -39,6 +40,16 @@ pub enum ChatStreamEvent {
         /// Token usage information
         usage: Usage,
     },
+    /// Citation supporting part of the response text
+    CitationDelta {
+        /// The citation
+        citation: Citation,
+    },
+    /// Web page consulted by search or grounding
+    SourceDelta {
+        /// The source
+        source: SearchSource,
+    },
     /// Stream start event with metadata
     StreamStart {
         /// Response metadata
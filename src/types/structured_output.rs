@@ -0,0 +1,763 @@
+//! Structured output types
+//!
+//! Provider-agnostic JSON schema description used by
+//! `ChatCapability::chat_with_schema` and `ChatExtensions::generate_object`.
+//! Each provider maps an `OutputSchema` to its native mechanism (`OpenAI`
+//! `response_format`, Gemini `responseSchema`, Anthropic forced tool use,
+//! Ollama `format`).
+
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::ChatResponse;
+use crate::error::LlmError;
+
+/// Maximum `$ref` nesting followed when inlining definitions (guards recursive types)
+const MAX_REF_DEPTH: usize = 32;
+
+/// JSON schema describing the expected output of a chat request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputSchema {
+    /// Schema name (`[a-zA-Z0-9_-]`, at most 64 characters)
+    pub name: String,
+    /// Optional description of the expected output
+    pub description: Option<String>,
+    /// JSON schema of the output
+    pub schema: Value,
+    /// Whether providers should enforce the schema strictly
+    pub strict: bool,
+    /// Whether the schema was derived from a type and may be rewritten for strict mode
+    #[serde(default)]
+    derived: bool,
+}
+
+impl OutputSchema {
+    /// Create a new output schema
+    ///
+    /// The schema is sent to providers as given.
+    pub fn new<S: Into<String>>(name: S, schema: Value) -> Self {
+        Self {
+            name: sanitize_name(&name.into()),
+            description: None,
+            schema,
+            strict: true,
+            derived: false,
+        }
+    }
+
+    /// Derive the output schema from a type implementing `JsonSchema`
+    pub fn from_type<T: JsonSchema>() -> Self {
+        let schema = serde_json::to_value(schemars::schema_for!(T)).unwrap_or(Value::Bool(true));
+        let description = schema
+            .get("description")
+            .and_then(Value::as_str)
+            .map(ToString::to_string);
+
+        Self {
+            name: sanitize_name(&T::schema_name()),
+            description,
+            schema,
+            strict: true,
+            derived: true,
+        }
+    }
+
+    /// Set the schema name
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = sanitize_name(&name.into());
+        self
+    }
+
+    /// Set the description
+    pub fn with_description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Enable or disable strict mode
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Schema with all `$ref`s inlined and definition blocks removed
+    pub fn resolved_schema(&self) -> Value {
+        let defs = self
+            .schema
+            .get("$defs")
+            .or_else(|| self.schema.get("definitions"))
+            .cloned()
+            .unwrap_or(Value::Null);
+
+        let mut resolved = resolve_refs(&self.schema, &defs, 0);
+        if let Value::Object(obj) = &mut resolved {
+            obj.remove("$schema");
+            obj.remove("$defs");
+            obj.remove("definitions");
+        }
+        resolved
+    }
+
+    /// Schema prepared for `OpenAI` strict mode
+    ///
+    /// For schemas derived with [`from_type`](Self::from_type), every object
+    /// gets `additionalProperties: false` and lists all of its properties as
+    /// required, and keywords rejected by strict mode are dropped. Schemas
+    /// created with [`new`](Self::new) are sent as given.
+    pub fn strict_schema(&self) -> Value {
+        if !self.derived {
+            return self.schema.clone();
+        }
+        let mut schema = self.schema.clone();
+        if let Value::Object(obj) = &mut schema {
+            obj.remove("$schema");
+        }
+        if self.strict {
+            make_strict(&mut schema);
+        }
+        schema
+    }
+
+    /// `OpenAI`-compatible `response_format` value (also used by Groq and xAI)
+    pub fn to_openai_response_format(&self) -> Value {
+        let mut json_schema = serde_json::json!({
+            "name": self.name,
+            "schema": self.strict_schema(),
+            "strict": self.strict,
+        });
+        if let Some(description) = &self.description {
+            json_schema["description"] = Value::String(description.clone());
+        }
+
+        serde_json::json!({
+            "type": "json_schema",
+            "json_schema": json_schema,
+        })
+    }
+
+    /// Gemini `responseSchema` value (`OpenAPI` schema subset)
+    pub fn to_gemini_schema(&self) -> Value {
+        to_openapi_schema(&self.resolved_schema())
+    }
+
+    /// Prompt instructions for providers without native structured output
+    pub fn instructions(&self) -> String {
+        let schema = serde_json::to_string_pretty(&self.resolved_schema()).unwrap_or_default();
+        let mut instructions = String::from(
+            "Respond only with a JSON value that conforms to the following JSON schema. \
+             Do not include explanations or markdown code fences.",
+        );
+        if let Some(description) = &self.description {
+            instructions.push_str(&format!("\n\nOutput description: {description}"));
+        }
+        instructions.push_str(&format!("\n\nJSON schema:\n{schema}"));
+        instructions
+    }
+
+    /// Validate a JSON value against the schema
+    pub fn validate(&self, value: &Value) -> Result<(), LlmError> {
+        validate_value(value, &self.resolved_schema(), "$")
+            .map_err(|e| LlmError::ParseError(format!("Schema validation failed: {e}")))
+    }
+
+    /// Extract, validate and deserialize the JSON output of a response
+    pub fn parse_response<T: DeserializeOwned>(
+        &self,
+        response: &ChatResponse,
+    ) -> Result<T, LlmError> {
+        let text = response.content.all_text();
+        let json = extract_json(&text)
+            .ok_or_else(|| LlmError::ParseError("No JSON found in response".to_string()))?;
+
+        let value: Value = serde_json::from_str(json)
+            .map_err(|e| LlmError::ParseError(format!("Invalid JSON: {e}")))?;
+        self.validate(&value)?;
+
+        serde_json::from_value(value)
+            .map_err(|e| LlmError::ParseError(format!("Failed to deserialize output: {e}")))
+    }
+}
+
+/// Options for `ChatExtensions::generate_object_with_options`
+#[derive(Debug, Clone)]
+pub struct GenerateObjectOptions {
+    /// Override the schema name derived from the type
+    pub name: Option<String>,
+    /// Description of the expected output
+    pub description: Option<String>,
+    /// Whether providers should enforce the schema strictly
+    pub strict: bool,
+    /// Number of repair rounds when the output fails validation
+    pub max_repair_attempts: u32,
+}
+
+impl Default for GenerateObjectOptions {
+    fn default() -> Self {
+        Self {
+            name: None,
+            description: None,
+            strict: true,
+            max_repair_attempts: 1,
+        }
+    }
+}
+
+impl GenerateObjectOptions {
+    /// Create default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the schema name
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the description
+    pub fn with_description<S: Into<String>>(mut self, description: S) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Enable or disable strict mode
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Set the number of repair rounds
+    pub const fn with_max_repair_attempts(mut self, attempts: u32) -> Self {
+        self.max_repair_attempts = attempts;
+        self
+    }
+
+    /// Build the output schema for `T`
+    pub fn schema_for<T: JsonSchema>(&self) -> OutputSchema {
+        let mut schema = OutputSchema::from_type::<T>().with_strict(self.strict);
+        if let Some(name) = &self.name {
+            schema = schema.with_name(name.clone());
+        }
+        if let Some(description) = &self.description {
+            schema = schema.with_description(description.clone());
+        }
+        schema
+    }
+}
+
+/// Typed structured output together with the raw response
+#[derive(Debug, Clone)]
+pub struct ObjectResponse<T> {
+    /// Deserialized output
+    pub object: T,
+    /// Final chat response
+    pub response: ChatResponse,
+    /// Number of model calls made (1 + repair rounds)
+    pub attempts: u32,
+}
+
+/// Extract the JSON payload from model output, tolerating code fences and surrounding prose
+pub fn extract_json(text: &str) -> Option<&str> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    // Markdown code fence
+    if let Some(start) = trimmed.find("```") {
+        let after = &trimmed[start + 3..];
+        let body_start = after.find('\n').map_or(0, |i| i + 1);
+        let body = &after[body_start..];
+        if let Some(end) = body.find("```") {
+            return Some(body[..end].trim());
+        }
+    }
+
+    if serde_json::from_str::<Value>(trimmed).is_ok() {
+        return Some(trimmed);
+    }
+
+    // First object/array through the matching last closing bracket
+    let start = trimmed.find(['{', '['])?;
+    let close = if trimmed[start..].starts_with('{') {
+        '}'
+    } else {
+        ']'
+    };
+    let end = trimmed.rfind(close)?;
+    (end > start).then(|| &trimmed[start..=end])
+}
+
+fn sanitize_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(64)
+        .collect();
+
+    if sanitized.is_empty() {
+        "output".to_string()
+    } else {
+        sanitized
+    }
+}
+
+fn resolve_refs(value: &Value, defs: &Value, depth: usize) -> Value {
+    match value {
+        Value::Object(obj) => {
+            if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+                let name = reference.rsplit('/').next().unwrap_or_default();
+                let target = match defs.get(name) {
+                    Some(target) if depth < MAX_REF_DEPTH => resolve_refs(target, defs, depth + 1),
+                    _ => Value::Object(Map::new()),
+                };
+
+                // Sibling keywords (e.g. description) override the referenced schema
+                let mut merged = match target {
+                    Value::Object(map) => map,
+                    _ => Map::new(),
+                };
+                for (key, val) in obj {
+                    if key != "$ref" {
+                        merged.insert(key.clone(), resolve_refs(val, defs, depth));
+                    }
+                }
+                return Value::Object(merged);
+            }
+
+            Value::Object(
+                obj.iter()
+                    .map(|(k, v)| (k.clone(), resolve_refs(v, defs, depth)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => {
+            Value::Array(items.iter().map(|v| resolve_refs(v, defs, depth)).collect())
+        }
+        other => other.clone(),
+    }
+}
+
+fn make_strict(value: &mut Value) {
+    match value {
+        Value::Object(obj) => {
+            obj.remove("default");
+            if obj
+                .get("format")
+                .and_then(Value::as_str)
+                .is_some_and(|f| !is_openai_string_format(f))
+            {
+                obj.remove("format");
+            }
+
+            if let Some(Value::Object(properties)) = obj.get("properties") {
+                let required: Vec<Value> = properties
+                    .keys()
+                    .map(|k| Value::String(k.clone()))
+                    .collect();
+                obj.insert("required".to_string(), Value::Array(required));
+                obj.insert("additionalProperties".to_string(), Value::Bool(false));
+            }
+
+            for (key, child) in obj.iter_mut() {
+                match key.as_str() {
+                    // Maps from names to schemas
+                    "properties" | "$defs" | "definitions" => {
+                        if let Value::Object(map) = child {
+                            map.values_mut().for_each(make_strict);
+                        }
+                    }
+                    // Data, not schemas
+                    "enum" | "const" | "required" | "examples" => {}
+                    _ => make_strict(child),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(make_strict),
+        _ => {}
+    }
+}
+
+fn is_openai_string_format(format: &str) -> bool {
+    matches!(
+        format,
+        "date-time"
+            | "time"
+            | "date"
+            | "duration"
+            | "email"
+            | "hostname"
+            | "ipv4"
+            | "ipv6"
+            | "uuid"
+    )
+}
+
+fn to_openapi_schema(schema: &Value) -> Value {
+    let Value::Object(obj) = schema else {
+        return Value::Object(Map::new());
+    };
+
+    let mut out = Map::new();
+    let mut nullable = false;
+
+    match obj.get("type") {
+        Some(Value::String(t)) => {
+            out.insert("type".to_string(), Value::String(t.to_uppercase()));
+        }
+        Some(Value::Array(types)) => {
+            let non_null: Vec<&str> = types
+                .iter()
+                .filter_map(Value::as_str)
+                .filter(|t| *t != "null")
+                .collect();
+            nullable = non_null.len() < types.len();
+            if let Some(t) = non_null.first() {
+                out.insert("type".to_string(), Value::String(t.to_uppercase()));
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(variants) = obj
+        .get("anyOf")
+        .or_else(|| obj.get("oneOf"))
+        .and_then(Value::as_array)
+    {
+        let mut branches: Vec<Value> = Vec::new();
+        for variant in variants {
+            if variant.get("type").and_then(Value::as_str) == Some("null") {
+                nullable = true;
+            } else {
+                branches.push(to_openapi_schema(variant));
+            }
+        }
+        if branches.len() == 1 {
+            if let Value::Object(branch) = branches.remove(0) {
+                out.extend(branch);
+            }
+        } else if !branches.is_empty() {
+            out.insert("anyOf".to_string(), Value::Array(branches));
+        }
+    }
+
+    if let Some(constant) = obj.get("const") {
+        out.insert("enum".to_string(), Value::Array(vec![constant.clone()]));
+        if constant.is_string() {
+            out.entry("type".to_string())
+                .or_insert_with(|| Value::String("STRING".to_string()));
+        }
+    }
+
+    if let Some(Value::Object(properties)) = obj.get("properties") {
+        let converted: Map<String, Value> = properties
+            .iter()
+            .map(|(k, v)| (k.clone(), to_openapi_schema(v)))
+            .collect();
+        out.insert("properties".to_string(), Value::Object(converted));
+    }
+
+    if let Some(items) = obj.get("items") {
+        out.insert("items".to_string(), to_openapi_schema(items));
+    }
+
+    for key in [
+        "description",
+        "enum",
+        "required",
+        "minItems",
+        "maxItems",
+        "minimum",
+        "maximum",
+    ] {
+        if let Some(value) = obj.get(key) {
+            out.insert(key.to_string(), value.clone());
+        }
+    }
+
+    if let Some(format) = obj.get("format").and_then(Value::as_str)
+        && matches!(format, "int32" | "int64" | "float" | "double" | "date-time")
+    {
+        out.insert("format".to_string(), Value::String(format.to_string()));
+    }
+
+    if nullable {
+        out.insert("nullable".to_string(), Value::Bool(true));
+    }
+
+    Value::Object(out)
+}
+
+fn validate_value(value: &Value, schema: &Value, path: &str) -> Result<(), String> {
+    let obj = match schema {
+        Value::Bool(true) => return Ok(()),
+        Value::Bool(false) => return Err(format!("{path}: no value is allowed")),
+        Value::Object(obj) => obj,
+        _ => return Ok(()),
+    };
+
+    for key in ["anyOf", "oneOf"] {
+        if let Some(variants) = obj.get(key).and_then(Value::as_array)
+            && !variants
+                .iter()
+                .any(|variant| validate_value(value, variant, path).is_ok())
+        {
+            return Err(format!("{path}: value does not match any allowed schema"));
+        }
+    }
+
+    if let Some(all) = obj.get("allOf").and_then(Value::as_array) {
+        for variant in all {
+            validate_value(value, variant, path)?;
+        }
+    }
+
+    if let Some(allowed) = obj.get("enum").and_then(Value::as_array)
+        && !allowed.contains(value)
+    {
+        return Err(format!("{path}: value {value} is not one of {allowed:?}"));
+    }
+
+    if let Some(constant) = obj.get("const")
+        && constant != value
+    {
+        return Err(format!("{path}: expected constant {constant}"));
+    }
+
+    match obj.get("type") {
+        Some(Value::String(t)) if !type_matches(value, t) => {
+            return Err(format!("{path}: expected {t}"));
+        }
+        Some(Value::Array(types))
+            if !types
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|t| type_matches(value, t)) =>
+        {
+            return Err(format!("{path}: expected one of {types:?}"));
+        }
+        _ => {}
+    }
+
+    if let Value::Object(map) = value {
+        if let Some(required) = obj.get("required").and_then(Value::as_array) {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !map.contains_key(name) {
+                    return Err(format!("{path}: missing required property '{name}'"));
+                }
+            }
+        }
+
+        let properties = obj.get("properties").and_then(Value::as_object);
+        for (key, child) in map {
+            match properties.and_then(|p| p.get(key)) {
+                Some(child_schema) => {
+                    validate_value(child, child_schema, &format!("{path}.{key}"))?;
+                }
+                None if obj.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                    return Err(format!("{path}: unexpected property '{key}'"));
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let Value::Array(items) = value {
+        if let Some(min) = obj.get("minItems").and_then(Value::as_u64)
+            && (items.len() as u64) < min
+        {
+            return Err(format!("{path}: expected at least {min} items"));
+        }
+        if let Some(max) = obj.get("maxItems").and_then(Value::as_u64)
+            && (items.len() as u64) > max
+        {
+            return Err(format!("{path}: expected at most {max} items"));
+        }
+        if let Some(item_schema) = obj.get("items") {
+            for (i, item) in items.iter().enumerate() {
+                validate_value(item, item_schema, &format!("{path}[{i}]"))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn type_matches(value: &Value, expected: &str) -> bool {
+    match expected {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|f| f.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MessageContent;
+
+    #[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+    struct Address {
+        city: String,
+    }
+
+    #[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+    struct Person {
+        name: String,
+        age: u32,
+        nickname: Option<String>,
+        address: Address,
+    }
+
+    #[test]
+    fn test_output_schema_from_type() {
+        let schema = OutputSchema::from_type::<Person>();
+        assert_eq!(schema.name, "Person");
+        assert!(schema.strict);
+
+        let resolved = schema.resolved_schema();
+        assert!(resolved.get("$defs").is_none());
+        assert_eq!(resolved["properties"]["address"]["type"], "object");
+    }
+
+    #[test]
+    fn test_openai_strict_schema() {
+        let format = OutputSchema::from_type::<Person>().to_openai_response_format();
+        let schema = &format["json_schema"]["schema"];
+
+        assert_eq!(format["type"], "json_schema");
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(schema["required"].as_array().unwrap().len(), 4);
+        assert!(schema["properties"]["age"].get("format").is_none());
+    }
+
+    #[test]
+    fn test_user_schema_sent_unchanged() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {"result": {"type": "string", "default": "none"}}
+        });
+        let format = OutputSchema::new("answer", schema.clone()).to_openai_response_format();
+
+        assert_eq!(format["json_schema"]["schema"], schema);
+        assert_eq!(format["json_schema"]["strict"], true);
+    }
+
+    #[test]
+    fn test_gemini_schema_conversion() {
+        let schema = OutputSchema::from_type::<Person>().to_gemini_schema();
+
+        assert_eq!(schema["type"], "OBJECT");
+        assert_eq!(schema["properties"]["nickname"]["type"], "STRING");
+        assert_eq!(schema["properties"]["nickname"]["nullable"], true);
+        assert_eq!(schema["properties"]["address"]["type"], "OBJECT");
+        assert!(schema.get("additionalProperties").is_none());
+    }
+
+    #[test]
+    fn test_validation() {
+        let schema = OutputSchema::from_type::<Person>();
+
+        let valid = serde_json::json!({
+            "name": "Ada", "age": 36, "nickname": null, "address": {"city": "London"}
+        });
+        assert!(schema.validate(&valid).is_ok());
+
+        let invalid =
+            serde_json::json!({"name": "Ada", "age": "36", "address": {"city": "London"}});
+        assert!(schema.validate(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_parse_response_with_code_fence() {
+        let schema = OutputSchema::from_type::<Address>();
+        let response = ChatResponse::new(MessageContent::Text(
+            "Here you go:\n```json\n{\"city\": \"Paris\"}\n```".to_string(),
+        ));
+
+        let address: Address = schema.parse_response(&response).unwrap();
+        assert_eq!(address.city, "Paris");
+    }
+
+    #[tokio::test]
+    async fn test_generate_object_repairs_invalid_output() {
+        use crate::stream::ChatStream;
+        use crate::traits::{ChatCapability, ChatExtensions};
+        use crate::types::{ChatMessage, Tool};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct FlakyClient {
+            calls: AtomicUsize,
+        }
+
+        #[async_trait::async_trait]
+        impl ChatCapability for FlakyClient {
+            async fn chat_with_tools(
+                &self,
+                _messages: Vec<ChatMessage>,
+                _tools: Option<Vec<Tool>>,
+            ) -> Result<ChatResponse, LlmError> {
+                let text = if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    r#"{"town": "Paris"}"#
+                } else {
+                    r#"{"city": "Paris"}"#
+                };
+                Ok(ChatResponse::new(MessageContent::Text(text.to_string())))
+            }
+
+            async fn chat_stream(
+                &self,
+                _messages: Vec<ChatMessage>,
+                _tools: Option<Vec<Tool>>,
+            ) -> Result<ChatStream, LlmError> {
+                Err(LlmError::UnsupportedOperation("streaming".to_string()))
+            }
+        }
+
+        let client = FlakyClient {
+            calls: AtomicUsize::new(0),
+        };
+        let result = client
+            .generate_object_with_options::<Address>(
+                vec![ChatMessage::user("Where is the Louvre?").build()],
+                GenerateObjectOptions::new(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.object.city, "Paris");
+        assert_eq!(result.attempts, 2);
+
+        let without_repair = FlakyClient {
+            calls: AtomicUsize::new(0),
+        }
+        .generate_object_with_options::<Address>(
+            vec![ChatMessage::user("Where is the Louvre?").build()],
+            GenerateObjectOptions::new().with_max_repair_attempts(0),
+        )
+        .await;
+        assert!(without_repair.is_err());
+    }
+
+    #[test]
+    fn test_extract_json() {
+        assert_eq!(extract_json("  {\"a\": 1} "), Some("{\"a\": 1}"));
+        assert_eq!(extract_json("Result: [1, 2] done"), Some("[1, 2]"));
+        assert_eq!(extract_json("no json"), None);
+    }
+}
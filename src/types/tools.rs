@@ -37,6 +37,18 @@ impl Tool {
             },
         }
     }
+
+    /// Create a function tool whose parameter schema is derived from `Args`
+    pub fn from_type<Args: schemars::JsonSchema>(
+        name: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        let mut parameters = super::OutputSchema::from_type::<Args>().resolved_schema();
+        if let serde_json::Value::Object(obj) = &mut parameters {
+            obj.remove("title");
+        }
+        Self::function(name.into(), description.into(), parameters)
+    }
 }
 
 /// Tool function definition
@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::SearchSource;
+
 /// Web search configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebSearchConfig {
@@ -66,3 +68,14 @@ pub struct WebSearchResult {
     /// Additional metadata
     pub metadata: HashMap<String, serde_json::Value>,
 }
+
+impl From<SearchSource> for WebSearchResult {
+    fn from(source: SearchSource) -> Self {
+        Self {
+            title: source.title.unwrap_or_default(),
+            url: source.url,
+            snippet: source.snippet.unwrap_or_default(),
+            metadata: HashMap::new(),
+        }
+    }
+}
@@ -6,16 +6,24 @@
 
 use crate::error::LlmError;
 use crate::stream::{ChatStream, ChatStreamEvent};
+use crate::types::{AudioStream, AudioStreamEvent, Citation, SearchSource};
 use crate::utils::sse_stream::SseStreamExt;
 use eventsource_stream::Event;
-use futures_util::StreamExt;
+use futures_util::{Stream, StreamExt};
+use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 /// Type alias for SSE event conversion future
 type SseEventFuture<'a> =
     Pin<Box<dyn Future<Output = Option<Result<ChatStreamEvent, LlmError>>> + Send + Sync + 'a>>;
 
+/// Type alias for multi-event SSE conversion future
+type SseEventsFuture<'a> =
+    Pin<Box<dyn Future<Output = Vec<Result<ChatStreamEvent, LlmError>>> + Send + Sync + 'a>>;
+
 /// Type alias for JSON event conversion future
 type JsonEventFuture<'a> =
     Pin<Box<dyn Future<Output = Option<Result<ChatStreamEvent, LlmError>>> + Send + Sync + 'a>>;
@@ -25,6 +33,14 @@ pub trait SseEventConverter: Send + Sync {
     /// Convert an SSE event to a ChatStreamEvent
     fn convert_event(&self, event: Event) -> SseEventFuture<'_>;
 
+    /// Convert an SSE event to any number of ChatStreamEvents
+    ///
+    /// Override when a single provider event carries several updates, such as
+    /// text together with grounding metadata. Defaults to `convert_event`.
+    fn convert_events(&self, event: Event) -> SseEventsFuture<'_> {
+        Box::pin(async move { self.convert_event(event).await.into_iter().collect() })
+    }
+
     /// Handle the end of stream
     fn handle_stream_end(&self) -> Option<Result<ChatStreamEvent, LlmError>> {
         None
@@ -37,6 +53,39 @@ pub trait JsonEventConverter: Send + Sync {
     fn convert_json<'a>(&'a self, json_data: &'a str) -> JsonEventFuture<'a>;
 }
 
+/// Combine a converted event with the citations and sources carried by the same provider event
+///
+/// Sources come first, then citations; both are emitted after content deltas but
+/// before a `StreamEnd`, whose response also receives them.
+pub fn with_grounding_events(
+    event: Option<ChatStreamEvent>,
+    citations: Vec<Citation>,
+    sources: Vec<SearchSource>,
+) -> Vec<ChatStreamEvent> {
+    let grounding = sources
+        .iter()
+        .cloned()
+        .map(|source| ChatStreamEvent::SourceDelta { source })
+        .chain(
+            citations
+                .iter()
+                .cloned()
+                .map(|citation| ChatStreamEvent::CitationDelta { citation }),
+        );
+
+    match event {
+        Some(ChatStreamEvent::StreamEnd { mut response }) => {
+            response.citations.extend(citations.iter().cloned());
+            SearchSource::merge(&mut response.sources, sources.iter().cloned());
+            grounding
+                .chain(std::iter::once(ChatStreamEvent::StreamEnd { response }))
+                .collect()
+        }
+        Some(event) => std::iter::once(event).chain(grounding).collect(),
+        None => grounding.collect(),
+    }
+}
+
 /// Stream factory for creating provider-specific streams
 ///
 /// This factory provides utilities for creating SSE and JSON streams
@@ -97,14 +146,26 @@ impl StreamFactory {
     where
         C: SseEventConverter + Clone + Send + 'static,
     {
-        use crate::utils::sse_stream::SseStreamExt;
-
         // Send the request and get the response
         let response = request_builder
             .send()
             .await
             .map_err(|e| LlmError::HttpError(format!("Failed to send request: {e}")))?;
 
+        Self::create_eventsource_stream_from_response(response, converter).await
+    }
+
+    /// Create a chat stream from an already sent SSE request
+    ///
+    /// Used when the request has to go through the rate limiter before it is
+    /// sent; otherwise identical to [`StreamFactory::create_eventsource_stream`].
+    pub async fn create_eventsource_stream_from_response<C>(
+        response: reqwest::Response,
+        converter: C,
+    ) -> Result<ChatStream, LlmError>
+    where
+        C: SseEventConverter + Clone + Send + 'static,
+    {
         // Check if the response is successful
         if !response.status().is_success() {
             let status = response.status();
@@ -125,33 +186,112 @@ impl StreamFactory {
         let sse_stream = byte_stream.into_sse_stream();
 
         // Convert SSE events to ChatStreamEvents
-        let chat_stream = sse_stream.filter_map(move |event_result| {
-            let converter = converter.clone();
-            async move {
-                match event_result {
-                    Ok(event) => {
-                        // Handle special [DONE] event
-                        if event.data.trim() == "[DONE]" {
-                            return converter.handle_stream_end();
-                        }
+        let chat_stream = sse_stream
+            .then(move |event_result| {
+                let converter = converter.clone();
+                async move {
+                    match event_result {
+                        Ok(event) => {
+                            // Handle special [DONE] event
+                            if event.data.trim() == "[DONE]" {
+                                return converter.handle_stream_end().into_iter().collect();
+                            }
 
-                        // Skip empty events
-                        if event.data.trim().is_empty() {
-                            return None;
-                        }
+                            // Skip empty events
+                            if event.data.trim().is_empty() {
+                                return Vec::new();
+                            }
 
-                        // Convert using provider-specific logic
-                        converter.convert_event(event).await
+                            // Convert using provider-specific logic
+                            converter.convert_events(event).await
+                        }
+                        Err(e) => vec![Err(LlmError::StreamError(format!(
+                            "SSE parsing error: {e}"
+                        )))],
                     }
-                    Err(e) => Some(Err(LlmError::StreamError(format!(
-                        "SSE parsing error: {e}"
-                    )))),
                 }
-            }
-        });
+            })
+            .flat_map(futures_util::stream::iter);
 
         Ok(Box::pin(chat_stream))
     }
+
+    /// Create an audio stream from a chunked binary HTTP response
+    ///
+    /// Emits a `Metadata` event, one `AudioDelta` per received chunk and a final
+    /// `Done` event carrying the total byte count. `sample_rate` should only be
+    /// given for raw PCM output; it is used to compute the duration.
+    pub fn create_audio_stream(
+        response: reqwest::Response,
+        format: String,
+        sample_rate: Option<u32>,
+        metadata: HashMap<String, serde_json::Value>,
+    ) -> AudioStream {
+        let byte_stream = response.bytes_stream().map(|chunk| {
+            chunk.map_err(|e| LlmError::StreamError(format!("Audio stream error: {e}")))
+        });
+
+        Self::create_audio_stream_from_bytes(byte_stream, format, sample_rate, metadata)
+    }
+
+    /// Create an audio stream from any stream of audio byte chunks
+    pub fn create_audio_stream_from_bytes<S>(
+        byte_stream: S,
+        format: String,
+        sample_rate: Option<u32>,
+        metadata: HashMap<String, serde_json::Value>,
+    ) -> AudioStream
+    where
+        S: Stream<Item = Result<bytes::Bytes, LlmError>> + Send + Sync + 'static,
+    {
+        let total_bytes = Arc::new(AtomicUsize::new(0));
+        let failed = Arc::new(AtomicBool::new(false));
+
+        let start = futures::stream::once(futures::future::ready(Ok(AudioStreamEvent::Metadata {
+            sample_rate,
+            duration: None,
+            metadata: metadata.clone(),
+        })));
+
+        let delta_bytes = total_bytes.clone();
+        let delta_failed = failed.clone();
+        let stop_failed = failed.clone();
+        // Nothing is read past the first error
+        let deltas = byte_stream
+            .take_while(move |_| futures::future::ready(!stop_failed.load(Ordering::Relaxed)))
+            .filter_map(move |chunk| {
+                let event = match chunk {
+                    Ok(bytes) if bytes.is_empty() => None,
+                    Ok(bytes) => {
+                        delta_bytes.fetch_add(bytes.len(), Ordering::Relaxed);
+                        Some(Ok(AudioStreamEvent::AudioDelta {
+                            data: bytes.to_vec(),
+                            format: format.clone(),
+                        }))
+                    }
+                    Err(e) => {
+                        delta_failed.store(true, Ordering::Relaxed);
+                        Some(Err(e))
+                    }
+                };
+                futures::future::ready(event)
+            });
+
+        let done = futures::stream::once(futures::future::lazy(move |_| {
+            if failed.load(Ordering::Relaxed) {
+                return None;
+            }
+            let total_bytes = total_bytes.load(Ordering::Relaxed);
+            // 16-bit mono PCM
+            let duration = sample_rate.map(|rate| total_bytes as f32 / (rate as f32 * 2.0));
+            let mut metadata = metadata;
+            metadata.insert("total_bytes".to_string(), serde_json::json!(total_bytes));
+            Some(Ok(AudioStreamEvent::Done { duration, metadata }))
+        }))
+        .filter_map(futures::future::ready);
+
+        Box::pin(start.chain(deltas).chain(done))
+    }
 }
 
 /// Helper macro to create SSE event converters
@@ -217,3 +357,65 @@ macro_rules! impl_json_converter {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_create_audio_stream_from_bytes() {
+        let chunks = futures::stream::iter(vec![
+            Ok(bytes::Bytes::from_static(&[0u8; 4800])),
+            Ok(bytes::Bytes::new()),
+            Ok(bytes::Bytes::from_static(&[0u8; 43200])),
+        ]);
+        let stream = StreamFactory::create_audio_stream_from_bytes(
+            chunks,
+            "pcm".to_string(),
+            Some(24000),
+            HashMap::new(),
+        );
+        let events: Vec<_> = stream.collect().await;
+
+        assert_eq!(events.len(), 4);
+        assert!(matches!(events[0], Ok(AudioStreamEvent::Metadata { .. })));
+        match &events[1] {
+            Ok(AudioStreamEvent::AudioDelta { data, format }) => {
+                assert_eq!(data.len(), 4800);
+                assert_eq!(format, "pcm");
+            }
+            other => panic!("Expected AudioDelta, got {other:?}"),
+        }
+        match &events[3] {
+            Ok(AudioStreamEvent::Done { duration, metadata }) => {
+                assert_eq!(*duration, Some(1.0));
+                assert_eq!(metadata["total_bytes"], 48000);
+            }
+            other => panic!("Expected Done, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_audio_stream_stops_after_error() {
+        let chunks = futures::stream::iter(vec![
+            Ok(bytes::Bytes::from_static(b"abc")),
+            Err(LlmError::StreamError("connection reset".to_string())),
+            Ok(bytes::Bytes::from_static(b"def")),
+        ]);
+        let stream = StreamFactory::create_audio_stream_from_bytes(
+            chunks,
+            "mp3".to_string(),
+            None,
+            HashMap::new(),
+        );
+        let events: Vec<_> = stream.collect().await;
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], Ok(AudioStreamEvent::Metadata { .. })));
+        assert!(matches!(
+            &events[1],
+            Ok(AudioStreamEvent::AudioDelta { data, .. }) if data == b"abc"
+        ));
+        assert!(events[2].is_err());
+    }
+}
@@ -3,17 +3,27 @@
 //! This module provides unified web search capabilities across different AI providers.
 //! Each provider implements web search differently:
 //! - `OpenAI`: Built-in web search tools via Responses API
-//! - Anthropic: `web_search` tool
+//! - Anthropic: server-side `web_search` tool
 //! - xAI: Live Search with `search_parameters`
-//! - Gemini: Search-augmented generation
-//! - `OpenRouter`: `search_prompt` parameter
+//! - Gemini: Grounding with the `google_search` tool
+//! - `OpenRouter`: `web` plugin
+//!
+//! Setting [`ChatRequest::web_search`] enables search for a single request; the
+//! [`WebSearchCapability`] implementations run a search-grounded chat and return
+//! the sources it consulted.
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::error::LlmError;
-use crate::types::{WebSearchConfig, WebSearchContextSize, WebSearchResult, WebSearchStrategy};
+use crate::types::{
+    ChatMessage, ChatRequest, ChatResponse, CommonParams, WebSearchConfig, WebSearchContextSize,
+    WebSearchResult, WebSearchStrategy,
+};
+
+/// Type of Anthropic's server-side web search tool
+pub const ANTHROPIC_WEB_SEARCH_TOOL_TYPE: &str = "web_search_20250305";
 
 /// Web search capability trait
 #[async_trait]
@@ -46,6 +56,105 @@ impl WebSearchProvider {
         Self { provider, config }
     }
 
+    /// Create a provider for an explicit `web_search` call; search is always enabled
+    pub fn for_query(provider: &str, config: Option<WebSearchConfig>) -> Self {
+        let config = WebSearchConfig {
+            enabled: true,
+            ..config.unwrap_or_default()
+        };
+        Self::new(provider.to_string(), config)
+    }
+
+    /// Chat request that answers `query` with web search enabled
+    pub fn search_request(&self, query: String, common_params: CommonParams) -> ChatRequest {
+        ChatRequest::new(vec![ChatMessage::user(query).build()])
+            .with_common_params(common_params)
+            .with_web_search(self.config.clone())
+    }
+
+    /// Collect the sources of a search-grounded response, honoring `max_results`.
+    ///
+    /// Sources without a snippet borrow the text of the first citation pointing at them.
+    pub fn results_from_response(&self, response: &ChatResponse) -> Vec<WebSearchResult> {
+        let limit = self
+            .config
+            .max_results
+            .map_or(usize::MAX, |max| max as usize);
+
+        response
+            .sources
+            .iter()
+            .take(limit)
+            .map(|source| {
+                let mut result = WebSearchResult::from(source.clone());
+                if result.snippet.is_empty()
+                    && let Some(text) = response
+                        .citations
+                        .iter()
+                        .filter(|citation| citation.url.as_ref() == Some(&source.url))
+                        .find_map(|citation| citation.cited_text.clone())
+                {
+                    result.snippet = text;
+                }
+                result
+            })
+            .collect()
+    }
+
+    /// Build Anthropic's server-side `web_search` tool definition.
+    ///
+    /// Provider parameters (`max_uses`, `allowed_domains`, `blocked_domains`,
+    /// `user_location`) are copied onto the tool.
+    pub fn build_anthropic_tool(&self) -> serde_json::Value {
+        let mut tool = serde_json::json!({
+            "type": ANTHROPIC_WEB_SEARCH_TOOL_TYPE,
+            "name": "web_search",
+        });
+        for (key, value) in &self.config.provider_params {
+            tool[key] = value.clone();
+        }
+        tool
+    }
+
+    /// Build xAI Live Search `search_parameters`.
+    ///
+    /// The `Auto` strategy lets the model decide whether to search; any other
+    /// strategy forces a search. Provider parameters (`sources`, `from_date`,
+    /// `to_date`, `mode`) override the defaults.
+    pub fn build_xai_search_parameters(&self) -> serde_json::Value {
+        let mode = if matches!(self.config.strategy, WebSearchStrategy::Auto) {
+            "auto"
+        } else {
+            "on"
+        };
+        let mut parameters = serde_json::json!({
+            "mode": mode,
+            "return_citations": true,
+        });
+        if let Some(max_results) = self.config.max_results {
+            parameters["max_search_results"] = max_results.into();
+        }
+        for (key, value) in &self.config.provider_params {
+            parameters[key] = value.clone();
+        }
+        parameters
+    }
+
+    /// Build the `OpenRouter` `web` plugin
+    pub fn build_openrouter_plugin(&self) -> serde_json::Value {
+        let mut plugin = serde_json::json!({ "id": "web" });
+        if let Some(max_results) = self.config.max_results {
+            plugin["max_results"] = max_results.into();
+        }
+        if let Some(search_prompt) = &self.config.search_prompt {
+            plugin["search_prompt"] = search_prompt.clone().into();
+        }
+        for (key, value) in &self.config.provider_params {
+            plugin[key] = value.clone();
+        }
+        plugin
+    }
+
     /// Build search parameters for `OpenAI`
     pub fn build_openai_params(&self, _query: &str) -> HashMap<String, serde_json::Value> {
         let mut params = HashMap::new();
@@ -78,104 +187,44 @@ impl WebSearchProvider {
     }
 
     /// Build search parameters for xAI
-    pub fn build_xai_params(&self, query: &str) -> HashMap<String, serde_json::Value> {
-        let mut params = HashMap::new();
-
-        params.insert(
-            "query".to_string(),
-            serde_json::Value::String(query.to_string()),
-        );
-
-        if let Some(max_results) = self.config.max_results {
-            params.insert(
-                "max_results".to_string(),
-                serde_json::Value::Number(max_results.into()),
-            );
-        }
-
-        // Add provider-specific parameters
-        for (key, value) in &self.config.provider_params {
-            params.insert(key.clone(), value.clone());
-        }
-
-        params
+    #[deprecated(note = "use `build_xai_search_parameters`")]
+    pub fn build_xai_params(&self, _query: &str) -> HashMap<String, serde_json::Value> {
+        into_map(self.build_xai_search_parameters())
     }
 
     /// Build search parameters for Anthropic
-    pub fn build_anthropic_params(&self, query: &str) -> HashMap<String, serde_json::Value> {
-        let mut params = HashMap::new();
-
-        params.insert(
-            "query".to_string(),
-            serde_json::Value::String(query.to_string()),
-        );
-
-        if let Some(max_results) = self.config.max_results {
-            params.insert(
-                "max_results".to_string(),
-                serde_json::Value::Number(max_results.into()),
-            );
-        }
-
-        // Add provider-specific parameters
-        for (key, value) in &self.config.provider_params {
-            params.insert(key.clone(), value.clone());
-        }
-
-        params
+    #[deprecated(note = "use `build_anthropic_tool`")]
+    pub fn build_anthropic_params(&self, _query: &str) -> HashMap<String, serde_json::Value> {
+        into_map(self.build_anthropic_tool())
     }
 
-    /// Build search parameters for Gemini
-    pub fn build_gemini_params(&self, query: &str) -> HashMap<String, serde_json::Value> {
-        let mut params = HashMap::new();
-
-        params.insert(
-            "query".to_string(),
-            serde_json::Value::String(query.to_string()),
-        );
-
-        if let Some(max_results) = self.config.max_results {
-            params.insert(
-                "max_results".to_string(),
-                serde_json::Value::Number(max_results.into()),
-            );
-        }
-
-        // Add provider-specific parameters
-        for (key, value) in &self.config.provider_params {
-            params.insert(key.clone(), value.clone());
-        }
-
-        params
+    /// Build search parameters for Gemini: the `google_search` grounding tool
+    #[deprecated(
+        note = "Gemini adds the `google_search` tool when `ChatRequest::web_search` is set"
+    )]
+    pub fn build_gemini_params(&self, _query: &str) -> HashMap<String, serde_json::Value> {
+        into_map(serde_json::json!({ "google_search": {} }))
     }
 
     /// Build search parameters for `OpenRouter`
-    pub fn build_openrouter_params(&self, query: &str) -> HashMap<String, serde_json::Value> {
-        let mut params = HashMap::new();
-
-        if let Some(search_prompt) = &self.config.search_prompt {
-            params.insert(
-                "search_prompt".to_string(),
-                serde_json::Value::String(search_prompt.clone()),
-            );
-        } else {
-            // Default search prompt
-            params.insert(
-                "search_prompt".to_string(),
-                serde_json::Value::String(format!("Search for information about: {query}")),
-            );
-        }
-
-        // Add provider-specific parameters
-        for (key, value) in &self.config.provider_params {
-            params.insert(key.clone(), value.clone());
-        }
+    #[deprecated(note = "use `build_openrouter_plugin`")]
+    pub fn build_openrouter_params(&self, _query: &str) -> HashMap<String, serde_json::Value> {
+        into_map(self.build_openrouter_plugin())
+    }
+}
 
-        params
+/// Fields of a JSON object as a map
+fn into_map(value: serde_json::Value) -> HashMap<String, serde_json::Value> {
+    match value {
+        serde_json::Value::Object(fields) => fields.into_iter().collect(),
+        _ => HashMap::new(),
     }
 }
 
 /// Web search tool for Anthropic
+#[deprecated(
+    note = "Anthropic searches with its server-side tool; set `ChatRequest::web_search` or use `WebSearchProvider::build_anthropic_tool`"
+)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnthropicWebSearchTool {
     /// Search query
@@ -184,6 +233,7 @@ pub struct AnthropicWebSearchTool {
     pub max_results: Option<u32>,
 }
 
+#[allow(deprecated)]
 impl AnthropicWebSearchTool {
     /// Create a new web search tool
     pub const fn new(query: String) -> Self {
@@ -228,6 +278,7 @@ impl AnthropicWebSearchTool {
 }
 
 /// xAI Live Search configuration
+#[deprecated(note = "use `WebSearchConfig`, with Live Search options in `provider_params`")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct XaiLiveSearchConfig {
     /// Whether to enable live search
@@ -236,6 +287,7 @@ pub struct XaiLiveSearchConfig {
     pub search_parameters: HashMap<String, serde_json::Value>,
 }
 
+#[allow(deprecated)]
 impl Default for XaiLiveSearchConfig {
     fn default() -> Self {
         Self {
@@ -245,6 +297,7 @@ impl Default for XaiLiveSearchConfig {
     }
 }
 
+#[allow(deprecated)]
 impl XaiLiveSearchConfig {
     /// Create a new live search config
     pub fn new() -> Self {
@@ -284,6 +337,7 @@ impl XaiLiveSearchConfig {
 }
 
 /// Gemini search configuration
+#[deprecated(note = "use `WebSearchConfig`")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GeminiSearchConfig {
     /// Whether to enable search-augmented generation
@@ -292,6 +346,7 @@ pub struct GeminiSearchConfig {
     pub search_parameters: HashMap<String, serde_json::Value>,
 }
 
+#[allow(deprecated)]
 impl Default for GeminiSearchConfig {
     fn default() -> Self {
         Self {
@@ -301,6 +356,7 @@ impl Default for GeminiSearchConfig {
     }
 }
 
+#[allow(deprecated)]
 impl GeminiSearchConfig {
     /// Create a new search config
     pub fn new() -> Self {
@@ -349,6 +405,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_anthropic_web_search_tool() {
         let tool = AnthropicWebSearchTool::new("test query".to_string()).with_max_results(10);
 
@@ -360,6 +417,89 @@ mod tests {
     }
 
     #[test]
+    fn test_provider_request_params() {
+        let mut config = WebSearchConfig {
+            enabled: true,
+            max_results: Some(3),
+            search_prompt: Some("Sources:".to_string()),
+            ..Default::default()
+        };
+        config
+            .provider_params
+            .insert("sources".to_string(), serde_json::json!([{"type": "web"}]));
+        let provider = WebSearchProvider::new("xai".to_string(), config);
+
+        let parameters = provider.build_xai_search_parameters();
+        assert_eq!(parameters["mode"], "auto");
+        assert_eq!(parameters["max_search_results"], 3);
+        assert_eq!(parameters["return_citations"], true);
+        assert_eq!(parameters["sources"][0]["type"], "web");
+
+        let plugin = provider.build_openrouter_plugin();
+        assert_eq!(plugin["id"], "web");
+        assert_eq!(plugin["max_results"], 3);
+        assert_eq!(plugin["search_prompt"], "Sources:");
+
+        let tool = provider.build_anthropic_tool();
+        assert_eq!(tool["type"], ANTHROPIC_WEB_SEARCH_TOOL_TYPE);
+        assert_eq!(tool["name"], "web_search");
+
+        // The deprecated builders return the same values
+        #[allow(deprecated)]
+        {
+            assert_eq!(
+                provider.build_xai_params("query")["max_search_results"],
+                parameters["max_search_results"]
+            );
+            assert_eq!(provider.build_openrouter_params("query")["id"], "web");
+            assert_eq!(
+                provider.build_anthropic_params("query")["type"],
+                ANTHROPIC_WEB_SEARCH_TOOL_TYPE
+            );
+            assert!(
+                provider
+                    .build_gemini_params("query")
+                    .contains_key("google_search")
+            );
+        }
+    }
+
+    #[test]
+    fn test_results_from_response() {
+        use crate::types::{Citation, MessageContent, SearchSource};
+
+        let provider = WebSearchProvider::for_query(
+            "gemini",
+            Some(WebSearchConfig {
+                enabled: false,
+                max_results: Some(2),
+                ..Default::default()
+            }),
+        );
+        assert!(provider.config.enabled);
+
+        let mut response = ChatResponse::new(MessageContent::Text("answer".to_string()));
+        response.sources = vec![
+            SearchSource::new("https://a.example").with_title("A"),
+            SearchSource::new("https://b.example").with_snippet("about b"),
+            SearchSource::new("https://c.example"),
+        ];
+        response.citations = vec![Citation {
+            url: Some("https://a.example".to_string()),
+            cited_text: Some("quoted from a".to_string()),
+            ..Default::default()
+        }];
+
+        let results = provider.results_from_response(&response);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].title, "A");
+        assert_eq!(results[0].snippet, "quoted from a");
+        assert_eq!(results[1].url, "https://b.example");
+        assert_eq!(results[1].snippet, "about b");
+    }
+
+    #[test]
+    #[allow(deprecated)]
     fn test_xai_live_search_config() {
         let config = XaiLiveSearchConfig::new()
             .enable()
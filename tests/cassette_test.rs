@@ -0,0 +1,127 @@
+//! Cassette Record/Replay Tests
+//!
+//! Records chat and streaming interactions against a local mock server, then
+//! replays them with the server shut down.
+
+use std::sync::Arc;
+
+use futures::StreamExt;
+use serde_json::json;
+use siumai::prelude::*;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const API_KEY: &str = "sk-cassette-test-secret";
+
+fn chat_completion() -> serde_json::Value {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 1_700_000_000,
+        "model": "gpt-4o-mini",
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": "Hello from the cassette"},
+            "finish_reason": "stop"
+        }],
+        "usage": {"prompt_tokens": 5, "completion_tokens": 4, "total_tokens": 9}
+    })
+}
+
+fn sse_body() -> String {
+    [
+        r#"{"id":"chatcmpl-2","object":"chat.completion.chunk","created":1700000000,"model":"gpt-4o-mini","choices":[{"index":0,"delta":{"role":"assistant","content":"Hel"},"finish_reason":null}]}"#,
+        r#"{"id":"chatcmpl-2","object":"chat.completion.chunk","created":1700000000,"model":"gpt-4o-mini","choices":[{"index":0,"delta":{"content":"lo"},"finish_reason":null}]}"#,
+        r#"{"id":"chatcmpl-2","object":"chat.completion.chunk","created":1700000000,"model":"gpt-4o-mini","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+    ]
+    .iter()
+    .map(|data| format!("data: {data}\n\n"))
+    .chain(std::iter::once("data: [DONE]\n\n".to_string()))
+    .collect()
+}
+
+async fn build_client(base_url: &str, cassette: Arc<Cassette>) -> impl ChatCapability {
+    LlmBuilder::new()
+        .with_cassette(cassette)
+        .openai()
+        .api_key(API_KEY)
+        .base_url(base_url)
+        .model("gpt-4o-mini")
+        .build()
+        .await
+        .unwrap()
+}
+
+async fn run_conversation(client: &impl ChatCapability) -> (String, String) {
+    let response = client.chat(vec![user!("Say hello")]).await.unwrap();
+
+    let mut stream = client
+        .chat_stream(vec![user!("Stream hello")], None)
+        .await
+        .unwrap();
+    let mut streamed = String::new();
+    while let Some(event) = stream.next().await {
+        if let ChatStreamEvent::ContentDelta { delta, .. } = event.unwrap() {
+            streamed.push_str(&delta);
+        }
+    }
+
+    (
+        response.content_text().unwrap_or_default().to_string(),
+        streamed,
+    )
+}
+
+#[tokio::test]
+async fn test_record_then_replay_offline() {
+    let cassette_path =
+        std::env::temp_dir().join(format!("siumai-cassette-test-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&cassette_path);
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .and(body_partial_json(json!({"stream": true})))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(sse_body()),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(chat_completion()))
+        .mount(&server)
+        .await;
+    let base_url = format!("{}/v1", server.uri());
+
+    // Record against the mock server
+    let recorder = Arc::new(Cassette::record(&cassette_path));
+    let client = build_client(&base_url, recorder.clone()).await;
+    let recorded = run_conversation(&client).await;
+    assert_eq!(recorded.0, "Hello from the cassette");
+    assert_eq!(recorded.1, "Hello");
+    assert_eq!(recorder.interactions().len(), 2);
+
+    let saved = std::fs::read_to_string(&cassette_path).unwrap();
+    assert!(!saved.contains(API_KEY));
+
+    // Replay with the server gone
+    drop(server);
+    let player = Arc::new(Cassette::replay(&cassette_path).unwrap());
+    assert_eq!(player.mode(), CassetteMode::Replay);
+    let client = build_client(&base_url, player.clone()).await;
+    let replayed = run_conversation(&client).await;
+    assert_eq!(replayed, recorded);
+    assert_eq!(player.remaining(), 0);
+
+    // Requests that were not recorded fail instead of reaching the network
+    let error = client
+        .chat(vec![user!("Something else")])
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("No recorded interaction"));
+
+    let _ = std::fs::remove_file(&cassette_path);
+}
@@ -97,6 +97,9 @@ mod tests {
             generation_config: None,
             safety_settings: None,
             timeout: Some(30),
+            rate_limiter: None,
+            cassette: None,
+            cached_content: None,
         };
         let http_client = reqwest::Client::new();
         let embeddings = GeminiEmbeddings::new(config, http_client);
@@ -135,6 +138,9 @@ mod tests {
             generation_config: None,
             safety_settings: None,
             timeout: Some(30),
+            rate_limiter: None,
+            cassette: None,
+            cached_content: None,
         };
         let http_client = reqwest::Client::new();
         let embeddings = GeminiEmbeddings::new(config, http_client);
@@ -181,6 +181,7 @@ mod tests {
             total_tokens: 150,
             reasoning_tokens: None,
             cached_tokens: None,
+            cache_write_tokens: None,
         };
 
         let usage2 = Usage {
@@ -189,6 +190,7 @@ mod tests {
             total_tokens: 275,
             reasoning_tokens: Some(25),
             cached_tokens: Some(10),
+            cache_write_tokens: None,
         };
 
         usage1.merge(&usage2);